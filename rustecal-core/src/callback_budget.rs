@@ -0,0 +1,111 @@
+//! Execution time budgets for callbacks invoked on eCAL middleware threads.
+//!
+//! Subscriber callbacks and service handlers run on eCAL's internal threads.
+//! Accidental blocking work in such a callback stalls message delivery for the
+//! whole process. A [`CallbackBudget`] measures every invocation and reports
+//! each one that takes longer than the configured budget, either by logging a
+//! warning through eCAL logging or by calling a user-provided handler.
+
+#[cfg(feature = "logging")]
+use crate::log::Log;
+use crate::log_level::LogLevel;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Duration, Instant};
+
+/// Details about a single callback invocation that exceeded its budget.
+#[derive(Debug, Clone)]
+pub struct BudgetExceeded {
+    /// Name of the measured callback (topic name or service method).
+    pub name: String,
+    /// The configured budget.
+    pub budget: Duration,
+    /// The measured execution time.
+    pub elapsed: Duration,
+}
+
+type ExceededHandler = Arc<dyn Fn(&BudgetExceeded) + Send + Sync + 'static>;
+
+/// An execution time budget applied to every invocation of a callback.
+///
+/// Cloning a `CallbackBudget` shares its exceeded counter.
+#[derive(Clone)]
+pub struct CallbackBudget {
+    budget: Duration,
+    log_level: Option<LogLevel>,
+    handler: Option<ExceededHandler>,
+    exceeded: Arc<AtomicU64>,
+}
+
+impl CallbackBudget {
+    /// Creates a budget that logs a warning via eCAL logging when exceeded.
+    pub fn new(budget: Duration) -> Self {
+        Self {
+            budget,
+            log_level: Some(LogLevel::Warning),
+            handler: None,
+            exceeded: Arc::new(AtomicU64::new(0)),
+        }
+    }
+
+    /// Sets the log level used when the budget is exceeded, or `None` to disable logging.
+    ///
+    /// Has no effect without the `logging` feature.
+    pub fn log_level(mut self, level: Option<LogLevel>) -> Self {
+        self.log_level = level;
+        self
+    }
+
+    /// Registers a handler that is invoked for every exceeded budget.
+    ///
+    /// The handler runs on the middleware thread right after the callback
+    /// returned, so it should be cheap.
+    pub fn on_exceeded<F>(mut self, handler: F) -> Self
+    where
+        F: Fn(&BudgetExceeded) + Send + Sync + 'static,
+    {
+        self.handler = Some(Arc::new(handler));
+        self
+    }
+
+    /// Returns the configured budget.
+    pub fn budget(&self) -> Duration {
+        self.budget
+    }
+
+    /// Returns how many invocations exceeded the budget so far.
+    pub fn exceeded_count(&self) -> u64 {
+        self.exceeded.load(Ordering::Relaxed)
+    }
+
+    /// Runs `f`, measuring its execution time against the budget.
+    pub fn measure<R>(&self, name: &str, f: impl FnOnce() -> R) -> R {
+        let start = Instant::now();
+        let result = f();
+        let elapsed = start.elapsed();
+
+        if elapsed > self.budget {
+            self.exceeded.fetch_add(1, Ordering::Relaxed);
+            let event = BudgetExceeded {
+                name: name.to_string(),
+                budget: self.budget,
+                elapsed,
+            };
+            #[cfg(feature = "logging")]
+            if let Some(level) = self.log_level {
+                Log::log(
+                    level,
+                    &format!(
+                        "callback '{}' took {:?}, exceeding its budget of {:?}",
+                        event.name, event.elapsed, event.budget
+                    ),
+                );
+            }
+            if let Some(handler) = &self.handler {
+                handler(&event);
+            }
+        }
+
+        result
+    }
+}
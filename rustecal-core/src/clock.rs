@@ -0,0 +1,88 @@
+//! Clock abstraction for running nodes against real or simulated time.
+//!
+//! Algorithms that read the time through a [`Clock`] instead of the system
+//! clock behave deterministically when a measurement is replayed: a replay
+//! tool drives a [`SimClock`] with the recorded timestamps, and every
+//! component reading that clock observes the replayed time.
+
+use crate::time::Time;
+use std::sync::{Condvar, Mutex, OnceLock};
+use std::time::Duration;
+
+/// A source of time in microseconds.
+pub trait Clock: Send + Sync {
+    /// Returns the current time of this clock in microseconds.
+    fn now(&self) -> i64;
+}
+
+/// A clock reading the (optionally synchronized) eCAL time base.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct EcalClock;
+
+impl Clock for EcalClock {
+    fn now(&self) -> i64 {
+        Time::microseconds()
+    }
+}
+
+/// A simulated clock that only advances when it is told to.
+///
+/// Use [`SimClock::global`] for the process-wide instance that is also used
+/// by `Timestamp::SimTime` when publishing.
+#[derive(Debug, Default)]
+pub struct SimClock {
+    now: Mutex<i64>,
+    changed: Condvar,
+}
+
+static GLOBAL_SIM_CLOCK: OnceLock<SimClock> = OnceLock::new();
+
+impl SimClock {
+    /// Creates a simulated clock starting at `start` microseconds.
+    pub fn new(start: i64) -> Self {
+        Self {
+            now: Mutex::new(start),
+            changed: Condvar::new(),
+        }
+    }
+
+    /// Returns the process-wide simulated clock (starting at `0`).
+    pub fn global() -> &'static SimClock {
+        GLOBAL_SIM_CLOCK.get_or_init(SimClock::default)
+    }
+
+    /// Sets the simulated time to `time` microseconds.
+    pub fn set(&self, time: i64) {
+        *self.now.lock().unwrap() = time;
+        self.changed.notify_all();
+    }
+
+    /// Advances the simulated time by `step`.
+    pub fn advance(&self, step: Duration) {
+        *self.now.lock().unwrap() += step.as_micros() as i64;
+        self.changed.notify_all();
+    }
+
+    /// Blocks until the simulated time reached `time` microseconds.
+    pub fn wait_until(&self, time: i64) {
+        let guard = self.now.lock().unwrap();
+        let _guard = self.changed.wait_while(guard, |now| *now < time).unwrap();
+    }
+
+    /// Blocks until the simulated time reached `time` microseconds or `timeout`
+    /// elapsed in real time. Returns `true` if the time was reached.
+    pub fn wait_until_timeout(&self, time: i64, timeout: Duration) -> bool {
+        let guard = self.now.lock().unwrap();
+        let (_guard, result) = self
+            .changed
+            .wait_timeout_while(guard, timeout, |now| *now < time)
+            .unwrap();
+        !result.timed_out()
+    }
+}
+
+impl Clock for SimClock {
+    fn now(&self) -> i64 {
+        *self.now.lock().unwrap()
+    }
+}
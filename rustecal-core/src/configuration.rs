@@ -6,7 +6,16 @@
 //! `eCAL_Configuration` instance via FFI. It supports initializing
 //! default settings or loading from a YAML file, and automatically
 //! frees the underlying C object on drop.
+//!
+//! Logging sinks (console, file, UDP) and their level filters are configured
+//! here as well, since eCAL applies them when it is initialized.
+//!
+//! The same holds for the Rust-side [`TopicPolicy`] and [`TopicRemap`], which
+//! [`Ecal::initialize`](crate::core::Ecal::initialize) installs process-wide.
 
+use crate::log_level::LogLevel;
+use crate::topic_policy::TopicPolicy;
+use crate::topic_remap::TopicRemap;
 use rustecal_sys as sys;
 use std::{
     ffi::{CStr, CString},
@@ -24,9 +33,26 @@ pub enum ConfigError {
     InvalidPath(String),
 }
 
+/// A sink of the eCAL logging provider.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LogSink {
+    /// Standard output of the process.
+    Console,
+    /// Log files in the configured log directory.
+    File,
+    /// UDP, received by eCAL Monitor and other log receivers.
+    Udp,
+}
+
 /// Safe Rust wrapper around eCAL_Configuration
 pub struct Configuration {
     inner: *mut sys::eCAL_Configuration,
+    /// Strings referenced by the C struct, kept alive as long as the configuration
+    strings: Vec<CString>,
+    /// Topic policy installed on initialization, if any
+    topic_policy: Option<TopicPolicy>,
+    /// Topic remap installed on initialization, if any
+    topic_remap: Option<TopicRemap>,
 }
 
 unsafe impl Send for Configuration {}
@@ -40,7 +66,12 @@ impl Configuration {
             return Err(ConfigError::NullPointer);
         }
         unsafe { sys::eCAL_Configuration_InitFromConfig(cfg) };
-        Ok(Configuration { inner: cfg })
+        Ok(Configuration {
+            inner: cfg,
+            strings: Vec::new(),
+            topic_policy: None,
+            topic_remap: None,
+        })
     }
 
     /// Loads a Configuration from a YAML file at the given path
@@ -54,7 +85,12 @@ impl Configuration {
             return Err(ConfigError::NullPointer);
         }
         unsafe { sys::eCAL_Configuration_InitFromFile(cfg, c_path.as_ptr()) };
-        Ok(Configuration { inner: cfg })
+        Ok(Configuration {
+            inner: cfg,
+            strings: Vec::new(),
+            topic_policy: None,
+            topic_remap: None,
+        })
     }
 
     /// Returns the path of the loaded configuration file, if any
@@ -69,6 +105,59 @@ impl Configuration {
         }
     }
 
+    /// Enables or disables a logging sink and sets the levels it emits.
+    ///
+    /// An empty `levels` slice filters out all messages of the sink.
+    pub fn set_log_sink(&mut self, sink: LogSink, enable: bool, levels: &[LogLevel]) {
+        let filter = levels
+            .iter()
+            .fold(0, |filter, level| filter | u32::from(*level));
+        let provider = &mut self.logging.provider;
+        let sink = match sink {
+            LogSink::Console => &mut provider.console,
+            LogSink::File => &mut provider.file,
+            LogSink::Udp => &mut provider.udp,
+        };
+        sink.enable = enable as _;
+        sink.log_level = filter as _;
+    }
+
+    /// Sets the directory the file sink writes its log files to.
+    pub fn set_log_file_path(&mut self, path: &str) -> Result<(), ConfigError> {
+        let c_path = CString::new(path).map_err(|_| ConfigError::InvalidPath(path.to_string()))?;
+        self.logging.provider.file_config.path = c_path.as_ptr() as _;
+        self.strings.push(c_path);
+        Ok(())
+    }
+
+    /// Sets the UDP port the UDP sink sends log messages to.
+    pub fn set_log_udp_port(&mut self, port: u16) {
+        self.logging.provider.udp_config.port = port as _;
+    }
+
+    /// Sets the topic policy installed when eCAL is initialized with this configuration.
+    pub fn set_topic_policy(&mut self, policy: TopicPolicy) {
+        self.topic_policy = Some(policy);
+    }
+
+    /// Returns the topic policy of this configuration, if any.
+    pub fn topic_policy(&self) -> Option<&TopicPolicy> {
+        self.topic_policy.as_ref()
+    }
+
+    /// Sets the topic remap installed when eCAL is initialized with this configuration.
+    ///
+    /// It takes precedence over the `RUSTECAL_TOPIC_PREFIX`/`RUSTECAL_TOPIC_REMAP`
+    /// environment variables.
+    pub fn set_topic_remap(&mut self, remap: TopicRemap) {
+        self.topic_remap = Some(remap);
+    }
+
+    /// Returns the topic remap of this configuration, if any.
+    pub fn topic_remap(&self) -> Option<&TopicRemap> {
+        self.topic_remap.as_ref()
+    }
+
     /// Returns a raw pointer to the underlying eCAL_Configuration for FFI calls
     pub(crate) fn as_ptr(&self) -> *const sys::eCAL_Configuration {
         self.inner as *const _
@@ -11,16 +11,30 @@
 //! - [`Ecal::version_string`], [`Ecal::version_date_string`] and [`Ecal::version_struct`] for version info
 //!
 //! Typically, you will call [`Ecal::initialize`] once at the beginning of your
-//! application and [`Ecal::finalize`] at shutdown.
+//! application and [`Ecal::finalize`] at shutdown. Alternatively,
+//! [`Ecal::initialize_guarded`] returns an [`EcalGuard`] that finalizes eCAL
+//! when dropped.
 
 use std::ffi::{CStr, CString};
 use std::ptr;
 
 use crate::components::EcalComponents;
+#[cfg(feature = "configuration")]
 use crate::configuration::Configuration;
 use crate::error::{RustecalError, check};
+use crate::error_hook::warn;
+use crate::handle_registry::{HandleRegistry, LiveHandle};
+use crate::log_level::LogLevel;
+#[cfg(feature = "configuration")]
+use crate::topic_policy::TopicPolicy;
+use crate::topic_remap::TopicRemap;
 use crate::types::Version;
 
+/// Without the `configuration` feature no configuration can be passed to
+/// [`Ecal::initialize`]; the uninhabited type leaves `None` as the only option.
+#[cfg(not(feature = "configuration"))]
+type Configuration = std::convert::Infallible;
+
 /// Provides access to the core initialization, shutdown, and state‑checking functions of eCAL.
 pub struct Ecal;
 
@@ -32,11 +46,16 @@ impl Ecal {
     /// * `unit_name` – Optional name to identify this process in eCAL.
     /// * `components` – Bitmask of which subsystems to enable.
     /// * `config` – Optional eCAL Configuration to use for initialization.
+    ///   Its topic policy, if set, replaces the process-wide [`TopicPolicy`](crate::TopicPolicy).
+    ///
+    /// The process-wide [`TopicRemap`] is replaced by the one of `config`, if
+    /// set, or else by the one read from the environment (see [`TopicRemap::from_env`]).
     ///
     /// # Errors
     ///
     /// Returns `Err(RustecalError::Ecal{..})` on any non‑zero C return code,
-    /// or `RustecalError::Internal` if the unit name contains an interior NUL.
+    /// `RustecalError::Internal` if the unit name contains an interior NUL,
+    /// or `RustecalError::InvalidRemap` if the remap environment variable is malformed.
     pub fn initialize(
         unit_name: Option<&str>,
         components: EcalComponents,
@@ -51,9 +70,28 @@ impl Ecal {
         };
 
         // Determine pointer to eCAL_Configuration (or null)
+        #[cfg(feature = "configuration")]
         let cfg_ptr = config
             .map(|c| c.as_ptr() as *mut rustecal_sys::eCAL_Configuration)
             .unwrap_or(ptr::null_mut());
+        #[cfg(not(feature = "configuration"))]
+        let cfg_ptr = match config {
+            Some(never) => match *never {},
+            None => ptr::null_mut(),
+        };
+
+        #[cfg(feature = "configuration")]
+        if let Some(policy) = config.and_then(Configuration::topic_policy) {
+            TopicPolicy::set_default(policy.clone());
+        }
+
+        #[cfg(feature = "configuration")]
+        let remap = config.and_then(Configuration::topic_remap).cloned();
+        #[cfg(not(feature = "configuration"))]
+        let remap = None;
+        if let Some(remap) = remap.map_or_else(TopicRemap::from_env, |r| Ok(Some(r)))? {
+            TopicRemap::set_default(remap);
+        }
 
         // Call the C API and map its return code
         let ret =
@@ -61,11 +99,47 @@ impl Ecal {
         check(ret)
     }
 
+    /// Initializes the eCAL runtime system and returns a guard that finalizes it on drop.
+    ///
+    /// Takes the same arguments as [`Ecal::initialize`].
+    pub fn initialize_guarded(
+        unit_name: Option<&str>,
+        components: EcalComponents,
+        config: Option<&Configuration>,
+    ) -> Result<EcalGuard, RustecalError> {
+        Self::initialize(unit_name, components, config)?;
+        Ok(EcalGuard { finalized: false })
+    }
+
     /// Finalizes and shuts down the eCAL runtime system.
     ///
     /// After calling this, all publishers, subscribers, and services are invalidated.
+    /// If handle tracking is enabled (see [`HandleRegistry`]), a warning listing
+    /// all handles still alive is logged first.
     pub fn finalize() {
+        Self::finalize_checked();
+    }
+
+    /// Finalizes eCAL like [`Ecal::finalize`] and returns the tracked handles
+    /// that were still alive.
+    ///
+    /// The list is always empty unless handle tracking is enabled.
+    pub fn finalize_checked() -> Vec<LiveHandle> {
+        let live = HandleRegistry::live_handles();
+        if !live.is_empty() {
+            let names: Vec<String> = live.iter().map(ToString::to_string).collect();
+            warn(
+                LogLevel::Warning,
+                &format!(
+                    "eCAL finalized with {} live handle(s): {}",
+                    live.len(),
+                    names.join(", ")
+                ),
+            );
+        }
+
         unsafe { rustecal_sys::eCAL_Finalize() };
+        live
     }
 
     /// Returns `true` if the eCAL system is currently operational.
@@ -115,3 +189,30 @@ impl Ecal {
         unsafe { rustecal_sys::eCAL_GetVersion().into() }
     }
 }
+
+/// Keeps eCAL initialized; finalizes it when dropped.
+///
+/// Created by [`Ecal::initialize_guarded`]. Declare the guard before any
+/// publisher, subscriber or service so that those are dropped first.
+#[must_use = "dropping the guard finalizes eCAL immediately"]
+pub struct EcalGuard {
+    finalized: bool,
+}
+
+impl EcalGuard {
+    /// Finalizes eCAL now and returns the tracked handles that were still alive.
+    ///
+    /// See [`Ecal::finalize_checked`].
+    pub fn finalize(mut self) -> Vec<LiveHandle> {
+        self.finalized = true;
+        Ecal::finalize_checked()
+    }
+}
+
+impl Drop for EcalGuard {
+    fn drop(&mut self) {
+        if !self.finalized {
+            Ecal::finalize();
+        }
+    }
+}
@@ -8,6 +8,7 @@ use std::os::raw::c_char;
 
 /// Represents a single log message emitted by eCAL.
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct LogMessage {
     pub level: LogLevel,
     pub timestamp: i64,
@@ -1,4 +1,6 @@
 //! Common eCAL types shared across pubsub and service layers.
 
+#[cfg(feature = "logging")]
 pub mod logging;
+#[cfg(feature = "monitoring")]
 pub mod monitoring;
@@ -3,6 +3,8 @@
 //! These types represent the full monitoring snapshot of the eCAL runtime system.
 
 use crate::types::DataTypeInfo;
+use std::borrow::Cow;
+use std::collections::BTreeMap;
 use std::ffi::CStr;
 use std::os::raw::c_char;
 
@@ -15,8 +17,49 @@ fn cstr(ptr: *const c_char) -> String {
     }
 }
 
+/// Borrows a C string as text without copying, treating a null pointer as empty.
+///
+/// Invalid UTF-8 is replaced, which only then allocates.
+pub(crate) fn cstr_text<'a>(ptr: *const c_char) -> Cow<'a, str> {
+    if ptr.is_null() {
+        Cow::Borrowed("")
+    } else {
+        unsafe { CStr::from_ptr(ptr) }.to_string_lossy()
+    }
+}
+
+/// Converts a C array into a `Vec`, treating a null pointer as empty.
+///
+/// # Safety
+/// `ptr` must be null or point to `len` valid elements.
+pub(crate) unsafe fn collect<R: Copy, T: From<R>>(ptr: *const R, len: usize) -> Vec<T> {
+    unsafe { collect_filtered(ptr, len, |_| true) }
+}
+
+/// Converts the elements of a C array that pass `keep` into a `Vec`.
+///
+/// Rejected elements are never converted.
+///
+/// # Safety
+/// `ptr` must be null or point to `len` valid elements.
+pub(crate) unsafe fn collect_filtered<R: Copy, T: From<R>>(
+    ptr: *const R,
+    len: usize,
+    keep: impl Fn(&R) -> bool,
+) -> Vec<T> {
+    if ptr.is_null() || len == 0 {
+        return Vec::new();
+    }
+    unsafe { std::slice::from_raw_parts(ptr, len) }
+        .iter()
+        .filter(|r| keep(r))
+        .map(|r| T::from(*r))
+        .collect()
+}
+
 /// Transport layer type.
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum TransportLayerType {
     None,
     UdpMulticast,
@@ -27,6 +70,7 @@ pub enum TransportLayerType {
 
 /// A single transport layer entry.
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct TransportLayer {
     pub transport_type: TransportLayerType,
     pub version: i32,
@@ -34,7 +78,8 @@ pub struct TransportLayer {
 }
 
 /// Full snapshot of monitoring information from the eCAL runtime.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct MonitoringSnapshot {
     pub processes: Vec<ProcessInfo>,
     pub publishers: Vec<TopicInfo>,
@@ -45,6 +90,7 @@ pub struct MonitoringSnapshot {
 
 /// A monitored eCAL process.
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ProcessInfo {
     pub registration_clock: i32,
     pub host_name: String,
@@ -66,6 +112,7 @@ pub struct ProcessInfo {
 
 /// A monitored topic (publisher or subscriber).
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct TopicInfo {
     pub registration_clock: i32,
     pub host_name: String,
@@ -84,11 +131,205 @@ pub struct TopicInfo {
     pub message_drops: i32,
     pub data_id: i64,
     pub data_clock: i64,
+    /// Send/receive frequency in mHz.
     pub data_frequency: i32,
 }
 
+impl MonitoringSnapshot {
+    /// Iterates over all publishers and subscribers.
+    pub fn topics(&self) -> impl Iterator<Item = &TopicInfo> {
+        self.publishers.iter().chain(self.subscribers.iter())
+    }
+
+    /// Returns the publishers and subscribers of `topic_name`.
+    pub fn topic(&self, topic_name: &str) -> impl Iterator<Item = &TopicInfo> {
+        self.topics()
+            .filter(move |topic| topic.topic_name == topic_name)
+    }
+
+    /// Returns the publishers of `topic_name`.
+    pub fn publishers_of<'a>(&'a self, topic_name: &'a str) -> impl Iterator<Item = &'a TopicInfo> {
+        self.publishers
+            .iter()
+            .filter(move |topic| topic.topic_name == topic_name)
+    }
+
+    /// Returns the subscribers of `topic_name`.
+    pub fn subscribers_of<'a>(
+        &'a self,
+        topic_name: &'a str,
+    ) -> impl Iterator<Item = &'a TopicInfo> {
+        self.subscribers
+            .iter()
+            .filter(move |topic| topic.topic_name == topic_name)
+    }
+
+    /// Returns the host name and process ID of the publisher, subscriber,
+    /// server or client with the given entity ID.
+    pub fn owner_of(&self, entity_id: u64) -> Option<(&str, i32)> {
+        let id = entity_id as i64;
+        self.topics()
+            .find(|t| t.topic_id == id)
+            .map(|t| (t.host_name.as_str(), t.process_id))
+            .or_else(|| {
+                self.servers
+                    .iter()
+                    .find(|s| s.service_id == id)
+                    .map(|s| (s.host_name.as_str(), s.process_id))
+            })
+            .or_else(|| {
+                self.clients
+                    .iter()
+                    .find(|c| c.service_id == id)
+                    .map(|c| (c.host_name.as_str(), c.process_id))
+            })
+    }
+
+    /// Returns the process owning the entity with the given ID, e.g. the
+    /// publisher of a received sample.
+    pub fn process_of(&self, entity_id: u64) -> Option<&ProcessInfo> {
+        let (host_name, process_id) = self.owner_of(entity_id)?;
+        self.processes
+            .iter()
+            .find(|p| p.host_name == host_name && p.process_id == process_id)
+    }
+
+    /// Returns the aggregated statistics of `topic_name`, or `None` if the
+    /// topic has neither publishers nor subscribers.
+    pub fn topic_stats(&self, topic_name: &str) -> Option<TopicStats> {
+        self.collect_topic_stats(|name| name == topic_name).pop()
+    }
+
+    /// Returns the aggregated statistics of all topics, sorted by topic name.
+    pub fn all_topic_stats(&self) -> Vec<TopicStats> {
+        self.collect_topic_stats(|_| true)
+    }
+
+    fn collect_topic_stats(&self, include: impl Fn(&str) -> bool) -> Vec<TopicStats> {
+        let mut stats: BTreeMap<&str, TopicStats> = BTreeMap::new();
+        for topic in self.publishers.iter().filter(|t| include(&t.topic_name)) {
+            stats_entry(&mut stats, topic).add_publisher(topic);
+        }
+        for topic in self.subscribers.iter().filter(|t| include(&t.topic_name)) {
+            stats_entry(&mut stats, topic).add_subscriber(topic);
+        }
+        stats.into_values().collect()
+    }
+
+    /// Returns the entities of the snapshot grouped by host, sorted by host name.
+    pub fn hosts(&self) -> Vec<HostInfo> {
+        let mut hosts: BTreeMap<&str, HostInfo> = BTreeMap::new();
+        for process in &self.processes {
+            host_entry(&mut hosts, &process.host_name).process_count += 1;
+        }
+        for topic in &self.publishers {
+            let info = host_entry(&mut hosts, &topic.host_name);
+            info.publisher_count += 1;
+            info.bytes_sent_per_second += topic.frequency_hz() * topic.topic_size.max(0) as f64;
+        }
+        for topic in &self.subscribers {
+            host_entry(&mut hosts, &topic.host_name).subscriber_count += 1;
+        }
+        for server in &self.servers {
+            host_entry(&mut hosts, &server.host_name).server_count += 1;
+        }
+        for client in &self.clients {
+            host_entry(&mut hosts, &client.host_name).client_count += 1;
+        }
+        hosts.into_values().collect()
+    }
+}
+
+impl TopicInfo {
+    /// Returns the data frequency in Hz.
+    pub fn frequency_hz(&self) -> f64 {
+        self.data_frequency as f64 / 1000.0
+    }
+}
+
+/// Traffic statistics of one topic, aggregated over all its publishers and subscribers.
+///
+/// Rates and totals are taken from the publisher side, i.e. they describe
+/// the data sent on the topic; with several publishers they are summed up.
+#[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TopicStats {
+    pub topic_name: String,
+    pub publisher_count: usize,
+    pub subscriber_count: usize,
+    /// Messages sent per second.
+    pub frequency_hz: f64,
+    /// Bytes sent per second, based on the latest message size of each publisher.
+    pub bytes_per_second: f64,
+    /// Messages sent since the publishers were created.
+    pub total_messages: u64,
+    /// Approximate bytes sent since the publishers were created
+    /// (messages times latest message size).
+    pub total_bytes: u64,
+    /// Messages dropped by the subscribers.
+    pub message_drops: u64,
+}
+
+fn stats_entry<'a, 'b>(
+    stats: &'a mut BTreeMap<&'b str, TopicStats>,
+    topic: &'b TopicInfo,
+) -> &'a mut TopicStats {
+    stats
+        .entry(&topic.topic_name)
+        .or_insert_with(|| TopicStats {
+            topic_name: topic.topic_name.clone(),
+            ..Default::default()
+        })
+}
+
+impl TopicStats {
+    fn add_publisher(&mut self, topic: &TopicInfo) {
+        let size = topic.topic_size.max(0) as u64;
+        let messages = topic.data_clock.max(0) as u64;
+        self.publisher_count += 1;
+        self.frequency_hz += topic.frequency_hz();
+        self.bytes_per_second += topic.frequency_hz() * size as f64;
+        self.total_messages += messages;
+        self.total_bytes += messages * size;
+    }
+
+    fn add_subscriber(&mut self, topic: &TopicInfo) {
+        self.subscriber_count += 1;
+        self.message_drops += topic.message_drops.max(0) as u64;
+    }
+}
+
+/// The eCAL entities running on one host.
+///
+/// eCAL 6 does not report host resources (CPU load, memory) in its
+/// monitoring data, so a host is described by the entities it runs and the
+/// data its publishers send.
+#[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct HostInfo {
+    pub host_name: String,
+    pub process_count: usize,
+    pub publisher_count: usize,
+    pub subscriber_count: usize,
+    pub server_count: usize,
+    pub client_count: usize,
+    /// Bytes sent per second by the host's publishers, based on their latest message sizes.
+    pub bytes_sent_per_second: f64,
+}
+
+fn host_entry<'a, 'b>(
+    hosts: &'a mut BTreeMap<&'b str, HostInfo>,
+    host_name: &'b str,
+) -> &'a mut HostInfo {
+    hosts.entry(host_name).or_insert_with(|| HostInfo {
+        host_name: host_name.to_string(),
+        ..Default::default()
+    })
+}
+
 /// A method entry of a service.
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct MethodInfo {
     pub method_name: String,
     pub request_type: DataTypeInfo,
@@ -98,6 +339,7 @@ pub struct MethodInfo {
 
 /// A monitored service server.
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ServerInfo {
     pub registration_clock: i32,
     pub host_name: String,
@@ -114,6 +356,7 @@ pub struct ServerInfo {
 
 /// A monitored service client.
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ClientInfo {
     pub registration_clock: i32,
     pub host_name: String,
@@ -160,13 +403,7 @@ impl From<rustecal_sys::eCAL_Monitoring_STransportLayer> for TransportLayer {
 
 impl From<rustecal_sys::eCAL_Monitoring_STopic> for TopicInfo {
     fn from(raw: rustecal_sys::eCAL_Monitoring_STopic) -> Self {
-        let transport_layers = unsafe {
-            std::slice::from_raw_parts(raw.transport_layer, raw.transport_layer_length)
-                .iter()
-                .cloned()
-                .map(TransportLayer::from)
-                .collect()
-        };
+        let transport_layers = unsafe { collect(raw.transport_layer, raw.transport_layer_length) };
 
         Self {
             registration_clock: raw.registration_clock,
@@ -227,13 +464,7 @@ impl From<rustecal_sys::eCAL_Monitoring_SMethod> for MethodInfo {
 
 impl From<rustecal_sys::eCAL_Monitoring_SServer> for ServerInfo {
     fn from(raw: rustecal_sys::eCAL_Monitoring_SServer) -> Self {
-        let methods = unsafe {
-            std::slice::from_raw_parts(raw.methods, raw.methods_length)
-                .iter()
-                .cloned()
-                .map(MethodInfo::from)
-                .collect()
-        };
+        let methods = unsafe { collect(raw.methods, raw.methods_length) };
 
         Self {
             registration_clock: raw.registration_clock,
@@ -253,13 +484,7 @@ impl From<rustecal_sys::eCAL_Monitoring_SServer> for ServerInfo {
 
 impl From<rustecal_sys::eCAL_Monitoring_SClient> for ClientInfo {
     fn from(raw: rustecal_sys::eCAL_Monitoring_SClient) -> Self {
-        let methods = unsafe {
-            std::slice::from_raw_parts(raw.methods, raw.methods_length)
-                .iter()
-                .cloned()
-                .map(MethodInfo::from)
-                .collect()
-        };
+        let methods = unsafe { collect(raw.methods, raw.methods_length) };
 
         Self {
             registration_clock: raw.registration_clock,
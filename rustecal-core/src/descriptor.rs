@@ -0,0 +1,141 @@
+//! Size control for type descriptors sent with every registration.
+//!
+//! eCAL repeats the [`DataTypeInfo`] of each publisher and subscriber in all
+//! of its registration packets, so large descriptors (e.g. protobuf
+//! descriptor pools with many imports) inflate the registration traffic of
+//! the whole system. A process-wide [`DescriptorPolicy`] can:
+//!
+//! - compress descriptors with zstd (feature `descriptor-compression`), and
+//! - cap the descriptor size, dropping descriptors that are still too large
+//!   with a warning.
+//!
+//! A compressed descriptor is marked by the zstd frame magic
+//! ([`ZSTD_MAGIC`]) at its start, which can never begin a serialized
+//! protobuf `FileDescriptorSet`. Consumers call
+//! [`DataTypeInfo::decompressed_descriptor`], which returns uncompressed
+//! descriptors unchanged. Tools that do not know the marker (e.g. the C++
+//! eCAL Monitor) see an opaque descriptor and fall back to raw display, so
+//! compression should only be enabled when all consumers of the descriptor
+//! are rustecal based.
+
+use crate::error_hook::warn;
+use crate::log_level::LogLevel;
+use crate::types::DataTypeInfo;
+use std::borrow::Cow;
+use std::sync::RwLock;
+
+/// Magic number at the start of every zstd frame.
+pub const ZSTD_MAGIC: [u8; 4] = [0x28, 0xb5, 0x2f, 0xfd];
+
+/// How descriptors are handled before they are registered with eCAL.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct DescriptorPolicy {
+    /// zstd compression level, or `None` to send descriptors as they are.
+    ///
+    /// Requires the `descriptor-compression` feature; ignored with a warning otherwise.
+    /// A compressed descriptor is only used if it is smaller than the original.
+    pub compression_level: Option<i32>,
+    /// Maximum descriptor size in bytes (after compression), or `None` for no limit.
+    ///
+    /// Larger descriptors are dropped with a warning; the topic is then
+    /// registered with type name and encoding only.
+    pub max_size: Option<usize>,
+}
+
+static POLICY: RwLock<DescriptorPolicy> = RwLock::new(DescriptorPolicy {
+    compression_level: None,
+    max_size: None,
+});
+
+impl DescriptorPolicy {
+    /// Sets the policy applied to all publishers and subscribers created afterwards.
+    pub fn set_default(policy: DescriptorPolicy) {
+        *POLICY.write().unwrap_or_else(|e| e.into_inner()) = policy;
+    }
+
+    /// Returns the process-wide policy.
+    pub fn current() -> DescriptorPolicy {
+        *POLICY.read().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Applies the policy to the data type of `topic_name`.
+    pub fn apply(&self, topic_name: &str, mut data_type: DataTypeInfo) -> DataTypeInfo {
+        if data_type.descriptor.is_empty() {
+            return data_type;
+        }
+
+        if let Some(level) = self
+            .compression_level
+            .filter(|_| !data_type.is_descriptor_compressed())
+        {
+            match compress(&data_type.descriptor, level) {
+                Some(compressed) if compressed.len() < data_type.descriptor.len() => {
+                    data_type.descriptor = compressed;
+                }
+                _ => {}
+            }
+        }
+
+        match self.max_size {
+            Some(max_size) if data_type.descriptor.len() > max_size => {
+                warn(
+                    LogLevel::Warning,
+                    &format!(
+                        "descriptor of topic '{}' ({} bytes) exceeds the limit of {} bytes and is not registered",
+                        topic_name,
+                        data_type.descriptor.len(),
+                        max_size
+                    ),
+                );
+                data_type.descriptor.clear();
+            }
+            _ => {}
+        }
+
+        data_type
+    }
+}
+
+#[cfg(feature = "descriptor-compression")]
+fn compress(descriptor: &[u8], level: i32) -> Option<Vec<u8>> {
+    zstd::bulk::compress(descriptor, level).ok()
+}
+
+#[cfg(not(feature = "descriptor-compression"))]
+fn compress(_descriptor: &[u8], _level: i32) -> Option<Vec<u8>> {
+    warn(
+        LogLevel::Warning,
+        "descriptor compression requested, but rustecal was built without the `descriptor-compression` feature",
+    );
+    None
+}
+
+impl DataTypeInfo {
+    /// Returns `true` if the descriptor carries the zstd marker.
+    pub fn is_descriptor_compressed(&self) -> bool {
+        self.descriptor.starts_with(&ZSTD_MAGIC)
+    }
+
+    /// Returns the descriptor in its original, uncompressed form.
+    ///
+    /// Uncompressed descriptors are returned as they are. A compressed
+    /// descriptor that cannot be decoded (corrupt, or the
+    /// `descriptor-compression` feature is disabled) yields an empty
+    /// descriptor, as if the publisher had sent none.
+    pub fn decompressed_descriptor(&self) -> Cow<'_, [u8]> {
+        if !self.is_descriptor_compressed() {
+            return Cow::Borrowed(&self.descriptor);
+        }
+        Cow::Owned(decompress(&self.descriptor).unwrap_or_default())
+    }
+}
+
+#[cfg(feature = "descriptor-compression")]
+fn decompress(descriptor: &[u8]) -> Option<Vec<u8>> {
+    zstd::stream::decode_all(descriptor).ok()
+}
+
+#[cfg(not(feature = "descriptor-compression"))]
+fn decompress(_descriptor: &[u8]) -> Option<Vec<u8>> {
+    None
+}
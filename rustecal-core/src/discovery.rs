@@ -0,0 +1,102 @@
+//! Waiting for communication partners to be discovered.
+//!
+//! eCAL connects publishers, subscribers, servers and clients asynchronously
+//! after they are created, so applications typically wait for a partner
+//! before sending the first message or request. [`wait_until`] implements
+//! that wait once; the publisher, subscriber and client types build their
+//! `wait_for_*` methods on it.
+//!
+//! With the `monitoring` feature, [`wait_for_topic`] and [`wait_for_service`]
+//! wait for partners by name, without creating an entity of their own.
+
+use crate::core::Ecal;
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// Interval at which the `wait_for_*` helpers re-check their condition.
+pub const DISCOVERY_POLL_INTERVAL: Duration = Duration::from_millis(20);
+
+/// Blocks until `condition` returns `true` or `timeout` has elapsed.
+///
+/// The condition is checked immediately and then every
+/// [`DISCOVERY_POLL_INTERVAL`]. Returns `false` on timeout or when eCAL is
+/// shutting down ([`Ecal::ok`] returns `false`).
+pub fn wait_until(timeout: Duration, mut condition: impl FnMut() -> bool) -> bool {
+    let deadline = Instant::now() + timeout;
+    loop {
+        if condition() {
+            return true;
+        }
+        let now = Instant::now();
+        if now >= deadline || !Ecal::ok() {
+            return false;
+        }
+        thread::sleep(DISCOVERY_POLL_INTERVAL.min(deadline - now));
+    }
+}
+
+/// Blocks until `topic_name` has at least one publisher, or `timeout` has elapsed.
+///
+/// Returns `true` if a publisher was found.
+#[cfg(feature = "monitoring")]
+pub fn wait_for_topic(topic_name: &str, timeout: Duration) -> bool {
+    use crate::monitoring::MonitoringEntities;
+    wait_for(topic_name, MonitoringEntities::PUBLISHER, timeout)
+}
+
+/// Blocks until `service_name` has at least one server, or `timeout` has elapsed.
+///
+/// Returns `true` if a server was found.
+#[cfg(feature = "monitoring")]
+pub fn wait_for_service(service_name: &str, timeout: Duration) -> bool {
+    use crate::monitoring::MonitoringEntities;
+    wait_for(service_name, MonitoringEntities::SERVER, timeout)
+}
+
+#[cfg(feature = "monitoring")]
+fn wait_for(
+    name: &str,
+    entities: crate::monitoring::MonitoringEntities,
+    timeout: Duration,
+) -> bool {
+    use crate::monitoring::{Monitoring, MonitoringFilter};
+
+    let filter = MonitoringFilter::new()
+        .entities(entities)
+        .name_pattern(&format!("^{}$", regex::escape(name)))
+        .expect("escaped name is a valid pattern");
+    wait_until(timeout, || {
+        Monitoring::snapshot_filtered(&filter)
+            .map(|snapshot| !snapshot.publishers.is_empty() || !snapshot.servers.is_empty())
+            .unwrap_or(false)
+    })
+}
+
+/// Async variant of [`wait_for_topic`].
+///
+/// The wait runs on a dedicated thread, so the future does not depend on a
+/// particular async runtime.
+#[cfg(all(feature = "tokio", feature = "monitoring"))]
+pub async fn wait_for_topic_async(topic_name: &str, timeout: Duration) -> bool {
+    let topic_name = topic_name.to_string();
+    run_blocking(move || wait_for_topic(&topic_name, timeout)).await
+}
+
+/// Async variant of [`wait_for_service`].
+///
+/// The wait runs on a dedicated thread, so the future does not depend on a
+/// particular async runtime.
+#[cfg(all(feature = "tokio", feature = "monitoring"))]
+pub async fn wait_for_service_async(service_name: &str, timeout: Duration) -> bool {
+    let service_name = service_name.to_string();
+    run_blocking(move || wait_for_service(&service_name, timeout)).await
+}
+
+#[cfg(all(feature = "tokio", feature = "monitoring"))]
+async fn run_blocking(wait: impl FnOnce() -> bool + Send + 'static) -> bool {
+    let (sender, receiver) = tokio::sync::oneshot::channel();
+    thread::spawn(move || {
+        let _ = sender.send(wait());
+    });
+    receiver.await.unwrap_or(false)
+}
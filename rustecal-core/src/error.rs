@@ -11,6 +11,18 @@ pub enum RustecalError {
     #[error("unexpected null pointer")]
     NullPointer,
 
+    /// The topic name is rejected by the process-wide topic policy.
+    #[error("topic '{0}' is not allowed by the topic policy")]
+    TopicNotAllowed(String),
+
+    /// A name pattern is not a valid regular expression.
+    #[error("invalid name pattern: {0}")]
+    InvalidPattern(String),
+
+    /// A topic rename is not of the form `from=to`.
+    #[error("invalid topic remap '{0}', expected 'from=to'")]
+    InvalidRemap(String),
+
     /// A catch‑all for any other internal Rust error.
     #[error("internal error: {0}")]
     Internal(String),
@@ -0,0 +1,126 @@
+//! Panic containment for user callbacks invoked from eCAL threads.
+//!
+//! Subscriber callbacks, service handlers, timer callbacks and payload writers
+//! are called from C code. A panic unwinding across that boundary is undefined
+//! behavior, so every such invocation is wrapped in [`guard`]. A caught panic
+//! is reported to the hook registered with [`ErrorHook::set`] (or as a
+//! warning, if no hook is registered) and the node keeps running.
+//!
+//! Warnings about problems rustecal cannot return as errors, such as handles
+//! still alive at finalize, go to the handler registered with
+//! [`ErrorHook::set_warning_handler`], or else to eCAL logging.
+
+use crate::log_level::LogLevel;
+use std::any::Any;
+use std::panic::{AssertUnwindSafe, catch_unwind};
+use std::sync::{Arc, RwLock};
+
+/// Details about a panic caught in a user callback.
+#[derive(Debug, Clone)]
+pub struct CallbackPanic {
+    /// Where the panic happened (e.g. `"subscriber 'topic'"`).
+    pub source: String,
+    /// The panic message, if it was a string.
+    pub message: String,
+}
+
+type PanicHandler = Arc<dyn Fn(&CallbackPanic) + Send + Sync + 'static>;
+type WarningHandler = Arc<dyn Fn(LogLevel, &str) + Send + Sync + 'static>;
+
+/// Process-wide handler for caught callback panics.
+static ERROR_HOOK: RwLock<Option<PanicHandler>> = RwLock::new(None);
+
+/// Process-wide handler for warnings.
+static WARNING_HOOK: RwLock<Option<WarningHandler>> = RwLock::new(None);
+
+/// Provides access to the process-wide callback error hook.
+pub struct ErrorHook;
+
+impl ErrorHook {
+    /// Registers a handler that is invoked for every panic caught in a callback.
+    ///
+    /// The handler runs on the eCAL thread that invoked the callback.
+    pub fn set<F>(handler: F)
+    where
+        F: Fn(&CallbackPanic) + Send + Sync + 'static,
+    {
+        *ERROR_HOOK.write().unwrap_or_else(|e| e.into_inner()) = Some(Arc::new(handler));
+    }
+
+    /// Registers a handler that is invoked for every warning instead of
+    /// eCAL logging.
+    ///
+    /// Without a handler, warnings are logged via eCAL logging, or dropped
+    /// if rustecal was built without the `logging` feature.
+    pub fn set_warning_handler<F>(handler: F)
+    where
+        F: Fn(LogLevel, &str) + Send + Sync + 'static,
+    {
+        *WARNING_HOOK.write().unwrap_or_else(|e| e.into_inner()) = Some(Arc::new(handler));
+    }
+
+    /// Removes the registered handlers and falls back to logging.
+    pub fn clear() {
+        *ERROR_HOOK.write().unwrap_or_else(|e| e.into_inner()) = None;
+        *WARNING_HOOK.write().unwrap_or_else(|e| e.into_inner()) = None;
+    }
+
+    /// Reports a caught panic to the registered handler.
+    pub fn report(panic: &CallbackPanic) {
+        let handler = ERROR_HOOK.read().unwrap_or_else(|e| e.into_inner()).clone();
+
+        match handler {
+            // a panicking handler must not escape into C either
+            Some(handler) => {
+                let _ = catch_unwind(AssertUnwindSafe(|| handler(panic)));
+            }
+            None => warn(
+                LogLevel::Error,
+                &format!("panic in {} callback: {}", panic.source, panic.message),
+            ),
+        }
+    }
+}
+
+/// Reports a warning to the handler registered with
+/// [`ErrorHook::set_warning_handler`], or else to eCAL logging.
+pub(crate) fn warn(level: LogLevel, text: &str) {
+    let handler = WARNING_HOOK
+        .read()
+        .unwrap_or_else(|e| e.into_inner())
+        .clone();
+
+    let Some(handler) = handler else {
+        #[cfg(feature = "logging")]
+        crate::log::Log::log(level, text);
+        return;
+    };
+    let _ = catch_unwind(AssertUnwindSafe(|| handler(level, text)));
+}
+
+/// Runs `f`, catching any panic so that it cannot unwind into C code.
+///
+/// Returns `None` if `f` panicked; the panic has then been reported via
+/// [`ErrorHook::report`].
+pub fn guard<R>(source: impl FnOnce() -> String, f: impl FnOnce() -> R) -> Option<R> {
+    match catch_unwind(AssertUnwindSafe(f)) {
+        Ok(result) => Some(result),
+        Err(payload) => {
+            ErrorHook::report(&CallbackPanic {
+                source: source(),
+                message: panic_message(payload.as_ref()),
+            });
+            None
+        }
+    }
+}
+
+fn panic_message(payload: &(dyn Any + Send)) -> String {
+    if let Some(s) = payload.downcast_ref::<&str>() {
+        s.to_string()
+    } else if let Some(s) = payload.downcast_ref::<String>() {
+        s.clone()
+    } else {
+        "<non-string panic payload>".to_string()
+    }
+}
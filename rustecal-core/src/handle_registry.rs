@@ -0,0 +1,103 @@
+//! Opt-in tracking of live eCAL entity handles.
+//!
+//! Dropping a publisher, subscriber or service after [`Ecal::finalize`] is a
+//! common misuse. With the registry enabled, every entity created by rustecal
+//! registers itself, and `Ecal::finalize` (or [`EcalGuard::finalize`]) reports
+//! which of them are still alive.
+//!
+//! Tracking is disabled by default and costs a single atomic load per created
+//! entity while disabled.
+//!
+//! [`Ecal::finalize`]: crate::core::Ecal::finalize
+//! [`EcalGuard::finalize`]: crate::core::EcalGuard::finalize
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+
+/// The kind of a tracked handle.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HandleKind {
+    Publisher,
+    Subscriber,
+    ServiceServer,
+    ServiceClient,
+}
+
+/// A handle that was alive when the registry was queried.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LiveHandle {
+    pub kind: HandleKind,
+    /// Topic or service name of the entity.
+    pub name: String,
+}
+
+impl fmt::Display for LiveHandle {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:?} '{}'", self.kind, self.name)
+    }
+}
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+static NEXT_ID: AtomicU64 = AtomicU64::new(0);
+static LIVE: Mutex<BTreeMap<u64, LiveHandle>> = Mutex::new(BTreeMap::new());
+
+/// Registration of a single handle; unregisters itself on drop.
+///
+/// Entity wrappers hold this as a field so that dropping the entity removes
+/// it from the registry.
+#[derive(Debug)]
+pub struct TrackedHandle {
+    id: u64,
+}
+
+impl Drop for TrackedHandle {
+    fn drop(&mut self) {
+        LIVE.lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .remove(&self.id);
+    }
+}
+
+/// Provides access to the process-wide handle registry.
+pub struct HandleRegistry;
+
+impl HandleRegistry {
+    /// Enables or disables tracking of newly created handles.
+    ///
+    /// Handles created while tracking was disabled are never reported.
+    pub fn set_enabled(enabled: bool) {
+        ENABLED.store(enabled, Ordering::Relaxed);
+    }
+
+    /// Returns `true` if tracking is enabled.
+    pub fn is_enabled() -> bool {
+        ENABLED.load(Ordering::Relaxed)
+    }
+
+    /// Registers a new handle, returning `None` if tracking is disabled.
+    pub fn track(kind: HandleKind, name: &str) -> Option<TrackedHandle> {
+        if !Self::is_enabled() {
+            return None;
+        }
+        let id = NEXT_ID.fetch_add(1, Ordering::Relaxed);
+        LIVE.lock().unwrap_or_else(|e| e.into_inner()).insert(
+            id,
+            LiveHandle {
+                kind,
+                name: name.to_string(),
+            },
+        );
+        Some(TrackedHandle { id })
+    }
+
+    /// Returns all tracked handles that are still alive, in creation order.
+    pub fn live_handles() -> Vec<LiveHandle> {
+        LIVE.lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .values()
+            .cloned()
+            .collect()
+    }
+}
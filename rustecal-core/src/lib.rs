@@ -6,24 +6,85 @@
 //! - Initialization (`Ecal::initialize`)
 //! - Finalization (`Ecal::finalize`)
 //! - System status queries and component management.
+//! - Registration events (`Ecal::on_registration`)
+//! - Topic allow/deny lists (`TopicPolicy`)
+//! - Topic prefixes and renames (`TopicRemap`)
+//! - Runtime directory discovery (`Ecal::data_dir`, `Ecal::log_dir`, `Ecal::config_file_used`)
 //!
 //! This crate is typically re-exported by the `rustecal` crate.
+//!
+//! ## Features
+//! - `configuration`: the [`Configuration`] API.
+//! - `logging`: the [`Log`] API and [`LogMessage`].
+//! - `log-backend`: [`log::EcalLogger`], forwarding the `log` crate macros to eCAL
+//!   (not enabled by default).
+//! - `tracing-layer`: [`tracing_layer::EcalLayer`], forwarding `tracing` events to
+//!   eCAL (not enabled by default).
+//! - `monitoring`: the [`monitoring::Monitoring`] API, [`monitoring_watch`] change events
+//!   and the [`monitoring_graph`] connectivity graph.
+//! - `tokio`: [`Log::stream`], an async stream of received log messages, and
+//!   async variants of the [`discovery`] helpers (not enabled by default).
+//! - `serde`: `Serialize`/`Deserialize` for [`LogMessage`], the monitoring
+//!   snapshot types and [`types::DataTypeInfo`] (not enabled by default).
+//! - `descriptor-compression`: zstd compression of type descriptors
+//!   (see [`descriptor`], not enabled by default).
+//!
+//! Except for `log-backend`, `tracing-layer`, `tokio`, `serde` and `descriptor-compression`, all of them are enabled by default. Disabling them produces a minimal
+//! build (e.g. pub/sub only) with fewer eCAL symbols linked in.
 
+pub mod callback_budget;
+pub mod clock;
 pub mod components;
+#[cfg(feature = "configuration")]
 pub mod configuration;
 pub mod core;
 pub mod core_types;
+pub mod descriptor;
+pub mod discovery;
 pub mod error;
+pub mod error_hook;
+pub mod handle_registry;
+#[cfg(feature = "logging")]
 pub mod log;
 pub mod log_level;
+#[cfg(feature = "monitoring")]
 pub mod monitoring;
+#[cfg(feature = "monitoring")]
+pub mod monitoring_graph;
+#[cfg(feature = "monitoring")]
+pub mod monitoring_watch;
+pub mod paths;
+pub mod registration;
+pub mod time;
+pub mod timer;
+pub mod topic_policy;
+pub mod topic_remap;
+#[cfg(feature = "tracing-layer")]
+pub mod tracing_layer;
 pub mod types;
 
 // Re‑exports for ergonomic access:
+pub use callback_budget::CallbackBudget;
+pub use clock::{Clock, EcalClock, SimClock};
 pub use components::EcalComponents;
-pub use configuration::Configuration;
-pub use core::Ecal;
+#[cfg(feature = "configuration")]
+pub use configuration::{Configuration, LogSink};
+pub use core::{Ecal, EcalGuard};
+#[cfg(feature = "logging")]
 pub use core_types::logging::LogMessage;
+pub use descriptor::DescriptorPolicy;
 pub use error::RustecalError;
-pub use log::Log;
+pub use error_hook::{CallbackPanic, ErrorHook};
+pub use handle_registry::HandleRegistry;
+#[cfg(feature = "logging")]
+pub use log::{Log, LogFilter};
 pub use log_level::LogLevel;
+#[cfg(feature = "monitoring")]
+pub use monitoring::{Monitoring, MonitoringEntities, MonitoringFilter};
+#[cfg(feature = "monitoring")]
+pub use monitoring_watch::{MonitoringEvent, MonitoringWatcher};
+pub use registration::{RegistrationEvent, RegistrationEventType};
+pub use time::Time;
+pub use timer::Timer;
+pub use topic_policy::TopicPolicy;
+pub use topic_remap::TopicRemap;
@@ -2,12 +2,200 @@
 //!
 //! This module wraps the C API from `ecal_c/log.h` and provides access to
 //! logging at various severity levels, as well as retrieval of current log entries.
+//!
+//! [`Log::get_logging_filtered`] applies a [`LogFilter`] while the entries
+//! are converted, so only matching entries are copied into Rust strings.
+//!
+//! [`Log::set_receive_callback`] delivers received log messages to a callback
+//! as they arrive; with the `tokio` feature, [`Log::stream`] yields them as an
+//! async [`LogStream`].
+//!
+//! With the `log-backend` feature, [`EcalLogger`] forwards the macros of the
+//! `log` crate (`log::info!` etc.) to eCAL logging.
 
 use crate::core_types::logging::LogMessage;
 use crate::error::RustecalError;
+use crate::error_hook::guard;
 use crate::log_level::LogLevel;
+use std::ffi::CStr;
+use std::os::raw::c_char;
+use std::sync::Mutex;
+use std::thread;
+use std::time::Duration;
 use std::{ffi::CString, ptr, slice};
 
+/// Interval at which the receive thread drains eCAL's log buffer.
+pub const LOG_RECEIVE_INTERVAL: Duration = Duration::from_millis(20);
+
+type ReceiveCallback = Box<dyn Fn(LogMessage) + Send + 'static>;
+
+/// Consumers of the thread draining eCAL's log buffer.
+#[derive(Default)]
+struct Receivers {
+    running: bool,
+    #[cfg(feature = "tokio")]
+    streams: Vec<tokio::sync::mpsc::UnboundedSender<LogMessage>>,
+}
+
+static RECEIVERS: Mutex<Receivers> = Mutex::new(Receivers {
+    running: false,
+    #[cfg(feature = "tokio")]
+    streams: Vec::new(),
+});
+
+/// The callback registered with [`Log::set_receive_callback`]; locked while it runs.
+static RECEIVE_CALLBACK: Mutex<Option<ReceiveCallback>> = Mutex::new(None);
+
+/// Starts the receive thread unless it is running.
+fn start_receiving(receivers: &mut Receivers) {
+    if !receivers.running {
+        receivers.running = true;
+        thread::spawn(receive);
+    }
+}
+
+/// Body of the receive thread shared by the receive callback and all streams.
+///
+/// Every entry is handed to each consumer, so consumers do not compete for
+/// entries. The thread ends once no consumer is left.
+fn receive() {
+    loop {
+        {
+            let mut receivers = RECEIVERS.lock().unwrap();
+            #[cfg(feature = "tokio")]
+            receivers.streams.retain(|stream| !stream.is_closed());
+            #[cfg(feature = "tokio")]
+            let streaming = !receivers.streams.is_empty();
+            #[cfg(not(feature = "tokio"))]
+            let streaming = false;
+            if !streaming && RECEIVE_CALLBACK.lock().unwrap().is_none() {
+                receivers.running = false;
+                return;
+            }
+        }
+        if let Ok(entries) = Log::get_logging() {
+            #[cfg(feature = "tokio")]
+            for stream in &RECEIVERS.lock().unwrap().streams {
+                for entry in &entries {
+                    let _ = stream.send(entry.clone());
+                }
+            }
+            if let Some(callback) = RECEIVE_CALLBACK.lock().unwrap().as_ref() {
+                for entry in entries {
+                    guard(|| "log receive".to_string(), || callback(entry));
+                }
+            }
+        }
+        thread::sleep(LOG_RECEIVE_INTERVAL);
+    }
+}
+
+/// Criteria selecting log entries returned by [`Log::get_logging_filtered`].
+///
+/// All criteria left unset match every entry.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LogFilter {
+    /// Least severe level to accept; `Debug4` < … < `Debug1` < `Info` < `Warning` < `Error` < `Fatal`.
+    pub min_level: Option<LogLevel>,
+    /// Host name the entry must originate from.
+    pub host_name: Option<String>,
+    /// Unit name the entry must originate from.
+    pub unit_name: Option<String>,
+    /// Earliest accepted timestamp (µs, inclusive).
+    pub since: Option<i64>,
+    /// Latest accepted timestamp (µs, exclusive).
+    pub until: Option<i64>,
+}
+
+impl LogFilter {
+    /// Creates a filter matching all entries.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Accepts only entries at `level` or more severe.
+    pub fn min_level(mut self, level: LogLevel) -> Self {
+        self.min_level = Some(level);
+        self
+    }
+
+    /// Accepts only entries from `host_name`.
+    pub fn host_name(mut self, host_name: &str) -> Self {
+        self.host_name = Some(host_name.to_string());
+        self
+    }
+
+    /// Accepts only entries from `unit_name`.
+    pub fn unit_name(mut self, unit_name: &str) -> Self {
+        self.unit_name = Some(unit_name.to_string());
+        self
+    }
+
+    /// Accepts only entries with `since <= timestamp < until` (µs).
+    pub fn time_range(mut self, since: i64, until: i64) -> Self {
+        self.since = Some(since);
+        self.until = Some(until);
+        self
+    }
+
+    /// Returns `true` if `message` passes the filter.
+    pub fn matches(&self, message: &LogMessage) -> bool {
+        self.accepts(
+            message.level,
+            message.timestamp,
+            message.host_name.as_bytes(),
+            message.thread_name.as_bytes(),
+        )
+    }
+
+    fn matches_raw(&self, raw: &rustecal_sys::eCAL_Logging_SLogMessage) -> bool {
+        self.accepts(
+            LogLevel::from(raw.level),
+            raw.time,
+            cstr_bytes(raw.host_name),
+            cstr_bytes(raw.unit_name),
+        )
+    }
+
+    fn accepts(&self, level: LogLevel, timestamp: i64, host_name: &[u8], unit_name: &[u8]) -> bool {
+        self.min_level
+            .is_none_or(|min| severity(level) >= severity(min))
+            && self.since.is_none_or(|since| timestamp >= since)
+            && self.until.is_none_or(|until| timestamp < until)
+            && self
+                .host_name
+                .as_ref()
+                .is_none_or(|host| host.as_bytes() == host_name)
+            && self
+                .unit_name
+                .as_ref()
+                .is_none_or(|unit| unit.as_bytes() == unit_name)
+    }
+}
+
+/// Orders the levels by severity; `None` and `All` rank below everything.
+fn severity(level: LogLevel) -> u8 {
+    match level {
+        LogLevel::None | LogLevel::All => 0,
+        LogLevel::Debug4 => 1,
+        LogLevel::Debug3 => 2,
+        LogLevel::Debug2 => 3,
+        LogLevel::Debug1 => 4,
+        LogLevel::Info => 5,
+        LogLevel::Warning => 6,
+        LogLevel::Error => 7,
+        LogLevel::Fatal => 8,
+    }
+}
+
+fn cstr_bytes<'a>(ptr: *const c_char) -> &'a [u8] {
+    if ptr.is_null() {
+        &[]
+    } else {
+        unsafe { CStr::from_ptr(ptr).to_bytes() }
+    }
+}
+
 /// Provides logging functions to emit and retrieve messages via the eCAL runtime.
 pub struct Log;
 
@@ -24,6 +212,63 @@ impl Log {
         }
     }
 
+    /// Emits a message attributed to a component of the application.
+    ///
+    /// The component is prepended as `[component]`, e.g. `[planner] replanning`.
+    pub fn log_component(level: LogLevel, component: &str, message: &str) {
+        Self::log(level, &format!("[{component}] {message}"));
+    }
+
+    /// Registers a callback invoked for every received log message.
+    ///
+    /// eCAL's C API offers no push notification for log messages, so a
+    /// dedicated thread drains the receive buffer every [`LOG_RECEIVE_INTERVAL`]
+    /// and invokes the callback for each entry in order. The thread is shared
+    /// with all [`Log::stream`]s, which receive the same entries. Requires the
+    /// logging receiver to be enabled (`EcalComponents::LOGGING`).
+    ///
+    /// Replaces a previously registered callback, so it must not be called
+    /// from within the callback. While a callback or stream is registered,
+    /// [`Log::get_logging`] competes for the same entries.
+    pub fn set_receive_callback<F>(callback: F)
+    where
+        F: Fn(LogMessage) + Send + 'static,
+    {
+        *RECEIVE_CALLBACK.lock().unwrap() = Some(Box::new(callback));
+        start_receiving(&mut RECEIVERS.lock().unwrap());
+    }
+
+    /// Removes the callback registered with [`Log::set_receive_callback`].
+    ///
+    /// Blocks until a running callback invocation has finished, so it must not
+    /// be called from within the callback.
+    pub fn remove_receive_callback() {
+        RECEIVE_CALLBACK.lock().unwrap().take();
+    }
+
+    /// Returns an async stream of received log messages.
+    ///
+    /// The stream is fed by the thread of [`Log::set_receive_callback`],
+    /// which drains the receive buffer every [`LOG_RECEIVE_INTERVAL`]. Every
+    /// stream and the receive callback get all entries; the thread stops once
+    /// none of them is left. The stream does not depend on a particular async
+    /// runtime.
+    ///
+    /// ```ignore
+    /// let mut stream = Log::stream();
+    /// while let Some(msg) = stream.next().await {
+    ///     println!("{}: {}", msg.process_name, msg.content);
+    /// }
+    /// ```
+    #[cfg(feature = "tokio")]
+    pub fn stream() -> LogStream {
+        let (sender, receiver) = tokio::sync::mpsc::unbounded_channel();
+        let mut receivers = RECEIVERS.lock().unwrap();
+        receivers.streams.push(sender);
+        start_receiving(&mut receivers);
+        LogStream { receiver }
+    }
+
     /// Fetches all current log messages stored in the eCAL runtime.
     ///
     /// If there are no logs available, returns an empty `Vec`.
@@ -33,6 +278,18 @@ impl Log {
     /// - `RustecalError::NullPointer` if the C API returns a null pointer
     ///   when a snapshot *should* have been provided.
     pub fn get_logging() -> Result<Vec<LogMessage>, RustecalError> {
+        Self::get_logging_filtered(&LogFilter::default())
+    }
+
+    /// Fetches the current log messages that pass `filter`.
+    ///
+    /// Entries are filtered before they are converted, so rejected entries
+    /// cost no allocation. Rejected entries are consumed as well.
+    ///
+    /// # Errors
+    ///
+    /// See [`Log::get_logging`].
+    pub fn get_logging_filtered(filter: &LogFilter) -> Result<Vec<LogMessage>, RustecalError> {
         // 1) Prepare a null pointer for the C function to fill in.
         let mut raw_ptr: *mut rustecal_sys::eCAL_Logging_SLogging = ptr::null_mut();
 
@@ -53,10 +310,15 @@ impl Log {
             let raw_messages = logging.log_messages;
             let len = logging.log_messages_length;
 
-            let entries = slice::from_raw_parts(raw_messages, len)
-                .iter()
-                .map(|msg| LogMessage::from(*msg))
-                .collect();
+            let entries = if raw_messages.is_null() {
+                Vec::new()
+            } else {
+                slice::from_raw_parts(raw_messages, len)
+                    .iter()
+                    .filter(|msg| filter.matches_raw(msg))
+                    .map(|msg| LogMessage::from(*msg))
+                    .collect()
+            };
 
             // free the C buffer
             rustecal_sys::eCAL_Free(raw_ptr as *mut _);
@@ -67,3 +329,95 @@ impl Log {
         Ok(logs)
     }
 }
+
+/// Async stream of received log messages, created by [`Log::stream`].
+#[cfg(feature = "tokio")]
+pub struct LogStream {
+    receiver: tokio::sync::mpsc::UnboundedReceiver<LogMessage>,
+}
+
+#[cfg(feature = "tokio")]
+impl futures_core::Stream for LogStream {
+    type Item = LogMessage;
+
+    fn poll_next(
+        mut self: std::pin::Pin<&mut Self>,
+        cx: &mut std::task::Context<'_>,
+    ) -> std::task::Poll<Option<LogMessage>> {
+        self.receiver.poll_recv(cx)
+    }
+}
+
+/// A [`log::Log`] implementation forwarding records to eCAL logging.
+///
+/// Levels are mapped as `Error` → [`LogLevel::Error`], `Warn` → [`LogLevel::Warning`],
+/// `Info` → [`LogLevel::Info`], `Debug` → [`LogLevel::Debug1`] and
+/// `Trace` → [`LogLevel::Debug2`]. eCAL attaches the unit name passed to
+/// [`Ecal::initialize`](crate::Ecal::initialize) to every message; the record's
+/// target is prepended as `[target]`.
+#[cfg(feature = "log-backend")]
+pub struct EcalLogger {
+    max_level: ::log::LevelFilter,
+}
+
+#[cfg(feature = "log-backend")]
+impl EcalLogger {
+    /// Creates a logger forwarding all levels up to `Trace`.
+    pub fn new() -> Self {
+        Self {
+            max_level: ::log::LevelFilter::Trace,
+        }
+    }
+
+    /// Restricts the forwarded records to `max_level` and above.
+    pub fn with_max_level(mut self, max_level: ::log::LevelFilter) -> Self {
+        self.max_level = max_level;
+        self
+    }
+
+    /// Installs this logger as the global `log` backend.
+    ///
+    /// # Errors
+    ///
+    /// Fails if another global logger was installed before.
+    pub fn init(self) -> Result<(), ::log::SetLoggerError> {
+        ::log::set_max_level(self.max_level);
+        ::log::set_boxed_logger(Box::new(self))
+    }
+
+    fn map_level(level: ::log::Level) -> LogLevel {
+        match level {
+            ::log::Level::Error => LogLevel::Error,
+            ::log::Level::Warn => LogLevel::Warning,
+            ::log::Level::Info => LogLevel::Info,
+            ::log::Level::Debug => LogLevel::Debug1,
+            ::log::Level::Trace => LogLevel::Debug2,
+        }
+    }
+}
+
+#[cfg(feature = "log-backend")]
+impl Default for EcalLogger {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(feature = "log-backend")]
+impl ::log::Log for EcalLogger {
+    fn enabled(&self, metadata: &::log::Metadata) -> bool {
+        metadata.level() <= self.max_level
+    }
+
+    fn log(&self, record: &::log::Record) {
+        if !self.enabled(record.metadata()) {
+            return;
+        }
+        Log::log(
+            Self::map_level(record.level()),
+            &format!("[{}] {}", record.target(), record.args()),
+        );
+    }
+
+    fn flush(&self) {}
+}
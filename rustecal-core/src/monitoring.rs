@@ -2,17 +2,228 @@
 //!
 //! This module wraps the C API `eCAL_Monitoring_GetMonitoring` and provides
 //! a safe Rust API to access a snapshot of the middleware's state.
+//!
+//! [`Monitoring::snapshot`] returns everything eCAL Monitor shows: processes,
+//! publishers and subscribers (with data type, frequency and payload size),
+//! service servers and clients. [`Monitoring::snapshot_of`] restricts the
+//! snapshot to selected [`MonitoringEntities`], which is cheaper on large systems.
+//! [`Monitoring::snapshot_filtered`] additionally selects entities by name
+//! pattern, unit and host with a [`MonitoringFilter`]; rejected entities are
+//! skipped before any Rust value is built for them.
 
 use crate::core_types::monitoring::{
-    ClientInfo, MonitoringSnapshot, ProcessInfo, ServerInfo, TopicInfo,
+    HostInfo, MonitoringSnapshot, ProcessInfo, TopicInfo, TopicStats, collect_filtered, cstr_text,
 };
 use crate::error::RustecalError;
-use std::{ptr, slice};
+use bitflags::bitflags;
+use regex::Regex;
+use std::os::raw::c_char;
+use std::ptr;
+
+bitflags! {
+    /// Entity kinds included in a monitoring snapshot.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub struct MonitoringEntities: u32 {
+        const PUBLISHER  = rustecal_sys::eCAL_Monitoring_Entity_Publisher;
+        const SUBSCRIBER = rustecal_sys::eCAL_Monitoring_Entity_Subscriber;
+        const SERVER     = rustecal_sys::eCAL_Monitoring_Entity_Server;
+        const CLIENT     = rustecal_sys::eCAL_Monitoring_Entity_Client;
+        const PROCESS    = rustecal_sys::eCAL_Monitoring_Entity_Process;
+        const HOST       = rustecal_sys::eCAL_Monitoring_Entity_Host;
+        const ALL        = rustecal_sys::eCAL_Monitoring_Entity_All;
+    }
+}
+
+/// Criteria selecting the entities of [`Monitoring::snapshot_filtered`].
+///
+/// All criteria left unset match every entity.
+#[derive(Debug, Clone)]
+pub struct MonitoringFilter {
+    /// Entity kinds to include.
+    pub entities: MonitoringEntities,
+    /// Pattern the topic name (publishers, subscribers) or service name
+    /// (servers, clients) must match. Processes are not affected.
+    pub name_pattern: Option<Regex>,
+    /// Unit name the entity must belong to.
+    pub unit_name: Option<String>,
+    /// Host name the entity must run on.
+    pub host_name: Option<String>,
+}
+
+impl Default for MonitoringFilter {
+    fn default() -> Self {
+        Self {
+            entities: MonitoringEntities::ALL,
+            name_pattern: None,
+            unit_name: None,
+            host_name: None,
+        }
+    }
+}
+
+impl MonitoringFilter {
+    /// Creates a filter matching all entities.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Includes only the given entity kinds.
+    pub fn entities(mut self, entities: MonitoringEntities) -> Self {
+        self.entities = entities;
+        self
+    }
+
+    /// Accepts only topics and services whose name matches the regular expression `pattern`.
+    ///
+    /// The pattern is unanchored; use `^…$` to match whole names.
+    ///
+    /// # Errors
+    ///
+    /// Returns `RustecalError::InvalidPattern` if `pattern` is not a valid regular expression.
+    pub fn name_pattern(mut self, pattern: &str) -> Result<Self, RustecalError> {
+        let regex =
+            Regex::new(pattern).map_err(|e| RustecalError::InvalidPattern(e.to_string()))?;
+        self.name_pattern = Some(regex);
+        Ok(self)
+    }
+
+    /// Accepts only entities of `unit_name`.
+    pub fn unit_name(mut self, unit_name: &str) -> Self {
+        self.unit_name = Some(unit_name.to_string());
+        self
+    }
+
+    /// Accepts only entities on `host_name`.
+    pub fn host_name(mut self, host_name: &str) -> Self {
+        self.host_name = Some(host_name.to_string());
+        self
+    }
+
+    /// Returns `true` if an entity with the given names passes the filter.
+    ///
+    /// `name` is the topic or service name, or `None` for processes.
+    pub fn matches(&self, host_name: &str, unit_name: &str, name: Option<&str>) -> bool {
+        self.host_name
+            .as_deref()
+            .is_none_or(|host| host == host_name)
+            && self
+                .unit_name
+                .as_deref()
+                .is_none_or(|unit| unit == unit_name)
+            && match (&self.name_pattern, name) {
+                (Some(pattern), Some(name)) => pattern.is_match(name),
+                _ => true,
+            }
+    }
+
+    fn matches_raw(
+        &self,
+        host_name: *const c_char,
+        unit_name: *const c_char,
+        name: Option<*const c_char>,
+    ) -> bool {
+        self.matches(
+            &cstr_text(host_name),
+            &cstr_text(unit_name),
+            name.map(cstr_text).as_deref(),
+        )
+    }
+}
 
 /// Provides access to eCAL runtime monitoring data.
 pub struct Monitoring;
 
 impl Monitoring {
+    /// Retrieves a full snapshot of the current system state.
+    ///
+    /// Equivalent to [`Monitoring::snapshot_of`] with [`MonitoringEntities::ALL`].
+    ///
+    /// # Errors
+    ///
+    /// See [`Monitoring::get_snapshot`].
+    pub fn snapshot() -> Result<MonitoringSnapshot, RustecalError> {
+        Self::snapshot_of(MonitoringEntities::ALL)
+    }
+
+    /// Returns the traffic statistics of `topic_name`, or `None` if the topic
+    /// is currently neither published nor subscribed.
+    ///
+    /// # Errors
+    ///
+    /// See [`Monitoring::get_snapshot`].
+    pub fn topic_stats(topic_name: &str) -> Result<Option<TopicStats>, RustecalError> {
+        Ok(Self::topic_snapshot()?.topic_stats(topic_name))
+    }
+
+    /// Returns the traffic statistics of all topics, sorted by topic name.
+    ///
+    /// # Errors
+    ///
+    /// See [`Monitoring::get_snapshot`].
+    pub fn all_topic_stats() -> Result<Vec<TopicStats>, RustecalError> {
+        Ok(Self::topic_snapshot()?.all_topic_stats())
+    }
+
+    /// Returns the current publishers of `topic_name`.
+    ///
+    /// # Errors
+    ///
+    /// See [`Monitoring::get_snapshot`].
+    pub fn publishers_of(topic_name: &str) -> Result<Vec<TopicInfo>, RustecalError> {
+        Ok(Self::named_snapshot(topic_name, MonitoringEntities::PUBLISHER)?.publishers)
+    }
+
+    /// Returns the current subscribers of `topic_name`.
+    ///
+    /// # Errors
+    ///
+    /// See [`Monitoring::get_snapshot`].
+    pub fn subscribers_of(topic_name: &str) -> Result<Vec<TopicInfo>, RustecalError> {
+        Ok(Self::named_snapshot(topic_name, MonitoringEntities::SUBSCRIBER)?.subscribers)
+    }
+
+    fn named_snapshot(
+        name: &str,
+        entities: MonitoringEntities,
+    ) -> Result<MonitoringSnapshot, RustecalError> {
+        let filter = MonitoringFilter::new()
+            .entities(entities)
+            .name_pattern(&format!("^{}$", regex::escape(name)))?;
+        Self::snapshot_filtered(&filter)
+    }
+
+    /// Returns the process owning the publisher, subscriber, server or client
+    /// with the given entity ID, or `None` if the entity is unknown.
+    ///
+    /// Subscribers receive the entity ID of the sending publisher with every
+    /// sample, so this resolves who sent a sample.
+    ///
+    /// # Errors
+    ///
+    /// See [`Monitoring::get_snapshot`].
+    pub fn process_of(entity_id: u64) -> Result<Option<ProcessInfo>, RustecalError> {
+        Ok(Self::snapshot()?.process_of(entity_id).cloned())
+    }
+
+    /// Returns the hosts of the current system with the entities running on
+    /// them, sorted by host name.
+    ///
+    /// # Errors
+    ///
+    /// See [`Monitoring::get_snapshot`].
+    pub fn hosts() -> Result<Vec<HostInfo>, RustecalError> {
+        let entities = MonitoringEntities::PROCESS
+            | MonitoringEntities::PUBLISHER
+            | MonitoringEntities::SUBSCRIBER
+            | MonitoringEntities::SERVER
+            | MonitoringEntities::CLIENT;
+        Ok(Self::snapshot_of(entities)?.hosts())
+    }
+
+    fn topic_snapshot() -> Result<MonitoringSnapshot, RustecalError> {
+        Self::snapshot_of(MonitoringEntities::PUBLISHER | MonitoringEntities::SUBSCRIBER)
+    }
+
     /// Retrieves a snapshot of the current system state from the eCAL runtime.
     ///
     /// If no eCAL instances are running (nothing to monitor), returns an
@@ -24,21 +235,41 @@ impl Monitoring {
     /// - `RustecalError::NullPointer` if the C API returns a null pointer
     ///   when a snapshot *should* have been provided.
     pub fn get_snapshot() -> Result<MonitoringSnapshot, RustecalError> {
+        Self::snapshot_of(MonitoringEntities::ALL)
+    }
+
+    /// Retrieves a snapshot containing only the selected entity kinds.
+    ///
+    /// The lists of all other kinds are left empty.
+    ///
+    /// # Errors
+    ///
+    /// See [`Monitoring::get_snapshot`].
+    pub fn snapshot_of(entities: MonitoringEntities) -> Result<MonitoringSnapshot, RustecalError> {
+        Self::snapshot_filtered(&MonitoringFilter::new().entities(entities))
+    }
+
+    /// Retrieves a snapshot containing only the entities that pass `filter`.
+    ///
+    /// Entities are filtered before they are converted, so rejected entities
+    /// cost no allocation.
+    ///
+    /// # Errors
+    ///
+    /// See [`Monitoring::get_snapshot`].
+    pub fn snapshot_filtered(
+        filter: &MonitoringFilter,
+    ) -> Result<MonitoringSnapshot, RustecalError> {
         // 1) Prepare a null pointer for the C function to fill in
         let mut raw: *mut rustecal_sys::eCAL_Monitoring_SMonitoring = ptr::null_mut();
+        let entities: std::os::raw::c_uint = filter.entities.bits();
 
         // 2) Call the FFI: non‑zero means “no snapshot available”
-        let ret = unsafe { rustecal_sys::eCAL_Monitoring_GetMonitoring(&mut raw, ptr::null()) };
+        let ret = unsafe { rustecal_sys::eCAL_Monitoring_GetMonitoring(&mut raw, &entities) };
 
         // 3) If nothing to monitor, return an empty snapshot
         if ret != 0 {
-            return Ok(MonitoringSnapshot {
-                processes: Vec::new(),
-                publishers: Vec::new(),
-                subscribers: Vec::new(),
-                servers: Vec::new(),
-                clients: Vec::new(),
-            });
+            return Ok(MonitoringSnapshot::default());
         }
 
         // 4) On success (ret == 0), ensure we got a valid pointer
@@ -48,61 +279,29 @@ impl Monitoring {
 
         // 5) Build the snapshot and free the C‑allocated memory
         let snapshot = unsafe {
-            let processes = {
-                let cnt = (*raw).processes_length;
-                let ptr = (*raw).processes;
-                slice::from_raw_parts(ptr, cnt)
-                    .iter()
-                    .map(|r| ProcessInfo::from(*r))
-                    .collect()
-            };
-
-            let publishers = {
-                let cnt = (*raw).publishers_length;
-                let ptr = (*raw).publishers;
-                slice::from_raw_parts(ptr, cnt)
-                    .iter()
-                    .map(|r| TopicInfo::from(*r))
-                    .collect()
-            };
-
-            let subscribers = {
-                let cnt = (*raw).subscribers_length;
-                let ptr = (*raw).subscribers;
-                slice::from_raw_parts(ptr, cnt)
-                    .iter()
-                    .map(|r| TopicInfo::from(*r))
-                    .collect()
-            };
-
-            let servers = {
-                let cnt = (*raw).servers_length;
-                let ptr = (*raw).servers;
-                slice::from_raw_parts(ptr, cnt)
-                    .iter()
-                    .map(|r| ServerInfo::from(*r))
-                    .collect()
-            };
-
-            let clients = {
-                let cnt = (*raw).clients_length;
-                let ptr = (*raw).clients;
-                slice::from_raw_parts(ptr, cnt)
-                    .iter()
-                    .map(|r| ClientInfo::from(*r))
-                    .collect()
+            let m = &*raw;
+            let snapshot = MonitoringSnapshot {
+                processes: collect_filtered(m.processes, m.processes_length, |p| {
+                    filter.matches_raw(p.host_name, p.unit_name, None)
+                }),
+                publishers: collect_filtered(m.publishers, m.publishers_length, |t| {
+                    filter.matches_raw(t.host_name, t.unit_name, Some(t.topic_name))
+                }),
+                subscribers: collect_filtered(m.subscribers, m.subscribers_length, |t| {
+                    filter.matches_raw(t.host_name, t.unit_name, Some(t.topic_name))
+                }),
+                servers: collect_filtered(m.servers, m.servers_length, |s| {
+                    filter.matches_raw(s.host_name, s.unit_name, Some(s.service_name))
+                }),
+                clients: collect_filtered(m.clients, m.clients_length, |c| {
+                    filter.matches_raw(c.host_name, c.unit_name, Some(c.service_name))
+                }),
             };
 
             // free the C‑allocated snapshot
             rustecal_sys::eCAL_Free(raw as *mut _);
 
-            MonitoringSnapshot {
-                processes,
-                publishers,
-                subscribers,
-                servers,
-                clients,
-            }
+            snapshot
         };
 
         Ok(snapshot)
@@ -0,0 +1,240 @@
+//! Connectivity graph of a running eCAL system.
+//!
+//! [`Monitoring::graph`] turns a monitoring snapshot into a [`SystemGraph`]:
+//! every process is a node, every topic connects its publishing processes to
+//! its subscribing processes, and every service connects its client processes
+//! to its server processes. The graph can be exported as Graphviz DOT
+//! ([`SystemGraph::to_dot`]) or, with the `serde` feature, JSON
+//! ([`SystemGraph::to_json`]) to generate architecture diagrams from a live
+//! system.
+
+use crate::core_types::monitoring::MonitoringSnapshot;
+use crate::error::RustecalError;
+use crate::monitoring::{Monitoring, MonitoringEntities};
+use std::collections::BTreeMap;
+use std::fmt::Write;
+
+/// A process of the system.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct GraphNode {
+    /// Unique node ID, `host_name:process_id`.
+    pub id: String,
+    pub host_name: String,
+    pub process_id: i32,
+    /// Unit name of the process.
+    pub name: String,
+}
+
+/// Whether an edge stands for a topic or a service.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "lowercase"))]
+pub enum EdgeKind {
+    /// Data flows from a publisher (`from`) to a subscriber (`to`).
+    Topic,
+    /// A client (`from`) calls a server (`to`).
+    Service,
+}
+
+/// A topic or service connecting two processes.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct GraphEdge {
+    pub from: String,
+    pub to: String,
+    pub kind: EdgeKind,
+    /// Topic or service name.
+    pub name: String,
+    /// Type name of the topic (empty for services).
+    pub type_name: String,
+}
+
+/// Processes and their pub/sub and service connections.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SystemGraph {
+    /// Nodes, sorted by ID.
+    pub nodes: Vec<GraphNode>,
+    /// Edges, sorted by kind, name, source and target.
+    pub edges: Vec<GraphEdge>,
+}
+
+/// A process as referenced by a topic or service entry.
+struct Endpoint<'a> {
+    host_name: &'a str,
+    process_id: i32,
+    unit_name: &'a str,
+}
+
+impl Endpoint<'_> {
+    fn id(&self) -> String {
+        format!("{}:{}", self.host_name, self.process_id)
+    }
+}
+
+impl SystemGraph {
+    /// Builds the graph of `snapshot`.
+    ///
+    /// Processes referenced by topics or services but missing from the
+    /// snapshot's process list are added as nodes as well.
+    pub fn from_snapshot(snapshot: &MonitoringSnapshot) -> Self {
+        let mut nodes: BTreeMap<String, GraphNode> = BTreeMap::new();
+        let mut add_node = |endpoint: &Endpoint| -> String {
+            let id = endpoint.id();
+            nodes.entry(id.clone()).or_insert_with(|| GraphNode {
+                id: id.clone(),
+                host_name: endpoint.host_name.to_string(),
+                process_id: endpoint.process_id,
+                name: endpoint.unit_name.to_string(),
+            });
+            id
+        };
+
+        for process in &snapshot.processes {
+            add_node(&Endpoint {
+                host_name: &process.host_name,
+                process_id: process.process_id,
+                unit_name: &process.unit_name,
+            });
+        }
+
+        // (kind, name, from, to) -> type name
+        let mut edges: BTreeMap<(EdgeKind, String, String, String), String> = BTreeMap::new();
+
+        let mut subscribers: BTreeMap<&str, Vec<String>> = BTreeMap::new();
+        for sub in &snapshot.subscribers {
+            let id = add_node(&Endpoint {
+                host_name: &sub.host_name,
+                process_id: sub.process_id,
+                unit_name: &sub.unit_name,
+            });
+            subscribers.entry(&sub.topic_name).or_default().push(id);
+        }
+        for publisher in &snapshot.publishers {
+            let from = add_node(&Endpoint {
+                host_name: &publisher.host_name,
+                process_id: publisher.process_id,
+                unit_name: &publisher.unit_name,
+            });
+            for to in subscribers
+                .get(publisher.topic_name.as_str())
+                .into_iter()
+                .flatten()
+            {
+                edges
+                    .entry((
+                        EdgeKind::Topic,
+                        publisher.topic_name.clone(),
+                        from.clone(),
+                        to.clone(),
+                    ))
+                    .or_insert_with(|| publisher.data_type.type_name.clone());
+            }
+        }
+
+        let mut servers: BTreeMap<&str, Vec<String>> = BTreeMap::new();
+        for server in &snapshot.servers {
+            let id = add_node(&Endpoint {
+                host_name: &server.host_name,
+                process_id: server.process_id,
+                unit_name: &server.unit_name,
+            });
+            servers.entry(&server.service_name).or_default().push(id);
+        }
+        for client in &snapshot.clients {
+            let from = add_node(&Endpoint {
+                host_name: &client.host_name,
+                process_id: client.process_id,
+                unit_name: &client.unit_name,
+            });
+            for to in servers
+                .get(client.service_name.as_str())
+                .into_iter()
+                .flatten()
+            {
+                edges
+                    .entry((
+                        EdgeKind::Service,
+                        client.service_name.clone(),
+                        from.clone(),
+                        to.clone(),
+                    ))
+                    .or_default();
+            }
+        }
+
+        SystemGraph {
+            nodes: nodes.into_values().collect(),
+            edges: edges
+                .into_iter()
+                .map(|((kind, name, from, to), type_name)| GraphEdge {
+                    from,
+                    to,
+                    kind,
+                    name,
+                    type_name,
+                })
+                .collect(),
+        }
+    }
+
+    /// Renders the graph in Graphviz DOT format.
+    ///
+    /// Topics are drawn as solid edges, services as dashed edges.
+    pub fn to_dot(&self) -> String {
+        let mut dot = String::from("digraph ecal {\n  rankdir=LR;\n  node [shape=box];\n");
+        for node in &self.nodes {
+            let _ = writeln!(
+                dot,
+                "  \"{}\" [label=\"{}\\n{}\"];",
+                dot_escape(&node.id),
+                dot_escape(&node.name),
+                dot_escape(&node.id)
+            );
+        }
+        for edge in &self.edges {
+            let style = match edge.kind {
+                EdgeKind::Topic => "",
+                EdgeKind::Service => ", style=dashed",
+            };
+            let _ = writeln!(
+                dot,
+                "  \"{}\" -> \"{}\" [label=\"{}\"{}];",
+                dot_escape(&edge.from),
+                dot_escape(&edge.to),
+                dot_escape(&edge.name),
+                style
+            );
+        }
+        dot.push_str("}\n");
+        dot
+    }
+
+    /// Renders the graph as JSON object with `nodes` and `edges` arrays,
+    /// the same shape its `Serialize` implementation produces.
+    #[cfg(feature = "serde")]
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).expect("graph types always serialize to JSON")
+    }
+}
+
+fn dot_escape(text: &str) -> String {
+    text.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+impl Monitoring {
+    /// Returns the connectivity graph of the current system.
+    ///
+    /// # Errors
+    ///
+    /// See [`Monitoring::get_snapshot`].
+    pub fn graph() -> Result<SystemGraph, RustecalError> {
+        let entities = MonitoringEntities::PROCESS
+            | MonitoringEntities::PUBLISHER
+            | MonitoringEntities::SUBSCRIBER
+            | MonitoringEntities::SERVER
+            | MonitoringEntities::CLIENT;
+        Ok(SystemGraph::from_snapshot(&Self::snapshot_of(entities)?))
+    }
+}
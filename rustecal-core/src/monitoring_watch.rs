@@ -0,0 +1,245 @@
+//! Change events derived from consecutive monitoring snapshots.
+//!
+//! [`Monitoring::watch`] polls [`Monitoring::snapshot`] at a fixed interval
+//! and reports the differences between consecutive snapshots as
+//! [`MonitoringEvent`]s, so supervisors can react to processes crashing or
+//! topics disappearing without writing their own diff logic.
+//!
+//! Entities are identified by host and process ID (processes), topic ID
+//! (publishers and subscribers) or service ID (servers and clients). An
+//! entity counts as changed when its state changes, e.g. a process's
+//! severity, a topic's data type or connection count, or a service's methods;
+//! counters that change with every registration (clocks, frequencies, call
+//! counts) are ignored. [`diff`] exposes the comparison for snapshots taken
+//! by other means.
+
+use crate::core_types::monitoring::{
+    ClientInfo, MethodInfo, MonitoringSnapshot, ProcessInfo, ServerInfo, TopicInfo,
+};
+use crate::error_hook::guard;
+use crate::monitoring::Monitoring;
+use std::collections::{HashMap, HashSet};
+use std::hash::Hash;
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError, Sender};
+use std::thread::{self, JoinHandle};
+use std::time::Duration;
+
+/// A single entity of a monitoring snapshot.
+#[derive(Debug, Clone)]
+pub enum MonitoringEntity {
+    Process(ProcessInfo),
+    Publisher(TopicInfo),
+    Subscriber(TopicInfo),
+    Server(ServerInfo),
+    Client(ClientInfo),
+}
+
+impl MonitoringEntity {
+    /// Returns the process name, topic name or service name of the entity.
+    pub fn name(&self) -> &str {
+        match self {
+            MonitoringEntity::Process(process) => &process.process_name,
+            MonitoringEntity::Publisher(topic) | MonitoringEntity::Subscriber(topic) => {
+                &topic.topic_name
+            }
+            MonitoringEntity::Server(server) => &server.service_name,
+            MonitoringEntity::Client(client) => &client.service_name,
+        }
+    }
+}
+
+/// A difference between two monitoring snapshots.
+#[derive(Debug, Clone)]
+pub enum MonitoringEvent {
+    /// The entity appeared.
+    EntityAdded(MonitoringEntity),
+    /// The entity disappeared, e.g. because its process terminated.
+    EntityRemoved(MonitoringEntity),
+    /// The state of the entity changed.
+    EntityChanged {
+        previous: Box<MonitoringEntity>,
+        current: Box<MonitoringEntity>,
+    },
+}
+
+/// Returns the events that turn `previous` into `current`.
+///
+/// Events are ordered by entity kind (processes, publishers, subscribers,
+/// servers, clients); within a kind, removals come first, followed by
+/// additions and changes in the order of `current`.
+pub fn diff(previous: &MonitoringSnapshot, current: &MonitoringSnapshot) -> Vec<MonitoringEvent> {
+    let mut events = Vec::new();
+    diff_entities(
+        &previous.processes,
+        &current.processes,
+        |p| (p.host_name.clone(), p.process_id),
+        process_changed,
+        MonitoringEntity::Process,
+        &mut events,
+    );
+    diff_entities(
+        &previous.publishers,
+        &current.publishers,
+        |t| t.topic_id,
+        topic_changed,
+        MonitoringEntity::Publisher,
+        &mut events,
+    );
+    diff_entities(
+        &previous.subscribers,
+        &current.subscribers,
+        |t| t.topic_id,
+        topic_changed,
+        MonitoringEntity::Subscriber,
+        &mut events,
+    );
+    diff_entities(
+        &previous.servers,
+        &current.servers,
+        |s| s.service_id,
+        server_changed,
+        MonitoringEntity::Server,
+        &mut events,
+    );
+    diff_entities(
+        &previous.clients,
+        &current.clients,
+        |c| c.service_id,
+        client_changed,
+        MonitoringEntity::Client,
+        &mut events,
+    );
+    events
+}
+
+fn diff_entities<T: Clone, K: Eq + Hash>(
+    previous: &[T],
+    current: &[T],
+    key: impl Fn(&T) -> K,
+    changed: fn(&T, &T) -> bool,
+    entity: fn(T) -> MonitoringEntity,
+    events: &mut Vec<MonitoringEvent>,
+) {
+    let mut remaining: HashMap<K, &T> = previous.iter().map(|e| (key(e), e)).collect();
+    let current_keys: Vec<K> = current.iter().map(&key).collect();
+    let present: HashSet<&K> = current_keys.iter().collect();
+
+    for old in previous {
+        if !present.contains(&key(old)) {
+            events.push(MonitoringEvent::EntityRemoved(entity(old.clone())));
+        }
+    }
+    for (new, new_key) in current.iter().zip(current_keys) {
+        match remaining.remove(&new_key) {
+            None => events.push(MonitoringEvent::EntityAdded(entity(new.clone()))),
+            Some(old) if changed(old, new) => events.push(MonitoringEvent::EntityChanged {
+                previous: Box::new(entity(old.clone())),
+                current: Box::new(entity(new.clone())),
+            }),
+            Some(_) => {}
+        }
+    }
+}
+
+fn process_changed(a: &ProcessInfo, b: &ProcessInfo) -> bool {
+    a.process_name != b.process_name
+        || a.state_severity != b.state_severity
+        || a.state_severity_level != b.state_severity_level
+        || a.state_info != b.state_info
+        || a.time_sync_state != b.time_sync_state
+        || a.component_init_state != b.component_init_state
+}
+
+fn topic_changed(a: &TopicInfo, b: &TopicInfo) -> bool {
+    a.data_type != b.data_type
+        || a.connections_local != b.connections_local
+        || a.connections_external != b.connections_external
+}
+
+fn method_names(methods: &[MethodInfo]) -> Vec<&str> {
+    methods.iter().map(|m| m.method_name.as_str()).collect()
+}
+
+fn server_changed(a: &ServerInfo, b: &ServerInfo) -> bool {
+    a.version != b.version
+        || a.tcp_port_v0 != b.tcp_port_v0
+        || a.tcp_port_v1 != b.tcp_port_v1
+        || method_names(&a.methods) != method_names(&b.methods)
+}
+
+fn client_changed(a: &ClientInfo, b: &ClientInfo) -> bool {
+    a.version != b.version || method_names(&a.methods) != method_names(&b.methods)
+}
+
+/// Handle of a running [`Monitoring::watch`]; stops watching when dropped.
+pub struct MonitoringWatcher {
+    stop: Option<Sender<()>>,
+    thread: Option<JoinHandle<()>>,
+}
+
+impl MonitoringWatcher {
+    /// Stops watching and waits for a running callback invocation to finish.
+    ///
+    /// Must not be called from within the callback.
+    pub fn stop(mut self) {
+        self.shutdown();
+    }
+
+    fn shutdown(&mut self) {
+        drop(self.stop.take());
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+    }
+}
+
+impl Drop for MonitoringWatcher {
+    fn drop(&mut self) {
+        self.shutdown();
+    }
+}
+
+impl Monitoring {
+    /// Invokes `callback` for every change between consecutive snapshots.
+    ///
+    /// A dedicated thread takes a snapshot every `interval`. The first
+    /// snapshot is compared against an empty one, so all entities present
+    /// when watching starts are reported as [`MonitoringEvent::EntityAdded`].
+    /// Snapshots that fail are skipped. Watching stops when the returned
+    /// [`MonitoringWatcher`] is dropped.
+    pub fn watch<F>(interval: Duration, mut callback: F) -> MonitoringWatcher
+    where
+        F: FnMut(MonitoringEvent) + Send + 'static,
+    {
+        let (stop, stopped) = mpsc::channel::<()>();
+        let thread = thread::spawn(move || {
+            let mut previous = MonitoringSnapshot::default();
+            loop {
+                if let Ok(current) = Self::snapshot() {
+                    for event in diff(&previous, &current) {
+                        guard(|| "monitoring watch".to_string(), || callback(event));
+                    }
+                    previous = current;
+                }
+                match stopped.recv_timeout(interval) {
+                    Err(RecvTimeoutError::Timeout) => continue,
+                    _ => break,
+                }
+            }
+        });
+
+        MonitoringWatcher {
+            stop: Some(stop),
+            thread: Some(thread),
+        }
+    }
+
+    /// Like [`Monitoring::watch`], but delivers the events through a channel.
+    pub fn watch_channel(interval: Duration) -> (MonitoringWatcher, Receiver<MonitoringEvent>) {
+        let (sender, receiver) = mpsc::channel();
+        let watcher = Self::watch(interval, move |event| {
+            let _ = sender.send(event);
+        });
+        (watcher, receiver)
+    }
+}
@@ -0,0 +1,51 @@
+//! Discovery of eCAL's runtime directories.
+//!
+//! Wraps the directory helpers from `ecal_c/util.h` so tooling built on
+//! rustecal can locate the data directory, the log directory and the loaded
+//! configuration file portably on Windows and Linux.
+
+use crate::core::Ecal;
+use std::ffi::CStr;
+use std::os::raw::c_char;
+use std::path::PathBuf;
+
+/// Converts a C path string into a `PathBuf`, treating null and empty as `None`.
+fn to_path(ptr: *const c_char) -> Option<PathBuf> {
+    if ptr.is_null() {
+        return None;
+    }
+    let path = unsafe { CStr::from_ptr(ptr) }.to_string_lossy();
+    if path.is_empty() {
+        None
+    } else {
+        Some(PathBuf::from(path.as_ref()))
+    }
+}
+
+impl Ecal {
+    /// Returns eCAL's data directory (e.g. `/etc/ecal` or `%ProgramData%\eCAL`).
+    ///
+    /// This is the directory where eCAL looks for its configuration files.
+    pub fn data_dir() -> Option<PathBuf> {
+        to_path(unsafe { rustecal_sys::eCAL_Util_GeteCALDataDir() })
+    }
+
+    /// Returns the directory eCAL writes its log files to.
+    pub fn log_dir() -> Option<PathBuf> {
+        to_path(unsafe { rustecal_sys::eCAL_Util_GeteCALLogDir() })
+    }
+
+    /// Returns the configuration file used by the running eCAL instance.
+    ///
+    /// `None` if eCAL is not initialized or runs with its built-in defaults.
+    #[cfg(feature = "configuration")]
+    pub fn config_file_used() -> Option<PathBuf> {
+        let config = unsafe { rustecal_sys::eCAL_GetConfiguration() };
+        if config.is_null() {
+            return None;
+        }
+        to_path(unsafe {
+            rustecal_sys::eCAL_Configuration_GetConfigurationFilePath(config as *mut _)
+        })
+    }
+}
@@ -0,0 +1,155 @@
+//! Registration events for publishers and subscribers on the network.
+//!
+//! This module wraps the event callbacks from `ecal_c/registration.h`. With
+//! [`Ecal::on_registration`] an application is notified whenever a publisher or
+//! subscriber appears or disappears, which allows custom discovery logic
+//! without polling the monitoring snapshot.
+//!
+//! eCAL only provides registration events for topics; service servers and
+//! clients have to be discovered via monitoring.
+
+use crate::core::Ecal;
+use crate::error_hook::guard;
+use crate::types::EntityId;
+use rustecal_sys::{
+    eCAL_Registration_CallbackToken, eCAL_Registration_RegistrationEventType, eCAL_STopicId,
+};
+use std::ffi::CStr;
+use std::os::raw::c_void;
+
+/// The kind of entity a registration event refers to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EntityKind {
+    Publisher,
+    Subscriber,
+}
+
+/// Whether an entity appeared or disappeared.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RegistrationEventType {
+    /// A new entity was registered.
+    NewEntity,
+    /// An entity was unregistered (or timed out).
+    DeletedEntity,
+}
+
+impl From<eCAL_Registration_RegistrationEventType> for RegistrationEventType {
+    fn from(raw: eCAL_Registration_RegistrationEventType) -> Self {
+        match raw {
+            rustecal_sys::eCAL_Registration_RegistrationEventType_eCAL_Registration_RegistrationEventType_deleted_entity => {
+                RegistrationEventType::DeletedEntity
+            }
+            _ => RegistrationEventType::NewEntity,
+        }
+    }
+}
+
+/// A publisher or subscriber appeared or disappeared.
+#[derive(Debug, Clone)]
+pub struct RegistrationEvent {
+    pub kind: EntityKind,
+    pub event_type: RegistrationEventType,
+    pub topic_name: String,
+    pub entity: EntityId,
+}
+
+type RegistrationCallback = Box<dyn Fn(&RegistrationEvent) + Send + Sync + 'static>;
+
+/// Keeps a registration callback alive; dropping it unregisters the callback.
+pub struct RegistrationCallbackHandle {
+    publisher_token: eCAL_Registration_CallbackToken,
+    subscriber_token: eCAL_Registration_CallbackToken,
+    callback: *mut RegistrationCallback,
+}
+
+// The callback itself is `Send + Sync`; the raw pointer is only freed on drop.
+unsafe impl Send for RegistrationCallbackHandle {}
+unsafe impl Sync for RegistrationCallbackHandle {}
+
+impl Drop for RegistrationCallbackHandle {
+    fn drop(&mut self) {
+        unsafe {
+            rustecal_sys::eCAL_Registration_RemPublisherEventCallback(self.publisher_token);
+            rustecal_sys::eCAL_Registration_RemSubscriberEventCallback(self.subscriber_token);
+            let _ = Box::from_raw(self.callback);
+        }
+    }
+}
+
+impl Ecal {
+    /// Registers a callback invoked whenever a publisher or subscriber appears
+    /// or disappears in the eCAL network.
+    ///
+    /// The callback runs on an eCAL registration thread. It stays registered
+    /// until the returned handle is dropped.
+    pub fn on_registration<F>(callback: F) -> RegistrationCallbackHandle
+    where
+        F: Fn(&RegistrationEvent) + Send + Sync + 'static,
+    {
+        let callback: *mut RegistrationCallback = Box::into_raw(Box::new(Box::new(callback)));
+
+        let (publisher_token, subscriber_token) = unsafe {
+            (
+                rustecal_sys::eCAL_Registration_AddPublisherEventCallback(
+                    Some(publisher_trampoline),
+                    callback as *mut c_void,
+                ),
+                rustecal_sys::eCAL_Registration_AddSubscriberEventCallback(
+                    Some(subscriber_trampoline),
+                    callback as *mut c_void,
+                ),
+            )
+        };
+
+        RegistrationCallbackHandle {
+            publisher_token,
+            subscriber_token,
+            callback,
+        }
+    }
+}
+
+unsafe extern "C" fn publisher_trampoline(
+    topic_id: *const eCAL_STopicId,
+    event_type: eCAL_Registration_RegistrationEventType,
+    user_data: *mut c_void,
+) {
+    unsafe { dispatch(EntityKind::Publisher, topic_id, event_type, user_data) }
+}
+
+unsafe extern "C" fn subscriber_trampoline(
+    topic_id: *const eCAL_STopicId,
+    event_type: eCAL_Registration_RegistrationEventType,
+    user_data: *mut c_void,
+) {
+    unsafe { dispatch(EntityKind::Subscriber, topic_id, event_type, user_data) }
+}
+
+unsafe fn dispatch(
+    kind: EntityKind,
+    topic_id: *const eCAL_STopicId,
+    event_type: eCAL_Registration_RegistrationEventType,
+    user_data: *mut c_void,
+) {
+    if topic_id.is_null() || user_data.is_null() {
+        return;
+    }
+
+    let topic_id = unsafe { &*topic_id };
+    let topic_name = if topic_id.topic_name.is_null() {
+        String::new()
+    } else {
+        unsafe { CStr::from_ptr(topic_id.topic_name) }
+            .to_string_lossy()
+            .into_owned()
+    };
+    let event = RegistrationEvent {
+        kind,
+        event_type: event_type.into(),
+        topic_name,
+        entity: topic_id.topic_id.into(),
+    };
+
+    let callback = unsafe { &*(user_data as *const RegistrationCallback) };
+    guard(|| "registration".to_string(), || callback(&event));
+}
@@ -0,0 +1,58 @@
+//! Access to the eCAL time base.
+//!
+//! This module wraps the C API from `ecal_c/time.h`. Depending on the loaded
+//! time synchronization module, eCAL time is either the local system time or
+//! a time base synchronized across hosts (or driven by a replay tool).
+
+use crate::error::{RustecalError, check};
+use std::ffi::CStr;
+use std::time::Duration;
+
+/// Provides access to the eCAL time base.
+pub struct Time;
+
+impl Time {
+    /// Returns the name of the active time synchronization module.
+    pub fn name() -> String {
+        let ptr = unsafe { rustecal_sys::eCAL_Time_GetName() };
+        if ptr.is_null() {
+            String::new()
+        } else {
+            unsafe { CStr::from_ptr(ptr).to_string_lossy().into_owned() }
+        }
+    }
+
+    /// Returns the current eCAL time in microseconds.
+    pub fn microseconds() -> i64 {
+        unsafe { rustecal_sys::eCAL_Time_GetMicroSeconds() }
+    }
+
+    /// Returns the current eCAL time in nanoseconds.
+    pub fn nanoseconds() -> i64 {
+        unsafe { rustecal_sys::eCAL_Time_GetNanoSeconds() }
+    }
+
+    /// Sets the eCAL time (only supported by time modules acting as master).
+    ///
+    /// # Errors
+    ///
+    /// Returns `RustecalError::Ecal` if the active time module rejected the request.
+    pub fn set_nanoseconds(time: i64) -> Result<(), RustecalError> {
+        check(unsafe { rustecal_sys::eCAL_Time_SetNanoSeconds(time) })
+    }
+
+    /// Returns `true` if the time base is synchronized.
+    pub fn is_synchronized() -> bool {
+        unsafe { rustecal_sys::eCAL_Time_IsSynchronized() != 0 }
+    }
+
+    /// Returns `true` if this process is the time master.
+    pub fn is_master() -> bool {
+        unsafe { rustecal_sys::eCAL_Time_IsMaster() != 0 }
+    }
+
+    /// Sleeps for the given duration measured in eCAL time.
+    pub fn sleep(duration: Duration) {
+        unsafe { rustecal_sys::eCAL_Time_SleepForNanoseconds(duration.as_nanos() as i64) };
+    }
+}
@@ -0,0 +1,164 @@
+//! Periodic timers driven by eCAL's time base.
+//!
+//! This module wraps the C API from `ecal_c/timer.h`. A [`Timer`] fires a Rust
+//! closure at a fixed period, so periodic publishers don't need to hand-roll
+//! `sleep` loops. The deviation of every period from the configured one is
+//! recorded and available as [`TimerStatistics`].
+
+use crate::error::{RustecalError, check};
+use crate::error_hook::guard;
+use crate::time::Time;
+use std::os::raw::c_void;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+/// Jitter statistics of a running timer.
+///
+/// Jitter is the difference between the measured interval of two consecutive
+/// callbacks and the configured period, in microseconds of eCAL time.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct TimerStatistics {
+    /// Number of callback invocations.
+    pub fired: u64,
+    /// Smallest observed jitter.
+    pub jitter_min_us: i64,
+    /// Largest observed jitter.
+    pub jitter_max_us: i64,
+    /// Mean of the observed jitter.
+    pub jitter_mean_us: f64,
+}
+
+struct TimerState {
+    callback: Mutex<Box<dyn FnMut() + Send + 'static>>,
+    period_us: i64,
+    last_fire_us: Mutex<Option<i64>>,
+    stats: Mutex<TimerStatistics>,
+}
+
+/// A periodic timer invoking a Rust closure.
+pub struct Timer {
+    handle: *mut rustecal_sys::eCAL_Timer,
+    state: Option<Arc<TimerState>>,
+}
+
+impl Timer {
+    /// Creates a new, stopped timer.
+    ///
+    /// # Errors
+    ///
+    /// Returns `RustecalError::NullPointer` if eCAL could not create the timer.
+    pub fn new() -> Result<Self, RustecalError> {
+        let handle = unsafe { rustecal_sys::eCAL_Timer_New() };
+        if handle.is_null() {
+            return Err(RustecalError::NullPointer);
+        }
+        Ok(Self {
+            handle,
+            state: None,
+        })
+    }
+
+    /// Starts the timer, invoking `callback` every `period` after an initial `delay`.
+    ///
+    /// A running timer is stopped first, and its statistics are reset.
+    ///
+    /// # Errors
+    ///
+    /// Returns `RustecalError::Ecal` if eCAL refused to start the timer.
+    pub fn start<F>(
+        &mut self,
+        period: Duration,
+        delay: Duration,
+        callback: F,
+    ) -> Result<(), RustecalError>
+    where
+        F: FnMut() + Send + 'static,
+    {
+        self.stop()?;
+
+        let state = Arc::new(TimerState {
+            callback: Mutex::new(Box::new(callback)),
+            period_us: period.as_micros() as i64,
+            last_fire_us: Mutex::new(None),
+            stats: Mutex::new(TimerStatistics::default()),
+        });
+
+        let ret = unsafe {
+            rustecal_sys::eCAL_Timer_Start(
+                self.handle,
+                period.as_millis() as i32,
+                Some(timer_trampoline),
+                delay.as_millis() as i32,
+                Arc::as_ptr(&state) as *mut c_void,
+            )
+        };
+        check(ret)?;
+        self.state = Some(state);
+        Ok(())
+    }
+
+    /// Stops the timer. Stopping a timer that is not running is a no‑op.
+    ///
+    /// # Errors
+    ///
+    /// Returns `RustecalError::Ecal` if eCAL failed to stop the timer.
+    pub fn stop(&mut self) -> Result<(), RustecalError> {
+        if self.state.is_none() {
+            return Ok(());
+        }
+        check(unsafe { rustecal_sys::eCAL_Timer_Stop(self.handle) })?;
+        self.state = None;
+        Ok(())
+    }
+
+    /// Returns `true` if the timer is running.
+    pub fn is_running(&self) -> bool {
+        self.state.is_some()
+    }
+
+    /// Returns the jitter statistics since the timer was last started.
+    pub fn statistics(&self) -> TimerStatistics {
+        self.state
+            .as_ref()
+            .map(|state| *state.stats.lock().unwrap())
+            .unwrap_or_default()
+    }
+}
+
+impl Drop for Timer {
+    /// Stops and deletes the underlying eCAL timer.
+    fn drop(&mut self) {
+        unsafe {
+            rustecal_sys::eCAL_Timer_Stop(self.handle);
+            rustecal_sys::eCAL_Timer_Delete(self.handle);
+        }
+    }
+}
+
+/// Internal trampoline invoked by eCAL on every timer tick.
+unsafe extern "C" fn timer_trampoline(user_data: *mut c_void) {
+    if user_data.is_null() {
+        return;
+    }
+    let state = unsafe { &*(user_data as *const TimerState) };
+
+    let now = Time::microseconds();
+    if let Some(last) = state.last_fire_us.lock().unwrap().replace(now) {
+        let jitter = (now - last) - state.period_us;
+        let mut stats = state.stats.lock().unwrap();
+        let samples = stats.fired as f64;
+        if stats.fired == 1 {
+            stats.jitter_min_us = jitter;
+            stats.jitter_max_us = jitter;
+        } else {
+            stats.jitter_min_us = stats.jitter_min_us.min(jitter);
+            stats.jitter_max_us = stats.jitter_max_us.max(jitter);
+        }
+        // this is the `fired`-th measured interval
+        stats.jitter_mean_us += (jitter as f64 - stats.jitter_mean_us) / samples;
+    }
+    state.stats.lock().unwrap().fired += 1;
+
+    let mut callback = state.callback.lock().unwrap();
+    guard(|| "timer".to_string(), &mut *callback);
+}
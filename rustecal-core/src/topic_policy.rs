@@ -0,0 +1,114 @@
+//! Process-wide allow and deny lists for topic names.
+//!
+//! A [`TopicPolicy`] is evaluated whenever a publisher or subscriber is
+//! created, so deployments can forbid debug or test topics in production and
+//! tooling can enforce naming conventions. It is installed with
+//! [`TopicPolicy::set_default`] or through
+//! [`Configuration::set_topic_policy`](crate::configuration::Configuration::set_topic_policy)
+//! when eCAL is initialized.
+//!
+//! Patterns are globs: `*` matches any sequence of characters (including
+//! `/`), `?` matches a single character.
+
+use crate::error::RustecalError;
+use std::sync::RwLock;
+
+/// Allow and deny lists for topic names.
+///
+/// A topic is allowed if it matches no deny pattern and, if any allow
+/// pattern is given, at least one of them. An empty policy allows everything.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TopicPolicy {
+    allow: Vec<String>,
+    deny: Vec<String>,
+}
+
+static POLICY: RwLock<TopicPolicy> = RwLock::new(TopicPolicy {
+    allow: Vec::new(),
+    deny: Vec::new(),
+});
+
+impl TopicPolicy {
+    /// Creates a policy allowing all topics.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a pattern to the allow list.
+    pub fn allow(mut self, pattern: &str) -> Self {
+        self.allow.push(pattern.to_string());
+        self
+    }
+
+    /// Adds a pattern to the deny list.
+    pub fn deny(mut self, pattern: &str) -> Self {
+        self.deny.push(pattern.to_string());
+        self
+    }
+
+    /// Returns `true` if `topic_name` may be used under this policy.
+    pub fn is_allowed(&self, topic_name: &str) -> bool {
+        let matches = |pattern: &String| glob_match(pattern, topic_name);
+        !self.deny.iter().any(matches) && (self.allow.is_empty() || self.allow.iter().any(matches))
+    }
+
+    /// Installs the process-wide policy, replacing the previous one.
+    ///
+    /// Entities created before are not affected.
+    pub fn set_default(policy: TopicPolicy) {
+        *POLICY.write().unwrap_or_else(|e| e.into_inner()) = policy;
+    }
+
+    /// Returns a copy of the process-wide policy.
+    pub fn current() -> TopicPolicy {
+        POLICY.read().unwrap_or_else(|e| e.into_inner()).clone()
+    }
+
+    /// Checks `topic_name` against the process-wide policy.
+    ///
+    /// # Errors
+    ///
+    /// Returns `RustecalError::TopicNotAllowed` if the policy rejects the topic.
+    pub fn check(topic_name: &str) -> Result<(), RustecalError> {
+        if POLICY
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .is_allowed(topic_name)
+        {
+            Ok(())
+        } else {
+            Err(RustecalError::TopicNotAllowed(topic_name.to_string()))
+        }
+    }
+}
+
+/// Matches `text` against a glob `pattern` supporting `*` and `?`
+/// (see the [module documentation](self)).
+pub fn glob_match(pattern: &str, text: &str) -> bool {
+    let (pattern, text) = (pattern.as_bytes(), text.as_bytes());
+    let (mut p, mut t) = (0, 0);
+    // position of the last `*` in the pattern and the text index it matched up to
+    let mut backtrack: Option<(usize, usize)> = None;
+
+    while t < text.len() {
+        match pattern.get(p) {
+            Some(b'*') => {
+                backtrack = Some((p, t));
+                p += 1;
+            }
+            Some(&c) if c == b'?' || c == text[t] => {
+                p += 1;
+                t += 1;
+            }
+            _ => match backtrack {
+                Some((star, matched)) => {
+                    p = star + 1;
+                    t = matched + 1;
+                    backtrack = Some((star, matched + 1));
+                }
+                None => return false,
+            },
+        }
+    }
+    pattern[p..].iter().all(|&c| c == b'*')
+}
@@ -0,0 +1,139 @@
+//! Process-wide renaming of topics.
+//!
+//! A [`TopicRemap`] is applied whenever a publisher or subscriber is created,
+//! so the same binary can run several times side by side with isolated
+//! topics, e.g. one instance per simulated robot. It renames individual
+//! topics and then puts a prefix in front of every topic name:
+//!
+//! ```ignore
+//! TopicRemap::set_default(
+//!     TopicRemap::new().prefix("robot2/").remap("cmd_vel", "teleop/cmd_vel"),
+//! );
+//! // "pose" -> "robot2/pose", "cmd_vel" -> "robot2/teleop/cmd_vel"
+//! ```
+//!
+//! Besides [`TopicRemap::set_default`] and
+//! [`Configuration::set_topic_remap`](crate::configuration::Configuration::set_topic_remap),
+//! [`Ecal::initialize`](crate::core::Ecal::initialize) reads the
+//! environment variables [`PREFIX_ENV`] and [`REMAP_ENV`], so a namespace can
+//! be chosen at launch without changing code.
+
+use crate::error::RustecalError;
+use std::env;
+use std::sync::RwLock;
+
+/// Environment variable holding the topic prefix.
+pub const PREFIX_ENV: &str = "RUSTECAL_TOPIC_PREFIX";
+
+/// Environment variable holding topic renames as `from=to` pairs separated by `,`.
+pub const REMAP_ENV: &str = "RUSTECAL_TOPIC_REMAP";
+
+/// Topic renames and a prefix for all topics.
+///
+/// Renames match complete topic names and are applied before the prefix.
+/// An empty remap leaves all names unchanged.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TopicRemap {
+    prefix: String,
+    remaps: Vec<(String, String)>,
+}
+
+static REMAP: RwLock<TopicRemap> = RwLock::new(TopicRemap {
+    prefix: String::new(),
+    remaps: Vec::new(),
+});
+
+impl TopicRemap {
+    /// Creates a remap leaving all topic names unchanged.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Puts `prefix` in front of every topic name; include a trailing `/`
+    /// to form a namespace.
+    pub fn prefix(mut self, prefix: &str) -> Self {
+        self.prefix = prefix.to_string();
+        self
+    }
+
+    /// Renames the topic `from` to `to`.
+    pub fn remap(mut self, from: &str, to: &str) -> Self {
+        self.remaps.push((from.to_string(), to.to_string()));
+        self
+    }
+
+    /// Returns `true` if the remap leaves all names unchanged.
+    pub fn is_empty(&self) -> bool {
+        self.prefix.is_empty() && self.remaps.is_empty()
+    }
+
+    /// Parses a prefix and a list of `from=to` renames separated by `,`
+    /// (the format of [`PREFIX_ENV`] and [`REMAP_ENV`]).
+    ///
+    /// # Errors
+    ///
+    /// Returns `RustecalError::InvalidRemap` for a rename without `=` or
+    /// with an empty side.
+    pub fn parse(prefix: &str, remaps: &str) -> Result<Self, RustecalError> {
+        let mut remap = TopicRemap::new().prefix(prefix.trim());
+        for entry in remaps.split(',').map(str::trim).filter(|e| !e.is_empty()) {
+            match entry.split_once('=') {
+                Some((from, to)) if !from.trim().is_empty() && !to.trim().is_empty() => {
+                    remap = remap.remap(from.trim(), to.trim());
+                }
+                _ => return Err(RustecalError::InvalidRemap(entry.to_string())),
+            }
+        }
+        Ok(remap)
+    }
+
+    /// Reads the remap from [`PREFIX_ENV`] and [`REMAP_ENV`].
+    ///
+    /// Returns `Ok(None)` if neither variable is set.
+    ///
+    /// # Errors
+    ///
+    /// See [`TopicRemap::parse`].
+    pub fn from_env() -> Result<Option<Self>, RustecalError> {
+        let prefix = env::var(PREFIX_ENV).ok();
+        let remaps = env::var(REMAP_ENV).ok();
+        if prefix.is_none() && remaps.is_none() {
+            return Ok(None);
+        }
+        Self::parse(
+            prefix.as_deref().unwrap_or_default(),
+            remaps.as_deref().unwrap_or_default(),
+        )
+        .map(Some)
+    }
+
+    /// Returns the name `topic_name` is renamed to.
+    pub fn apply(&self, topic_name: &str) -> String {
+        let renamed = self
+            .remaps
+            .iter()
+            .find(|(from, _)| from == topic_name)
+            .map_or(topic_name, |(_, to)| to.as_str());
+        format!("{}{renamed}", self.prefix)
+    }
+
+    /// Installs the process-wide remap, replacing the previous one.
+    ///
+    /// Entities created before are not affected.
+    pub fn set_default(remap: TopicRemap) {
+        *REMAP.write().unwrap_or_else(|e| e.into_inner()) = remap;
+    }
+
+    /// Returns a copy of the process-wide remap.
+    pub fn current() -> TopicRemap {
+        REMAP.read().unwrap_or_else(|e| e.into_inner()).clone()
+    }
+
+    /// Applies the process-wide remap to `topic_name`.
+    pub fn resolve(topic_name: &str) -> String {
+        REMAP
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .apply(topic_name)
+    }
+}
@@ -0,0 +1,143 @@
+//! A `tracing-subscriber` layer forwarding events to eCAL logging.
+//!
+//! [`EcalLayer`] turns every `tracing` event into an eCAL log message, so
+//! applications using `tracing` get their logs into eCAL Monitor without a
+//! second logging stack. The message contains the event's target, the
+//! enclosing spans with their fields, and the event's fields:
+//!
+//! ```text
+//! [my_app::net] connect{peer=10.0.0.2}:handshake: timed out retries=3
+//! ```
+
+use crate::log::Log;
+use crate::log_level::LogLevel;
+use std::fmt::{self, Write};
+use tracing::field::{Field, Visit};
+use tracing::span::{Attributes, Id, Record};
+use tracing::{Event, Level, Subscriber};
+use tracing_subscriber::layer::{Context, Layer};
+use tracing_subscriber::registry::LookupSpan;
+
+/// Collects the `message` and all other fields of an event or span.
+#[derive(Default)]
+struct FieldVisitor {
+    message: String,
+    fields: String,
+}
+
+impl Visit for FieldVisitor {
+    fn record_str(&mut self, field: &Field, value: &str) {
+        if field.name() == "message" {
+            self.message.push_str(value);
+        } else {
+            self.record_debug(field, &value);
+        }
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+        if field.name() == "message" {
+            let _ = write!(self.message, "{value:?}");
+        } else {
+            if !self.fields.is_empty() {
+                self.fields.push(' ');
+            }
+            let _ = write!(self.fields, "{}={:?}", field.name(), value);
+        }
+    }
+}
+
+/// Formatted fields of a span, stored in the span's extensions.
+struct SpanFields(String);
+
+/// A [`Layer`] emitting `tracing` events through eCAL logging.
+///
+/// Levels are mapped as `ERROR` → [`LogLevel::Error`], `WARN` → [`LogLevel::Warning`],
+/// `INFO` → [`LogLevel::Info`], `DEBUG` → [`LogLevel::Debug1`] and
+/// `TRACE` → [`LogLevel::Debug2`].
+///
+/// ```ignore
+/// use tracing_subscriber::prelude::*;
+///
+/// tracing_subscriber::registry().with(EcalLayer::new()).init();
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct EcalLayer {
+    _private: (),
+}
+
+impl EcalLayer {
+    /// Creates a new layer.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn map_level(level: &Level) -> LogLevel {
+        match *level {
+            Level::ERROR => LogLevel::Error,
+            Level::WARN => LogLevel::Warning,
+            Level::INFO => LogLevel::Info,
+            Level::DEBUG => LogLevel::Debug1,
+            Level::TRACE => LogLevel::Debug2,
+        }
+    }
+}
+
+impl<S> Layer<S> for EcalLayer
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+{
+    fn on_new_span(&self, attrs: &Attributes<'_>, id: &Id, ctx: Context<'_, S>) {
+        let Some(span) = ctx.span(id) else { return };
+        let mut visitor = FieldVisitor::default();
+        attrs.record(&mut visitor);
+        span.extensions_mut().insert(SpanFields(visitor.fields));
+    }
+
+    fn on_record(&self, id: &Id, values: &Record<'_>, ctx: Context<'_, S>) {
+        let Some(span) = ctx.span(id) else { return };
+        let mut extensions = span.extensions_mut();
+        if let Some(SpanFields(fields)) = extensions.get_mut::<SpanFields>() {
+            let mut visitor = FieldVisitor {
+                message: String::new(),
+                fields: std::mem::take(fields),
+            };
+            values.record(&mut visitor);
+            *fields = visitor.fields;
+        }
+    }
+
+    fn on_event(&self, event: &Event<'_>, ctx: Context<'_, S>) {
+        let metadata = event.metadata();
+        let mut text = format!("[{}] ", metadata.target());
+
+        // span context, outermost first
+        if let Some(scope) = ctx.event_scope(event) {
+            let mut spans = Vec::new();
+            for span in scope.from_root() {
+                let extensions = span.extensions();
+                match extensions.get::<SpanFields>() {
+                    Some(SpanFields(fields)) if !fields.is_empty() => {
+                        spans.push(format!("{}{{{}}}", span.name(), fields))
+                    }
+                    _ => spans.push(span.name().to_string()),
+                }
+            }
+            if !spans.is_empty() {
+                text.push_str(&spans.join(":"));
+                text.push_str(": ");
+            }
+        }
+
+        let mut visitor = FieldVisitor::default();
+        event.record(&mut visitor);
+        text.push_str(&visitor.message);
+        if !visitor.fields.is_empty() {
+            if !visitor.message.is_empty() {
+                text.push(' ');
+            }
+            text.push_str(&visitor.fields);
+        }
+
+        Log::log(Self::map_level(metadata.level()), &text);
+    }
+}
@@ -5,6 +5,7 @@ use std::os::raw::c_char;
 
 /// Represents a globally unique entity in eCAL.
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct EntityId {
     pub entity_id: u64,
     pub process_id: i32,
@@ -22,7 +23,8 @@ impl From<rustecal_sys::eCAL_SEntityId> for EntityId {
 }
 
 /// Rust-safe representation of `eCAL_SDataTypeInformation`.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct DataTypeInfo {
     pub type_name: String,
     pub encoding: String,
@@ -52,6 +54,7 @@ impl From<rustecal_sys::eCAL_SDataTypeInformation> for DataTypeInfo {
 
 /// Rust-safe representation of `eCAL_SVersion`.
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Version {
     pub major: i32,
     pub minor: i32,
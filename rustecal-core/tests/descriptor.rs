@@ -0,0 +1,64 @@
+use rustecal_core::descriptor::{DescriptorPolicy, ZSTD_MAGIC};
+use rustecal_core::types::DataTypeInfo;
+
+fn data_type(descriptor: Vec<u8>) -> DataTypeInfo {
+    DataTypeInfo {
+        type_name: "pb.Example".into(),
+        encoding: "proto".into(),
+        descriptor,
+    }
+}
+
+#[test]
+fn uncompressed_descriptor_passes_through() {
+    let info = DescriptorPolicy::default().apply("topic", data_type(vec![0x0a, 1, 2, 3]));
+    assert!(!info.is_descriptor_compressed());
+    assert_eq!(&*info.decompressed_descriptor(), &[0x0a, 1, 2, 3]);
+}
+
+#[test]
+fn oversized_descriptor_is_dropped() {
+    let policy = DescriptorPolicy {
+        compression_level: None,
+        max_size: Some(4),
+    };
+    let info = policy.apply("topic", data_type(vec![0x0a; 5]));
+    assert!(info.descriptor.is_empty());
+    assert_eq!(info.type_name, "pb.Example");
+
+    let info = policy.apply("topic", data_type(vec![0x0a; 4]));
+    assert_eq!(info.descriptor.len(), 4);
+}
+
+#[test]
+fn undecodable_descriptor_falls_back_to_empty() {
+    let mut corrupt = ZSTD_MAGIC.to_vec();
+    corrupt.extend_from_slice(&[0xff; 8]);
+    let info = data_type(corrupt);
+    assert!(info.is_descriptor_compressed());
+    assert!(info.decompressed_descriptor().is_empty());
+}
+
+#[cfg(feature = "descriptor-compression")]
+#[test]
+fn compressed_descriptor_round_trips() {
+    let original: Vec<u8> = b"\x0a\x20google/protobuf/descriptor.proto"
+        .iter()
+        .copied()
+        .cycle()
+        .take(4096)
+        .collect();
+    let policy = DescriptorPolicy {
+        compression_level: Some(3),
+        max_size: Some(1024),
+    };
+
+    let info = policy.apply("topic", data_type(original.clone()));
+    assert!(info.is_descriptor_compressed());
+    assert!(info.descriptor.len() < 1024);
+    assert_eq!(&*info.decompressed_descriptor(), original.as_slice());
+
+    // already compressed descriptors are left alone
+    let again = policy.apply("topic", info.clone());
+    assert_eq!(again.descriptor, info.descriptor);
+}
@@ -0,0 +1,17 @@
+use rustecal_core::discovery::wait_until;
+use std::time::Duration;
+
+#[test]
+fn wait_until_returns_once_condition_holds() {
+    let mut checks = 0;
+    assert!(wait_until(Duration::from_secs(10), || {
+        checks += 1;
+        checks == 1
+    }));
+    assert_eq!(checks, 1);
+}
+
+#[test]
+fn wait_until_times_out() {
+    assert!(!wait_until(Duration::ZERO, || false));
+}
@@ -0,0 +1,20 @@
+use rustecal_core::error_hook::{ErrorHook, guard};
+use std::sync::{Arc, Mutex};
+
+#[test]
+fn guard_reports_panic_to_hook() {
+    let caught = Arc::new(Mutex::new(Vec::new()));
+    let sink = caught.clone();
+    ErrorHook::set(move |panic| sink.lock().unwrap().push(panic.clone()));
+
+    assert_eq!(guard(|| "test".to_string(), || 42), Some(42));
+    assert_eq!(
+        guard(|| "test".to_string(), || -> i32 { panic!("boom") }),
+        None
+    );
+
+    let caught = caught.lock().unwrap();
+    assert_eq!(caught.len(), 1);
+    assert_eq!(caught[0].source, "test");
+    assert_eq!(caught[0].message, "boom");
+}
@@ -0,0 +1,28 @@
+use rustecal_core::handle_registry::{HandleKind, HandleRegistry, LiveHandle};
+
+#[test]
+fn tracked_handles_are_reported_until_dropped() {
+    assert!(HandleRegistry::track(HandleKind::Publisher, "untracked").is_none());
+
+    HandleRegistry::set_enabled(true);
+    let publisher = HandleRegistry::track(HandleKind::Publisher, "chatter");
+    let server = HandleRegistry::track(HandleKind::ServiceServer, "mirror");
+
+    assert_eq!(
+        HandleRegistry::live_handles(),
+        vec![
+            LiveHandle {
+                kind: HandleKind::Publisher,
+                name: "chatter".into()
+            },
+            LiveHandle {
+                kind: HandleKind::ServiceServer,
+                name: "mirror".into()
+            },
+        ]
+    );
+
+    drop(publisher);
+    drop(server);
+    assert!(HandleRegistry::live_handles().is_empty());
+}
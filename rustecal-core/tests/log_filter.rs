@@ -0,0 +1,44 @@
+use rustecal_core::{LogFilter, LogLevel, LogMessage};
+
+fn entry(level: LogLevel, timestamp: i64, host_name: &str, unit_name: &str) -> LogMessage {
+    LogMessage {
+        level,
+        timestamp,
+        host_name: host_name.into(),
+        process_name: "proc".into(),
+        process_id: 1,
+        thread_name: unit_name.into(),
+        content: "text".into(),
+    }
+}
+
+#[test]
+fn default_filter_matches_everything() {
+    let filter = LogFilter::new();
+    assert!(filter.matches(&entry(LogLevel::Debug4, 0, "", "")));
+    assert!(filter.matches(&entry(LogLevel::Fatal, i64::MAX, "host", "unit")));
+}
+
+#[test]
+fn min_level_orders_by_severity() {
+    let filter = LogFilter::new().min_level(LogLevel::Warning);
+    assert!(filter.matches(&entry(LogLevel::Warning, 0, "", "")));
+    assert!(filter.matches(&entry(LogLevel::Fatal, 0, "", "")));
+    assert!(!filter.matches(&entry(LogLevel::Info, 0, "", "")));
+    // debug levels have higher numeric values but lower severity
+    assert!(!filter.matches(&entry(LogLevel::Debug1, 0, "", "")));
+}
+
+#[test]
+fn host_unit_and_time_range_must_all_match() {
+    let filter = LogFilter::new()
+        .host_name("robot")
+        .unit_name("planner")
+        .time_range(100, 200);
+
+    assert!(filter.matches(&entry(LogLevel::Info, 100, "robot", "planner")));
+    assert!(!filter.matches(&entry(LogLevel::Info, 200, "robot", "planner")));
+    assert!(!filter.matches(&entry(LogLevel::Info, 99, "robot", "planner")));
+    assert!(!filter.matches(&entry(LogLevel::Info, 150, "desk", "planner")));
+    assert!(!filter.matches(&entry(LogLevel::Info, 150, "robot", "driver")));
+}
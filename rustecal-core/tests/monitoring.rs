@@ -0,0 +1,170 @@
+#![cfg(feature = "monitoring")]
+
+use rustecal_core::core_types::monitoring::{MonitoringSnapshot, ProcessInfo, TopicInfo};
+use rustecal_core::types::DataTypeInfo;
+
+fn topic(topic_name: &str, direction: &str, data_frequency: i32) -> TopicInfo {
+    TopicInfo {
+        registration_clock: 1,
+        host_name: "robot".into(),
+        shm_transport_domain: "robot".into(),
+        process_id: 7,
+        process_name: "planner".into(),
+        unit_name: "planner".into(),
+        topic_id: 1,
+        topic_name: topic_name.into(),
+        direction: direction.into(),
+        data_type: DataTypeInfo {
+            type_name: "pb.Pose".into(),
+            encoding: "proto".into(),
+            descriptor: Vec::new(),
+        },
+        transport_layers: Vec::new(),
+        topic_size: 64,
+        connections_local: 1,
+        connections_external: 0,
+        message_drops: 0,
+        data_id: 0,
+        data_clock: 10,
+        data_frequency,
+    }
+}
+
+#[test]
+fn snapshot_topic_lookup() {
+    let snapshot = MonitoringSnapshot {
+        publishers: vec![topic("pose", "publisher", 10_000)],
+        subscribers: vec![
+            topic("pose", "subscriber", 9_500),
+            topic("map", "subscriber", 500),
+        ],
+        ..Default::default()
+    };
+
+    assert_eq!(snapshot.topics().count(), 3);
+    let pose: Vec<_> = snapshot.topic("pose").collect();
+    assert_eq!(pose.len(), 2);
+    assert_eq!(pose[0].frequency_hz(), 10.0);
+    assert_eq!(snapshot.topic("map").next().unwrap().frequency_hz(), 0.5);
+}
+
+#[test]
+fn topic_stats_aggregate_publishers_and_subscribers() {
+    let mut second = topic("pose", "publisher", 5_000);
+    second.topic_size = 128;
+    second.data_clock = 5;
+    let mut dropping = topic("pose", "subscriber", 9_500);
+    dropping.message_drops = 3;
+    let snapshot = MonitoringSnapshot {
+        publishers: vec![topic("pose", "publisher", 10_000), second],
+        subscribers: vec![dropping, topic("map", "subscriber", 500)],
+        ..Default::default()
+    };
+
+    let stats = snapshot.topic_stats("pose").unwrap();
+    assert_eq!(stats.publisher_count, 2);
+    assert_eq!(stats.subscriber_count, 1);
+    assert_eq!(stats.frequency_hz, 15.0);
+    assert_eq!(stats.bytes_per_second, 10.0 * 64.0 + 5.0 * 128.0);
+    assert_eq!(stats.total_messages, 15);
+    assert_eq!(stats.total_bytes, 10 * 64 + 5 * 128);
+    assert_eq!(stats.message_drops, 3);
+
+    assert!(snapshot.topic_stats("missing").is_none());
+    let names: Vec<_> = snapshot
+        .all_topic_stats()
+        .into_iter()
+        .map(|s| s.topic_name)
+        .collect();
+    assert_eq!(names, ["map", "pose"]);
+}
+
+#[test]
+fn monitoring_filter_matches_names() {
+    use rustecal_core::{MonitoringFilter, RustecalError};
+
+    let filter = MonitoringFilter::new()
+        .name_pattern("^robot1/.*")
+        .unwrap()
+        .host_name("robot");
+    assert!(filter.matches("robot", "planner", Some("robot1/pose")));
+    assert!(!filter.matches("robot", "planner", Some("robot2/pose")));
+    assert!(!filter.matches("station", "planner", Some("robot1/pose")));
+    // processes have no topic or service name
+    assert!(filter.matches("robot", "planner", None));
+
+    let filter = MonitoringFilter::new().unit_name("planner");
+    assert!(filter.matches("any", "planner", Some("pose")));
+    assert!(!filter.matches("any", "driver", Some("pose")));
+
+    assert!(matches!(
+        MonitoringFilter::new().name_pattern("("),
+        Err(RustecalError::InvalidPattern(_))
+    ));
+}
+
+#[test]
+fn hosts_group_entities_by_host() {
+    let mut remote = topic("map", "subscriber", 500);
+    remote.host_name = "station".into();
+    let snapshot = MonitoringSnapshot {
+        publishers: vec![topic("pose", "publisher", 10_000)],
+        subscribers: vec![topic("pose", "subscriber", 9_500), remote],
+        ..Default::default()
+    };
+
+    let hosts = snapshot.hosts();
+    assert_eq!(hosts.len(), 2);
+    assert_eq!(hosts[0].host_name, "robot");
+    assert_eq!(hosts[0].publisher_count, 1);
+    assert_eq!(hosts[0].subscriber_count, 1);
+    assert_eq!(hosts[0].bytes_sent_per_second, 10.0 * 64.0);
+    assert_eq!(hosts[1].host_name, "station");
+    assert_eq!(hosts[1].subscriber_count, 1);
+    assert_eq!(hosts[1].bytes_sent_per_second, 0.0);
+}
+
+#[test]
+fn entity_lookup_resolves_owner_process() {
+    let mut publisher = topic("pose", "publisher", 10_000);
+    publisher.topic_id = 42;
+    let mut other = topic("map", "publisher", 500);
+    other.topic_id = 43;
+    other.process_id = 8;
+    let planner = ProcessInfo {
+        registration_clock: 1,
+        host_name: "robot".into(),
+        shm_transport_domain: "robot".into(),
+        process_id: 7,
+        process_name: "planner".into(),
+        unit_name: "planner".into(),
+        process_parameter: String::new(),
+        state_severity: 1,
+        state_severity_level: 1,
+        state_info: String::new(),
+        time_sync_state: 0,
+        time_sync_module_name: String::new(),
+        component_init_state: 0,
+        component_init_info: String::new(),
+        runtime_version: "v6".into(),
+        config_file_path: String::new(),
+    };
+    let snapshot = MonitoringSnapshot {
+        processes: vec![planner],
+        publishers: vec![publisher, other],
+        subscribers: vec![topic("pose", "subscriber", 9_500)],
+        ..Default::default()
+    };
+
+    let publishers: Vec<_> = snapshot.publishers_of("pose").collect();
+    assert_eq!(publishers.len(), 1);
+    assert_eq!(publishers[0].topic_id, 42);
+    assert_eq!(snapshot.subscribers_of("pose").count(), 1);
+
+    assert_eq!(snapshot.owner_of(42), Some(("robot", 7)));
+    assert_eq!(snapshot.process_of(42).unwrap().unit_name, "planner");
+    // the owner of 43 is not in the process list
+    assert_eq!(snapshot.owner_of(43), Some(("robot", 8)));
+    assert!(snapshot.process_of(43).is_none());
+    assert!(snapshot.owner_of(99).is_none());
+}
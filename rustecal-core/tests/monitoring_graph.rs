@@ -0,0 +1,121 @@
+#![cfg(feature = "monitoring")]
+
+use rustecal_core::core_types::monitoring::{
+    ClientInfo, MonitoringSnapshot, ServerInfo, TopicInfo,
+};
+use rustecal_core::monitoring_graph::{EdgeKind, SystemGraph};
+use rustecal_core::types::DataTypeInfo;
+
+fn topic(unit_name: &str, process_id: i32, topic_name: &str) -> TopicInfo {
+    TopicInfo {
+        registration_clock: 1,
+        host_name: "robot".into(),
+        shm_transport_domain: "robot".into(),
+        process_id,
+        process_name: unit_name.into(),
+        unit_name: unit_name.into(),
+        topic_id: process_id as i64,
+        topic_name: topic_name.into(),
+        direction: String::new(),
+        data_type: DataTypeInfo {
+            type_name: "pb.Pose".into(),
+            encoding: "proto".into(),
+            descriptor: Vec::new(),
+        },
+        transport_layers: Vec::new(),
+        topic_size: 64,
+        connections_local: 1,
+        connections_external: 0,
+        message_drops: 0,
+        data_id: 0,
+        data_clock: 0,
+        data_frequency: 0,
+    }
+}
+
+fn snapshot() -> MonitoringSnapshot {
+    MonitoringSnapshot {
+        publishers: vec![topic("localizer", 1, "pose")],
+        subscribers: vec![
+            topic("planner", 2, "pose"),
+            topic("logger", 3, "pose"),
+            topic("planner", 2, "map"),
+        ],
+        servers: vec![ServerInfo {
+            registration_clock: 1,
+            host_name: "robot".into(),
+            process_name: "map_server".into(),
+            unit_name: "map_server".into(),
+            process_id: 4,
+            service_name: "map".into(),
+            service_id: 4,
+            version: 1,
+            tcp_port_v0: 0,
+            tcp_port_v1: 0,
+            methods: Vec::new(),
+        }],
+        clients: vec![ClientInfo {
+            registration_clock: 1,
+            host_name: "robot".into(),
+            process_name: "planner".into(),
+            unit_name: "planner".into(),
+            process_id: 2,
+            service_name: "map".into(),
+            service_id: 5,
+            version: 1,
+            methods: Vec::new(),
+        }],
+        ..Default::default()
+    }
+}
+
+#[test]
+fn graph_connects_publishers_subscribers_and_services() {
+    let graph = SystemGraph::from_snapshot(&snapshot());
+
+    let ids: Vec<_> = graph.nodes.iter().map(|n| n.id.as_str()).collect();
+    assert_eq!(ids, ["robot:1", "robot:2", "robot:3", "robot:4"]);
+    assert_eq!(graph.nodes[1].name, "planner");
+
+    // "map" has no publisher, so only the two "pose" edges and the service edge exist
+    let edges: Vec<_> = graph
+        .edges
+        .iter()
+        .map(|e| (e.kind, e.name.as_str(), e.from.as_str(), e.to.as_str()))
+        .collect();
+    assert_eq!(
+        edges,
+        [
+            (EdgeKind::Topic, "pose", "robot:1", "robot:2"),
+            (EdgeKind::Topic, "pose", "robot:1", "robot:3"),
+            (EdgeKind::Service, "map", "robot:2", "robot:4"),
+        ]
+    );
+    assert_eq!(graph.edges[0].type_name, "pb.Pose");
+}
+
+#[test]
+fn graph_exports_dot() {
+    let graph = SystemGraph::from_snapshot(&snapshot());
+
+    let dot = graph.to_dot();
+    assert!(dot.starts_with("digraph ecal {"));
+    assert!(dot.contains("\"robot:1\" -> \"robot:2\" [label=\"pose\"];"));
+    assert!(dot.contains("\"robot:2\" -> \"robot:4\" [label=\"map\", style=dashed];"));
+}
+
+#[test]
+#[cfg(feature = "serde")]
+fn graph_exports_json_in_serde_shape() {
+    let graph = SystemGraph::from_snapshot(&snapshot());
+
+    let json: serde_json::Value = serde_json::from_str(&graph.to_json()).unwrap();
+    assert_eq!(json["nodes"].as_array().unwrap().len(), 4);
+    assert_eq!(json["edges"][0]["kind"], "topic");
+    assert_eq!(json["edges"][2]["kind"], "service");
+    assert_eq!(json["edges"][0]["type_name"], "pb.Pose");
+
+    assert_eq!(json, serde_json::to_value(&graph).unwrap());
+    let parsed: SystemGraph = serde_json::from_value(json).unwrap();
+    assert_eq!(parsed, graph);
+}
@@ -0,0 +1,113 @@
+#![cfg(feature = "monitoring")]
+
+use rustecal_core::core_types::monitoring::{MonitoringSnapshot, ProcessInfo, TopicInfo};
+use rustecal_core::monitoring_watch::{MonitoringEntity, MonitoringEvent, diff};
+use rustecal_core::types::DataTypeInfo;
+
+fn process(process_id: i32, state_severity: i32) -> ProcessInfo {
+    ProcessInfo {
+        registration_clock: 1,
+        host_name: "robot".into(),
+        shm_transport_domain: "robot".into(),
+        process_id,
+        process_name: format!("process_{process_id}"),
+        unit_name: "unit".into(),
+        process_parameter: String::new(),
+        state_severity,
+        state_severity_level: 1,
+        state_info: String::new(),
+        time_sync_state: 0,
+        time_sync_module_name: String::new(),
+        component_init_state: 0,
+        component_init_info: String::new(),
+        runtime_version: "v6".into(),
+        config_file_path: String::new(),
+    }
+}
+
+fn publisher(topic_id: i64, connections_local: i32, data_clock: i64) -> TopicInfo {
+    TopicInfo {
+        registration_clock: 1,
+        host_name: "robot".into(),
+        shm_transport_domain: "robot".into(),
+        process_id: 7,
+        process_name: "process_7".into(),
+        unit_name: "unit".into(),
+        topic_id,
+        topic_name: format!("topic_{topic_id}"),
+        direction: "publisher".into(),
+        data_type: DataTypeInfo {
+            type_name: "pb.Pose".into(),
+            encoding: "proto".into(),
+            descriptor: Vec::new(),
+        },
+        transport_layers: Vec::new(),
+        topic_size: 64,
+        connections_local,
+        connections_external: 0,
+        message_drops: 0,
+        data_id: 0,
+        data_clock,
+        data_frequency: 10_000,
+    }
+}
+
+#[test]
+fn identical_snapshots_produce_no_events() {
+    let snapshot = MonitoringSnapshot {
+        processes: vec![process(7, 0)],
+        publishers: vec![publisher(1, 1, 10)],
+        ..Default::default()
+    };
+    assert!(diff(&snapshot, &snapshot.clone()).is_empty());
+}
+
+#[test]
+fn added_and_removed_entities() {
+    let previous = MonitoringSnapshot {
+        processes: vec![process(7, 0), process(8, 0)],
+        ..Default::default()
+    };
+    let current = MonitoringSnapshot {
+        processes: vec![process(7, 0), process(9, 0)],
+        ..Default::default()
+    };
+
+    let events = diff(&previous, &current);
+    assert_eq!(events.len(), 2);
+    assert!(matches!(
+        &events[0],
+        MonitoringEvent::EntityRemoved(MonitoringEntity::Process(p)) if p.process_id == 8
+    ));
+    assert!(matches!(
+        &events[1],
+        MonitoringEvent::EntityAdded(MonitoringEntity::Process(p)) if p.process_id == 9
+    ));
+}
+
+#[test]
+fn volatile_counters_are_not_changes() {
+    let previous = MonitoringSnapshot {
+        publishers: vec![publisher(1, 1, 10), publisher(2, 1, 10)],
+        ..Default::default()
+    };
+    let current = MonitoringSnapshot {
+        publishers: vec![publisher(1, 1, 20), publisher(2, 2, 20)],
+        ..Default::default()
+    };
+
+    let events = diff(&previous, &current);
+    assert_eq!(events.len(), 1);
+    match &events[0] {
+        MonitoringEvent::EntityChanged { previous, current } => {
+            assert_eq!(current.name(), "topic_2");
+            assert!(
+                matches!(&**previous, MonitoringEntity::Publisher(t) if t.connections_local == 1)
+            );
+            assert!(
+                matches!(&**current, MonitoringEntity::Publisher(t) if t.connections_local == 2)
+            );
+        }
+        other => panic!("unexpected event {other:?}"),
+    }
+}
@@ -0,0 +1,67 @@
+#![cfg(all(feature = "serde", feature = "logging", feature = "monitoring"))]
+
+use rustecal_core::core_types::monitoring::{TopicInfo, TransportLayer, TransportLayerType};
+use rustecal_core::types::DataTypeInfo;
+use rustecal_core::{LogLevel, LogMessage};
+
+#[test]
+fn log_message_round_trips_through_json() {
+    let message = LogMessage {
+        level: LogLevel::Warning,
+        timestamp: 42,
+        host_name: "robot".into(),
+        process_name: "planner".into(),
+        process_id: 7,
+        thread_name: "planner unit".into(),
+        content: "replanning".into(),
+    };
+
+    let json = serde_json::to_value(&message).unwrap();
+    assert_eq!(json["level"], "Warning");
+    assert_eq!(json["content"], "replanning");
+
+    let back: LogMessage = serde_json::from_value(json).unwrap();
+    assert_eq!(back.level, LogLevel::Warning);
+    assert_eq!(back.host_name, "robot");
+}
+
+#[test]
+fn topic_info_serializes_nested_types() {
+    let topic = TopicInfo {
+        registration_clock: 1,
+        host_name: "robot".into(),
+        shm_transport_domain: "robot".into(),
+        process_id: 7,
+        process_name: "planner".into(),
+        unit_name: "planner".into(),
+        topic_id: 99,
+        topic_name: "pose".into(),
+        direction: "publisher".into(),
+        data_type: DataTypeInfo {
+            type_name: "pb.Pose".into(),
+            encoding: "proto".into(),
+            descriptor: vec![1, 2],
+        },
+        transport_layers: vec![TransportLayer {
+            transport_type: TransportLayerType::Shm,
+            version: 1,
+            active: true,
+        }],
+        topic_size: 64,
+        connections_local: 1,
+        connections_external: 0,
+        message_drops: 0,
+        data_id: 0,
+        data_clock: 10,
+        data_frequency: 1000,
+    };
+
+    let json = serde_json::to_string(&topic).unwrap();
+    let back: TopicInfo = serde_json::from_str(&json).unwrap();
+    assert_eq!(back.topic_name, "pose");
+    assert_eq!(back.data_type.descriptor, vec![1, 2]);
+    assert!(matches!(
+        back.transport_layers[0].transport_type,
+        TransportLayerType::Shm
+    ));
+}
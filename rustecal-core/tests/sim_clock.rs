@@ -0,0 +1,33 @@
+use rustecal_core::clock::{Clock, SimClock};
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+
+#[test]
+fn sim_clock_set_and_advance() {
+    let clock = SimClock::new(1_000);
+    assert_eq!(clock.now(), 1_000);
+
+    clock.advance(Duration::from_millis(2));
+    assert_eq!(clock.now(), 3_000);
+
+    clock.set(42);
+    assert_eq!(clock.now(), 42);
+}
+
+#[test]
+fn sim_clock_wait_until_is_released_by_driver() {
+    let clock = Arc::new(SimClock::new(0));
+    let driver = {
+        let clock = Arc::clone(&clock);
+        thread::spawn(move || {
+            for _ in 0..10 {
+                clock.advance(Duration::from_micros(100));
+            }
+        })
+    };
+
+    assert!(clock.wait_until_timeout(1_000, Duration::from_secs(5)));
+    driver.join().unwrap();
+    assert!(!clock.wait_until_timeout(2_000, Duration::from_millis(10)));
+}
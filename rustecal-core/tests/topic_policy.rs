@@ -0,0 +1,40 @@
+use rustecal_core::{RustecalError, TopicPolicy};
+
+#[test]
+fn empty_policy_allows_everything() {
+    assert!(TopicPolicy::new().is_allowed("any/topic"));
+}
+
+#[test]
+fn deny_patterns_take_precedence() {
+    let policy = TopicPolicy::new()
+        .allow("robot/*")
+        .deny("*/debug*")
+        .deny("test_?");
+
+    assert!(policy.is_allowed("robot/pose"));
+    assert!(policy.is_allowed("robot/arm/joints"));
+    assert!(!policy.is_allowed("robot/debug_pose"));
+    assert!(!policy.is_allowed("camera/image"));
+    assert!(!policy.is_allowed("test_1"));
+}
+
+#[test]
+fn glob_wildcards() {
+    let policy = TopicPolicy::new().allow("a*b?c");
+    assert!(policy.is_allowed("abxc"));
+    assert!(policy.is_allowed("a/x/b/c"));
+    assert!(!policy.is_allowed("abc"));
+    assert!(!policy.is_allowed("abxcd"));
+}
+
+#[test]
+fn process_wide_policy_is_checked() {
+    TopicPolicy::set_default(TopicPolicy::new().deny("scratch*"));
+    assert!(TopicPolicy::check("pose").is_ok());
+    assert!(matches!(
+        TopicPolicy::check("scratchpad"),
+        Err(RustecalError::TopicNotAllowed(topic)) if topic == "scratchpad"
+    ));
+    TopicPolicy::set_default(TopicPolicy::new());
+}
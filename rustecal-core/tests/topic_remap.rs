@@ -0,0 +1,45 @@
+use rustecal_core::{RustecalError, TopicRemap};
+
+#[test]
+fn empty_remap_keeps_names() {
+    let remap = TopicRemap::new();
+    assert!(remap.is_empty());
+    assert_eq!(remap.apply("robot/pose"), "robot/pose");
+}
+
+#[test]
+fn renames_are_applied_before_the_prefix() {
+    let remap = TopicRemap::new()
+        .prefix("robot2/")
+        .remap("cmd_vel", "teleop/cmd_vel");
+    assert_eq!(remap.apply("pose"), "robot2/pose");
+    assert_eq!(remap.apply("cmd_vel"), "robot2/teleop/cmd_vel");
+    assert_eq!(remap.apply("cmd_vel/raw"), "robot2/cmd_vel/raw");
+}
+
+#[test]
+fn parses_environment_format() {
+    let remap = TopicRemap::parse(" ns/ ", "a=b, c = d ,").unwrap();
+    assert_eq!(
+        remap,
+        TopicRemap::new()
+            .prefix("ns/")
+            .remap("a", "b")
+            .remap("c", "d")
+    );
+    assert!(TopicRemap::parse("", "").unwrap().is_empty());
+    assert!(matches!(
+        TopicRemap::parse("", "a=b,c"),
+        Err(RustecalError::InvalidRemap(entry)) if entry == "c"
+    ));
+    assert!(TopicRemap::parse("", "=b").is_err());
+}
+
+#[test]
+fn process_wide_remap_is_resolved() {
+    TopicRemap::set_default(TopicRemap::new().prefix("sim/"));
+    assert_eq!(TopicRemap::resolve("pose"), "sim/pose");
+    assert_eq!(TopicRemap::current(), TopicRemap::new().prefix("sim/"));
+    TopicRemap::set_default(TopicRemap::new());
+    assert_eq!(TopicRemap::resolve("pose"), "pose");
+}
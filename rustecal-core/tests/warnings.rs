@@ -0,0 +1,23 @@
+use rustecal_core::error_hook::{ErrorHook, guard};
+use rustecal_core::log_level::LogLevel;
+use std::sync::{Arc, Mutex};
+
+#[test]
+fn unhandled_panic_is_reported_as_warning() {
+    let warnings = Arc::new(Mutex::new(Vec::new()));
+    let sink = warnings.clone();
+    ErrorHook::set_warning_handler(move |level, text| {
+        sink.lock().unwrap().push((level, text.to_string()))
+    });
+
+    assert_eq!(
+        guard(|| "test".to_string(), || -> i32 { panic!("boom") }),
+        None
+    );
+
+    let warnings = warnings.lock().unwrap();
+    assert_eq!(
+        *warnings,
+        [(LogLevel::Error, "panic in test callback: boom".to_string())]
+    );
+}
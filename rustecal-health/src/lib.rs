@@ -0,0 +1,12 @@
+//! # rustecal-health
+//!
+//! Health supervision for eCAL systems built on the monitoring API.
+//!
+//! ## Features
+//! - Watchdog for expected topics, processes and services (`Watchdog`).
+//! - Missing/recovered events via callback (`HealthEvent`).
+//! - Plain text health reports published on a topic.
+
+pub mod watchdog;
+
+pub use watchdog::{Expected, ExpectedKind, HealthEvent, HealthState, Watchdog, WatchdogHandle};
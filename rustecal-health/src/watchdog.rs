@@ -0,0 +1,337 @@
+//! Supervision of expected topics, processes and services.
+//!
+//! A [`Watchdog`] is configured with the entities a system needs and the
+//! longest silence tolerated for each of them. It checks the monitoring
+//! snapshot periodically and reports an entity as missing once it was silent
+//! for longer than allowed, and as recovered when it shows up again:
+//!
+//! - a topic is silent while it has no publisher or its publishers send no data,
+//! - a process is silent while no process of that unit (or process) name is registered,
+//! - a service is silent while it has no server.
+//!
+//! Events are delivered to a callback and, optionally, summarized as plain
+//! text on a health topic after every check.
+
+use rustecal_core::core_types::monitoring::MonitoringSnapshot;
+use rustecal_core::error_hook::guard;
+use rustecal_core::monitoring::{Monitoring, MonitoringEntities};
+use rustecal_pubsub::TypedPublisher;
+use rustecal_pubsub::publisher::Timestamp;
+use rustecal_types_string::StringMessage;
+use std::fmt;
+use std::sync::mpsc::{self, RecvTimeoutError, Sender};
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
+
+/// Interval between two checks unless set with [`Watchdog::check_interval`].
+pub const DEFAULT_CHECK_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Kind of a supervised entity.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ExpectedKind {
+    Topic,
+    Process,
+    Service,
+}
+
+impl fmt::Display for ExpectedKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ExpectedKind::Topic => f.write_str("topic"),
+            ExpectedKind::Process => f.write_str("process"),
+            ExpectedKind::Service => f.write_str("service"),
+        }
+    }
+}
+
+/// An entity the system is expected to contain.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Expected {
+    pub kind: ExpectedKind,
+    pub name: String,
+    /// Longest tolerated silence before the entity is reported missing.
+    pub max_silence: Duration,
+}
+
+/// A change in the health of a supervised entity.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum HealthEvent {
+    /// The entity was silent for longer than its `max_silence`.
+    Missing {
+        expected: Expected,
+        silent_for: Duration,
+    },
+    /// A missing entity is back.
+    Recovered { expected: Expected },
+}
+
+struct Tracked {
+    expected: Expected,
+    last_seen: Instant,
+    /// Sum of the data clocks of a topic's publishers at the last check.
+    last_clock: Option<i64>,
+    missing: bool,
+}
+
+/// Health state of all supervised entities, updated from monitoring snapshots.
+///
+/// This is the evaluation behind [`Watchdog`]; use it directly to supervise
+/// snapshots taken by other means.
+pub struct HealthState {
+    tracked: Vec<Tracked>,
+}
+
+impl HealthState {
+    /// Starts supervising `expected`; every entity gets its full `max_silence`
+    /// from `now` to show up.
+    pub fn new(expected: Vec<Expected>, now: Instant) -> Self {
+        Self {
+            tracked: expected
+                .into_iter()
+                .map(|expected| Tracked {
+                    expected,
+                    last_seen: now,
+                    last_clock: None,
+                    missing: false,
+                })
+                .collect(),
+        }
+    }
+
+    /// Updates the state from `snapshot` taken at `now` and returns the resulting events.
+    pub fn update(&mut self, snapshot: &MonitoringSnapshot, now: Instant) -> Vec<HealthEvent> {
+        let mut events = Vec::new();
+        for tracked in &mut self.tracked {
+            let name = tracked.expected.name.as_str();
+            let seen = match tracked.expected.kind {
+                ExpectedKind::Topic => {
+                    let mut publishers = snapshot
+                        .publishers
+                        .iter()
+                        .filter(|p| p.topic_name == name)
+                        .peekable();
+                    let clock = publishers
+                        .peek()
+                        .is_some()
+                        .then(|| publishers.map(|p| p.data_clock).sum::<i64>());
+                    let sending = clock.is_some() && clock != tracked.last_clock;
+                    tracked.last_clock = clock;
+                    sending
+                }
+                ExpectedKind::Process => snapshot
+                    .processes
+                    .iter()
+                    .any(|p| p.unit_name == name || p.process_name == name),
+                ExpectedKind::Service => snapshot.servers.iter().any(|s| s.service_name == name),
+            };
+
+            if seen {
+                tracked.last_seen = now;
+                if tracked.missing {
+                    tracked.missing = false;
+                    events.push(HealthEvent::Recovered {
+                        expected: tracked.expected.clone(),
+                    });
+                }
+            } else {
+                let silent_for = now.saturating_duration_since(tracked.last_seen);
+                if !tracked.missing && silent_for > tracked.expected.max_silence {
+                    tracked.missing = true;
+                    events.push(HealthEvent::Missing {
+                        expected: tracked.expected.clone(),
+                        silent_for,
+                    });
+                }
+            }
+        }
+        events
+    }
+
+    /// Returns `true` if no supervised entity is missing.
+    pub fn is_healthy(&self) -> bool {
+        self.tracked.iter().all(|t| !t.missing)
+    }
+
+    /// Returns the supervised entities that are currently missing.
+    pub fn missing(&self) -> impl Iterator<Item = &Expected> {
+        self.tracked
+            .iter()
+            .filter(|t| t.missing)
+            .map(|t| &t.expected)
+    }
+
+    /// Returns a plain text summary, one line per supervised entity.
+    pub fn report(&self, now: Instant) -> String {
+        let mut report = String::new();
+        for tracked in &self.tracked {
+            let expected = &tracked.expected;
+            let status = if tracked.missing {
+                format!(
+                    "MISSING (silent for {:.1} s)",
+                    now.saturating_duration_since(tracked.last_seen)
+                        .as_secs_f64()
+                )
+            } else {
+                "ok".to_string()
+            };
+            report.push_str(&format!(
+                "{} {}: {}\n",
+                expected.kind, expected.name, status
+            ));
+        }
+        report
+    }
+}
+
+type EventCallback = Box<dyn FnMut(&HealthEvent) + Send + 'static>;
+
+/// Periodically checks that all expected entities are alive.
+pub struct Watchdog {
+    expected: Vec<Expected>,
+    interval: Duration,
+    health_topic: Option<String>,
+    on_event: Option<EventCallback>,
+}
+
+impl Default for Watchdog {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Watchdog {
+    /// Creates a watchdog without expectations.
+    pub fn new() -> Self {
+        Self {
+            expected: Vec::new(),
+            interval: DEFAULT_CHECK_INTERVAL,
+            health_topic: None,
+            on_event: None,
+        }
+    }
+
+    /// Expects data on `topic_name` at least every `max_silence`.
+    pub fn expect_topic(self, topic_name: &str, max_silence: Duration) -> Self {
+        self.expect(ExpectedKind::Topic, topic_name, max_silence)
+    }
+
+    /// Expects a process with unit or process name `name`.
+    pub fn expect_process(self, name: &str, max_silence: Duration) -> Self {
+        self.expect(ExpectedKind::Process, name, max_silence)
+    }
+
+    /// Expects a server for `service_name`.
+    pub fn expect_service(self, service_name: &str, max_silence: Duration) -> Self {
+        self.expect(ExpectedKind::Service, service_name, max_silence)
+    }
+
+    fn expect(mut self, kind: ExpectedKind, name: &str, max_silence: Duration) -> Self {
+        self.expected.push(Expected {
+            kind,
+            name: name.to_string(),
+            max_silence,
+        });
+        self
+    }
+
+    /// Sets the interval between two checks.
+    ///
+    /// Silences are detected with a delay of up to one interval.
+    pub fn check_interval(mut self, interval: Duration) -> Self {
+        self.interval = interval;
+        self
+    }
+
+    /// Registers the callback receiving [`HealthEvent`]s.
+    pub fn on_event<F>(mut self, callback: F) -> Self
+    where
+        F: FnMut(&HealthEvent) + Send + 'static,
+    {
+        self.on_event = Some(Box::new(callback));
+        self
+    }
+
+    /// Publishes the [`HealthState::report`] as string message on `topic_name` after every check.
+    pub fn publish_health(mut self, topic_name: &str) -> Self {
+        self.health_topic = Some(topic_name.to_string());
+        self
+    }
+
+    /// Starts supervising on a dedicated thread.
+    ///
+    /// Requires eCAL to be initialized with `EcalComponents::MONITORING`.
+    /// Supervision stops when the returned [`WatchdogHandle`] is dropped.
+    pub fn start(self) -> WatchdogHandle {
+        let Watchdog {
+            expected,
+            interval,
+            health_topic,
+            mut on_event,
+        } = self;
+
+        let (stop, stopped) = mpsc::channel::<()>();
+        let thread = thread::spawn(move || {
+            // created on this thread, as `TypedPublisher` is not `Send`
+            let publisher =
+                health_topic.and_then(|topic| TypedPublisher::<StringMessage>::new(&topic).ok());
+            let entities = MonitoringEntities::PUBLISHER
+                | MonitoringEntities::PROCESS
+                | MonitoringEntities::SERVER;
+            let mut state = HealthState::new(expected, Instant::now());
+
+            loop {
+                if let Ok(snapshot) = Monitoring::snapshot_of(entities) {
+                    let now = Instant::now();
+                    for event in state.update(&snapshot, now) {
+                        if let Some(callback) = on_event.as_mut() {
+                            guard(|| "watchdog".to_string(), || callback(&event));
+                        }
+                    }
+                    if let Some(publisher) = &publisher {
+                        let report = StringMessage {
+                            data: state.report(now).into(),
+                        };
+                        let _ = publisher.send(&report, Timestamp::Auto);
+                    }
+                }
+                match stopped.recv_timeout(interval) {
+                    Err(RecvTimeoutError::Timeout) => continue,
+                    _ => break,
+                }
+            }
+        });
+
+        WatchdogHandle {
+            stop: Some(stop),
+            thread: Some(thread),
+        }
+    }
+}
+
+/// Handle of a running [`Watchdog`]; stops supervision when dropped.
+pub struct WatchdogHandle {
+    stop: Option<Sender<()>>,
+    thread: Option<JoinHandle<()>>,
+}
+
+impl WatchdogHandle {
+    /// Stops supervision and waits for a running check to finish.
+    ///
+    /// Must not be called from within the event callback.
+    pub fn stop(mut self) {
+        self.shutdown();
+    }
+
+    fn shutdown(&mut self) {
+        drop(self.stop.take());
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+    }
+}
+
+impl Drop for WatchdogHandle {
+    fn drop(&mut self) {
+        self.shutdown();
+    }
+}
@@ -0,0 +1,109 @@
+use rustecal_core::core_types::monitoring::{MonitoringSnapshot, ServerInfo, TopicInfo};
+use rustecal_core::types::DataTypeInfo;
+use rustecal_health::{Expected, ExpectedKind, HealthEvent, HealthState};
+use std::time::{Duration, Instant};
+
+fn publisher(topic_name: &str, data_clock: i64) -> TopicInfo {
+    TopicInfo {
+        registration_clock: 1,
+        host_name: "robot".into(),
+        shm_transport_domain: "robot".into(),
+        process_id: 7,
+        process_name: "camera".into(),
+        unit_name: "camera".into(),
+        topic_id: 1,
+        topic_name: topic_name.into(),
+        direction: "publisher".into(),
+        data_type: DataTypeInfo {
+            type_name: "Image".into(),
+            encoding: "proto".into(),
+            descriptor: Vec::new(),
+        },
+        transport_layers: Vec::new(),
+        topic_size: 64,
+        connections_local: 1,
+        connections_external: 0,
+        message_drops: 0,
+        data_id: 0,
+        data_clock,
+        data_frequency: 10_000,
+    }
+}
+
+fn server(service_name: &str) -> ServerInfo {
+    ServerInfo {
+        registration_clock: 1,
+        host_name: "robot".into(),
+        process_name: "mirror".into(),
+        unit_name: "mirror".into(),
+        process_id: 8,
+        service_name: service_name.into(),
+        service_id: 1,
+        version: 1,
+        tcp_port_v0: 0,
+        tcp_port_v1: 0,
+        methods: Vec::new(),
+    }
+}
+
+fn expected(kind: ExpectedKind, name: &str) -> Expected {
+    Expected {
+        kind,
+        name: name.into(),
+        max_silence: Duration::from_secs(1),
+    }
+}
+
+#[test]
+fn stalled_topic_is_reported_and_recovers() {
+    let start = Instant::now();
+    let mut state = HealthState::new(vec![expected(ExpectedKind::Topic, "image")], start);
+    let sending = |clock| MonitoringSnapshot {
+        publishers: vec![publisher("image", clock)],
+        ..Default::default()
+    };
+
+    assert!(state.update(&sending(1), start).is_empty());
+    // registered, but the data clock stands still
+    let at = |ms| start + Duration::from_millis(ms);
+    assert!(state.update(&sending(1), at(900)).is_empty());
+    let events = state.update(&sending(1), at(1500));
+    assert!(matches!(
+        &events[..],
+        [HealthEvent::Missing { silent_for, .. }] if *silent_for == Duration::from_millis(1500)
+    ));
+    assert!(!state.is_healthy());
+    assert!(state.report(at(1500)).contains("topic image: MISSING"));
+
+    // missing is reported once only
+    assert!(state.update(&sending(1), at(2000)).is_empty());
+
+    let events = state.update(&sending(2), at(2500));
+    assert!(matches!(&events[..], [HealthEvent::Recovered { .. }]));
+    assert!(state.is_healthy());
+}
+
+#[test]
+fn vanished_service_is_reported() {
+    let start = Instant::now();
+    let mut state = HealthState::new(
+        vec![
+            expected(ExpectedKind::Service, "mirror"),
+            expected(ExpectedKind::Process, "planner"),
+        ],
+        start,
+    );
+    let with_server = MonitoringSnapshot {
+        servers: vec![server("mirror")],
+        ..Default::default()
+    };
+
+    assert!(state.update(&with_server, start).is_empty());
+    let events = state.update(
+        &MonitoringSnapshot::default(),
+        start + Duration::from_secs(2),
+    );
+    assert_eq!(events.len(), 2);
+    let missing: Vec<_> = state.missing().map(|e| e.name.as_str()).collect();
+    assert_eq!(missing, ["mirror", "planner"]);
+}
@@ -0,0 +1,78 @@
+//! Send path benchmark for small (control) messages.
+//!
+//! Publishes a message of a few bytes in three ways:
+//!
+//! - `raw`   : the untyped `Publisher::send` on a prepared buffer, i.e. the C API baseline
+//! - `arc`   : serializing every message into a fresh `Arc<[u8]>` before sending
+//! - `typed` : `TypedPublisher::send`, serializing into the reused send buffer
+//!
+//! Needs an eCAL runtime: `cargo bench -p rustecal-pubsub --bench small_message_send`.
+//! Start a receiver on the `Performance_*` topics to include the transport in
+//! the measurement.
+
+use criterion::{BenchmarkId, Criterion, Throughput, criterion_group, criterion_main};
+use rustecal_core::types::DataTypeInfo;
+use rustecal_pubsub::publisher::Timestamp;
+use rustecal_pubsub::typed_publisher::PublisherMessage;
+use rustecal_pubsub::{Ecal, EcalComponents, Publisher, TypedPublisher};
+use std::hint::black_box;
+use std::sync::Arc;
+
+const PAYLOAD_SIZES: [usize; 3] = [16, 64, 256];
+
+/// A control message serialized through `write_bytes`, like the bundled
+/// string, protobuf and serde types.
+struct Control(Vec<u8>);
+
+impl PublisherMessage for Control {
+    fn datatype() -> DataTypeInfo {
+        DataTypeInfo {
+            encoding: "raw".into(),
+            type_name: "control".into(),
+            descriptor: Vec::new(),
+        }
+    }
+
+    fn to_bytes(&self) -> Arc<[u8]> {
+        Arc::from(self.0.as_slice())
+    }
+
+    fn write_bytes(&self, buffer: &mut Vec<u8>) {
+        buffer.extend_from_slice(&self.0);
+    }
+}
+
+fn small_message_send(c: &mut Criterion) {
+    Ecal::initialize(
+        Some("small message send bench"),
+        EcalComponents::DEFAULT,
+        None,
+    )
+    .expect("eCAL initialization failed");
+
+    let raw = Publisher::new("Performance_raw", Control::datatype()).unwrap();
+    let arc = Publisher::new("Performance_arc", Control::datatype()).unwrap();
+    let typed = TypedPublisher::<Control>::new("Performance_typed").unwrap();
+
+    let mut group = c.benchmark_group("small_message_send");
+    for size in PAYLOAD_SIZES {
+        let message = Control(vec![42; size]);
+        group.throughput(Throughput::Elements(1));
+        group.bench_with_input(BenchmarkId::new("raw", size), &message, |b, message| {
+            b.iter(|| raw.send(black_box(&message.0), Timestamp::Auto))
+        });
+        group.bench_with_input(BenchmarkId::new("arc", size), &message, |b, message| {
+            b.iter(|| arc.send(&black_box(message).to_bytes(), Timestamp::Auto))
+        });
+        group.bench_with_input(BenchmarkId::new("typed", size), &message, |b, message| {
+            b.iter(|| typed.send(black_box(message), Timestamp::Auto))
+        });
+    }
+    group.finish();
+
+    drop((raw, arc, typed));
+    Ecal::finalize();
+}
+
+criterion_group!(benches, small_message_send);
+criterion_main!(benches);
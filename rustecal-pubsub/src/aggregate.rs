@@ -0,0 +1,102 @@
+//! Fan-in of several subscriptions into one callback or channel.
+//!
+//! Sensor-fusion nodes typically consume a handful of topics of different
+//! types and process them in one place. An [`AggregatedSubscriber`] owns the
+//! subscribers of all these topics and delivers their messages, converted
+//! into one message type `M` (usually an enum), to a single callback or
+//! channel. [`Received::topic_name`] tells the source topics apart:
+//!
+//! ```ignore
+//! enum Sensor {
+//!     Imu(Arc<Imu>),
+//!     Gps(Arc<Gps>),
+//! }
+//!
+//! let mut fusion = AggregatedSubscriber::new(|msg: Received<Sensor>| fuse(msg));
+//! fusion.add::<ProtobufMessage<Imu>, _>("imu", |m| Sensor::Imu(m.data))?;
+//! fusion.add::<ProtobufMessage<Gps>, _>("gps", |m| Sensor::Gps(m.data))?;
+//! ```
+//!
+//! The callback is invoked on the receive threads of the individual topics,
+//! so messages of different topics may be delivered concurrently.
+
+use crate::channel::{DropPolicy, channel_sink};
+use crate::typed_subscriber::{Received, SubscriberMessage, TypedSubscriber};
+use crossbeam_channel::Receiver;
+use std::any::Any;
+use std::sync::Arc;
+
+type Sink<M> = Arc<dyn Fn(Received<M>) + Send + Sync + 'static>;
+
+/// Subscribers of several topics sharing one callback.
+pub struct AggregatedSubscriber<M> {
+    sink: Sink<M>,
+    /// The typed subscribers of all topics, kept alive until drop.
+    subscribers: Vec<(String, Box<dyn Any>)>,
+}
+
+impl<M: 'static> AggregatedSubscriber<M> {
+    /// Creates an aggregation delivering to `callback`.
+    pub fn new<F>(callback: F) -> Self
+    where
+        F: Fn(Received<M>) + Send + Sync + 'static,
+    {
+        Self {
+            sink: Arc::new(callback),
+            subscribers: Vec::new(),
+        }
+    }
+
+    /// Creates an aggregation delivering into a channel of `capacity`
+    /// messages; `policy` decides what happens while the channel is full.
+    pub fn with_channel(capacity: usize, policy: DropPolicy) -> (Self, Receiver<Received<M>>)
+    where
+        M: Send,
+    {
+        let (sink, receiver) = channel_sink(capacity, policy);
+        let aggregation = Self {
+            sink: Arc::from(sink),
+            subscribers: Vec::new(),
+        };
+        (aggregation, receiver)
+    }
+
+    /// Subscribes to `topic_name` with message type `U` and converts its
+    /// payloads with `convert`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `Err(String)` if the subscriber could not be created.
+    pub fn add<U, G>(&mut self, topic_name: &str, convert: G) -> Result<(), String>
+    where
+        U: SubscriberMessage<'static> + 'static,
+        G: Fn(U) -> M + Send + Sync + 'static,
+    {
+        let mut subscriber = TypedSubscriber::<U>::new(topic_name)?;
+        let sink = self.sink.clone();
+        subscriber.set_callback(move |msg: Received<U>| sink(msg.map(&convert)));
+        self.subscribers
+            .push((topic_name.to_string(), Box::new(subscriber)));
+        Ok(())
+    }
+
+    /// Subscribes to `topic_name` with the aggregated message type itself.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `Err(String)` if the subscriber could not be created.
+    pub fn subscribe(&mut self, topic_name: &str) -> Result<(), String>
+    where
+        M: SubscriberMessage<'static>,
+    {
+        self.add::<M, _>(topic_name, |msg| msg)
+    }
+
+    /// Returns the names of the aggregated topics in the order they were added.
+    pub fn topics(&self) -> Vec<String> {
+        self.subscribers
+            .iter()
+            .map(|(topic_name, _)| topic_name.clone())
+            .collect()
+    }
+}
@@ -0,0 +1,101 @@
+//! Delivery of received messages into a channel.
+//!
+//! [`TypedSubscriber::into_channel`] forwards every received message into a
+//! bounded [`crossbeam_channel`], so applications consume a topic like any
+//! other channel (`recv`, `recv_timeout`, `iter`, `select!`) without writing
+//! the callback plumbing themselves. A [`DropPolicy`] decides what happens
+//! when the consumer falls behind and the channel is full.
+
+use crate::typed_subscriber::{OwnedMessage, Received, SubscriberMessage, TypedSubscriber};
+use crossbeam_channel::{Receiver, TrySendError};
+use std::ops::Deref;
+
+/// What to do with a message that arrives while the channel is full.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum DropPolicy {
+    /// Drop the arriving message and keep the queued ones.
+    #[default]
+    DropNewest,
+    /// Drop the oldest queued message to make room for the arriving one.
+    DropOldest,
+    /// Wait until the consumer made room.
+    ///
+    /// This blocks eCAL's receive thread of the topic, so messages may be
+    /// lost at the transport layer instead.
+    Block,
+}
+
+/// Receiving end of [`TypedSubscriber::into_channel`].
+///
+/// Dereferences to the [`crossbeam_channel::Receiver`]; dropping it drops the
+/// subscriber. The channel stays on the thread that created the subscriber;
+/// hand [`SubscriberChannel::receiver`] to other threads.
+pub struct SubscriberChannel<T: SubscriberMessage<'static> + OwnedMessage> {
+    receiver: Receiver<Received<T>>,
+    _subscriber: TypedSubscriber<'static, T>,
+}
+
+impl<T: SubscriberMessage<'static> + OwnedMessage> SubscriberChannel<T> {
+    /// Returns a clone of the receiver, e.g. to hand it to another thread.
+    ///
+    /// The clone stops receiving once this `SubscriberChannel` is dropped.
+    pub fn receiver(&self) -> Receiver<Received<T>> {
+        self.receiver.clone()
+    }
+}
+
+impl<T: SubscriberMessage<'static> + OwnedMessage> Deref for SubscriberChannel<T> {
+    type Target = Receiver<Received<T>>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.receiver
+    }
+}
+
+/// Creates a bounded channel of `capacity` messages and the function
+/// feeding it according to `policy`.
+pub(crate) fn channel_sink<M: Send + 'static>(
+    capacity: usize,
+    policy: DropPolicy,
+) -> (Box<dyn Fn(M) + Send + Sync + 'static>, Receiver<M>) {
+    let (sender, receiver) = crossbeam_channel::bounded(capacity.max(1));
+    let sink: Box<dyn Fn(M) + Send + Sync> = match policy {
+        DropPolicy::DropNewest => Box::new(move |msg| {
+            let _ = sender.try_send(msg);
+        }),
+        DropPolicy::DropOldest => {
+            // never blocks, so holding a receiver cannot keep the channel stuck
+            let oldest = receiver.clone();
+            Box::new(move |msg| {
+                let mut msg = msg;
+                while let Err(TrySendError::Full(rejected)) = sender.try_send(msg) {
+                    let _ = oldest.try_recv();
+                    msg = rejected;
+                }
+            })
+        }
+        DropPolicy::Block => Box::new(move |msg| {
+            let _ = sender.send(msg);
+        }),
+    };
+    (sink, receiver)
+}
+
+impl<T: SubscriberMessage<'static> + OwnedMessage> TypedSubscriber<'static, T> {
+    /// Converts the subscriber into a channel holding up to `capacity`
+    /// received messages, replacing the callback.
+    ///
+    /// `policy` decides what happens to messages arriving while the channel
+    /// is full. Messages are detached from the receive buffer before they
+    /// are queued (see [`OwnedMessage`]).
+    pub fn into_channel(mut self, capacity: usize, policy: DropPolicy) -> SubscriberChannel<T> {
+        let (sink, receiver) = channel_sink(capacity, policy);
+        self.set_callback(move |msg: Received<T>| sink(msg.into_owned()));
+        let gauge = receiver.clone();
+        self.set_queue_gauge(Box::new(move || (gauge.len(), capacity.max(1))));
+        SubscriberChannel {
+            receiver,
+            _subscriber: self,
+        }
+    }
+}
@@ -0,0 +1,73 @@
+//! Clock source selection for outgoing message timestamps.
+//!
+//! A [`ClockSource`] decides which time base is used when a message is sent
+//! with [`Timestamp::Auto`](crate::publisher::Timestamp::Auto). Each publisher
+//! may override the clock source, otherwise the process-wide default set via
+//! [`ClockSource::set_default`] is used. This allows switching all topics of a
+//! process to the same time base in one place.
+
+use rustecal_core::clock::{Clock, SimClock};
+use std::sync::{Arc, OnceLock, RwLock};
+use std::time::{Instant, SystemTime, UNIX_EPOCH};
+
+/// The time base used to timestamp messages sent with `Timestamp::Auto`.
+#[derive(Clone, Default)]
+pub enum ClockSource {
+    /// Let eCAL assign its internal (optionally synchronized) send timestamp.
+    #[default]
+    Ecal,
+    /// Microseconds elapsed on a monotonic clock since the first use in this process.
+    Monotonic,
+    /// Microseconds since the UNIX epoch according to the system wall clock.
+    WallClock,
+    /// The process-wide simulated clock ([`SimClock::global`]).
+    Sim,
+    /// A user-provided function returning the timestamp in microseconds.
+    Custom(Arc<dyn Fn() -> i64 + Send + Sync>),
+}
+
+/// Process-wide default clock source used by publishers without an override.
+static DEFAULT_CLOCK_SOURCE: RwLock<Option<ClockSource>> = RwLock::new(None);
+
+/// Anchor for the monotonic clock source.
+static MONOTONIC_START: OnceLock<Instant> = OnceLock::new();
+
+impl ClockSource {
+    /// Sets the process-wide default clock source.
+    ///
+    /// Affects every publisher that has no clock source of its own,
+    /// including publishers that already exist.
+    pub fn set_default(source: ClockSource) {
+        *DEFAULT_CLOCK_SOURCE.write().unwrap() = Some(source);
+    }
+
+    /// Returns the current process-wide default clock source.
+    pub fn default_source() -> ClockSource {
+        DEFAULT_CLOCK_SOURCE
+            .read()
+            .unwrap()
+            .clone()
+            .unwrap_or_default()
+    }
+
+    /// Returns the current time of this clock source in microseconds.
+    ///
+    /// `None` means the timestamp is left to eCAL.
+    pub fn now(&self) -> Option<i64> {
+        match self {
+            ClockSource::Ecal => None,
+            ClockSource::Monotonic => {
+                let start = MONOTONIC_START.get_or_init(Instant::now);
+                Some(start.elapsed().as_micros() as i64)
+            }
+            ClockSource::WallClock => Some(
+                SystemTime::now()
+                    .duration_since(UNIX_EPOCH)
+                    .map(|d| d.as_micros() as i64)
+                    .unwrap_or(0),
+            ),
+            ClockSource::Sim => Some(SimClock::global().now()),
+            ClockSource::Custom(f) => Some(f()),
+        }
+    }
+}
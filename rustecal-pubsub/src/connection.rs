@@ -0,0 +1,116 @@
+//! Waiting for publishers and subscribers to connect.
+//!
+//! [`Publisher::wait_for_subscribers`] and [`Subscriber::wait_for_publishers`]
+//! block until a number of partners is connected, e.g. before sending the
+//! first message of a test. Instead of polling the connection count, they
+//! sleep until a connection event of their topic arrives (see
+//! [`crate::events`]) and re-check the count then. The count is also
+//! re-checked every [`RECHECK_INTERVAL`], so a wait ends promptly when eCAL
+//! shuts down.
+//!
+//! With the `tokio` feature, the `*_async` variants wait the same way without
+//! blocking the calling task; they do not depend on a particular async runtime.
+//!
+//! [`Publisher::wait_for_subscribers`]: crate::publisher::Publisher::wait_for_subscribers
+//! [`Subscriber::wait_for_publishers`]: crate::subscriber::Subscriber::wait_for_publishers
+
+use crate::events::EventRegistry;
+use rustecal_core::Ecal;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+/// Interval at which a connection wait re-checks the count without an event.
+pub const RECHECK_INTERVAL: Duration = Duration::from_millis(250);
+
+/// Event registration removed on drop, also when an async wait is cancelled.
+struct Registration<'a, E> {
+    events: &'a EventRegistry<E>,
+    token: u64,
+}
+
+impl<'a, E> Registration<'a, E> {
+    fn new<F>(events: &'a EventRegistry<E>, topic_name: &str, on_event: F) -> Self
+    where
+        F: Fn() + Send + Sync + 'static,
+    {
+        let token = events.register(topic_name, Arc::new(move |_: &E| on_event()));
+        Self { events, token }
+    }
+}
+
+impl<E> Drop for Registration<'_, E> {
+    fn drop(&mut self) {
+        self.events.unregister(self.token);
+    }
+}
+
+/// Blocks until `connected` returns `true`, re-checking it on every event
+/// of `topic_name`, or until `timeout` has elapsed.
+pub(crate) fn wait_for_connections<E>(
+    events: &EventRegistry<E>,
+    topic_name: &str,
+    timeout: Duration,
+    connected: impl Fn() -> bool,
+) -> bool {
+    let (notify, notified) = crossbeam_channel::bounded::<()>(1);
+    // registered before the first check, so no event is missed
+    let _registration = Registration::new(events, topic_name, move || {
+        let _ = notify.try_send(());
+    });
+    let deadline = Instant::now() + timeout;
+    loop {
+        if connected() {
+            return true;
+        }
+        let now = Instant::now();
+        if now >= deadline || !Ecal::ok() {
+            return false;
+        }
+        let _ = notified.recv_timeout(RECHECK_INTERVAL.min(deadline - now));
+    }
+}
+
+/// Async variant of [`wait_for_connections`].
+///
+/// A helper thread wakes the future every [`RECHECK_INTERVAL`] and at the
+/// deadline; it exits when the future completes or is dropped.
+#[cfg(feature = "tokio")]
+pub(crate) async fn wait_for_connections_async<E>(
+    events: &EventRegistry<E>,
+    topic_name: &str,
+    timeout: Duration,
+    connected: impl Fn() -> bool,
+) -> bool {
+    use crossbeam_channel::RecvTimeoutError;
+    use tokio::sync::Notify;
+
+    let notify = Arc::new(Notify::new());
+    let on_event = notify.clone();
+    let _registration = Registration::new(events, topic_name, move || on_event.notify_one());
+    let deadline = Instant::now() + timeout;
+
+    let (_stop, stopped) = crossbeam_channel::bounded::<()>(0);
+    let ticker = notify.clone();
+    std::thread::spawn(move || {
+        loop {
+            let wait = RECHECK_INTERVAL.min(deadline.saturating_duration_since(Instant::now()));
+            match stopped.recv_timeout(wait) {
+                Err(RecvTimeoutError::Timeout) => ticker.notify_one(),
+                _ => break,
+            }
+            if Instant::now() >= deadline {
+                break;
+            }
+        }
+    });
+
+    loop {
+        if connected() {
+            return true;
+        }
+        if Instant::now() >= deadline || !Ecal::ok() {
+            return false;
+        }
+        notify.notified().await;
+    }
+}
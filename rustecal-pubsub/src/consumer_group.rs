@@ -0,0 +1,244 @@
+//! Competing consumers on top of pub/sub.
+//!
+//! All [`GroupSubscriber`]s that subscribe to the same topic with the same
+//! group name form a consumer group: every message is processed by only one
+//! member of the group, turning a topic into a shared work queue.
+//!
+//! Members find each other through heartbeats on a small coordination topic
+//! (`__consumer_group/<group>@<topic>`). Each member keeps the same sorted
+//! list of live members and handles exactly the messages whose key (derived
+//! from the publisher's send timestamp and clock) maps to its own slot. No
+//! round trip is needed per message.
+//!
+//! While the membership changes (a member joins, leaves or crashes), the
+//! members' views may briefly disagree, so a message may then be processed
+//! twice or not at all. A crashed member is removed after
+//! [`MEMBER_TIMEOUT_FACTOR`] missed heartbeats.
+
+use crate::publisher::Timestamp;
+use crate::typed_publisher::{PublisherMessage, TypedPublisher};
+use crate::typed_subscriber::{Received, SubscriberMessage, TypedSubscriber};
+use rustecal_core::types::DataTypeInfo;
+use std::collections::HashMap;
+use std::sync::mpsc::{self, RecvTimeoutError, Sender};
+use std::sync::{Arc, Mutex};
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+/// Default interval between two heartbeats of a group member.
+pub const DEFAULT_HEARTBEAT_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Number of heartbeat intervals after which a silent member is considered gone.
+pub const MEMBER_TIMEOUT_FACTOR: u32 = 3;
+
+/// Membership announcement on the coordination topic.
+struct Heartbeat {
+    member_id: u64,
+    leaving: bool,
+}
+
+impl PublisherMessage for Heartbeat {
+    fn datatype() -> DataTypeInfo {
+        DataTypeInfo {
+            encoding: "raw".into(),
+            type_name: "rustecal.ConsumerGroupHeartbeat".into(),
+            descriptor: Vec::new(),
+        }
+    }
+
+    fn to_bytes(&self) -> Arc<[u8]> {
+        let mut bytes = Vec::with_capacity(9);
+        self.write_bytes(&mut bytes);
+        Arc::from(bytes)
+    }
+
+    fn write_bytes(&self, buffer: &mut Vec<u8>) {
+        buffer.extend_from_slice(&self.member_id.to_le_bytes());
+        buffer.push(self.leaving as u8);
+    }
+}
+
+impl SubscriberMessage<'_> for Heartbeat {
+    fn datatype() -> DataTypeInfo {
+        <Heartbeat as PublisherMessage>::datatype()
+    }
+
+    fn from_bytes(bytes: &[u8], _data_type_info: &DataTypeInfo) -> Option<Self> {
+        let id: [u8; 8] = bytes.get(..8)?.try_into().ok()?;
+        Some(Heartbeat {
+            member_id: u64::from_le_bytes(id),
+            leaving: bytes.get(8).is_some_and(|&b| b != 0),
+        })
+    }
+}
+
+/// SplitMix64 finalizer, stable across platforms and Rust versions.
+fn mix(mut x: u64) -> u64 {
+    x = x.wrapping_add(0x9e37_79b9_7f4a_7c15);
+    x = (x ^ (x >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    x = (x ^ (x >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    x ^ (x >> 31)
+}
+
+/// The live members of a group as seen by this member.
+struct Membership {
+    own_id: u64,
+    timeout: Duration,
+    others: Mutex<HashMap<u64, Instant>>,
+}
+
+impl Membership {
+    fn update(&self, heartbeat: &Heartbeat) {
+        if heartbeat.member_id == self.own_id {
+            return;
+        }
+        let mut others = self.others.lock().unwrap();
+        if heartbeat.leaving {
+            others.remove(&heartbeat.member_id);
+        } else {
+            others.insert(heartbeat.member_id, Instant::now());
+        }
+    }
+
+    /// Returns the sorted IDs of all live members, including this one.
+    fn members(&self) -> Vec<u64> {
+        let mut others = self.others.lock().unwrap();
+        others.retain(|_, seen| seen.elapsed() < self.timeout);
+
+        let mut members: Vec<u64> = others.keys().copied().collect();
+        members.push(self.own_id);
+        members.sort_unstable();
+        members
+    }
+
+    /// Returns `true` if the message identified by `timestamp` and `clock` is ours.
+    fn owns(&self, timestamp: i64, clock: i64) -> bool {
+        let members = self.members();
+        let key = mix(timestamp as u64 ^ mix(clock as u64));
+        members[(key % members.len() as u64) as usize] == self.own_id
+    }
+}
+
+/// A subscriber that shares the messages of a topic with the other members of its group.
+pub struct GroupSubscriber<'buf, T: SubscriberMessage<'buf>> {
+    subscriber: TypedSubscriber<'buf, T>,
+    membership: Arc<Membership>,
+    _heartbeats: TypedSubscriber<'static, Heartbeat>,
+    stop: Option<Sender<()>>,
+    heartbeat_thread: Option<JoinHandle<()>>,
+}
+
+impl<'buf, T: SubscriberMessage<'buf>> GroupSubscriber<'buf, T> {
+    /// Joins the consumer group `group` on `topic_name`.
+    ///
+    /// Uses [`DEFAULT_HEARTBEAT_INTERVAL`].
+    pub fn new(topic_name: &str, group: &str) -> Result<Self, String> {
+        Self::with_heartbeat_interval(topic_name, group, DEFAULT_HEARTBEAT_INTERVAL)
+    }
+
+    /// Joins the consumer group `group` on `topic_name` with a custom heartbeat interval.
+    ///
+    /// All members of a group must use the same interval.
+    pub fn with_heartbeat_interval(
+        topic_name: &str,
+        group: &str,
+        interval: Duration,
+    ) -> Result<Self, String> {
+        let nanos = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_nanos() as u64)
+            .unwrap_or(0);
+        let membership = Arc::new(Membership {
+            own_id: mix(((std::process::id() as u64) << 32) ^ nanos),
+            timeout: interval * MEMBER_TIMEOUT_FACTOR,
+            others: Mutex::new(HashMap::new()),
+        });
+
+        let coordination_topic = format!("__consumer_group/{group}@{topic_name}");
+
+        let mut heartbeats = TypedSubscriber::<Heartbeat>::new(&coordination_topic)?;
+        let seen = membership.clone();
+        heartbeats.set_callback(move |msg: Received<Heartbeat>| seen.update(&msg.payload));
+
+        // the publisher lives on the heartbeat thread; eCAL handles are not `Send`
+        let (stop, stopped) = mpsc::channel::<()>();
+        let (ready_tx, ready_rx) = mpsc::channel::<Result<(), String>>();
+        let own_id = membership.own_id;
+        let heartbeat_thread = thread::spawn(move || {
+            let publisher = match TypedPublisher::<Heartbeat>::new(&coordination_topic) {
+                Ok(publisher) => {
+                    let _ = ready_tx.send(Ok(()));
+                    publisher
+                }
+                Err(e) => {
+                    let _ = ready_tx.send(Err(e));
+                    return;
+                }
+            };
+
+            let mut beat = Heartbeat {
+                member_id: own_id,
+                leaving: false,
+            };
+            loop {
+                let _ = publisher.send(&beat, Timestamp::Auto);
+                match stopped.recv_timeout(interval) {
+                    Err(RecvTimeoutError::Timeout) => continue,
+                    _ => break,
+                }
+            }
+            // announce the departure so the others take over immediately
+            beat.leaving = true;
+            let _ = publisher.send(&beat, Timestamp::Auto);
+        });
+        ready_rx
+            .recv()
+            .map_err(|_| "Consumer group heartbeat thread terminated".to_string())??;
+
+        Ok(Self {
+            subscriber: TypedSubscriber::new(topic_name)?,
+            membership,
+            _heartbeats: heartbeats,
+            stop: Some(stop),
+            heartbeat_thread: Some(heartbeat_thread),
+        })
+    }
+
+    /// Registers the callback invoked for every message assigned to this member.
+    pub fn set_callback<F>(&mut self, callback: F)
+    where
+        F: Fn(Received<T>) + Send + Sync + 'static,
+    {
+        let membership = self.membership.clone();
+        self.subscriber.set_callback(move |msg: Received<T>| {
+            if membership.owns(msg.timestamp, msg.clock) {
+                callback(msg);
+            }
+        });
+    }
+
+    /// Returns the number of live group members, including this one.
+    pub fn member_count(&self) -> usize {
+        self.membership.members().len()
+    }
+
+    /// Returns the number of currently connected publishers.
+    pub fn get_publisher_count(&self) -> usize {
+        self.subscriber.get_publisher_count()
+    }
+
+    /// Returns the underlying typed subscriber.
+    pub fn subscriber(&self) -> &TypedSubscriber<'buf, T> {
+        &self.subscriber
+    }
+}
+
+impl<'buf, T: SubscriberMessage<'buf>> Drop for GroupSubscriber<'buf, T> {
+    /// Leaves the group and stops the heartbeat thread.
+    fn drop(&mut self) {
+        drop(self.stop.take());
+        if let Some(thread) = self.heartbeat_thread.take() {
+            let _ = thread.join();
+        }
+    }
+}
@@ -0,0 +1,84 @@
+//! Thinning out high-rate topics on the subscriber side.
+//!
+//! A viewer on a slow machine rarely needs every sample of a 1 kHz topic.
+//! [`TypedSubscriber::set_decimation`] makes the subscriber deliver only
+//! every n-th message, or at most one message per interval; the others are
+//! discarded on the receive thread before they are decoded:
+//!
+//! ```ignore
+//! subscriber.set_decimation(Some(Decimation::max_rate(10.0)?));
+//! ```
+//!
+//! Decimation applies to the messages passing the source and payload
+//! filters, across all publishers of the topic. Discarded messages still
+//! count as received in the statistics.
+//!
+//! [`TypedSubscriber::set_decimation`]: crate::typed_subscriber::TypedSubscriber::set_decimation
+
+use crate::throttle::RateLimiter;
+use std::time::{Duration, Instant};
+
+/// Which messages a decimating subscriber delivers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Decimation {
+    /// Deliver the first message and then every n-th one.
+    EveryNth(u32),
+    /// Deliver at most one message per interval.
+    MinInterval(Duration),
+}
+
+impl Decimation {
+    /// Delivers at most `max_rate` messages per second.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `Err(String)` if `max_rate` is not a positive, finite number.
+    pub fn max_rate(max_rate: f64) -> Result<Self, String> {
+        RateLimiter::new(max_rate).map(|limiter| Decimation::MinInterval(limiter.interval()))
+    }
+}
+
+/// Decides per message whether a [`Decimation`] delivers it.
+#[derive(Debug, Clone)]
+pub struct Decimator {
+    decimation: Decimation,
+    seen: u64,
+    limiter: RateLimiter,
+}
+
+impl Decimator {
+    /// Creates a decimator that delivers the next message.
+    pub fn new(decimation: Decimation) -> Self {
+        let interval = match decimation {
+            Decimation::MinInterval(interval) => interval,
+            Decimation::EveryNth(_) => Duration::ZERO,
+        };
+        Self {
+            decimation,
+            seen: 0,
+            limiter: RateLimiter::with_interval(interval),
+        }
+    }
+
+    /// Returns the decimation this decimator applies.
+    pub fn decimation(&self) -> Decimation {
+        self.decimation
+    }
+
+    /// Returns `true` if a message arriving at `now` is to be delivered.
+    pub fn admit_at(&mut self, now: Instant) -> bool {
+        match self.decimation {
+            Decimation::EveryNth(n) => {
+                let admit = self.seen.is_multiple_of(u64::from(n.max(1)));
+                self.seen += 1;
+                admit
+            }
+            Decimation::MinInterval(_) => self.limiter.try_acquire_at(now),
+        }
+    }
+
+    /// Returns `true` if a message arriving now is to be delivered.
+    pub fn admit(&mut self) -> bool {
+        self.admit_at(Instant::now())
+    }
+}
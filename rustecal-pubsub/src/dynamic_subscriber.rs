@@ -0,0 +1,200 @@
+//! Subscriptions to topics of any type.
+//!
+//! Recorders, bridges and other generic tools do not know the message types
+//! of the topics they handle at compile time. A [`DynamicSubscriber`] has no
+//! type parameter: it subscribes without declaring a data type and hands every
+//! message to its callback as serialized bytes together with the data type
+//! the publisher declared and a [`SampleMeta`]:
+//!
+//! ```ignore
+//! let mut subscriber = DynamicSubscriber::new("camera/front")?;
+//! subscriber.set_callback(|payload, data_type, meta| {
+//!     recorder.write(&meta.topic_name, &data_type.type_name, meta.timestamp, payload);
+//! });
+//! ```
+//!
+//! The payload borrows eCAL's receive buffer and is only valid during the
+//! callback.
+
+use crate::events::topic_name_of;
+use crate::subscriber::Subscriber;
+use crate::type_tracking::SubscriberEvent;
+use rustecal_core::error_hook::guard;
+use rustecal_core::time::Time;
+use rustecal_core::types::{DataTypeInfo, EntityId};
+use rustecal_sys::{eCAL_SDataTypeInformation, eCAL_SReceiveCallbackData, eCAL_STopicId};
+use std::ffi::c_void;
+use std::time::Duration;
+use std::{ptr, slice};
+
+/// Metadata of a message received by a [`DynamicSubscriber`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SampleMeta {
+    /// The topic name this message was received on.
+    pub topic_name: String,
+    /// The publisher's send timestamp (microseconds since epoch).
+    pub timestamp: i64,
+    /// The publisher's logical clock at send time.
+    pub clock: i64,
+    /// The size of the serialized payload in bytes.
+    pub size: usize,
+    /// Entity ID of the sending publisher, resolvable with `Monitoring::process_of`.
+    pub publisher_id: u64,
+    /// Host name of the sending process.
+    pub host_name: String,
+    /// Process ID of the sending process.
+    pub process_id: i32,
+}
+
+impl SampleMeta {
+    /// Returns the time between sending and now in microseconds.
+    ///
+    /// See `Received::latency_us`.
+    pub fn latency_us(&self) -> i64 {
+        Time::microseconds() - self.timestamp
+    }
+
+    /// Returns the entity ID of the sending publisher.
+    pub fn publisher(&self) -> EntityId {
+        EntityId {
+            entity_id: self.publisher_id,
+            process_id: self.process_id,
+            host_name: self.host_name.clone(),
+        }
+    }
+}
+
+type Callback = Box<dyn Fn(&[u8], &DataTypeInfo, SampleMeta) + Send + Sync + 'static>;
+
+/// A subscriber delivering serialized messages of any data type.
+pub struct DynamicSubscriber {
+    subscriber: Subscriber,
+    user_data: *mut Callback,
+}
+
+impl DynamicSubscriber {
+    /// Subscribes to `topic_name` without declaring a data type.
+    ///
+    /// Messages are discarded until a callback is set.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `Err(String)` if the underlying eCAL subscriber could not be created.
+    pub fn new(topic_name: &str) -> Result<Self, String> {
+        let data_type = DataTypeInfo {
+            type_name: String::new(),
+            encoding: String::new(),
+            descriptor: Vec::new(),
+        };
+        let subscriber = Subscriber::new(topic_name, data_type, trampoline)?;
+        Ok(Self {
+            subscriber,
+            user_data: ptr::null_mut(),
+        })
+    }
+
+    /// Registers the callback receiving the payload, the publisher's data
+    /// type and the metadata of every message, replacing a previous one.
+    pub fn set_callback<F>(&mut self, callback: F)
+    where
+        F: Fn(&[u8], &DataTypeInfo, SampleMeta) + Send + Sync + 'static,
+    {
+        let callback: Callback = Box::new(callback);
+        let user_data = Box::into_raw(Box::new(callback));
+        unsafe {
+            rustecal_sys::eCAL_Subscriber_SetReceiveCallback(
+                self.subscriber.raw_handle(),
+                Some(trampoline),
+                user_data as *mut _,
+            );
+        }
+        self.release_callback();
+        self.user_data = user_data;
+    }
+
+    /// Registers a callback for the connection events of this subscriber.
+    ///
+    /// See [`Subscriber::set_event_callback`].
+    pub fn set_event_callback<F>(&mut self, callback: F)
+    where
+        F: Fn(&SubscriberEvent) + Send + Sync + 'static,
+    {
+        self.subscriber.set_event_callback(callback);
+    }
+
+    /// Returns the number of currently connected publishers.
+    pub fn get_publisher_count(&self) -> usize {
+        self.subscriber.get_publisher_count()
+    }
+
+    /// Blocks until at least `count` publishers are connected, or `timeout` has elapsed.
+    ///
+    /// Returns `true` if enough publishers are connected.
+    pub fn wait_for_publishers(&self, count: usize, timeout: Duration) -> bool {
+        self.subscriber.wait_for_publishers(count, timeout)
+    }
+
+    /// Async variant of [`DynamicSubscriber::wait_for_publishers`].
+    #[cfg(feature = "tokio")]
+    pub async fn wait_for_publishers_async(&self, count: usize, timeout: Duration) -> bool {
+        self.subscriber
+            .wait_for_publishers_async(count, timeout)
+            .await
+    }
+
+    /// Returns the name of the subscribed topic.
+    pub fn get_topic_name(&self) -> Option<String> {
+        self.subscriber.get_topic_name()
+    }
+
+    fn release_callback(&mut self) {
+        if !self.user_data.is_null() {
+            unsafe {
+                let _ = Box::from_raw(self.user_data);
+            }
+        }
+    }
+}
+
+impl Drop for DynamicSubscriber {
+    /// Removes the callback before releasing it.
+    fn drop(&mut self) {
+        unsafe {
+            rustecal_sys::eCAL_Subscriber_RemoveReceiveCallback(self.subscriber.raw_handle());
+        }
+        self.release_callback();
+    }
+}
+
+extern "C" fn trampoline(
+    topic_id: *const eCAL_STopicId,
+    data_type_info: *const eCAL_SDataTypeInformation,
+    data: *const eCAL_SReceiveCallbackData,
+    user_data: *mut c_void,
+) {
+    if topic_id.is_null() || data_type_info.is_null() || data.is_null() || user_data.is_null() {
+        return;
+    }
+    let (topic_id, rd) = unsafe { (&*topic_id, &*data) };
+    let payload: &[u8] = if rd.buffer.is_null() {
+        &[]
+    } else {
+        unsafe { slice::from_raw_parts(rd.buffer as *const u8, rd.buffer_size) }
+    };
+    let data_type: DataTypeInfo = unsafe { *data_type_info }.into();
+    let publisher: EntityId = topic_id.topic_id.into();
+    let meta = SampleMeta {
+        topic_name: topic_name_of(topic_id),
+        timestamp: rd.send_timestamp,
+        clock: rd.send_clock,
+        size: rd.buffer_size,
+        publisher_id: publisher.entity_id,
+        host_name: publisher.host_name,
+        process_id: publisher.process_id,
+    };
+    let callback = unsafe { &*(user_data as *const Callback) };
+    guard(
+        || format!("dynamic subscriber '{}'", topic_name_of(topic_id)),
+        || callback(payload, &data_type, meta),
+    );
+}
@@ -0,0 +1,130 @@
+//! Errors and results of publishing.
+//!
+//! eCAL only reports whether a send succeeded. [`Publisher::send`] adds the
+//! context needed to act on a failure: a send without connected subscribers
+//! is reported as [`PubSubError::NoSubscribers`], every other failure (e.g. a
+//! shared memory acknowledgement timeout or a payload exceeding the transport
+//! limits) as [`PubSubError::SendFailed`] with the payload size and the number
+//! of subscribers at send time. A zero-copy send whose [`PayloadWriter`]
+//! failed is reported as [`PubSubError::PayloadWrite`] with the writer's
+//! [`PayloadError`].
+//!
+//! A publisher waiting for shared memory acknowledgements (see
+//! `PublisherBuilder::acknowledge_timeout`) reports a failed send that
+//! waited for the whole timeout as [`PubSubError::NotAcknowledged`]; eCAL
+//! returns the same error code for every failure, so a send failing sooner
+//! is a [`PubSubError::SendFailed`].
+//!
+//! [`PayloadWriter`]: crate::payload_writer::PayloadWriter
+//!
+//! [`Publisher::send`]: crate::publisher::Publisher::send
+
+use std::time::Duration;
+use thiserror::Error;
+
+/// Result of a successful send.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SendOutcome {
+    /// Size of the sent payload in bytes.
+    pub bytes_written: usize,
+    /// Number of connected subscribers at send time.
+    pub subscriber_count: usize,
+    /// Upper bound of the subscribers that acknowledged the message, if the
+    /// publisher waits for acknowledgements (`None` otherwise).
+    ///
+    /// eCAL fails the send if any subscriber missed the timeout, but only
+    /// shared memory subscribers acknowledge and eCAL does not tell them
+    /// apart from UDP and TCP subscribers, so this is the subscriber count.
+    pub acknowledged: Option<usize>,
+}
+
+/// Errors returned by the publisher types.
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum PubSubError {
+    /// The send failed while no subscriber was connected.
+    #[error("no subscribers connected")]
+    NoSubscribers,
+
+    /// eCAL rejected the send despite connected subscribers.
+    #[error("sending {size} bytes to {subscriber_count} subscriber(s) failed")]
+    SendFailed {
+        size: usize,
+        subscriber_count: usize,
+    },
+
+    /// Not all subscribers acknowledged the message within the publisher's
+    /// acknowledgement timeout.
+    #[error("{subscriber_count} subscriber(s) did not acknowledge within {timeout:?}")]
+    NotAcknowledged {
+        subscriber_count: usize,
+        timeout: Duration,
+    },
+
+    /// The payload writer of a zero-copy send failed.
+    #[error("payload writer failed: {0}")]
+    PayloadWrite(PayloadError),
+}
+
+/// Why a [`PayloadWriter`](crate::payload_writer::PayloadWriter) could not fill the buffer.
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+#[error("{message}")]
+pub struct PayloadError {
+    pub message: String,
+}
+
+impl PayloadError {
+    /// Creates an error with the given description.
+    pub fn new(message: impl Into<String>) -> Self {
+        Self {
+            message: message.into(),
+        }
+    }
+
+    /// The buffer eCAL provided is smaller than the payload.
+    pub fn buffer_too_small(needed: usize, available: usize) -> Self {
+        Self::new(format!(
+            "buffer of {available} bytes too small for {needed} bytes"
+        ))
+    }
+}
+
+/// Check an eCAL send result: `0` → `Ok(SendOutcome)`, non‑zero → the matching
+/// [`PubSubError`], given the payload size and the subscribers at send time.
+pub fn check_send(
+    code: i32,
+    size: usize,
+    subscriber_count: usize,
+) -> Result<SendOutcome, PubSubError> {
+    check_acknowledged_send(code, size, subscriber_count, None, Duration::ZERO)
+}
+
+/// Like [`check_send`] for a publisher waiting up to `acknowledge_timeout`
+/// for acknowledgements (`None` if it does not wait); `waited` is how long
+/// the send took.
+///
+/// A failed send is only reported as [`PubSubError::NotAcknowledged`] if it
+/// waited for the whole timeout.
+pub fn check_acknowledged_send(
+    code: i32,
+    size: usize,
+    subscriber_count: usize,
+    acknowledge_timeout: Option<Duration>,
+    waited: Duration,
+) -> Result<SendOutcome, PubSubError> {
+    match (code, subscriber_count, acknowledge_timeout) {
+        (0, _, _) => Ok(SendOutcome {
+            bytes_written: size,
+            subscriber_count,
+            acknowledged: acknowledge_timeout.map(|_| subscriber_count),
+        }),
+        (_, 0, _) => Err(PubSubError::NoSubscribers),
+        (_, _, Some(timeout)) if waited >= timeout => Err(PubSubError::NotAcknowledged {
+            subscriber_count,
+            timeout,
+        }),
+        (_, _, _) => Err(PubSubError::SendFailed {
+            size,
+            subscriber_count,
+        }),
+    }
+}
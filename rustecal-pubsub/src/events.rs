@@ -0,0 +1,182 @@
+//! Connection events of publishers and subscribers.
+//!
+//! eCAL reports to a publisher when a subscriber connects or disconnects and
+//! when a message could not be delivered to a subscriber. A callback set with
+//! [`Publisher::set_event_callback`] receives these as [`PublisherEvent`]s,
+//! e.g. to start an expensive pipeline only once someone is listening.
+//!
+//! Subscribers are told the same about their publishers; a callback set with
+//! [`Subscriber::set_event_callback`] receives the connection variants of
+//! [`SubscriberEvent`], which tell "no data because no publisher" apart from
+//! "publisher alive but silent".
+//!
+//! The eCAL C API installs the event callback when the entity is created
+//! and passes no user data to it, so events are dispatched by topic name:
+//! entities of the same kind and topic within one process see each other's
+//! events.
+//!
+//! [`Publisher::set_event_callback`]: crate::publisher::Publisher::set_event_callback
+//! [`Subscriber::set_event_callback`]: crate::subscriber::Subscriber::set_event_callback
+
+use crate::type_tracking::SubscriberEvent;
+use rustecal_core::error_hook::guard;
+use rustecal_core::types::{DataTypeInfo, EntityId};
+use rustecal_sys::{eCAL_SPubEventCallbackData, eCAL_SSubEventCallbackData, eCAL_STopicId};
+use std::collections::BTreeMap;
+use std::ffi::CStr;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, RwLock};
+
+/// What happened between a publisher and one of its subscribers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PublisherEventType {
+    /// A subscriber connected.
+    Connected,
+    /// A subscriber disconnected (or timed out).
+    Disconnected,
+    /// A message was not delivered to the subscriber.
+    Dropped,
+}
+
+/// A connection event of a publisher.
+#[derive(Debug, Clone)]
+pub struct PublisherEvent {
+    pub event_type: PublisherEventType,
+    pub topic_name: String,
+    /// The subscriber the event refers to.
+    pub subscriber: EntityId,
+    /// The data type declared by the subscriber.
+    pub subscriber_data_type: DataTypeInfo,
+    /// eCAL time of the event (microseconds).
+    pub time: i64,
+}
+
+type Callback<E> = Arc<dyn Fn(&E) + Send + Sync + 'static>;
+
+/// Event callbacks of one entity kind, keyed by a registration token.
+pub(crate) struct EventRegistry<E> {
+    next_token: AtomicU64,
+    callbacks: RwLock<BTreeMap<u64, (String, Callback<E>)>>,
+}
+
+impl<E> EventRegistry<E> {
+    pub(crate) const fn new() -> Self {
+        Self {
+            next_token: AtomicU64::new(0),
+            callbacks: RwLock::new(BTreeMap::new()),
+        }
+    }
+
+    /// Registers `callback` for events of `topic_name` and returns its token.
+    pub(crate) fn register(&self, topic_name: &str, callback: Callback<E>) -> u64 {
+        let token = self.next_token.fetch_add(1, Ordering::Relaxed);
+        self.callbacks
+            .write()
+            .unwrap()
+            .insert(token, (topic_name.to_string(), callback));
+        token
+    }
+
+    pub(crate) fn unregister(&self, token: u64) {
+        self.callbacks.write().unwrap().remove(&token);
+    }
+
+    /// Invokes all callbacks of `topic_name` with `event`.
+    pub(crate) fn dispatch(&self, topic_name: &str, event: &E) {
+        // callbacks may create or drop entities, so they run without the lock
+        let callbacks: Vec<Callback<E>> = self
+            .callbacks
+            .read()
+            .unwrap()
+            .values()
+            .filter(|(topic, _)| topic == topic_name)
+            .map(|(_, callback)| callback.clone())
+            .collect();
+        for callback in callbacks {
+            guard(
+                || format!("event callback of '{topic_name}'"),
+                || callback(event),
+            );
+        }
+    }
+}
+
+pub(crate) static PUBLISHER_EVENTS: EventRegistry<PublisherEvent> = EventRegistry::new();
+pub(crate) static SUBSCRIBER_EVENTS: EventRegistry<SubscriberEvent> = EventRegistry::new();
+
+pub(crate) fn topic_name_of(topic_id: &eCAL_STopicId) -> String {
+    if topic_id.topic_name.is_null() {
+        String::new()
+    } else {
+        unsafe { CStr::from_ptr(topic_id.topic_name) }
+            .to_string_lossy()
+            .into_owned()
+    }
+}
+
+/// Event callback installed on every publisher.
+pub(crate) unsafe extern "C" fn publisher_event_trampoline(
+    topic_id: *const eCAL_STopicId,
+    data: *const eCAL_SPubEventCallbackData,
+) {
+    if topic_id.is_null() || data.is_null() {
+        return;
+    }
+    let (topic_id, data) = unsafe { (&*topic_id, &*data) };
+    let event_type = match data.event_type {
+        rustecal_sys::eCAL_ePublisherEvent_eCAL_ePublisherEvent_connected => {
+            PublisherEventType::Connected
+        }
+        rustecal_sys::eCAL_ePublisherEvent_eCAL_ePublisherEvent_disconnected => {
+            PublisherEventType::Disconnected
+        }
+        rustecal_sys::eCAL_ePublisherEvent_eCAL_ePublisherEvent_dropped => {
+            PublisherEventType::Dropped
+        }
+        _ => return,
+    };
+    let event = PublisherEvent {
+        event_type,
+        topic_name: topic_name_of(topic_id),
+        subscriber: topic_id.topic_id.into(),
+        subscriber_data_type: data.subscriber_datatype.into(),
+        time: data.event_time,
+    };
+    PUBLISHER_EVENTS.dispatch(&event.topic_name, &event);
+}
+
+/// Event callback installed on every subscriber.
+pub(crate) unsafe extern "C" fn subscriber_event_trampoline(
+    topic_id: *const eCAL_STopicId,
+    data: *const eCAL_SSubEventCallbackData,
+) {
+    if topic_id.is_null() || data.is_null() {
+        return;
+    }
+    let (topic_id, data) = unsafe { (&*topic_id, &*data) };
+    let topic_name = topic_name_of(topic_id);
+    let publisher = topic_id.topic_id.into();
+    let event = match data.event_type {
+        rustecal_sys::eCAL_eSubscriberEvent_eCAL_eSubscriberEvent_connected => {
+            SubscriberEvent::PublisherConnected {
+                topic_name: topic_name.clone(),
+                publisher,
+                data_type: data.publisher_datatype.into(),
+            }
+        }
+        rustecal_sys::eCAL_eSubscriberEvent_eCAL_eSubscriberEvent_disconnected => {
+            SubscriberEvent::PublisherDisconnected {
+                topic_name: topic_name.clone(),
+                publisher,
+            }
+        }
+        rustecal_sys::eCAL_eSubscriberEvent_eCAL_eSubscriberEvent_dropped => {
+            SubscriberEvent::Dropped {
+                topic_name: topic_name.clone(),
+                publisher,
+            }
+        }
+        _ => return,
+    };
+    SUBSCRIBER_EVENTS.dispatch(&topic_name, &event);
+}
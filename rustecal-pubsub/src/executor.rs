@@ -0,0 +1,102 @@
+//! Worker threads running receive callbacks off eCAL's receive threads.
+//!
+//! eCAL invokes receive callbacks on its internal receive threads, so a
+//! callback doing heavy work per message delays the delivery of further
+//! messages, on its own topic and on other topics of the process. An
+//! [`Executor`] owns a pool of worker threads fed through a bounded queue;
+//! [`TypedSubscriber::set_callback_on`] hands each received message to it,
+//! so the receive thread only enqueues:
+//!
+//! ```ignore
+//! let executor = Executor::new(4, 256, DropPolicy::DropOldest);
+//! subscriber.set_callback_on(&executor, |msg| process(msg));
+//! ```
+//!
+//! One executor can serve many subscribers. With more than one worker,
+//! messages are processed concurrently and may complete out of order.
+//!
+//! [`TypedSubscriber::set_callback_on`]: crate::typed_subscriber::TypedSubscriber::set_callback_on
+
+use crate::channel::{DropPolicy, channel_sink};
+use crossbeam_channel::{Sender, TryRecvError, select};
+use rustecal_core::error_hook::guard;
+use std::sync::Arc;
+use std::thread::{self, JoinHandle};
+
+type Job = Box<dyn FnOnce() + Send + 'static>;
+
+/// Enqueues a job according to the executor's [`DropPolicy`].
+pub(crate) type Submit = Arc<dyn Fn(Job) + Send + Sync + 'static>;
+
+/// A pool of worker threads running jobs from a bounded queue.
+///
+/// Dropping the executor stops the workers after their current job; jobs
+/// still queued are discarded and later submissions are ignored.
+pub struct Executor {
+    submit: Submit,
+    stop: Option<Sender<()>>,
+    workers: Vec<JoinHandle<()>>,
+}
+
+impl Executor {
+    /// Starts `threads` workers (at least one) sharing a queue of `capacity`
+    /// jobs; `policy` decides what happens to jobs submitted while it is full.
+    pub fn new(threads: usize, capacity: usize, policy: DropPolicy) -> Self {
+        let (submit, jobs) = channel_sink::<Job>(capacity, policy);
+        let (stop, stopped) = crossbeam_channel::bounded::<()>(0);
+        let workers = (0..threads.max(1))
+            .map(|_| {
+                let jobs = jobs.clone();
+                let stopped = stopped.clone();
+                thread::spawn(move || {
+                    loop {
+                        select! {
+                            recv(jobs) -> job => match job {
+                                // a job picked up after the drop is discarded as well
+                                Ok(_) if stopped.try_recv() == Err(TryRecvError::Disconnected) => {
+                                    break;
+                                }
+                                Ok(job) => {
+                                    guard(|| "executor job".to_string(), job);
+                                }
+                                Err(_) => break,
+                            },
+                            recv(stopped) -> _ => break,
+                        }
+                    }
+                })
+            })
+            .collect();
+        Self {
+            submit: Arc::from(submit),
+            stop: Some(stop),
+            workers,
+        }
+    }
+
+    /// Runs `job` on one of the workers.
+    pub fn spawn<F>(&self, job: F)
+    where
+        F: FnOnce() + Send + 'static,
+    {
+        (self.submit)(Box::new(job));
+    }
+
+    /// Returns the number of worker threads.
+    pub fn threads(&self) -> usize {
+        self.workers.len()
+    }
+
+    pub(crate) fn submitter(&self) -> Submit {
+        self.submit.clone()
+    }
+}
+
+impl Drop for Executor {
+    fn drop(&mut self) {
+        drop(self.stop.take());
+        for worker in self.workers.drain(..) {
+            let _ = worker.join();
+        }
+    }
+}
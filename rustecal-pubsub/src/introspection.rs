@@ -0,0 +1,53 @@
+//! Debugging helpers for inspecting binary payloads.
+
+use std::fmt::Write;
+
+/// Number of bytes shown per hexdump line.
+const BYTES_PER_LINE: usize = 16;
+
+/// Formats up to `limit` bytes of `bytes` as a classic hexdump.
+///
+/// Every line shows the offset, 16 bytes in hex and their printable ASCII
+/// representation (`.` for non-printable bytes). If the buffer is longer than
+/// `limit`, a final line states how many bytes were omitted.
+///
+/// ```
+/// use rustecal_pubsub::introspection::hexdump;
+///
+/// assert_eq!(hexdump(b"Hi!", 16), "00000000  48 69 21  Hi!");
+/// ```
+pub fn hexdump(bytes: &[u8], limit: usize) -> String {
+    let shown = &bytes[..bytes.len().min(limit)];
+    let mut out = String::new();
+
+    for (line, chunk) in shown.chunks(BYTES_PER_LINE).enumerate() {
+        if line > 0 {
+            out.push('\n');
+        }
+        let _ = write!(out, "{:08x} ", line * BYTES_PER_LINE);
+        for byte in chunk {
+            let _ = write!(out, " {byte:02x}");
+        }
+        // align the ASCII column of a short last line
+        if shown.len() > BYTES_PER_LINE {
+            out.push_str(&"   ".repeat(BYTES_PER_LINE - chunk.len()));
+        }
+        out.push_str("  ");
+        out.extend(chunk.iter().map(|&b| {
+            if b.is_ascii_graphic() || b == b' ' {
+                b as char
+            } else {
+                '.'
+            }
+        }));
+    }
+
+    if bytes.len() > shown.len() {
+        if !out.is_empty() {
+            out.push('\n');
+        }
+        let _ = write!(out, "... ({} more bytes)", bytes.len() - shown.len());
+    }
+
+    out
+}
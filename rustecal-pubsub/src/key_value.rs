@@ -0,0 +1,247 @@
+//! Keyed last-value cache on top of pub/sub.
+//!
+//! A [`KeyValueTopic`] turns a topic into a shared map: every participant can
+//! set or remove keyed entries, and every participant keeps the latest value
+//! per key. This is the common "world model over pub/sub" pattern, e.g. the
+//! last known pose of every robot in a fleet.
+//!
+//! Each update is sent as one message: an operation byte (`0` = set,
+//! `1` = remove), the writer ID as little-endian `u64`, the key length as
+//! little-endian `u32`, the UTF-8 key and, for set operations, the value
+//! serialized by `T`. The topic's encoding is `kv:` followed by `T`'s encoding.
+//!
+//! Updates from different writers are applied in arrival order, so the last
+//! received update of a key wins. Participants joining later only see
+//! entries sent after they connected; writers call
+//! [`KeyValueTopic::republish`] to bring them up to date.
+
+use crate::publisher::{Publisher, Timestamp};
+use crate::typed_publisher::PublisherMessage;
+use crate::typed_subscriber::{Received, SubscriberMessage, TypedSubscriber};
+use rustecal_core::types::DataTypeInfo;
+use std::collections::{HashMap, HashSet};
+use std::marker::PhantomData;
+use std::sync::{Arc, Mutex, RwLock};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+const OP_SET: u8 = 0;
+const OP_REMOVE: u8 = 1;
+
+/// A change of the cached map.
+#[derive(Debug)]
+pub enum KeyChange<T> {
+    /// `key` was set to `value`.
+    Updated { key: String, value: Arc<T> },
+    /// `key` was removed.
+    Removed { key: String },
+}
+
+type ChangeCallback<T> = Box<dyn Fn(&KeyChange<T>) + Send + Sync + 'static>;
+
+/// Returns the data type of a key/value topic carrying values of type `T`.
+fn datatype<T: PublisherMessage>() -> DataTypeInfo {
+    let value = T::datatype();
+    DataTypeInfo {
+        encoding: format!("kv:{}", value.encoding),
+        type_name: value.type_name,
+        descriptor: value.descriptor,
+    }
+}
+
+/// Encodes a key/value update (see the module documentation for the layout).
+fn encode(op: u8, writer_id: u64, key: &str, value: &[u8]) -> Vec<u8> {
+    let mut bytes = Vec::with_capacity(13 + key.len() + value.len());
+    bytes.push(op);
+    bytes.extend_from_slice(&writer_id.to_le_bytes());
+    bytes.extend_from_slice(&(key.len() as u32).to_le_bytes());
+    bytes.extend_from_slice(key.as_bytes());
+    bytes.extend_from_slice(value);
+    bytes
+}
+
+/// A decoded key/value update.
+struct Update<T> {
+    writer_id: u64,
+    key: String,
+    /// `None` for remove operations.
+    value: Option<T>,
+}
+
+impl<'a, T> SubscriberMessage<'a> for Update<T>
+where
+    T: PublisherMessage + SubscriberMessage<'a>,
+{
+    fn datatype() -> DataTypeInfo {
+        datatype::<T>()
+    }
+
+    fn from_bytes(bytes: &'a [u8], _data_type_info: &DataTypeInfo) -> Option<Self> {
+        let op = *bytes.first()?;
+        let writer_id = u64::from_le_bytes(bytes.get(1..9)?.try_into().ok()?);
+        let key_len = u32::from_le_bytes(bytes.get(9..13)?.try_into().ok()?) as usize;
+        let key = std::str::from_utf8(bytes.get(13..13 + key_len)?).ok()?;
+        let value = match op {
+            OP_SET => Some(T::from_bytes(
+                &bytes[13 + key_len..],
+                &<T as PublisherMessage>::datatype(),
+            )?),
+            OP_REMOVE => None,
+            _ => return None,
+        };
+        Some(Update {
+            writer_id,
+            key: key.to_string(),
+            value,
+        })
+    }
+}
+
+/// State shared with the subscriber callback.
+struct Shared<T> {
+    writer_id: u64,
+    entries: RwLock<HashMap<String, Arc<T>>>,
+    /// Keys whose latest value was set by this instance, resent by `republish`.
+    owned: Mutex<HashSet<String>>,
+    on_change: RwLock<Option<ChangeCallback<T>>>,
+}
+
+impl<T> Shared<T> {
+    fn apply(&self, key: String, value: Option<Arc<T>>) {
+        let change = {
+            let mut entries = self.entries.write().unwrap();
+            match value {
+                Some(value) => {
+                    entries.insert(key.clone(), value.clone());
+                    KeyChange::Updated { key, value }
+                }
+                None => {
+                    if entries.remove(&key).is_none() {
+                        return;
+                    }
+                    KeyChange::Removed { key }
+                }
+            }
+        };
+        if let Some(callback) = &*self.on_change.read().unwrap() {
+            callback(&change);
+        }
+    }
+}
+
+/// A shared map of keyed values of type `T`, kept in sync over a topic.
+pub struct KeyValueTopic<T>
+where
+    T: PublisherMessage + for<'a> SubscriberMessage<'a> + Send + Sync + 'static,
+{
+    publisher: Publisher,
+    _subscriber: TypedSubscriber<'static, Update<T>>,
+    shared: Arc<Shared<T>>,
+    _phantom: PhantomData<T>,
+}
+
+impl<T> KeyValueTopic<T>
+where
+    T: PublisherMessage + for<'a> SubscriberMessage<'a> + Send + Sync + 'static,
+{
+    /// Joins the key/value topic `topic_name`.
+    pub fn new(topic_name: &str) -> Result<Self, String> {
+        let nanos = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_nanos() as u64)
+            .unwrap_or(0);
+        let shared = Arc::new(Shared {
+            writer_id: ((std::process::id() as u64) << 32) ^ nanos,
+            entries: RwLock::new(HashMap::new()),
+            owned: Mutex::new(HashSet::new()),
+            on_change: RwLock::new(None),
+        });
+
+        let publisher = Publisher::new(topic_name, datatype::<T>())?;
+        let mut subscriber = TypedSubscriber::<Update<T>>::new(topic_name)?;
+        let receiver = shared.clone();
+        subscriber.set_callback(move |msg: Received<Update<T>>| {
+            let update = msg.payload;
+            // own updates are applied when they are sent
+            if update.writer_id != receiver.writer_id {
+                receiver.owned.lock().unwrap().remove(&update.key);
+                receiver.apply(update.key, update.value.map(Arc::new));
+            }
+        });
+
+        Ok(Self {
+            publisher,
+            _subscriber: subscriber,
+            shared,
+            _phantom: PhantomData,
+        })
+    }
+
+    /// Sets `key` to `value` locally and for all other participants.
+    ///
+    /// Returns `false` if the update could not be sent.
+    pub fn set(&self, key: &str, value: T) -> bool {
+        let bytes = encode(OP_SET, self.shared.writer_id, key, &value.to_bytes());
+        self.shared.owned.lock().unwrap().insert(key.to_string());
+        self.shared.apply(key.to_string(), Some(Arc::new(value)));
+        self.publisher.send(&bytes, Timestamp::Auto).is_ok()
+    }
+
+    /// Removes `key` locally and for all other participants.
+    ///
+    /// Returns `false` if the update could not be sent.
+    pub fn remove(&self, key: &str) -> bool {
+        let bytes = encode(OP_REMOVE, self.shared.writer_id, key, &[]);
+        self.shared.owned.lock().unwrap().remove(key);
+        self.shared.apply(key.to_string(), None);
+        self.publisher.send(&bytes, Timestamp::Auto).is_ok()
+    }
+
+    /// Resends all entries whose latest value was set by this instance.
+    ///
+    /// Call this when new participants join (e.g. when
+    /// [`KeyValueTopic::get_subscriber_count`] increases), so that their
+    /// cache includes entries set before they connected.
+    pub fn republish(&self) {
+        let owned: Vec<String> = self.shared.owned.lock().unwrap().iter().cloned().collect();
+        let entries = self.shared.entries.read().unwrap();
+        for key in owned {
+            if let Some(value) = entries.get(&key) {
+                let bytes = encode(OP_SET, self.shared.writer_id, &key, &value.to_bytes());
+                let _ = self.publisher.send(&bytes, Timestamp::Auto);
+            }
+        }
+    }
+
+    /// Returns the latest value of `key`.
+    pub fn get(&self, key: &str) -> Option<Arc<T>> {
+        self.shared.entries.read().unwrap().get(key).cloned()
+    }
+
+    /// Returns a copy of the full map.
+    pub fn snapshot(&self) -> HashMap<String, Arc<T>> {
+        self.shared.entries.read().unwrap().clone()
+    }
+
+    /// Returns the number of cached keys.
+    pub fn len(&self) -> usize {
+        self.shared.entries.read().unwrap().len()
+    }
+
+    /// Returns `true` if no key is cached.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Registers a callback invoked for every change of the map, local or remote.
+    pub fn on_change<F>(&self, callback: F)
+    where
+        F: Fn(&KeyChange<T>) + Send + Sync + 'static,
+    {
+        *self.shared.on_change.write().unwrap() = Some(Box::new(callback));
+    }
+
+    /// Returns the number of connected subscribers, including this instance's own.
+    pub fn get_subscriber_count(&self) -> usize {
+        self.publisher.get_subscriber_count()
+    }
+}
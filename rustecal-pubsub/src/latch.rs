@@ -0,0 +1,76 @@
+//! Re-delivery of the last sample to late-joining subscribers.
+//!
+//! A latched publisher (see [`Publisher::set_latched`]) keeps a copy of the
+//! last message it sent. Whenever a subscriber of its topic connects, the
+//! copy is sent again, so configuration or map topics published once reach
+//! subscribers started later.
+//!
+//! The resend runs on a short-lived thread, so eCAL's event thread never
+//! waits for a send (e.g. for shared memory acknowledgements). Subscribers
+//! that are already connected receive the resent sample as well.
+//!
+//! [`Publisher::set_latched`]: crate::publisher::Publisher::set_latched
+
+use crate::events::{PublisherEvent, PublisherEventType};
+use rustecal_sys::{eCAL_Publisher, eCAL_Publisher_Send};
+use std::ptr;
+use std::sync::{Arc, Mutex, RwLock};
+use std::thread;
+
+/// The eCAL publisher handle, shared with resend threads.
+struct SendHandle(*mut eCAL_Publisher);
+
+// eCAL publishers may be used from any thread; the handle is only
+// dereferenced under the read lock of `Latch::handle`.
+unsafe impl Send for SendHandle {}
+unsafe impl Sync for SendHandle {}
+
+/// Payload and send timestamp of a message.
+type Sample = (Arc<[u8]>, Option<i64>);
+
+/// The last sample of a latched publisher.
+pub(crate) struct Latch {
+    /// Cleared before the publisher is deleted.
+    handle: RwLock<Option<SendHandle>>,
+    last: Mutex<Option<Sample>>,
+}
+
+impl Latch {
+    pub(crate) fn new(handle: *mut eCAL_Publisher) -> Self {
+        Self {
+            handle: RwLock::new(Some(SendHandle(handle))),
+            last: Mutex::new(None),
+        }
+    }
+
+    /// Remembers `data` sent with timestamp `ts` as the last sample.
+    pub(crate) fn store(&self, data: &[u8], ts: Option<i64>) {
+        *self.last.lock().unwrap() = Some((Arc::from(data), ts));
+    }
+
+    /// Detaches the latch from the publisher handle, waiting for running resends.
+    pub(crate) fn close(&self) {
+        *self.handle.write().unwrap() = None;
+    }
+
+    /// Resends the last sample when a subscriber connected.
+    pub(crate) fn on_event(self: &Arc<Self>, event: &PublisherEvent) {
+        if event.event_type != PublisherEventType::Connected {
+            return;
+        }
+        let Some((data, ts)) = self.last.lock().unwrap().clone() else {
+            return;
+        };
+        let latch = self.clone();
+        thread::spawn(move || {
+            if let Some(SendHandle(handle)) = &*latch.handle.read().unwrap() {
+                let ts_ptr = ts
+                    .as_ref()
+                    .map_or(ptr::null(), |t| t as *const i64 as *const _);
+                unsafe {
+                    eCAL_Publisher_Send(*handle, data.as_ptr() as *const _, data.len(), ts_ptr);
+                }
+            }
+        });
+    }
+}
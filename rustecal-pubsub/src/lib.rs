@@ -6,6 +6,29 @@
 //! - Zero-copy shared memory support.
 //! - Strongly-typed publishers and subscribers.
 //! - Topic introspection and metadata.
+//! - Consumer groups sharing a topic as work queue (`GroupSubscriber`).
+//! - Latched publishers re-delivering their last sample to late joiners (`Publisher::set_latched`).
+//! - Keyed last-value caches shared over a topic (`KeyValueTopic`).
+//! - Detection of publisher schema changes (`SubscriberEvent::TypeChanged`).
+//! - Payload debugging helpers (`hexdump`, `Received::summary`).
+//! - Connect/disconnect and drop events on publishers and subscribers (`PublisherEvent`, `SubscriberEvent`).
+//! - Waiting for a number of connected partners (`Publisher::wait_for_subscribers`, `Subscriber::wait_for_publishers`).
+//! - Async message streams (`TypedSubscriber::into_stream`, feature `tokio`).
+//! - Publishing from async pipelines (`Sink` for `TypedPublisher`, feature `futures-sink`).
+//! - Subscriptions to all topics matching a pattern (`MultiSubscriber`, feature `monitoring`).
+//! - Untyped subscriptions for recorders and bridges (`DynamicSubscriber`).
+//! - Fan-in of several topics into one callback or channel (`AggregatedSubscriber`).
+//! - Publishing at a capped rate (`ThrottledPublisher`).
+//! - Last-value cache of a subscriber (`TypedSubscriber::keep_latest`).
+//! - Receive callbacks on a worker pool instead of eCAL's receive threads (`Executor`).
+//! - Subscribers delivering into channels (`TypedSubscriber::into_channel`).
+//! - Per-topic transport options (`Publisher::builder`, `TypedSubscriber::builder`).
+//! - Filtering messages by publishing host, process or publisher (`SourceFilter`).
+//! - Discarding messages before decoding (`TypedSubscriber::set_payload_filter`).
+//! - Delivering every n-th message or at a capped rate (`TypedSubscriber::set_decimation`).
+//! - Detection of topics that stopped delivering data (`TypedSubscriber::on_timeout`).
+//! - Message and drop counters (`get_statistics`) and sample loss callbacks.
+//! - Simulation time master and follower (`SimClockPublisher`, `SimClockFollower`).
 //!
 //! ## Key Types
 //! - `TypedPublisher<T>`
@@ -15,19 +38,71 @@
 // Re-export core init & types
 pub use rustecal_core::{Ecal, EcalComponents};
 
+// Re-export the channel crate of `TypedSubscriber::into_channel`
+pub use crossbeam_channel;
+
 // Sub‑modules
+pub mod aggregate;
+pub mod channel;
+pub mod clock_source;
+pub mod connection;
+pub mod consumer_group;
+pub mod decimation;
+pub mod dynamic_subscriber;
+pub mod error;
+pub mod events;
+pub mod executor;
+pub mod introspection;
+pub mod key_value;
+pub mod latch;
+#[cfg(feature = "monitoring")]
+pub mod multi_subscriber;
 pub mod payload_writer;
+pub mod pressure;
 pub mod publisher;
+pub mod publisher_builder;
+pub mod silence;
+pub mod sim_time;
+#[cfg(feature = "futures-sink")]
+pub mod sink;
+pub mod statistics;
+#[cfg(feature = "tokio")]
+pub mod stream;
 pub mod subscriber;
+pub mod subscriber_builder;
+pub mod throttle;
+pub mod type_tracking;
 pub mod typed_publisher;
 pub mod typed_subscriber;
 pub mod types;
 
 // Public API
+pub use aggregate::AggregatedSubscriber;
+pub use channel::{DropPolicy, SubscriberChannel};
+pub use clock_source::ClockSource;
+pub use consumer_group::GroupSubscriber;
+pub use decimation::{Decimation, Decimator};
+pub use dynamic_subscriber::{DynamicSubscriber, SampleMeta};
+pub use error::{PayloadError, PubSubError, SendOutcome};
+pub use events::{PublisherEvent, PublisherEventType};
+pub use executor::Executor;
+pub use key_value::{KeyChange, KeyValueTopic};
+#[cfg(feature = "monitoring")]
+pub use multi_subscriber::{MultiSubscriber, TopicPattern};
 pub use payload_writer::PayloadWriter;
-pub use publisher::Publisher;
+pub use pressure::{Pressure, PressureConfig, PressureMonitor};
+pub use publisher::{Publisher, SendOptions, TransportLayers};
+pub use publisher_builder::PublisherBuilder;
+pub use silence::{SilenceEvent, SilenceWatch};
+pub use sim_time::{SimClockFollower, SimClockPublisher, SimulationState};
+pub use statistics::{PublisherStatistics, SampleLoss, SubscriberStatistics};
+#[cfg(feature = "tokio")]
+pub use stream::SubscriberStream;
 pub use subscriber::Subscriber;
+pub use subscriber_builder::SubscriberBuilder;
+pub use throttle::{RateLimiter, ThrottleMode, ThrottledPublisher};
+pub use type_tracking::{CompatibilityPolicy, MismatchReason, SubscriberEvent, TypeMismatch};
 pub use typed_publisher::PublisherMessage;
 pub use typed_publisher::TypedPublisher;
-pub use typed_subscriber::SubscriberMessage;
 pub use typed_subscriber::TypedSubscriber;
+pub use typed_subscriber::{OwnedMessage, SourceFilter, SubscriberMessage};
@@ -0,0 +1,173 @@
+//! Subscriptions to all topics matching a pattern.
+//!
+//! A [`MultiSubscriber`] receives every topic whose name matches a
+//! [`TopicPattern`], e.g. `camera/*/image`. A discovery thread polls the
+//! publishers in the monitoring snapshot, attaches a subscriber when a
+//! matching topic appears and detaches it once the topic has no publisher
+//! anymore. All messages go to a single callback; [`Received::topic_name`]
+//! tells the topics apart.
+//!
+//! Discovery requires eCAL to be initialized with
+//! `EcalComponents::MONITORING`. Topics are picked up with a delay of up to
+//! one discovery interval plus the monitoring registration delay, so the
+//! first messages of a new topic may be missed.
+
+use crate::typed_subscriber::{Received, SubscriberMessage, TypedSubscriber};
+use regex::Regex;
+use rustecal_core::monitoring::{Monitoring, MonitoringEntities};
+use rustecal_core::topic_policy::glob_match;
+use std::collections::{BTreeSet, HashMap};
+use std::marker::PhantomData;
+use std::sync::mpsc::{self, RecvTimeoutError, Sender};
+use std::sync::{Arc, Mutex};
+use std::thread::{self, JoinHandle};
+use std::time::Duration;
+
+/// Interval between two discovery passes unless set with [`MultiSubscriber::with_interval`].
+pub const DEFAULT_DISCOVERY_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Topic names a [`MultiSubscriber`] subscribes to.
+#[derive(Debug, Clone)]
+pub enum TopicPattern {
+    /// A glob: `*` matches any sequence of characters (including `/`), `?` a single character.
+    Glob(String),
+    /// A regular expression, matched anywhere in the topic name unless anchored.
+    Regex(Regex),
+}
+
+impl TopicPattern {
+    /// Creates a glob pattern.
+    pub fn glob(pattern: &str) -> Self {
+        TopicPattern::Glob(pattern.to_string())
+    }
+
+    /// Creates a regular expression pattern.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `Err(String)` if `pattern` is not a valid regular expression.
+    pub fn regex(pattern: &str) -> Result<Self, String> {
+        Regex::new(pattern)
+            .map(TopicPattern::Regex)
+            .map_err(|e| e.to_string())
+    }
+
+    /// Returns `true` if `topic_name` matches the pattern.
+    pub fn matches(&self, topic_name: &str) -> bool {
+        match self {
+            TopicPattern::Glob(pattern) => glob_match(pattern, topic_name),
+            TopicPattern::Regex(regex) => regex.is_match(topic_name),
+        }
+    }
+}
+
+type Callback<T> = Arc<dyn Fn(Received<T>) + Send + Sync + 'static>;
+
+/// Subscribes to all topics matching a [`TopicPattern`].
+///
+/// Subscribers are owned by the discovery thread; dropping the
+/// `MultiSubscriber` stops discovery and detaches all of them.
+pub struct MultiSubscriber<T> {
+    topics: Arc<Mutex<BTreeSet<String>>>,
+    stop: Option<Sender<()>>,
+    thread: Option<JoinHandle<()>>,
+    _phantom: PhantomData<fn() -> T>,
+}
+
+impl<T: SubscriberMessage<'static> + 'static> MultiSubscriber<T> {
+    /// Subscribes to all topics matching `pattern`, delivering their
+    /// messages to `callback`.
+    pub fn new<F>(pattern: TopicPattern, callback: F) -> Self
+    where
+        F: Fn(Received<T>) + Send + Sync + 'static,
+    {
+        Self::with_interval(pattern, DEFAULT_DISCOVERY_INTERVAL, callback)
+    }
+
+    /// Like [`MultiSubscriber::new`], discovering topics every `interval`.
+    pub fn with_interval<F>(pattern: TopicPattern, interval: Duration, callback: F) -> Self
+    where
+        F: Fn(Received<T>) + Send + Sync + 'static,
+    {
+        let callback: Callback<T> = Arc::new(callback);
+        let topics = Arc::new(Mutex::new(BTreeSet::new()));
+        let attached = topics.clone();
+
+        let (stop, stopped) = mpsc::channel::<()>();
+        let thread = thread::spawn(move || {
+            // created on this thread, as `TypedSubscriber` is not `Send`
+            let mut subscribers: HashMap<String, TypedSubscriber<'static, T>> = HashMap::new();
+            // topics the topic policy rejected, not retried while they exist
+            let mut rejected: BTreeSet<String> = BTreeSet::new();
+
+            loop {
+                if let Ok(snapshot) = Monitoring::snapshot_of(MonitoringEntities::PUBLISHER) {
+                    let current: BTreeSet<&str> = snapshot
+                        .publishers
+                        .iter()
+                        .map(|p| p.topic_name.as_str())
+                        .filter(|name| pattern.matches(name))
+                        .collect();
+
+                    subscribers.retain(|name, _| current.contains(name.as_str()));
+                    rejected.retain(|name| current.contains(name.as_str()));
+                    for name in current {
+                        if subscribers.contains_key(name) || rejected.contains(name) {
+                            continue;
+                        }
+                        // discovered names are already remapped
+                        match TypedSubscriber::<T>::with_configuration(name, None) {
+                            Ok(mut subscriber) => {
+                                let callback = callback.clone();
+                                subscriber.set_callback(move |msg| callback(msg));
+                                subscribers.insert(name.to_string(), subscriber);
+                            }
+                            Err(_) => {
+                                rejected.insert(name.to_string());
+                            }
+                        }
+                    }
+                    *attached.lock().unwrap() = subscribers.keys().cloned().collect();
+                }
+                match stopped.recv_timeout(interval) {
+                    Err(RecvTimeoutError::Timeout) => continue,
+                    _ => break,
+                }
+            }
+        });
+
+        Self {
+            topics,
+            stop: Some(stop),
+            thread: Some(thread),
+            _phantom: PhantomData,
+        }
+    }
+
+    /// Returns the names of the topics currently subscribed to, sorted.
+    pub fn topics(&self) -> Vec<String> {
+        self.topics.lock().unwrap().iter().cloned().collect()
+    }
+
+    /// Stops discovery and detaches all subscribers.
+    ///
+    /// Must not be called from within the callback.
+    pub fn stop(mut self) {
+        self.shutdown();
+    }
+}
+
+impl<T> MultiSubscriber<T> {
+    fn shutdown(&mut self) {
+        drop(self.stop.take());
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+    }
+}
+
+impl<T> Drop for MultiSubscriber<T> {
+    fn drop(&mut self) {
+        self.shutdown();
+    }
+}
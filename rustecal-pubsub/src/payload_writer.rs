@@ -4,65 +4,206 @@
 // plus thread-local storage and C-style callback functions to integrate
 // with eCAL's `SendPayloadWriter` API, using mutable references rather than owning values.
 
+use crate::error::PayloadError;
+use rustecal_core::error_hook::guard;
 use std::cell::RefCell;
+use std::ops::Range;
 use std::os::raw::{c_int, c_void};
 
 /// A zero‐copy payload writer: you fill the shared‐memory buffer in place.
+///
+/// An error returned by a write is reported by `send_payload_writer` as
+/// `PubSubError::PayloadWrite`.
 pub trait PayloadWriter {
     /// Called once when the memory is first allocated (or resized).
-    /// Return `true` on success.
-    fn write_full(&mut self, buf: &mut [u8]) -> bool;
+    fn write_full(&mut self, buf: &mut [u8]) -> Result<(), PayloadError>;
 
     /// Called on subsequent sends to modify only parts of the buffer.
     /// By default this falls back to `write_full`.
-    fn write_modified(&mut self, buf: &mut [u8]) -> bool {
+    fn write_modified(&mut self, buf: &mut [u8]) -> Result<(), PayloadError> {
         self.write_full(buf)
     }
 
+    /// Byte ranges changed since the previous send, or `None` to let
+    /// `write_modified` update the buffer.
+    ///
+    /// When ranges are returned, each is written with `write_range` instead of
+    /// calling `write_modified`, so sparse updates of large buffers touch
+    /// only the changed bytes.
+    fn modified_ranges(&self) -> Option<Vec<Range<usize>>> {
+        None
+    }
+
+    /// Writes the bytes of `range`; `chunk` is `buf[range]` of the buffer.
+    ///
+    /// Required if `modified_ranges` returns ranges.
+    fn write_range(&mut self, range: Range<usize>, chunk: &mut [u8]) -> Result<(), PayloadError> {
+        let _ = chunk;
+        Err(PayloadError::new(format!(
+            "write_range not implemented for range {range:?}"
+        )))
+    }
+
     /// Must return the exact number of bytes you’ll write.
     fn get_size(&self) -> usize;
 }
 
-// Thread-local slot for the currently active writer reference during a send call
+/// Updates `buf` through `writer` on a modifying send: range by range if
+/// the writer reports its [`PayloadWriter::modified_ranges`], otherwise
+/// with [`PayloadWriter::write_modified`].
+///
+/// # Errors
+///
+/// Returns the writer's error, or a [`PayloadError`] if a range exceeds the buffer.
+pub fn write_modified_ranges<W: PayloadWriter + ?Sized>(
+    writer: &mut W,
+    buf: &mut [u8],
+) -> Result<(), PayloadError> {
+    let Some(ranges) = writer.modified_ranges() else {
+        return writer.write_modified(buf);
+    };
+    for range in ranges {
+        let len = buf.len();
+        let chunk = buf.get_mut(range.clone()).ok_or_else(|| {
+            PayloadError::new(format!(
+                "modified range {range:?} exceeds the buffer of {len} bytes"
+            ))
+        })?;
+        writer.write_range(range, chunk)?;
+    }
+    Ok(())
+}
+
+/// Adapts a serializing closure to [`PayloadWriter`] for
+/// `TypedPublisher::send_in_place`.
+///
+/// The closure returns the number of bytes it wrote, which has to match the
+/// announced size: eCAL sends the whole buffer of that size.
+pub(crate) struct InPlaceWriter<F> {
+    size: usize,
+    fill: F,
+}
+
+impl<F: FnMut(&mut [u8]) -> usize> InPlaceWriter<F> {
+    pub(crate) fn new(size: usize, fill: F) -> Self {
+        Self { size, fill }
+    }
+}
+
+impl<F: FnMut(&mut [u8]) -> usize> PayloadWriter for InPlaceWriter<F> {
+    fn write_full(&mut self, buf: &mut [u8]) -> Result<(), PayloadError> {
+        let available = buf.len();
+        let buf = buf
+            .get_mut(..self.size)
+            .ok_or_else(|| PayloadError::buffer_too_small(self.size, available))?;
+        let written = (self.fill)(buf);
+        if written != self.size {
+            return Err(PayloadError::new(format!(
+                "serializer wrote {written} bytes, announced {}",
+                self.size
+            )));
+        }
+        Ok(())
+    }
+
+    fn get_size(&self) -> usize {
+        self.size
+    }
+}
+
+// Thread-local slot for the currently active writer reference during a send call.
+//
+// eCAL's C payload writer callbacks carry no user data, so the writer has to be
+// found through the calling thread. eCAL invokes the callbacks synchronously on
+// the sending thread, so sends on different threads never see each other's
+// writer; `WriterScope` makes nested sends from within a writer safe.
 thread_local! {
     /// Holds a raw pointer to the active PayloadWriter while eCAL invokes callbacks
-    pub(crate) static CURRENT_WRITER: RefCell<Option<*mut dyn PayloadWriter>> = RefCell::new(None);
+    static CURRENT_WRITER: RefCell<Option<*mut dyn PayloadWriter>> = const { RefCell::new(None) };
+
+    /// The error of the last failed write of the active send
+    static WRITE_ERROR: RefCell<Option<PayloadError>> = const { RefCell::new(None) };
+}
+
+/// Installs a writer for the duration of one send call.
+///
+/// Dropping the scope (also when unwinding) restores the writer and write
+/// error of an enclosing send, so a writer may itself send through another
+/// publisher.
+pub(crate) struct WriterScope {
+    previous_writer: Option<*mut dyn PayloadWriter>,
+    previous_error: Option<PayloadError>,
+}
+
+impl WriterScope {
+    pub(crate) fn enter(writer: *mut dyn PayloadWriter) -> Self {
+        Self {
+            previous_writer: CURRENT_WRITER.with(|cell| cell.borrow_mut().replace(writer)),
+            previous_error: WRITE_ERROR.with(|cell| cell.borrow_mut().take()),
+        }
+    }
+
+    /// Ends the send and returns the error of a failed write, if any.
+    pub(crate) fn finish(self) -> Option<PayloadError> {
+        WRITE_ERROR.with(|cell| cell.borrow_mut().take())
+    }
+}
+
+impl Drop for WriterScope {
+    fn drop(&mut self) {
+        let writer = self.previous_writer.take();
+        let error = self.previous_error.take();
+        CURRENT_WRITER.with(|cell| *cell.borrow_mut() = writer);
+        WRITE_ERROR.with(|cell| *cell.borrow_mut() = error);
+    }
+}
+
+/// Runs `f` with the active writer, or returns `None` outside a send.
+///
+/// The slot is not borrowed while `f` runs, so `f` may start a nested send.
+fn with_writer<R>(f: impl FnOnce(&mut dyn PayloadWriter) -> R) -> Option<R> {
+    let writer_ptr = CURRENT_WRITER.with(|cell| *cell.borrow())?;
+    // the pointer stems from a `&mut` held by `send_payload_writer` for the whole call
+    Some(f(unsafe { &mut *writer_ptr }))
+}
+
+/// Maps the outcome of a (guarded) write to eCAL's return code, keeping the error.
+fn write_result(result: Option<Result<(), PayloadError>>) -> c_int {
+    let error = match result {
+        Some(Ok(())) => return 0,
+        Some(Err(error)) => error,
+        None => PayloadError::new("payload writer panicked"),
+    };
+    WRITE_ERROR.with(|cell| *cell.borrow_mut() = Some(error));
+    -1
 }
 
 /// C callback: perform a full write into the shared-memory buffer
 pub(crate) unsafe extern "C" fn write_full_cb(buffer: *mut c_void, size: usize) -> c_int {
-    CURRENT_WRITER.with(|cell| {
-        if let Some(writer_ptr) = *cell.borrow() {
-            let writer: &mut dyn PayloadWriter = unsafe { &mut *writer_ptr };
-            let buf = unsafe { std::slice::from_raw_parts_mut(buffer as *mut u8, size) };
-            if writer.write_full(buf) { 0 } else { -1 }
-        } else {
-            -1
-        }
+    let buf = unsafe { std::slice::from_raw_parts_mut(buffer as *mut u8, size) };
+    with_writer(|writer| {
+        write_result(guard(
+            || "payload writer".to_string(),
+            || writer.write_full(buf),
+        ))
     })
+    .unwrap_or(-1)
 }
 
 /// C callback: perform a partial modification of the shared-memory buffer
 pub(crate) unsafe extern "C" fn write_mod_cb(buffer: *mut c_void, size: usize) -> c_int {
-    CURRENT_WRITER.with(|cell| {
-        if let Some(writer_ptr) = *cell.borrow() {
-            let writer: &mut dyn PayloadWriter = unsafe { &mut *writer_ptr };
-            let buf = unsafe { std::slice::from_raw_parts_mut(buffer as *mut u8, size) };
-            if writer.write_modified(buf) { 0 } else { -1 }
-        } else {
-            -1
-        }
+    let buf = unsafe { std::slice::from_raw_parts_mut(buffer as *mut u8, size) };
+    with_writer(|writer| {
+        write_result(guard(
+            || "payload writer".to_string(),
+            || write_modified_ranges(writer, buf),
+        ))
     })
+    .unwrap_or(-1)
 }
 
 /// C callback: return the size of the payload buffer needed
 pub(crate) unsafe extern "C" fn get_size_cb() -> usize {
-    CURRENT_WRITER.with(|cell| {
-        if let Some(writer_ptr) = *cell.borrow() {
-            let writer: &mut dyn PayloadWriter = unsafe { &mut *writer_ptr };
-            writer.get_size()
-        } else {
-            0
-        }
-    })
+    with_writer(|writer| guard(|| "payload writer".to_string(), || writer.get_size()).unwrap_or(0))
+        .unwrap_or(0)
 }
@@ -0,0 +1,253 @@
+//! Subscriber pressure signal with hysteresis callbacks.
+//!
+//! A [`PressureMonitor`] combines the callback execution time, the ratio of
+//! dropped samples (detected via gaps in the publisher's send clock), the
+//! fill level of the receive queue and optionally the delivery lag into a
+//! single pressure level. A level of `1.0`
+//! means that at least one of the inputs reached its configured limit.
+//!
+//! When the level rises to the high watermark, the `on_pressure_high` callback
+//! fires once; `on_pressure_normal` fires once the level fell back to the low
+//! watermark. Applications use these hooks to shed load (e.g. lower an image
+//! resolution) while they are falling behind.
+
+use rustecal_core::time::Time;
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::Duration;
+
+/// Limits and smoothing used to compute the pressure level.
+#[derive(Debug, Clone)]
+pub struct PressureConfig {
+    /// Averaged callback execution time that corresponds to a level of `1.0`.
+    pub callback_time_limit: Duration,
+    /// Averaged ratio of dropped samples that corresponds to a level of `1.0`.
+    pub drop_ratio_limit: f64,
+    /// Fill ratio of the receive queue (queued messages / capacity) that
+    /// corresponds to a level of `1.0`.
+    ///
+    /// Only applies while the subscriber queues messages, e.g. for
+    /// [`TypedSubscriber::recv`](crate::TypedSubscriber::recv), a channel or
+    /// a stream.
+    pub queue_fill_limit: f64,
+    /// Averaged delivery lag that corresponds to a level of `1.0`.
+    ///
+    /// The lag is measured against eCAL time, so it is only meaningful on
+    /// synchronized hosts. `None` ignores the lag.
+    pub lag_limit: Option<Duration>,
+    /// Level at which the subscriber is considered under high pressure.
+    pub high_watermark: f64,
+    /// Level at which the subscriber returns to normal pressure.
+    pub low_watermark: f64,
+    /// Weight of the newest sample in the exponential moving averages (0..=1).
+    pub smoothing: f64,
+}
+
+impl Default for PressureConfig {
+    fn default() -> Self {
+        Self {
+            callback_time_limit: Duration::from_millis(10),
+            drop_ratio_limit: 0.05,
+            queue_fill_limit: 0.5,
+            lag_limit: None,
+            high_watermark: 1.0,
+            low_watermark: 0.7,
+            smoothing: 0.1,
+        }
+    }
+}
+
+impl PressureConfig {
+    /// Checks that the limits are positive, so every input maps to a finite level.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `Err(String)` naming the first invalid limit.
+    pub fn validate(&self) -> Result<(), String> {
+        if self.callback_time_limit.is_zero() {
+            return Err("callback time limit must be greater than zero".into());
+        }
+        if !(self.drop_ratio_limit.is_finite() && self.drop_ratio_limit > 0.0) {
+            return Err(format!(
+                "invalid drop ratio limit {}",
+                self.drop_ratio_limit
+            ));
+        }
+        if !(self.queue_fill_limit.is_finite() && self.queue_fill_limit > 0.0) {
+            return Err(format!(
+                "invalid queue fill limit {}",
+                self.queue_fill_limit
+            ));
+        }
+        if self.lag_limit.is_some_and(|limit| limit.is_zero()) {
+            return Err("lag limit must be greater than zero".into());
+        }
+        Ok(())
+    }
+}
+
+/// Pressure state of a subscriber.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum PressureState {
+    /// The subscriber keeps up with the incoming data.
+    #[default]
+    Normal,
+    /// The subscriber is falling behind.
+    High,
+}
+
+/// A snapshot of the pressure signal.
+#[derive(Debug, Clone, Default)]
+pub struct Pressure {
+    /// Combined pressure level (`1.0` = a configured limit was reached).
+    pub level: f64,
+    /// Current state after applying the hysteresis.
+    pub state: PressureState,
+    /// Averaged callback execution time.
+    pub callback_time: Duration,
+    /// Averaged ratio of dropped samples.
+    pub drop_ratio: f64,
+    /// Number of queued messages after the latest sample (zero without a queue).
+    pub queue_depth: usize,
+    /// Averaged delivery lag (zero if lag measurement is disabled).
+    pub lag: Duration,
+    /// Total number of dropped samples detected so far.
+    pub dropped: u64,
+}
+
+type PressureCallback = Box<dyn Fn(&Pressure) + Send + Sync + 'static>;
+
+#[derive(Default)]
+struct MonitorState {
+    pressure: Pressure,
+    callback_secs: f64,
+    lag_secs: f64,
+    last_clocks: HashMap<u64, i64>,
+}
+
+/// Computes the pressure signal of a subscriber and fires hysteresis callbacks.
+pub struct PressureMonitor {
+    config: PressureConfig,
+    on_high: Option<PressureCallback>,
+    on_normal: Option<PressureCallback>,
+    state: Mutex<MonitorState>,
+}
+
+impl PressureMonitor {
+    /// Creates a monitor with the given configuration.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `Err(String)` if a limit of `config` is not positive (see
+    /// [`PressureConfig::validate`]).
+    pub fn new(config: PressureConfig) -> Result<Self, String> {
+        config.validate()?;
+        Ok(Self {
+            config,
+            on_high: None,
+            on_normal: None,
+            state: Mutex::new(MonitorState::default()),
+        })
+    }
+
+    /// Registers a callback fired when the pressure becomes high.
+    pub fn on_pressure_high<F>(mut self, callback: F) -> Self
+    where
+        F: Fn(&Pressure) + Send + Sync + 'static,
+    {
+        self.on_high = Some(Box::new(callback));
+        self
+    }
+
+    /// Registers a callback fired when the pressure returns to normal.
+    pub fn on_pressure_normal<F>(mut self, callback: F) -> Self
+    where
+        F: Fn(&Pressure) + Send + Sync + 'static,
+    {
+        self.on_normal = Some(Box::new(callback));
+        self
+    }
+
+    /// Returns the current pressure snapshot.
+    pub fn pressure(&self) -> Pressure {
+        self.state.lock().unwrap().pressure.clone()
+    }
+
+    /// Feeds one delivered sample into the monitor.
+    ///
+    /// # Arguments
+    ///
+    /// * `publisher_id` - Entity ID of the sending publisher.
+    /// * `clock` - The publisher's send clock of the sample.
+    /// * `send_timestamp` - The publisher's send timestamp (microseconds).
+    /// * `callback_time` - Execution time of the user callback.
+    /// * `queue` - Number of queued messages and queue capacity, if the
+    ///   subscriber queues messages.
+    pub fn record(
+        &self,
+        publisher_id: u64,
+        clock: i64,
+        send_timestamp: i64,
+        callback_time: Duration,
+        queue: Option<(usize, usize)>,
+    ) {
+        let alpha = self.config.smoothing.clamp(0.0, 1.0);
+        let mut state = self.state.lock().unwrap();
+
+        // dropped samples show up as gaps in the publisher's send clock
+        let dropped = match state.last_clocks.insert(publisher_id, clock) {
+            Some(last) if clock > last + 1 => (clock - last - 1) as u64,
+            _ => 0,
+        };
+        let drop_sample = dropped as f64 / (dropped + 1) as f64;
+
+        state.callback_secs += alpha * (callback_time.as_secs_f64() - state.callback_secs);
+        state.pressure.drop_ratio += alpha * (drop_sample - state.pressure.drop_ratio);
+        state.pressure.dropped += dropped;
+
+        let mut level = state.callback_secs / self.config.callback_time_limit.as_secs_f64();
+        level = level.max(state.pressure.drop_ratio / self.config.drop_ratio_limit);
+
+        // a rendezvous queue (capacity 0) never holds messages
+        let (queued, capacity) = queue.unwrap_or_default();
+        state.pressure.queue_depth = queued;
+        if capacity > 0 {
+            let fill = queued as f64 / capacity as f64;
+            level = level.max(fill / self.config.queue_fill_limit);
+        }
+
+        if let Some(lag_limit) = self.config.lag_limit {
+            let lag_us = (Time::microseconds() - send_timestamp).max(0);
+            state.lag_secs += alpha * (lag_us as f64 / 1e6 - state.lag_secs);
+            level = level.max(state.lag_secs / lag_limit.as_secs_f64());
+        }
+
+        state.pressure.level = level;
+        state.pressure.callback_time = Duration::from_secs_f64(state.callback_secs);
+        state.pressure.lag = Duration::from_secs_f64(state.lag_secs);
+
+        let transition = match state.pressure.state {
+            PressureState::Normal if level >= self.config.high_watermark => {
+                Some(PressureState::High)
+            }
+            PressureState::High if level <= self.config.low_watermark => {
+                Some(PressureState::Normal)
+            }
+            _ => None,
+        };
+
+        if let Some(next) = transition {
+            state.pressure.state = next;
+            let snapshot = state.pressure.clone();
+            drop(state);
+
+            let callback = match next {
+                PressureState::High => &self.on_high,
+                PressureState::Normal => &self.on_normal,
+            };
+            if let Some(callback) = callback {
+                callback(&snapshot);
+            }
+        }
+    }
+}
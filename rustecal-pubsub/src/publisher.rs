@@ -1,18 +1,95 @@
-use crate::payload_writer::{
-    CURRENT_WRITER, PayloadWriter, get_size_cb, write_full_cb, write_mod_cb,
-};
+use crate::clock_source::ClockSource;
+use crate::connection::wait_for_connections;
+use crate::error::{PubSubError, SendOutcome, check_acknowledged_send};
+use crate::events::{PUBLISHER_EVENTS, PublisherEvent, publisher_event_trampoline};
+use crate::latch::Latch;
+use crate::payload_writer::{PayloadWriter, WriterScope, get_size_cb, write_full_cb, write_mod_cb};
+use crate::publisher_builder::PublisherBuilder;
+use crate::statistics::{PublisherStatistics, SendCounters};
 use crate::types::TopicId;
+use rustecal_core::clock::{Clock, EcalClock, SimClock};
+use rustecal_core::descriptor::DescriptorPolicy;
+use rustecal_core::handle_registry::{HandleKind, HandleRegistry, TrackedHandle};
+use rustecal_core::topic_policy::TopicPolicy;
+use rustecal_core::topic_remap::TopicRemap;
 use rustecal_core::types::DataTypeInfo;
 use rustecal_sys::*;
+use std::cell::OnceCell;
 use std::ffi::{CStr, CString};
 use std::ptr;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
 
 /// When to assign a timestamp to an outgoing message.
 pub enum Timestamp {
-    /// Let eCAL assign its internal send timestamp.
+    /// Use the publisher's [`ClockSource`] (by default, eCAL's internal send timestamp).
     Auto,
     /// Use this custom timestamp (microseconds since epoch).
     Custom(i64),
+    /// Use the current eCAL time, regardless of the publisher's clock source.
+    EcalTime,
+    /// Use the current time of the process-wide [`SimClock`].
+    SimTime,
+}
+
+/// Transport layers a message may be sent on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct TransportLayers {
+    pub shm: bool,
+    pub udp: bool,
+    pub tcp: bool,
+}
+
+impl TransportLayers {
+    /// Shared memory only: the message stays on the local host.
+    pub const LOCAL: TransportLayers = TransportLayers {
+        shm: true,
+        udp: false,
+        tcp: false,
+    };
+
+    pub(crate) fn apply(self, configuration: &mut eCAL_Publisher_Configuration) {
+        configuration.layer.shm.enable = self.shm as i32;
+        configuration.layer.udp.enable = self.udp as i32;
+        configuration.layer.tcp.enable = self.tcp as i32;
+    }
+}
+
+/// Options of a single send, see [`Publisher::send_with`].
+pub struct SendOptions {
+    /// When to timestamp the message.
+    pub timestamp: Timestamp,
+    /// Keeps the message on the local host ([`TransportLayers::LOCAL`]).
+    pub local_only: bool,
+}
+
+impl Default for SendOptions {
+    fn default() -> Self {
+        Self {
+            timestamp: Timestamp::Auto,
+            local_only: false,
+        }
+    }
+}
+
+impl SendOptions {
+    /// Creates options equivalent to [`Publisher::send`] with [`Timestamp::Auto`].
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets when to timestamp the message.
+    pub fn timestamp(mut self, timestamp: Timestamp) -> Self {
+        self.timestamp = timestamp;
+        self
+    }
+
+    /// Keeps the message on the local host, e.g. a debug sample that must
+    /// not go over the network.
+    pub fn local_only(mut self) -> Self {
+        self.local_only = true;
+        self
+    }
 }
 
 /// A safe and ergonomic wrapper around the eCAL C publisher API.
@@ -25,6 +102,19 @@ pub struct Publisher {
     _encoding: CString,
     _type_name: CString,
     _descriptor: Vec<u8>,
+    clock_source: Option<ClockSource>,
+    counters: SendCounters,
+    topic_name: String,
+    event_token: Option<u64>,
+    /// Last sample and event registration of a latched publisher.
+    latch: Option<(Arc<Latch>, u64)>,
+    /// Configuration the publisher was created with (`None` = process-wide configuration).
+    configuration: Option<eCAL_Publisher_Configuration>,
+    /// How long a send waits for shared memory acknowledgements, if at all.
+    acknowledge_timeout: Option<Duration>,
+    /// Publisher of the same topic sending local-only messages, see [`Publisher::send_with`].
+    local: OnceCell<Box<Publisher>>,
+    _tracked: Option<TrackedHandle>,
 }
 
 impl Publisher {
@@ -37,8 +127,28 @@ impl Publisher {
     ///
     /// # Returns
     ///
-    /// Returns `Ok(Publisher)` if creation succeeds, or `Err` with a message if it fails.
+    /// Returns `Ok(Publisher)` if creation succeeds, or `Err` with a message if it fails
+    /// (including topics rejected by the process-wide [`TopicPolicy`]).
+    ///
+    /// The topic name is renamed by the process-wide [`TopicRemap`] first.
     pub fn new(topic_name: &str, data_type: DataTypeInfo) -> Result<Self, String> {
+        Self::with_configuration(&TopicRemap::resolve(topic_name), data_type, None)
+    }
+
+    /// Starts a [`PublisherBuilder`] for a publisher on `topic_name` with
+    /// its own transport options.
+    pub fn builder(topic_name: &str) -> PublisherBuilder {
+        PublisherBuilder::new(topic_name)
+    }
+
+    /// Creates a publisher on `topic_name` as given, without applying the [`TopicRemap`].
+    pub(crate) fn with_configuration(
+        topic_name: &str,
+        data_type: DataTypeInfo,
+        configuration: Option<eCAL_Publisher_Configuration>,
+    ) -> Result<Self, String> {
+        TopicPolicy::check(topic_name).map_err(|e| e.to_string())?;
+        let data_type = DescriptorPolicy::current().apply(topic_name, data_type);
         let c_topic = CString::new(topic_name).map_err(|_| "Invalid topic name")?;
         let c_encoding = CString::new(data_type.encoding).map_err(|_| "Invalid encoding string")?;
         let c_type_name = CString::new(data_type.type_name).map_err(|_| "Invalid type name")?;
@@ -56,21 +166,76 @@ impl Publisher {
             descriptor_length: data_type.descriptor.len(),
         };
 
-        let handle =
-            unsafe { eCAL_Publisher_New(c_topic.as_ptr(), &data_type_info, None, ptr::null()) };
+        let handle = unsafe {
+            eCAL_Publisher_New(
+                c_topic.as_ptr(),
+                &data_type_info,
+                Some(publisher_event_trampoline),
+                configuration
+                    .as_ref()
+                    .map_or(ptr::null(), |c| c as *const _),
+            )
+        };
 
         if handle.is_null() {
             Err("Failed to create eCAL_Publisher".into())
         } else {
+            let effective = match &configuration {
+                Some(configuration) => Some(configuration.layer.shm),
+                None => {
+                    let defaults = unsafe { eCAL_GetConfiguration() };
+                    (!defaults.is_null()).then(|| unsafe { (*defaults).publisher.layer.shm })
+                }
+            };
+            let acknowledge_timeout = effective
+                .filter(|shm| shm.enable != 0 && shm.acknowledge_timeout_ms > 0)
+                .map(|shm| Duration::from_millis(shm.acknowledge_timeout_ms.into()));
             Ok(Self {
                 handle,
                 _encoding: c_encoding,
                 _type_name: c_type_name,
                 _descriptor: data_type.descriptor,
+                clock_source: None,
+                counters: SendCounters::default(),
+                topic_name: topic_name.to_string(),
+                event_token: None,
+                latch: None,
+                configuration,
+                acknowledge_timeout,
+                local: OnceCell::new(),
+                _tracked: HandleRegistry::track(HandleKind::Publisher, topic_name),
             })
         }
     }
 
+    /// Overrides the clock source used for messages sent with [`Timestamp::Auto`].
+    ///
+    /// Passing `None` reverts to the process-wide default
+    /// (see [`ClockSource::set_default`]).
+    pub fn set_clock_source(&mut self, clock_source: Option<ClockSource>) {
+        self.clock_source = clock_source;
+    }
+
+    /// Returns the clock source currently in effect for this publisher.
+    pub fn clock_source(&self) -> ClockSource {
+        self.clock_source
+            .clone()
+            .unwrap_or_else(ClockSource::default_source)
+    }
+
+    /// Resolves a [`Timestamp`] into the value handed to eCAL (`None` = eCAL assigns it).
+    fn resolve_timestamp(&self, timestamp: Timestamp) -> Option<i64> {
+        match timestamp {
+            Timestamp::Auto => match &self.clock_source {
+                Some(source) => source.now(),
+                None => ClockSource::default_source().now(),
+            },
+            Timestamp::Custom(t) => Some(t),
+            Timestamp::EcalTime => Some(EcalClock.now()),
+            Timestamp::SimTime => Some(SimClock::global().now()),
+        }
+    }
+
     /// Sends a serialized message to all connected subscribers.
     ///
     /// # Arguments
@@ -80,17 +245,170 @@ impl Publisher {
     ///
     /// # Returns
     ///
-    /// `true` on success, `false` on failure.
-    pub fn send(&self, data: &[u8], timestamp: Timestamp) -> bool {
-        let ts_ptr = match timestamp {
-            Timestamp::Auto => ptr::null(),
-            Timestamp::Custom(t) => &t as *const i64 as *const _,
-        };
+    /// The [`SendOutcome`] on success.
+    ///
+    /// # Errors
+    ///
+    /// [`PubSubError::NoSubscribers`] if the send failed without connected
+    /// subscribers, [`PubSubError::NotAcknowledged`] if the publisher waits
+    /// for acknowledgements, [`PubSubError::SendFailed`] for any other failure.
+    pub fn send(&self, data: &[u8], timestamp: Timestamp) -> Result<SendOutcome, PubSubError> {
+        let ts = self.resolve_timestamp(timestamp);
+        self.send_raw(data, ts)
+    }
+
+    fn send_raw(&self, data: &[u8], ts: Option<i64>) -> Result<SendOutcome, PubSubError> {
+        let ts_ptr = ts
+            .as_ref()
+            .map_or(ptr::null(), |t| t as *const i64 as *const _);
+        let start = Instant::now();
         let ret = unsafe {
             eCAL_Publisher_Send(self.handle, data.as_ptr() as *const _, data.len(), ts_ptr)
         };
+        let waited = start.elapsed();
         // eCAL returns 0 on success
-        ret == 0
+        self.counters.record(ret == 0, data.len());
+        self.latch(data, ts);
+        check_acknowledged_send(
+            ret,
+            data.len(),
+            self.get_subscriber_count(),
+            self.acknowledge_timeout,
+            waited,
+        )
+    }
+
+    /// Returns how long a send waits for subscribers to acknowledge a shared
+    /// memory message, or `None` if acknowledgements are disabled.
+    ///
+    /// Set with [`PublisherBuilder::acknowledge_timeout`] or the process-wide
+    /// configuration.
+    pub fn acknowledge_timeout(&self) -> Option<Duration> {
+        self.acknowledge_timeout
+    }
+
+    fn latch(&self, data: &[u8], ts: Option<i64>) {
+        if let Some((latch, _)) = &self.latch {
+            latch.store(data, ts);
+        }
+    }
+
+    /// Makes the publisher re-deliver its last sample to subscribers that
+    /// connect later (see [`crate::latch`]).
+    ///
+    /// Messages sent with [`Publisher::send_payload_writer`] are written
+    /// directly into the transport buffers and cannot be latched.
+    pub fn set_latched(&mut self, latched: bool) {
+        match (&self.latch, latched) {
+            (None, true) => {
+                let latch = Arc::new(Latch::new(self.handle));
+                let on_event = latch.clone();
+                let token = PUBLISHER_EVENTS.register(
+                    &self.topic_name,
+                    Arc::new(move |event: &PublisherEvent| on_event.on_event(event)),
+                );
+                self.latch = Some((latch, token));
+            }
+            (Some(_), false) => self.close_latch(),
+            _ => {}
+        }
+    }
+
+    /// Returns `true` if the publisher re-delivers its last sample.
+    pub fn is_latched(&self) -> bool {
+        self.latch.is_some()
+    }
+
+    fn close_latch(&mut self) {
+        if let Some((latch, token)) = self.latch.take() {
+            PUBLISHER_EVENTS.unregister(token);
+            latch.close();
+        }
+    }
+
+    /// Sends a serialized message with per-message [`SendOptions`].
+    ///
+    /// eCAL fixes the transport layers of a publisher when it is created, so
+    /// local-only messages are sent by a second publisher of the topic
+    /// restricted to [`TransportLayers::LOCAL`], created on first use (or by
+    /// [`Publisher::prepare_local_only`]). Subscribers see it as a publisher
+    /// of its own, with its own entity id and send clock; like any new
+    /// publisher, it only delivers once the subscribers discovered it. A
+    /// publisher already restricted to shared memory sends them itself.
+    ///
+    /// Local-only messages are counted in this publisher's statistics, but
+    /// not latched.
+    ///
+    /// # Errors
+    ///
+    /// See [`Publisher::send`]; a local publisher that cannot be created is
+    /// reported as [`PubSubError::SendFailed`].
+    pub fn send_with(&self, data: &[u8], options: SendOptions) -> Result<SendOutcome, PubSubError> {
+        let ts = self.resolve_timestamp(options.timestamp);
+        if !options.local_only {
+            return self.send_raw(data, ts);
+        }
+        let Ok(local) = self.local_publisher() else {
+            self.counters.record(false, data.len());
+            return Err(PubSubError::SendFailed {
+                size: data.len(),
+                subscriber_count: self.get_subscriber_count(),
+            });
+        };
+        if ptr::eq(local, self) {
+            return self.send_raw(data, ts);
+        }
+        let result = local.send_raw(data, ts);
+        self.counters.record(result.is_ok(), data.len());
+        result
+    }
+
+    /// Creates the publisher for local-only messages in advance, so that
+    /// subscribers discover it before the first such message.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `Err(String)` if the eCAL configuration is unavailable or
+    /// the publisher could not be created.
+    pub fn prepare_local_only(&self) -> Result<(), String> {
+        self.local_publisher().map(|_| ())
+    }
+
+    /// Returns the publisher sending local-only messages: this one if it
+    /// already uses shared memory only, otherwise one of the same topic
+    /// restricted to [`TransportLayers::LOCAL`], created on first use.
+    fn local_publisher(&self) -> Result<&Publisher, String> {
+        if let Some(local) = self.local.get() {
+            return Ok(local);
+        }
+        let mut configuration = match self.configuration {
+            Some(configuration) => configuration,
+            None => {
+                let defaults = unsafe { eCAL_GetConfiguration() };
+                if defaults.is_null() {
+                    return Err("eCAL configuration unavailable".into());
+                }
+                unsafe { (*defaults).publisher }
+            }
+        };
+        if configuration.layer.udp.enable == 0 && configuration.layer.tcp.enable == 0 {
+            return Ok(self);
+        }
+        TransportLayers::LOCAL.apply(&mut configuration);
+        let data_type = DataTypeInfo {
+            encoding: self._encoding.to_string_lossy().into_owned(),
+            type_name: self._type_name.to_string_lossy().into_owned(),
+            descriptor: self._descriptor.clone(),
+        };
+        let local =
+            Publisher::with_configuration(&self.topic_name, data_type, Some(configuration))?;
+        Ok(self.local.get_or_init(|| Box::new(local)))
+    }
+
+    /// Sends a serialized message and returns `true` on success.
+    #[deprecated(note = "use `send`, which reports why a send failed")]
+    pub fn send_bool(&self, data: &[u8], timestamp: Timestamp) -> bool {
+        self.send(data, timestamp).is_ok()
     }
 
     /// Sends a zero-copy payload using a [`PayloadWriter`].
@@ -102,17 +420,19 @@ impl Publisher {
     ///
     /// # Returns
     ///
-    /// `true` on success, `false` on failure.
+    /// The [`SendOutcome`] on success.
+    ///
+    /// # Errors
+    ///
+    /// See [`Publisher::send`].
     pub fn send_payload_writer<W: PayloadWriter>(
         &self,
         writer: &mut W,
         timestamp: Timestamp,
-    ) -> bool {
-        // stash the writer pointer in TLS
+    ) -> Result<SendOutcome, PubSubError> {
+        // make the writer reachable from the callbacks for the duration of the call
         let ptr = writer as *mut W as *mut dyn PayloadWriter;
-        CURRENT_WRITER.with(|cell| {
-            *cell.borrow_mut() = Some(ptr);
-        });
+        let scope = WriterScope::enter(ptr);
 
         // build the C payload writer struct
         let c_writer = eCAL_PayloadWriter {
@@ -122,22 +442,42 @@ impl Publisher {
         };
 
         // prepare timestamp pointer
-        let ts_ptr = match timestamp {
-            Timestamp::Auto => ptr::null(),
-            Timestamp::Custom(t) => &t as *const i64 as *const _,
-        };
+        let ts = self.resolve_timestamp(timestamp);
+        let ts_ptr = ts
+            .as_ref()
+            .map_or(ptr::null(), |t| t as *const i64 as *const _);
 
         // call into the FFI
+        let start = Instant::now();
         let result =
             unsafe { eCAL_Publisher_SendPayloadWriter(self.handle, &c_writer as *const _, ts_ptr) };
+        let waited = start.elapsed();
 
-        // clear the slot
-        CURRENT_WRITER.with(|cell| {
-            cell.borrow_mut().take();
-        });
+        let write_error = scope.finish();
 
         // eCAL returns 0 on success
-        result == 0
+        let size = writer.get_size();
+        self.counters.record(result == 0, size);
+        if let Some(error) = write_error {
+            return Err(PubSubError::PayloadWrite(error));
+        }
+        check_acknowledged_send(
+            result,
+            size,
+            self.get_subscriber_count(),
+            self.acknowledge_timeout,
+            waited,
+        )
+    }
+
+    /// Sends a zero-copy payload and returns `true` on success.
+    #[deprecated(note = "use `send_payload_writer`, which reports why a send failed")]
+    pub fn send_payload_writer_bool<W: PayloadWriter>(
+        &self,
+        writer: &mut W,
+        timestamp: Timestamp,
+    ) -> bool {
+        self.send_payload_writer(writer, timestamp).is_ok()
     }
 
     /// Retrieves the number of currently connected subscribers.
@@ -145,6 +485,49 @@ impl Publisher {
         unsafe { eCAL_Publisher_GetSubscriberCount(self.handle) }
     }
 
+    /// Registers a callback for [`PublisherEvent`]s, replacing a previous one.
+    ///
+    /// The callback runs on an eCAL thread whenever a subscriber connects or
+    /// disconnects, or a message could not be delivered to a subscriber.
+    /// See [`crate::events`] for how events of publishers sharing a topic
+    /// within one process are dispatched.
+    pub fn set_event_callback<F>(&mut self, callback: F)
+    where
+        F: Fn(&PublisherEvent) + Send + Sync + 'static,
+    {
+        if let Some(token) = self.event_token.take() {
+            PUBLISHER_EVENTS.unregister(token);
+        }
+        self.event_token = Some(PUBLISHER_EVENTS.register(&self.topic_name, Arc::new(callback)));
+    }
+
+    /// Returns the message and failure counters of this publisher.
+    pub fn get_statistics(&self) -> PublisherStatistics {
+        self.counters.snapshot()
+    }
+
+    /// Blocks until at least `count` subscribers are connected, or `timeout` has elapsed.
+    ///
+    /// Returns `true` if enough subscribers are connected. The count is
+    /// re-checked on the connection events of the topic (see [`crate::connection`]).
+    pub fn wait_for_subscribers(&self, count: usize, timeout: Duration) -> bool {
+        wait_for_connections(&PUBLISHER_EVENTS, &self.topic_name, timeout, || {
+            self.get_subscriber_count() >= count
+        })
+    }
+
+    /// Async variant of [`Publisher::wait_for_subscribers`].
+    #[cfg(feature = "tokio")]
+    pub async fn wait_for_subscribers_async(&self, count: usize, timeout: Duration) -> bool {
+        crate::connection::wait_for_connections_async(
+            &PUBLISHER_EVENTS,
+            &self.topic_name,
+            timeout,
+            || self.get_subscriber_count() >= count,
+        )
+        .await
+    }
+
     /// Retrieves the name of the topic being published.
     ///
     /// # Returns
@@ -223,8 +606,12 @@ impl Publisher {
 impl Drop for Publisher {
     /// Cleans up the underlying eCAL publisher resource.
     fn drop(&mut self) {
+        self.close_latch();
         unsafe {
             eCAL_Publisher_Delete(self.handle);
         }
+        if let Some(token) = self.event_token.take() {
+            PUBLISHER_EVENTS.unregister(token);
+        }
     }
 }
@@ -0,0 +1,169 @@
+//! Builder for publishers with per-topic transport options.
+//!
+//! eCAL reads the transport settings of a publisher (enabled layers, shared
+//! memory buffering, zero-copy mode) from the process-wide configuration
+//! unless the publisher is created with its own configuration.
+//! [`Publisher::builder`] creates publishers with their own settings, so one
+//! topic can be tuned without changing the configuration passed to
+//! `Ecal::initialize`:
+//!
+//! ```ignore
+//! let publisher = Publisher::builder("camera/image")
+//!     .datatype(data_type)
+//!     .shm_buffer_count(2)
+//!     .zero_copy(true)
+//!     .create()?;
+//! ```
+//!
+//! Options that are not set keep the value of the process-wide configuration.
+
+use crate::clock_source::ClockSource;
+use crate::publisher::{Publisher, TransportLayers};
+use crate::typed_publisher::{PublisherMessage, TypedPublisher};
+use rustecal_core::topic_remap::TopicRemap;
+use rustecal_core::types::DataTypeInfo;
+use rustecal_sys::{eCAL_GetConfiguration, eCAL_Publisher_Configuration};
+use std::time::Duration;
+
+/// Configures and creates a [`Publisher`] or [`TypedPublisher`].
+#[derive(Clone)]
+pub struct PublisherBuilder {
+    topic_name: String,
+    data_type: Option<DataTypeInfo>,
+    layers: Option<TransportLayers>,
+    shm_buffer_count: Option<u32>,
+    zero_copy: Option<bool>,
+    acknowledge_timeout: Option<Duration>,
+    clock_source: Option<ClockSource>,
+    latched: bool,
+}
+
+impl PublisherBuilder {
+    /// Starts a builder for a publisher on `topic_name`.
+    pub fn new(topic_name: &str) -> Self {
+        Self {
+            topic_name: topic_name.to_string(),
+            data_type: None,
+            layers: None,
+            shm_buffer_count: None,
+            zero_copy: None,
+            acknowledge_timeout: None,
+            clock_source: None,
+            latched: false,
+        }
+    }
+
+    /// Sets the type metadata of the topic (required by [`PublisherBuilder::create`]).
+    pub fn datatype(mut self, data_type: DataTypeInfo) -> Self {
+        self.data_type = Some(data_type);
+        self
+    }
+
+    /// Sets the transport layers the publisher sends on.
+    pub fn layers(mut self, layers: TransportLayers) -> Self {
+        self.layers = Some(layers);
+        self
+    }
+
+    /// Sets the number of shared memory buffers.
+    ///
+    /// More than one buffer lets the publisher write the next message while
+    /// slow subscribers still read the previous one.
+    pub fn shm_buffer_count(mut self, count: u32) -> Self {
+        self.shm_buffer_count = Some(count);
+        self
+    }
+
+    /// Enables or disables zero-copy delivery over shared memory.
+    pub fn zero_copy(mut self, enabled: bool) -> Self {
+        self.zero_copy = Some(enabled);
+        self
+    }
+
+    /// Makes every send wait up to `timeout` for subscribers to acknowledge
+    /// the shared memory message; a zero timeout disables acknowledgements.
+    pub fn acknowledge_timeout(mut self, timeout: Duration) -> Self {
+        self.acknowledge_timeout = Some(timeout);
+        self
+    }
+
+    /// Sets the clock source of messages sent with `Timestamp::Auto`
+    /// (see [`Publisher::set_clock_source`]).
+    pub fn clock_source(mut self, clock_source: ClockSource) -> Self {
+        self.clock_source = Some(clock_source);
+        self
+    }
+
+    /// Re-delivers the last sample to late-joining subscribers
+    /// (see [`Publisher::set_latched`]).
+    pub fn latched(mut self, latched: bool) -> Self {
+        self.latched = latched;
+        self
+    }
+
+    /// Returns `true` if any transport option deviates from the process-wide configuration.
+    fn has_transport_options(&self) -> bool {
+        self.layers.is_some()
+            || self.shm_buffer_count.is_some()
+            || self.zero_copy.is_some()
+            || self.acknowledge_timeout.is_some()
+    }
+
+    /// Applies the transport options to `configuration`.
+    fn apply(&self, configuration: &mut eCAL_Publisher_Configuration) {
+        if let Some(layers) = self.layers {
+            layers.apply(configuration);
+        }
+        let shm = &mut configuration.layer.shm;
+        if let Some(count) = self.shm_buffer_count {
+            shm.memfile_buffer_count = count;
+        }
+        if let Some(enabled) = self.zero_copy {
+            shm.zero_copy_mode = enabled as i32;
+        }
+        if let Some(timeout) = self.acknowledge_timeout {
+            shm.acknowledge_timeout_ms = timeout.as_millis().min(u32::MAX as u128) as u32;
+        }
+    }
+
+    /// Creates the publisher.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `Err(String)` if no data type was set, the eCAL
+    /// configuration is unavailable, or the publisher could not be created.
+    pub fn create(self) -> Result<Publisher, String> {
+        let data_type = self
+            .data_type
+            .clone()
+            .ok_or_else(|| format!("No data type set for publisher of '{}'", self.topic_name))?;
+
+        let configuration = if self.has_transport_options() {
+            let defaults = unsafe { eCAL_GetConfiguration() };
+            if defaults.is_null() {
+                return Err("eCAL configuration unavailable".into());
+            }
+            let mut configuration = unsafe { (*defaults).publisher };
+            self.apply(&mut configuration);
+            Some(configuration)
+        } else {
+            None
+        };
+
+        let topic_name = TopicRemap::resolve(&self.topic_name);
+        let mut publisher = Publisher::with_configuration(&topic_name, data_type, configuration)?;
+        publisher.set_clock_source(self.clock_source);
+        publisher.set_latched(self.latched);
+        Ok(publisher)
+    }
+
+    /// Creates a typed publisher for messages of type `T`.
+    ///
+    /// Uses `T::datatype()` unless a data type was set.
+    pub fn create_typed<T: PublisherMessage>(mut self) -> Result<TypedPublisher<T>, String> {
+        if self.data_type.is_none() {
+            self.data_type = Some(T::datatype());
+        }
+        self.create().map(TypedPublisher::from_publisher)
+    }
+}
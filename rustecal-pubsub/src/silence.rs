@@ -0,0 +1,189 @@
+//! Detection of topics that stopped delivering data.
+//!
+//! [`TypedSubscriber::on_timeout`] reports when no message arrived within a
+//! window, e.g. because a sensor stalled, and again when data resumes:
+//!
+//! ```ignore
+//! subscriber.on_timeout(Duration::from_millis(200), |event| match event {
+//!     SilenceEvent::TimedOut { topic_name, .. } => health.mark_stale(topic_name),
+//!     SilenceEvent::Resumed { topic_name, .. } => health.mark_ok(topic_name),
+//! });
+//! ```
+//!
+//! All [`SilenceWatch`]es of the process share one monitor thread, which
+//! sleeps until the earliest deadline. The window starts when the watch is
+//! created, so a topic that never delivers data times out as well.
+//! `TimedOut` is reported on the monitor thread, `Resumed` on the receive
+//! thread of the message that ended the silence.
+//!
+//! [`TypedSubscriber::on_timeout`]: crate::typed_subscriber::TypedSubscriber::on_timeout
+
+use rustecal_core::error_hook::guard;
+use std::sync::{Arc, Condvar, Mutex, Once, Weak};
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// A change in whether a watched topic delivers data.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SilenceEvent {
+    /// No message arrived within the window.
+    TimedOut {
+        topic_name: String,
+        window: Duration,
+    },
+    /// A message arrived after the topic timed out.
+    Resumed {
+        topic_name: String,
+        /// Time since the last message before the silence (or since the
+        /// watch was created).
+        silent_for: Duration,
+    },
+}
+
+type Callback = Box<dyn Fn(&SilenceEvent) + Send + Sync + 'static>;
+
+struct State {
+    last_seen: Instant,
+    silent: bool,
+}
+
+struct Watch {
+    topic_name: String,
+    window: Duration,
+    state: Mutex<State>,
+    callback: Callback,
+}
+
+impl Watch {
+    /// Reports a timeout if the window has elapsed at `now`.
+    fn expire(&self, now: Instant) {
+        {
+            let mut state = self.state.lock().unwrap();
+            if state.silent || now < state.last_seen + self.window {
+                return;
+            }
+            state.silent = true;
+        }
+        self.report(&SilenceEvent::TimedOut {
+            topic_name: self.topic_name.clone(),
+            window: self.window,
+        });
+    }
+
+    fn report(&self, event: &SilenceEvent) {
+        guard(
+            || format!("timeout callback of '{}'", self.topic_name),
+            || (self.callback)(event),
+        );
+    }
+}
+
+static WATCHES: Mutex<Vec<Weak<Watch>>> = Mutex::new(Vec::new());
+static WAKE: Condvar = Condvar::new();
+static MONITOR: Once = Once::new();
+
+/// Body of the monitor thread shared by all watches.
+fn monitor() {
+    let mut watches = WATCHES.lock().unwrap();
+    loop {
+        watches.retain(|watch| watch.strong_count() > 0);
+        let now = Instant::now();
+        let mut expired = Vec::new();
+        let mut next: Option<Instant> = None;
+        for watch in watches.iter().filter_map(Weak::upgrade) {
+            let state = watch.state.lock().unwrap();
+            if state.silent {
+                continue;
+            }
+            let deadline = state.last_seen + watch.window;
+            drop(state);
+            if deadline <= now {
+                expired.push(watch);
+            } else {
+                next = Some(next.map_or(deadline, |next| next.min(deadline)));
+            }
+        }
+        if !expired.is_empty() {
+            // callbacks may create or drop watches, so they run without the lock
+            drop(watches);
+            for watch in expired {
+                watch.expire(now);
+            }
+            watches = WATCHES.lock().unwrap();
+            continue;
+        }
+        watches = match next {
+            Some(deadline) => WAKE.wait_timeout(watches, deadline - now).unwrap().0,
+            None => WAKE.wait(watches).unwrap(),
+        };
+    }
+}
+
+/// Wakes the monitor thread to recompute its deadline.
+fn wake_monitor() {
+    // taking the lock orders the wake-up after a running deadline computation
+    drop(WATCHES.lock().unwrap());
+    WAKE.notify_all();
+}
+
+/// Reports when no sample was recorded within a window.
+///
+/// Dropping the watch stops it.
+pub struct SilenceWatch {
+    watch: Arc<Watch>,
+}
+
+impl SilenceWatch {
+    /// Starts watching `topic_name`: `callback` is invoked when no sample is
+    /// recorded within `window`, and again when samples resume.
+    pub fn new<F>(topic_name: &str, window: Duration, callback: F) -> Self
+    where
+        F: Fn(&SilenceEvent) + Send + Sync + 'static,
+    {
+        let watch = Arc::new(Watch {
+            topic_name: topic_name.to_string(),
+            window,
+            state: Mutex::new(State {
+                last_seen: Instant::now(),
+                silent: false,
+            }),
+            callback: Box::new(callback),
+        });
+        MONITOR.call_once(|| {
+            thread::spawn(monitor);
+        });
+        WATCHES.lock().unwrap().push(Arc::downgrade(&watch));
+        WAKE.notify_all();
+        Self { watch }
+    }
+
+    /// Records a received sample, reporting [`SilenceEvent::Resumed`] if the
+    /// topic had timed out.
+    pub fn record_sample(&self) {
+        let now = Instant::now();
+        let silent_for = {
+            let mut state = self.watch.state.lock().unwrap();
+            let silent_for = state.silent.then(|| now - state.last_seen);
+            state.last_seen = now;
+            state.silent = false;
+            silent_for
+        };
+        if let Some(silent_for) = silent_for {
+            wake_monitor();
+            self.watch.report(&SilenceEvent::Resumed {
+                topic_name: self.watch.topic_name.clone(),
+                silent_for,
+            });
+        }
+    }
+
+    /// Returns `true` while the topic is timed out.
+    pub fn is_silent(&self) -> bool {
+        self.watch.state.lock().unwrap().silent
+    }
+
+    /// Returns the window after which the topic times out.
+    pub fn window(&self) -> Duration {
+        self.watch.window
+    }
+}
@@ -0,0 +1,299 @@
+//! Simulation time master and follower over eCAL's sim time topic.
+//!
+//! eCAL's simulation time plugin (`ecaltime-simtime`) follows the time
+//! published by a time master on the topic [`SIM_TIME_TOPIC`] as protobuf
+//! message `eCAL.pb.SimTime`: the play state, the simulation time, the real
+//! time factor, and the local time at which the state was sampled.
+//!
+//! - A [`SimClockPublisher`] makes a Rust simulator the time master: every
+//!   published state is picked up by eCAL nodes using the sim time plugin and
+//!   by Rust nodes running a [`SimClockFollower`].
+//! - A [`SimClockFollower`] drives a [`SimClock`] from the received states, so
+//!   Rust nodes reading that clock (and publishers using `Timestamp::SimTime`
+//!   or `ClockSource::Sim`) follow an external simulator.
+//!
+//! The follower sets the clock to the published simulation time and does not
+//! extrapolate between two states, which keeps closed-loop runs
+//! deterministic; the master publishes a state for every simulation step.
+//! The message is encoded by hand, so no descriptor is registered with it.
+
+use crate::publisher::{Publisher, Timestamp};
+use crate::typed_publisher::PublisherMessage;
+use crate::typed_subscriber::{OwnedMessage, Received, SubscriberMessage, TypedSubscriber};
+use rustecal_core::clock::{Clock, SimClock};
+use rustecal_core::types::DataTypeInfo;
+use std::sync::{Arc, RwLock};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// Topic on which the time master publishes the simulation state.
+pub const SIM_TIME_TOPIC: &str = "__sim_time__";
+
+/// Protobuf type name of the simulation state message.
+pub const SIM_TIME_TYPE_NAME: &str = "eCAL.pb.SimTime";
+
+/// Play state of the simulation.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum SimulationState {
+    #[default]
+    Stopped,
+    Playing,
+    Paused,
+}
+
+impl SimulationState {
+    fn to_raw(self) -> u64 {
+        match self {
+            SimulationState::Stopped => 0,
+            SimulationState::Playing => 1,
+            SimulationState::Paused => 2,
+        }
+    }
+
+    fn from_raw(raw: u64) -> Self {
+        match raw {
+            1 => SimulationState::Playing,
+            2 => SimulationState::Paused,
+            _ => SimulationState::Stopped,
+        }
+    }
+}
+
+/// The `eCAL.pb.SimTime` message.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SimTimeMessage {
+    pub simulation_state: SimulationState,
+    /// Simulation time in nanoseconds.
+    pub simulation_time_nsecs: i64,
+    /// Speed of the simulation relative to real time.
+    pub real_time_factor: f64,
+    /// Local (system) time in nanoseconds at which the state was sampled.
+    pub local_time_nsecs: i64,
+    pub host_name: String,
+    pub process_id: i32,
+}
+
+impl SimTimeMessage {
+    /// Encodes the message in protobuf wire format.
+    pub fn encode(&self) -> Vec<u8> {
+        let mut buf = Vec::with_capacity(48 + self.host_name.len());
+        self.encode_into(&mut buf);
+        buf
+    }
+
+    /// Encodes the message in protobuf wire format, appending to `buf`.
+    pub fn encode_into(&self, buf: &mut Vec<u8>) {
+        put_varint(buf, 1 << 3, self.simulation_state.to_raw());
+        put_varint(buf, 2 << 3, self.simulation_time_nsecs as u64);
+        buf.push((3 << 3) | 1);
+        buf.extend_from_slice(&self.real_time_factor.to_le_bytes());
+        put_varint(buf, 4 << 3, self.local_time_nsecs as u64);
+        buf.push((5 << 3) | 2);
+        write_varint(buf, self.host_name.len() as u64);
+        buf.extend_from_slice(self.host_name.as_bytes());
+        // int32 fields are sign-extended to 64 bit on the wire
+        put_varint(buf, 6 << 3, self.process_id as i64 as u64);
+    }
+
+    /// Decodes a message in protobuf wire format, skipping unknown fields.
+    pub fn decode(mut bytes: &[u8]) -> Option<Self> {
+        let mut msg = SimTimeMessage::default();
+        while !bytes.is_empty() {
+            let key = read_varint(&mut bytes)?;
+            match (key >> 3, key & 7) {
+                (1, 0) => {
+                    msg.simulation_state = SimulationState::from_raw(read_varint(&mut bytes)?)
+                }
+                (2, 0) => msg.simulation_time_nsecs = read_varint(&mut bytes)? as i64,
+                (3, 1) => {
+                    let (value, rest) = bytes.split_first_chunk::<8>()?;
+                    msg.real_time_factor = f64::from_le_bytes(*value);
+                    bytes = rest;
+                }
+                (4, 0) => msg.local_time_nsecs = read_varint(&mut bytes)? as i64,
+                (5, 2) => {
+                    let len = read_varint(&mut bytes)? as usize;
+                    let value = bytes.get(..len)?;
+                    msg.host_name = String::from_utf8_lossy(value).into_owned();
+                    bytes = &bytes[len..];
+                }
+                (6, 0) => msg.process_id = read_varint(&mut bytes)? as i32,
+                (_, 0) => {
+                    read_varint(&mut bytes)?;
+                }
+                (_, 1) => bytes = bytes.get(8..)?,
+                (_, 2) => {
+                    let len = read_varint(&mut bytes)? as usize;
+                    bytes = bytes.get(len..)?;
+                }
+                (_, 5) => bytes = bytes.get(4..)?,
+                _ => return None,
+            }
+        }
+        Some(msg)
+    }
+}
+
+fn put_varint(buf: &mut Vec<u8>, key: u8, value: u64) {
+    buf.push(key);
+    write_varint(buf, value);
+}
+
+fn write_varint(buf: &mut Vec<u8>, mut value: u64) {
+    while value >= 0x80 {
+        buf.push((value as u8) | 0x80);
+        value >>= 7;
+    }
+    buf.push(value as u8);
+}
+
+fn read_varint(bytes: &mut &[u8]) -> Option<u64> {
+    let mut value = 0u64;
+    for (i, byte) in bytes.iter().enumerate().take(10) {
+        value |= ((byte & 0x7f) as u64) << (7 * i);
+        if byte & 0x80 == 0 {
+            *bytes = &bytes[i + 1..];
+            return Some(value);
+        }
+    }
+    None
+}
+
+impl PublisherMessage for SimTimeMessage {
+    fn datatype() -> DataTypeInfo {
+        DataTypeInfo {
+            encoding: "proto".into(),
+            type_name: SIM_TIME_TYPE_NAME.into(),
+            descriptor: vec![],
+        }
+    }
+
+    fn to_bytes(&self) -> Arc<[u8]> {
+        Arc::from(self.encode())
+    }
+
+    fn write_bytes(&self, buffer: &mut Vec<u8>) {
+        self.encode_into(buffer);
+    }
+}
+
+impl SubscriberMessage<'_> for SimTimeMessage {
+    fn datatype() -> DataTypeInfo {
+        <SimTimeMessage as PublisherMessage>::datatype()
+    }
+
+    fn from_bytes(bytes: &[u8], _data_type_info: &DataTypeInfo) -> Option<Self> {
+        SimTimeMessage::decode(bytes)
+    }
+}
+
+impl OwnedMessage for SimTimeMessage {
+    fn into_owned(self) -> Self {
+        self
+    }
+}
+
+fn system_time_nsecs() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_nanos() as i64)
+        .unwrap_or(0)
+}
+
+fn host_name() -> String {
+    std::env::var("HOSTNAME")
+        .or_else(|_| std::env::var("COMPUTERNAME"))
+        .unwrap_or_default()
+}
+
+/// Publishes the simulation time as eCAL time master.
+pub struct SimClockPublisher {
+    publisher: Publisher,
+    host_name: String,
+}
+
+impl SimClockPublisher {
+    /// Creates the publisher on [`SIM_TIME_TOPIC`].
+    pub fn new() -> Result<Self, String> {
+        Ok(Self {
+            publisher: Publisher::new(
+                SIM_TIME_TOPIC,
+                <SimTimeMessage as PublisherMessage>::datatype(),
+            )?,
+            host_name: host_name(),
+        })
+    }
+
+    /// Publishes the simulation time `time_us` (microseconds) with the given state.
+    ///
+    /// `real_time_factor` is informational for followers that extrapolate
+    /// (e.g. the eCAL sim time plugin); use `1.0` for real-time playback.
+    pub fn publish(&self, time_us: i64, state: SimulationState, real_time_factor: f64) -> bool {
+        let message = SimTimeMessage {
+            simulation_state: state,
+            simulation_time_nsecs: time_us.saturating_mul(1000),
+            real_time_factor,
+            local_time_nsecs: system_time_nsecs(),
+            host_name: self.host_name.clone(),
+            process_id: std::process::id() as i32,
+        };
+        self.publisher
+            .send(&message.encode(), Timestamp::Auto)
+            .is_ok()
+    }
+
+    /// Publishes the current time of `clock` as playing simulation.
+    pub fn publish_clock(&self, clock: &SimClock) -> bool {
+        self.publish(clock.now(), SimulationState::Playing, 1.0)
+    }
+
+    /// Advances `clock` by `step` and publishes the new time.
+    pub fn step(&self, clock: &SimClock, step: Duration) -> bool {
+        clock.advance(step);
+        self.publish_clock(clock)
+    }
+
+    /// Returns the number of connected followers.
+    pub fn get_subscriber_count(&self) -> usize {
+        self.publisher.get_subscriber_count()
+    }
+}
+
+/// Drives a [`SimClock`] from the simulation time of an external time master.
+pub struct SimClockFollower {
+    _subscriber: TypedSubscriber<'static, SimTimeMessage>,
+    last: Arc<RwLock<Option<SimTimeMessage>>>,
+}
+
+impl SimClockFollower {
+    /// Follows the time master with the process-wide [`SimClock::global`].
+    pub fn new() -> Result<Self, String> {
+        Self::with_clock(SimClock::global())
+    }
+
+    /// Follows the time master with `clock`.
+    ///
+    /// Every received state sets the clock to its simulation time; states
+    /// of a stopped simulation are recorded but leave the clock unchanged.
+    pub fn with_clock(clock: &'static SimClock) -> Result<Self, String> {
+        let last = Arc::new(RwLock::new(None));
+        let mut subscriber = TypedSubscriber::<SimTimeMessage>::new(SIM_TIME_TOPIC)?;
+        let state = last.clone();
+        subscriber.set_callback(move |msg: Received<SimTimeMessage>| {
+            let message = msg.payload;
+            if message.simulation_state != SimulationState::Stopped {
+                clock.set(message.simulation_time_nsecs / 1000);
+            }
+            *state.write().unwrap() = Some(message);
+        });
+
+        Ok(Self {
+            _subscriber: subscriber,
+            last,
+        })
+    }
+
+    /// Returns the last state received from the time master.
+    pub fn last_state(&self) -> Option<SimTimeMessage> {
+        self.last.read().unwrap().clone()
+    }
+}
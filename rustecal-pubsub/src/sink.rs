@@ -0,0 +1,41 @@
+//! `futures_sink::Sink` implementation for [`TypedPublisher`].
+//!
+//! Async pipelines can publish directly to a topic, e.g. with
+//! `StreamExt::forward`. eCAL sends synchronously: each item is sent in
+//! `start_send`, which blocks while eCAL waits for shared memory
+//! acknowledgements (if configured). This wait is the sink's backpressure;
+//! `poll_ready` and `poll_flush` are always ready.
+//!
+//! Sends without connected subscribers are not errors for the sink, so a
+//! pipeline keeps running while nobody listens; any other failed send ends
+//! it with [`PubSubError::SendFailed`].
+
+use crate::error::PubSubError;
+use crate::publisher::Timestamp;
+use crate::typed_publisher::{PublisherMessage, TypedPublisher};
+use futures_sink::Sink;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+impl<T: PublisherMessage> Sink<T> for TypedPublisher<T> {
+    type Error = PubSubError;
+
+    fn poll_ready(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<Result<(), PubSubError>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn start_send(self: Pin<&mut Self>, item: T) -> Result<(), PubSubError> {
+        match self.send(&item, Timestamp::Auto) {
+            Ok(_) | Err(PubSubError::NoSubscribers) => Ok(()),
+            Err(err) => Err(err),
+        }
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<Result<(), PubSubError>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn poll_close(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<Result<(), PubSubError>> {
+        Poll::Ready(Ok(()))
+    }
+}
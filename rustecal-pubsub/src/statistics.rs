@@ -0,0 +1,163 @@
+//! Message and drop counters of publishers and subscribers.
+//!
+//! A publisher counts the messages it handed to eCAL and the sends eCAL
+//! rejected, e.g. because a shared memory acknowledgement timed out
+//! ([`PublisherStatistics`]). A subscriber counts the messages it received
+//! and detects lost messages as gaps in the send clock of each publisher,
+//! which covers UDP packet loss as well as samples overwritten in shared
+//! memory before they were read ([`SubscriberStatistics`]). Each detected
+//! gap or reordering is also reported as [`SampleLoss`].
+
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+/// Counters of a publisher since its creation.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct PublisherStatistics {
+    /// Messages eCAL accepted for sending.
+    pub messages_sent: u64,
+    /// Payload bytes of the accepted messages.
+    pub bytes_sent: u64,
+    /// Sends eCAL reported as failed (e.g. shared memory acknowledgement timeouts).
+    pub send_failures: u64,
+}
+
+/// Counters of a subscriber since its creation.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SubscriberStatistics {
+    /// Messages delivered by eCAL, including those dropped by the compatibility policy.
+    pub messages_received: u64,
+    /// Payload bytes of the delivered messages.
+    pub bytes_received: u64,
+    /// Messages missing between two consecutive messages of the same publisher.
+    pub messages_lost: u64,
+    /// Messages whose send clock did not advance over the previous message of
+    /// the same publisher (duplicates, reordering or a restarted publisher).
+    pub messages_out_of_order: u64,
+    /// Messages discarded because of their data type, see `TypeMismatch`.
+    pub type_mismatches: u64,
+}
+
+/// A gap or reordering in the send clocks of one publisher.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SampleLoss {
+    /// `lost` messages between `previous_clock` and `clock` never arrived.
+    Gap {
+        publisher_id: u64,
+        previous_clock: i64,
+        clock: i64,
+        lost: u64,
+    },
+    /// The clock did not advance over the previous message (duplicate,
+    /// reordering or a restarted publisher).
+    OutOfOrder {
+        publisher_id: u64,
+        previous_clock: i64,
+        clock: i64,
+    },
+}
+
+/// Lock-free send counters shared by the publisher types.
+#[derive(Debug, Default)]
+pub(crate) struct SendCounters {
+    sent: AtomicU64,
+    bytes: AtomicU64,
+    failures: AtomicU64,
+}
+
+impl SendCounters {
+    pub(crate) fn record(&self, success: bool, size: usize) {
+        if success {
+            self.sent.fetch_add(1, Ordering::Relaxed);
+            self.bytes.fetch_add(size as u64, Ordering::Relaxed);
+        } else {
+            self.failures.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    pub(crate) fn snapshot(&self) -> PublisherStatistics {
+        PublisherStatistics {
+            messages_sent: self.sent.load(Ordering::Relaxed),
+            bytes_sent: self.bytes.load(Ordering::Relaxed),
+            send_failures: self.failures.load(Ordering::Relaxed),
+        }
+    }
+}
+
+/// Detects lost messages from the send clocks of the received messages.
+///
+/// eCAL increments a publisher's send clock by one per message, so a jump
+/// by more than one means messages were lost in between. Clocks are tracked
+/// per publisher entity ID.
+#[derive(Debug, Default)]
+pub struct ClockGapTracker {
+    state: Mutex<TrackerState>,
+}
+
+#[derive(Debug, Default)]
+struct TrackerState {
+    statistics: SubscriberStatistics,
+    last_clocks: HashMap<u64, i64>,
+}
+
+impl ClockGapTracker {
+    /// Creates a tracker without recorded messages.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Records a received message and returns the number of messages lost
+    /// since the previous message of the same publisher.
+    ///
+    /// # Arguments
+    ///
+    /// * `publisher_id` - Entity ID of the sending publisher.
+    /// * `clock` - The publisher's send clock of the message.
+    /// * `size` - Payload size in bytes.
+    pub fn record(&self, publisher_id: u64, clock: i64, size: usize) -> u64 {
+        match self.observe(publisher_id, clock, size) {
+            Some(SampleLoss::Gap { lost, .. }) => lost,
+            _ => 0,
+        }
+    }
+
+    /// Records a received message like [`ClockGapTracker::record`] and
+    /// returns the gap or reordering it revealed, if any.
+    pub fn observe(&self, publisher_id: u64, clock: i64, size: usize) -> Option<SampleLoss> {
+        let mut state = self.state.lock().unwrap();
+        let loss = match state.last_clocks.insert(publisher_id, clock) {
+            Some(last) if clock > last + 1 => Some(SampleLoss::Gap {
+                publisher_id,
+                previous_clock: last,
+                clock,
+                lost: (clock - last - 1) as u64,
+            }),
+            Some(last) if clock <= last => Some(SampleLoss::OutOfOrder {
+                publisher_id,
+                previous_clock: last,
+                clock,
+            }),
+            _ => None,
+        };
+        let statistics = &mut state.statistics;
+        statistics.messages_received += 1;
+        statistics.bytes_received += size as u64;
+        match loss {
+            Some(SampleLoss::Gap { lost, .. }) => statistics.messages_lost += lost,
+            Some(SampleLoss::OutOfOrder { .. }) => statistics.messages_out_of_order += 1,
+            None => {}
+        }
+        loss
+    }
+
+    /// Counts a received message that was discarded because of its data type.
+    pub fn record_type_mismatch(&self) {
+        self.state.lock().unwrap().statistics.type_mismatches += 1;
+    }
+
+    /// Returns the counters accumulated so far.
+    pub fn statistics(&self) -> SubscriberStatistics {
+        self.state.lock().unwrap().statistics
+    }
+}
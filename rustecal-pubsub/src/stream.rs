@@ -0,0 +1,82 @@
+//! Async stream of received messages.
+//!
+//! [`TypedSubscriber::into_stream`] turns a subscriber into a
+//! [`SubscriberStream`], so topics can be consumed in async code, e.g. in a
+//! `tokio::select!` loop next to sockets and timers. The receive callback
+//! forwards every message into a bounded channel; the stream does not depend
+//! on a particular async runtime.
+
+use crate::typed_subscriber::{
+    DEFAULT_QUEUE_CAPACITY, OwnedMessage, Received, SubscriberMessage, TypedSubscriber,
+};
+use std::pin::Pin;
+use std::task::{Context, Poll};
+use tokio::sync::mpsc;
+
+/// Async stream of the messages of a [`TypedSubscriber`], created by
+/// [`TypedSubscriber::into_stream`].
+///
+/// Dropping the stream drops the subscriber. The stream stays on the thread
+/// that created the subscriber, so poll it with a local task set (e.g.
+/// `tokio::task::LocalSet`) or in the task that created it.
+pub struct SubscriberStream<T: SubscriberMessage<'static> + OwnedMessage> {
+    receiver: mpsc::Receiver<Received<T>>,
+    _subscriber: TypedSubscriber<'static, T>,
+}
+
+impl<T: SubscriberMessage<'static> + OwnedMessage> TypedSubscriber<'static, T> {
+    /// Converts the subscriber into an async stream of received messages,
+    /// replacing the callback.
+    ///
+    /// The stream buffers up to [`DEFAULT_QUEUE_CAPACITY`] messages; while
+    /// the buffer is full, newly arriving messages are dropped. Messages are
+    /// detached from the receive buffer before they are buffered (see
+    /// [`OwnedMessage`]).
+    ///
+    /// ```ignore
+    /// let mut stream = subscriber.into_stream();
+    /// while let Some(msg) = stream.next().await {
+    ///     println!("{}", msg.payload.data);
+    /// }
+    /// ```
+    pub fn into_stream(self) -> SubscriberStream<T> {
+        self.into_stream_with_capacity(DEFAULT_QUEUE_CAPACITY)
+    }
+
+    /// Like [`TypedSubscriber::into_stream`], buffering up to `capacity` messages.
+    pub fn into_stream_with_capacity(mut self, capacity: usize) -> SubscriberStream<T> {
+        let (sender, receiver) = mpsc::channel(capacity.max(1));
+        let gauge = sender.clone();
+        self.set_callback(move |msg| {
+            let _ = sender.try_send(msg.into_owned());
+        });
+        self.set_queue_gauge(Box::new(move || {
+            (
+                gauge.max_capacity() - gauge.capacity(),
+                gauge.max_capacity(),
+            )
+        }));
+        SubscriberStream {
+            receiver,
+            _subscriber: self,
+        }
+    }
+}
+
+impl<T: SubscriberMessage<'static> + OwnedMessage> SubscriberStream<T> {
+    /// Receives the next message.
+    pub async fn recv(&mut self) -> Option<Received<T>> {
+        self.receiver.recv().await
+    }
+}
+
+// no field is structurally pinned: polling only borrows the receiver
+impl<T: SubscriberMessage<'static> + OwnedMessage> Unpin for SubscriberStream<T> {}
+
+impl<T: SubscriberMessage<'static> + OwnedMessage> futures_core::Stream for SubscriberStream<T> {
+    type Item = Received<T>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Received<T>>> {
+        self.receiver.poll_recv(cx)
+    }
+}
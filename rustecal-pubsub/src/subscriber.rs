@@ -1,9 +1,18 @@
+use crate::connection::wait_for_connections;
+use crate::events::{SUBSCRIBER_EVENTS, subscriber_event_trampoline};
+use crate::type_tracking::SubscriberEvent;
 use crate::types::TopicId;
+use rustecal_core::descriptor::DescriptorPolicy;
+use rustecal_core::handle_registry::{HandleKind, HandleRegistry, TrackedHandle};
+use rustecal_core::topic_policy::TopicPolicy;
+use rustecal_core::topic_remap::TopicRemap;
 use rustecal_core::types::DataTypeInfo;
 use rustecal_sys::*;
 use std::ffi::c_void;
 use std::ffi::{CStr, CString};
 use std::ptr;
+use std::sync::Arc;
+use std::time::Duration;
 
 /// A safe and ergonomic wrapper around the eCAL C subscriber API.
 ///
@@ -15,6 +24,9 @@ pub struct Subscriber {
     _encoding: CString,
     _type_name: CString,
     _descriptor: Vec<u8>,
+    topic_name: String,
+    event_token: Option<u64>,
+    _tracked: Option<TrackedHandle>,
 }
 
 impl Subscriber {
@@ -28,7 +40,8 @@ impl Subscriber {
     ///
     /// # Returns
     ///
-    /// `Ok(Self)` on success or `Err(String)` on failure.
+    /// `Ok(Self)` on success or `Err(String)` on failure, e.g. if the process-wide
+    /// [`TopicPolicy`] rejects the topic.
     pub fn new(
         topic_name: &str,
         data_type: DataTypeInfo,
@@ -39,6 +52,23 @@ impl Subscriber {
             *mut c_void,
         ),
     ) -> Result<Self, String> {
+        Self::with_configuration(&TopicRemap::resolve(topic_name), data_type, callback, None)
+    }
+
+    /// Creates a subscriber on `topic_name` as given, without applying the [`TopicRemap`].
+    pub(crate) fn with_configuration(
+        topic_name: &str,
+        data_type: DataTypeInfo,
+        callback: extern "C" fn(
+            *const eCAL_STopicId,
+            *const eCAL_SDataTypeInformation,
+            *const eCAL_SReceiveCallbackData,
+            *mut c_void,
+        ),
+        configuration: Option<eCAL_Subscriber_Configuration>,
+    ) -> Result<Self, String> {
+        TopicPolicy::check(topic_name).map_err(|e| e.to_string())?;
+        let data_type = DescriptorPolicy::current().apply(topic_name, data_type);
         let c_topic = CString::new(topic_name).map_err(|_| "Invalid topic name")?;
         let c_encoding = CString::new(data_type.encoding).map_err(|_| "Invalid encoding")?;
         let c_type_name = CString::new(data_type.type_name).map_err(|_| "Invalid type name")?;
@@ -56,8 +86,16 @@ impl Subscriber {
             descriptor_length: data_type.descriptor.len(),
         };
 
-        let handle =
-            unsafe { eCAL_Subscriber_New(c_topic.as_ptr(), &data_type_info, None, ptr::null()) };
+        let handle = unsafe {
+            eCAL_Subscriber_New(
+                c_topic.as_ptr(),
+                &data_type_info,
+                Some(subscriber_event_trampoline),
+                configuration
+                    .as_ref()
+                    .map_or(ptr::null(), |c| c as *const _),
+            )
+        };
 
         if handle.is_null() {
             return Err("Failed to create eCAL_Subscriber".into());
@@ -70,9 +108,28 @@ impl Subscriber {
             _encoding: c_encoding,
             _type_name: c_type_name,
             _descriptor: data_type.descriptor,
+            topic_name: topic_name.to_string(),
+            event_token: None,
+            _tracked: HandleRegistry::track(HandleKind::Subscriber, topic_name),
         })
     }
 
+    /// Registers a callback for the connection events of this subscriber,
+    /// replacing a previous one.
+    ///
+    /// The callback runs on an eCAL thread whenever a publisher connects or
+    /// disconnects, or samples were dropped. See [`crate::events`] for how
+    /// events of subscribers sharing a topic within one process are dispatched.
+    pub fn set_event_callback<F>(&mut self, callback: F)
+    where
+        F: Fn(&SubscriberEvent) + Send + Sync + 'static,
+    {
+        if let Some(token) = self.event_token.take() {
+            SUBSCRIBER_EVENTS.unregister(token);
+        }
+        self.event_token = Some(SUBSCRIBER_EVENTS.register(&self.topic_name, Arc::new(callback)));
+    }
+
     /// Returns the raw pointer to the underlying eCAL subscriber.
     ///
     /// This is primarily useful for advanced FFI use cases or low-level access.
@@ -85,6 +142,28 @@ impl Subscriber {
         unsafe { eCAL_Subscriber_GetPublisherCount(self.handle) }
     }
 
+    /// Blocks until at least `count` publishers are connected, or `timeout` has elapsed.
+    ///
+    /// Returns `true` if enough publishers are connected. The count is
+    /// re-checked on the connection events of the topic (see [`crate::connection`]).
+    pub fn wait_for_publishers(&self, count: usize, timeout: Duration) -> bool {
+        wait_for_connections(&SUBSCRIBER_EVENTS, &self.topic_name, timeout, || {
+            self.get_publisher_count() >= count
+        })
+    }
+
+    /// Async variant of [`Subscriber::wait_for_publishers`].
+    #[cfg(feature = "tokio")]
+    pub async fn wait_for_publishers_async(&self, count: usize, timeout: Duration) -> bool {
+        crate::connection::wait_for_connections_async(
+            &SUBSCRIBER_EVENTS,
+            &self.topic_name,
+            timeout,
+            || self.get_publisher_count() >= count,
+        )
+        .await
+    }
+
     /// Retrieves the name of the topic this subscriber is connected to.
     ///
     /// # Returns
@@ -167,5 +246,8 @@ impl Drop for Subscriber {
             eCAL_Subscriber_RemoveReceiveCallback(self.handle);
             eCAL_Subscriber_Delete(self.handle);
         }
+        if let Some(token) = self.event_token.take() {
+            SUBSCRIBER_EVENTS.unregister(token);
+        }
     }
 }
@@ -0,0 +1,274 @@
+//! Builder for typed subscribers.
+//!
+//! A [`TypedSubscriber`] has a growing set of per-subscriber settings: the
+//! receive callback or queue, source filters, compatibility policy, callback
+//! budget, pressure monitoring, events and transport layers.
+//! [`TypedSubscriber::builder`] sets them in one expression:
+//!
+//! ```ignore
+//! let subscriber = TypedSubscriber::<BytesMessage>::builder("camera/raw")
+//!     .source_host("cam-ecu")
+//!     .queue_depth(16)
+//!     .build()?;
+//! ```
+//!
+//! Transport options that are not set keep the value of the process-wide
+//! configuration.
+
+use crate::decimation::Decimation;
+use crate::pressure::PressureMonitor;
+use crate::publisher::TransportLayers;
+use crate::silence::SilenceEvent;
+use crate::statistics::SampleLoss;
+use crate::type_tracking::{CompatibilityPolicy, SubscriberEvent, TypeMismatch};
+use crate::typed_subscriber::{
+    OwnedMessage, PayloadFilter, Received, SourceFilter, SubscriberMessage, TypedSubscriber,
+};
+use rustecal_core::callback_budget::CallbackBudget;
+use rustecal_core::topic_remap::TopicRemap;
+use rustecal_core::types::DataTypeInfo;
+use rustecal_sys::eCAL_GetConfiguration;
+use std::sync::Arc;
+use std::time::Duration;
+
+type ReceiveCallback<T> = Box<dyn FnMut(Received<T>) + Send + 'static>;
+type EventCallback = Arc<dyn Fn(&SubscriberEvent) + Send + Sync + 'static>;
+type SampleLossCallback = Arc<dyn Fn(&SampleLoss) + Send + Sync + 'static>;
+type TypeMismatchCallback = Arc<dyn Fn(&TypeMismatch) + Send + Sync + 'static>;
+type TimeoutCallback = Arc<dyn Fn(&SilenceEvent) + Send + Sync + 'static>;
+
+/// Configures and creates a [`TypedSubscriber`].
+pub struct SubscriberBuilder<T: SubscriberMessage<'static>> {
+    topic_name: String,
+    callback: Option<ReceiveCallback<T>>,
+    queue_depth: Option<usize>,
+    source: Option<SourceFilter>,
+    payload_filter: Option<PayloadFilter>,
+    decimation: Option<Decimation>,
+    policy: Option<CompatibilityPolicy>,
+    budget: Option<CallbackBudget>,
+    pressure: Option<PressureMonitor>,
+    on_event: Option<EventCallback>,
+    on_loss: Option<SampleLossCallback>,
+    on_type_mismatch: Option<TypeMismatchCallback>,
+    on_timeout: Option<(Duration, TimeoutCallback)>,
+    layers: Option<TransportLayers>,
+    drop_out_of_order: Option<bool>,
+}
+
+impl<T: SubscriberMessage<'static> + OwnedMessage> SubscriberBuilder<T> {
+    /// Starts a builder for a subscriber on `topic_name`.
+    pub fn new(topic_name: &str) -> Self {
+        Self {
+            topic_name: topic_name.to_string(),
+            callback: None,
+            queue_depth: None,
+            source: None,
+            payload_filter: None,
+            decimation: None,
+            policy: None,
+            budget: None,
+            pressure: None,
+            on_event: None,
+            on_loss: None,
+            on_type_mismatch: None,
+            on_timeout: None,
+            layers: None,
+            drop_out_of_order: None,
+        }
+    }
+
+    /// Sets the receive callback (see [`TypedSubscriber::set_callback`]).
+    pub fn callback<F>(mut self, callback: F) -> Self
+    where
+        F: FnMut(Received<T>) + Send + 'static,
+    {
+        self.callback = Some(Box::new(callback));
+        self
+    }
+
+    /// Receives into a queue of `depth` messages instead of a callback
+    /// (see [`TypedSubscriber::set_queue_capacity`]).
+    pub fn queue_depth(mut self, depth: usize) -> Self {
+        self.queue_depth = Some(depth);
+        self
+    }
+
+    /// Accepts messages of publishers on `host_name`; may be repeated.
+    pub fn source_host(self, host_name: &str) -> Self {
+        self.map_source(|filter| filter.host(host_name))
+    }
+
+    /// Accepts messages of publishers in process `process_id`; may be repeated.
+    pub fn source_process(self, process_id: i32) -> Self {
+        self.map_source(|filter| filter.process_id(process_id))
+    }
+
+    /// Accepts messages of the publisher `publisher_id`; may be repeated.
+    pub fn source_publisher(self, publisher_id: u64) -> Self {
+        self.map_source(|filter| filter.publisher(publisher_id))
+    }
+
+    fn map_source(mut self, f: impl FnOnce(SourceFilter) -> SourceFilter) -> Self {
+        self.source = Some(f(self.source.take().unwrap_or_default()));
+        self
+    }
+
+    /// Sets the source filter (see [`TypedSubscriber::set_source_filter`]).
+    pub fn source_filter(mut self, filter: SourceFilter) -> Self {
+        self.source = Some(filter);
+        self
+    }
+
+    /// Sets the payload filter (see [`TypedSubscriber::set_payload_filter`]).
+    pub fn payload_filter<F>(mut self, filter: F) -> Self
+    where
+        F: Fn(&[u8], &DataTypeInfo) -> bool + Send + Sync + 'static,
+    {
+        self.payload_filter = Some(Arc::new(filter));
+        self
+    }
+
+    /// Sets the decimation (see [`TypedSubscriber::set_decimation`]).
+    pub fn decimation(mut self, decimation: Decimation) -> Self {
+        self.decimation = Some(decimation);
+        self
+    }
+
+    /// Delivers only every `n`-th message (see [`Decimation::EveryNth`]).
+    pub fn deliver_every_nth(self, n: u32) -> Self {
+        self.decimation(Decimation::EveryNth(n))
+    }
+
+    /// Sets the compatibility policy (see [`TypedSubscriber::set_compatibility_policy`]).
+    pub fn compatibility_policy(mut self, policy: CompatibilityPolicy) -> Self {
+        self.policy = Some(policy);
+        self
+    }
+
+    /// Sets the callback budget (see [`TypedSubscriber::set_callback_budget`]).
+    pub fn callback_budget(mut self, budget: CallbackBudget) -> Self {
+        self.budget = Some(budget);
+        self
+    }
+
+    /// Attaches a pressure monitor (see [`TypedSubscriber::set_pressure_monitor`]).
+    pub fn pressure_monitor(mut self, monitor: PressureMonitor) -> Self {
+        self.pressure = Some(monitor);
+        self
+    }
+
+    /// Sets the event callback (see [`TypedSubscriber::set_event_callback`]).
+    pub fn event_callback<F>(mut self, callback: F) -> Self
+    where
+        F: Fn(&SubscriberEvent) + Send + Sync + 'static,
+    {
+        self.on_event = Some(Arc::new(callback));
+        self
+    }
+
+    /// Sets the sample loss callback (see [`TypedSubscriber::set_sample_loss_callback`]).
+    pub fn sample_loss_callback<F>(mut self, callback: F) -> Self
+    where
+        F: Fn(&SampleLoss) + Send + Sync + 'static,
+    {
+        self.on_loss = Some(Arc::new(callback));
+        self
+    }
+
+    /// Sets the type mismatch callback (see [`TypedSubscriber::set_type_mismatch_callback`]).
+    pub fn type_mismatch_callback<F>(mut self, callback: F) -> Self
+    where
+        F: Fn(&TypeMismatch) + Send + Sync + 'static,
+    {
+        self.on_type_mismatch = Some(Arc::new(callback));
+        self
+    }
+
+    /// Sets the timeout callback (see [`TypedSubscriber::on_timeout`]).
+    pub fn on_timeout<F>(mut self, window: Duration, callback: F) -> Self
+    where
+        F: Fn(&SilenceEvent) + Send + Sync + 'static,
+    {
+        self.on_timeout = Some((window, Arc::new(callback)));
+        self
+    }
+
+    /// Sets the transport layers the subscriber receives on.
+    pub fn layers(mut self, layers: TransportLayers) -> Self {
+        self.layers = Some(layers);
+        self
+    }
+
+    /// Lets eCAL drop messages older than the last received one of the same publisher.
+    pub fn drop_out_of_order_messages(mut self, enabled: bool) -> Self {
+        self.drop_out_of_order = Some(enabled);
+        self
+    }
+
+    /// Creates the subscriber.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `Err(String)` if both a callback and a queue depth were
+    /// set, the eCAL configuration is unavailable, or the subscriber could
+    /// not be created.
+    pub fn build(self) -> Result<TypedSubscriber<'static, T>, String> {
+        if self.callback.is_some() && self.queue_depth.is_some() {
+            return Err(format!(
+                "Subscriber of '{}' cannot have both a callback and a queue",
+                self.topic_name
+            ));
+        }
+
+        let configuration = if self.layers.is_some() || self.drop_out_of_order.is_some() {
+            let defaults = unsafe { eCAL_GetConfiguration() };
+            if defaults.is_null() {
+                return Err("eCAL configuration unavailable".into());
+            }
+            let mut configuration = unsafe { (*defaults).subscriber };
+            if let Some(layers) = self.layers {
+                configuration.layer.shm.enable = layers.shm as i32;
+                configuration.layer.udp.enable = layers.udp as i32;
+                configuration.layer.tcp.enable = layers.tcp as i32;
+            }
+            if let Some(enabled) = self.drop_out_of_order {
+                configuration.drop_out_of_order_messages = enabled as i32;
+            }
+            Some(configuration)
+        } else {
+            None
+        };
+
+        let topic_name = TopicRemap::resolve(&self.topic_name);
+        let mut subscriber = TypedSubscriber::with_configuration(&topic_name, configuration)?;
+        if let Some(policy) = self.policy {
+            subscriber.set_compatibility_policy(policy);
+        }
+        subscriber.set_source_filter(self.source);
+        if let Some(filter) = self.payload_filter {
+            subscriber.set_payload_filter(move |payload, data_type| filter(payload, data_type));
+        }
+        subscriber.set_decimation(self.decimation);
+        subscriber.set_callback_budget(self.budget);
+        subscriber.set_pressure_monitor(self.pressure);
+        if let Some(on_event) = self.on_event {
+            subscriber.set_event_callback(move |event| on_event(event));
+        }
+        if let Some(on_loss) = self.on_loss {
+            subscriber.set_sample_loss_callback(move |loss| on_loss(loss));
+        }
+        if let Some(on_type_mismatch) = self.on_type_mismatch {
+            subscriber.set_type_mismatch_callback(move |mismatch| on_type_mismatch(mismatch));
+        }
+        if let Some((window, on_timeout)) = self.on_timeout {
+            subscriber.on_timeout(window, move |event| on_timeout(event));
+        }
+        if let Some(callback) = self.callback {
+            subscriber.set_callback(callback);
+        } else if let Some(depth) = self.queue_depth {
+            subscriber.set_queue_capacity(depth);
+        }
+        Ok(subscriber)
+    }
+}
@@ -0,0 +1,226 @@
+//! Publishing at a capped rate.
+//!
+//! Debug and visualization topics are often derived from data produced at
+//! kHz rates, while their consumers only need a few updates per second. A
+//! [`ThrottledPublisher`] wraps a [`TypedPublisher`] and sends at most one
+//! message per interval:
+//!
+//! ```ignore
+//! let preview = ThrottledPublisher::<BytesMessage>::new("camera/preview", 10.0, ThrottleMode::Coalesce)?;
+//! loop {
+//!     let frame = camera.next_frame();
+//!     preview.send(&frame, Timestamp::Auto)?; // at most 10 Hz
+//! }
+//! ```
+//!
+//! With [`ThrottleMode::Drop`], messages arriving too early are discarded.
+//! With [`ThrottleMode::Coalesce`], the most recent of them is kept and sent
+//! by [`ThrottledPublisher::poll`] once the interval has elapsed, unless a
+//! newer message is sent first, so the last value of a burst is not lost.
+//! [`RateLimiter`] provides the same gate for other uses.
+
+use crate::error::{PubSubError, SendOutcome};
+use crate::publisher::Timestamp;
+use crate::typed_publisher::{PublisherMessage, TypedPublisher};
+use std::cell::{Cell, RefCell};
+use std::time::{Duration, Instant};
+
+/// Admits at most one event per interval.
+#[derive(Debug, Clone)]
+pub struct RateLimiter {
+    interval: Duration,
+    last: Option<Instant>,
+}
+
+impl RateLimiter {
+    /// Creates a limiter admitting at most `max_rate` events per second.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `Err(String)` if `max_rate` is not a positive, finite number.
+    pub fn new(max_rate: f64) -> Result<Self, String> {
+        if !(max_rate.is_finite() && max_rate > 0.0) {
+            return Err(format!("invalid maximum rate {max_rate} Hz"));
+        }
+        Ok(Self::with_interval(Duration::from_secs_f64(1.0 / max_rate)))
+    }
+
+    /// Creates a limiter admitting at most one event per `interval`.
+    pub fn with_interval(interval: Duration) -> Self {
+        Self {
+            interval,
+            last: None,
+        }
+    }
+
+    /// Returns the minimum time between two admitted events.
+    pub fn interval(&self) -> Duration {
+        self.interval
+    }
+
+    /// Returns `true` if an event at `now` would be admitted.
+    pub fn is_ready_at(&self, now: Instant) -> bool {
+        self.last
+            .is_none_or(|last| now.saturating_duration_since(last) >= self.interval)
+    }
+
+    /// Admits an event at `now` if the interval since the last admitted event has elapsed.
+    pub fn try_acquire_at(&mut self, now: Instant) -> bool {
+        let ready = self.is_ready_at(now);
+        if ready {
+            self.last = Some(now);
+        }
+        ready
+    }
+
+    /// Admits an event now if the interval since the last admitted event has elapsed.
+    pub fn try_acquire(&mut self) -> bool {
+        self.try_acquire_at(Instant::now())
+    }
+}
+
+/// What a [`ThrottledPublisher`] does with messages sent before the interval has elapsed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ThrottleMode {
+    /// Discard them.
+    Drop,
+    /// Keep the most recent one and send it once the interval has elapsed.
+    Coalesce,
+}
+
+/// A [`TypedPublisher`] sending at most one message per interval.
+pub struct ThrottledPublisher<T: PublisherMessage> {
+    publisher: TypedPublisher<T>,
+    mode: ThrottleMode,
+    limiter: RefCell<RateLimiter>,
+    /// Serialized message kept back in [`ThrottleMode::Coalesce`].
+    pending: RefCell<Option<(Vec<u8>, Timestamp)>>,
+    throttled: Cell<u64>,
+}
+
+impl<T: PublisherMessage> ThrottledPublisher<T> {
+    /// Creates a publisher on `topic_name` sending at most `max_rate` messages per second.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `Err(String)` if `max_rate` is invalid (see [`RateLimiter::new`])
+    /// or the underlying eCAL publisher could not be created.
+    pub fn new(topic_name: &str, max_rate: f64, mode: ThrottleMode) -> Result<Self, String> {
+        let limiter = RateLimiter::new(max_rate)?;
+        Ok(Self::with_limiter(
+            TypedPublisher::new(topic_name)?,
+            limiter,
+            mode,
+        ))
+    }
+
+    /// Throttles an existing publisher, e.g. one created with [`TypedPublisher::builder`].
+    pub fn with_limiter(
+        publisher: TypedPublisher<T>,
+        limiter: RateLimiter,
+        mode: ThrottleMode,
+    ) -> Self {
+        Self {
+            publisher,
+            mode,
+            limiter: RefCell::new(limiter),
+            pending: RefCell::new(None),
+            throttled: Cell::new(0),
+        }
+    }
+
+    /// Sends `message` if the interval since the last send has elapsed.
+    ///
+    /// Returns `Ok(None)` if the message was held back: discarded in
+    /// [`ThrottleMode::Drop`], kept for a later send in [`ThrottleMode::Coalesce`]
+    /// (replacing a message kept before).
+    ///
+    /// # Errors
+    ///
+    /// See [`TypedPublisher::send`].
+    pub fn send(
+        &self,
+        message: &T,
+        timestamp: Timestamp,
+    ) -> Result<Option<SendOutcome>, PubSubError> {
+        if self.limiter.borrow_mut().try_acquire() {
+            // a newer message supersedes the one kept back
+            self.pending.borrow_mut().take();
+            return self.publisher.send(message, timestamp).map(Some);
+        }
+        self.throttled.set(self.throttled.get() + 1);
+        if self.mode == ThrottleMode::Coalesce {
+            let mut pending = self.pending.borrow_mut();
+            let mut buffer = pending.take().map(|(buffer, _)| buffer).unwrap_or_default();
+            buffer.clear();
+            message.write_bytes(&mut buffer);
+            *pending = Some((buffer, timestamp));
+        }
+        Ok(None)
+    }
+
+    /// Sends the message kept back in [`ThrottleMode::Coalesce`] if the
+    /// interval has elapsed; call it periodically when messages may stop
+    /// arriving for a while.
+    ///
+    /// Returns `Ok(None)` if nothing was sent.
+    ///
+    /// # Errors
+    ///
+    /// See [`TypedPublisher::send`].
+    pub fn poll(&self) -> Result<Option<SendOutcome>, PubSubError> {
+        if self.pending.borrow().is_none() || !self.limiter.borrow_mut().try_acquire() {
+            return Ok(None);
+        }
+        self.send_pending()
+    }
+
+    /// Sends the message kept back in [`ThrottleMode::Coalesce`] right away,
+    /// regardless of the interval.
+    ///
+    /// Returns `Ok(None)` if no message was kept back.
+    ///
+    /// # Errors
+    ///
+    /// See [`TypedPublisher::send`].
+    pub fn flush(&self) -> Result<Option<SendOutcome>, PubSubError> {
+        if self.pending.borrow().is_none() {
+            return Ok(None);
+        }
+        // the forced send starts a new interval
+        self.limiter.borrow_mut().last = Some(Instant::now());
+        self.send_pending()
+    }
+
+    fn send_pending(&self) -> Result<Option<SendOutcome>, PubSubError> {
+        match self.pending.borrow_mut().take() {
+            Some((buffer, timestamp)) => self.publisher.send_raw(&buffer, timestamp).map(Some),
+            None => Ok(None),
+        }
+    }
+
+    /// Returns `true` if a message is kept back for a later send.
+    pub fn has_pending(&self) -> bool {
+        self.pending.borrow().is_some()
+    }
+
+    /// Returns the number of messages held back so far, including coalesced ones.
+    pub fn throttled_count(&self) -> u64 {
+        self.throttled.get()
+    }
+
+    /// Returns the minimum time between two sends.
+    pub fn interval(&self) -> Duration {
+        self.limiter.borrow().interval()
+    }
+
+    /// Returns the throttle mode.
+    pub fn mode(&self) -> ThrottleMode {
+        self.mode
+    }
+
+    /// Returns the wrapped publisher, e.g. to wait for subscribers.
+    pub fn publisher(&self) -> &TypedPublisher<T> {
+        &self.publisher
+    }
+}
@@ -0,0 +1,211 @@
+//! Detection of data type changes on a subscribed topic.
+//!
+//! When a publisher restarts with an updated schema, eCAL delivers the new
+//! [`DataTypeInfo`] with every message, but nothing else signals the change.
+//! A [`TypeTracker`] remembers the data type last seen on a topic, raises a
+//! [`SubscriberEvent::TypeChanged`] event when it differs, and re-evaluates
+//! the subscriber's [`CompatibilityPolicy`] against the new type. Messages of
+//! an incompatible type are not decoded.
+//!
+//! Messages that are not delivered because of their type, rejected by the
+//! policy or failing to decode, are reported as [`TypeMismatch`]es, so a
+//! misconfigured topic does not just fall silent.
+
+use rustecal_core::types::{DataTypeInfo, EntityId};
+use std::fmt;
+use std::sync::{Arc, Mutex, RwLock};
+
+/// Custom compatibility check of `(expected, received)` data types.
+pub type CompatibilityCheck = Arc<dyn Fn(&DataTypeInfo, &DataTypeInfo) -> bool + Send + Sync>;
+
+/// Decides whether a publisher's data type can be decoded by the subscriber.
+#[derive(Clone, Default)]
+pub enum CompatibilityPolicy {
+    /// Accepts every data type (the default).
+    #[default]
+    AcceptAll,
+    /// Requires the encoding and type name to match the subscriber's type.
+    SameType,
+    /// Like [`CompatibilityPolicy::SameType`], additionally requiring equal
+    /// descriptors if both sides declare one.
+    Strict,
+    /// Only requires the encoding to match, e.g. for bindings of other
+    /// languages reporting different type names for the same proto.
+    EncodingOnly,
+    /// Custom check of `(expected, received)` data types.
+    Custom(CompatibilityCheck),
+}
+
+impl CompatibilityPolicy {
+    /// Returns `true` if messages of type `received` can be decoded as `expected`.
+    pub fn is_compatible(&self, expected: &DataTypeInfo, received: &DataTypeInfo) -> bool {
+        match self {
+            CompatibilityPolicy::AcceptAll => true,
+            CompatibilityPolicy::SameType => {
+                expected.encoding == received.encoding && expected.type_name == received.type_name
+            }
+            CompatibilityPolicy::Strict => {
+                CompatibilityPolicy::SameType.is_compatible(expected, received)
+                    && (expected.descriptor.is_empty()
+                        || received.descriptor.is_empty()
+                        || expected.descriptor == received.descriptor)
+            }
+            CompatibilityPolicy::EncodingOnly => expected.encoding == received.encoding,
+            CompatibilityPolicy::Custom(check) => check(expected, received),
+        }
+    }
+}
+
+impl fmt::Debug for CompatibilityPolicy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CompatibilityPolicy::AcceptAll => f.write_str("AcceptAll"),
+            CompatibilityPolicy::SameType => f.write_str("SameType"),
+            CompatibilityPolicy::Strict => f.write_str("Strict"),
+            CompatibilityPolicy::EncodingOnly => f.write_str("EncodingOnly"),
+            CompatibilityPolicy::Custom(_) => f.write_str("Custom(..)"),
+        }
+    }
+}
+
+/// An event raised by a subscriber.
+#[derive(Debug, Clone)]
+pub enum SubscriberEvent {
+    /// The data type received on the topic differs from the one seen before.
+    TypeChanged {
+        topic_name: String,
+        previous: DataTypeInfo,
+        current: DataTypeInfo,
+        /// Result of the compatibility policy for the new type.
+        compatible: bool,
+    },
+    /// A publisher connected to the subscriber.
+    PublisherConnected {
+        topic_name: String,
+        publisher: EntityId,
+        /// The data type declared by the publisher.
+        data_type: DataTypeInfo,
+    },
+    /// A publisher disconnected (or timed out).
+    PublisherDisconnected {
+        topic_name: String,
+        publisher: EntityId,
+    },
+    /// Samples of a publisher were dropped before they reached the subscriber.
+    Dropped {
+        topic_name: String,
+        publisher: EntityId,
+    },
+}
+
+/// Why a message was not delivered to a typed subscriber.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MismatchReason {
+    /// The compatibility policy rejected the publisher's data type.
+    Incompatible,
+    /// The payload could not be decoded as the subscriber's message type.
+    DecodeFailed,
+}
+
+/// A message discarded because of its data type.
+#[derive(Debug, Clone)]
+pub struct TypeMismatch {
+    pub topic_name: String,
+    /// Entity ID of the sending publisher.
+    pub publisher_id: u64,
+    /// The data type declared by the subscriber.
+    pub expected: DataTypeInfo,
+    /// The data type declared by the publisher.
+    pub received: DataTypeInfo,
+    pub reason: MismatchReason,
+}
+
+type EventCallback = Arc<dyn Fn(&SubscriberEvent) + Send + Sync + 'static>;
+
+#[derive(Default)]
+struct TrackerState {
+    current: Option<DataTypeInfo>,
+    compatible: bool,
+}
+
+/// Tracks the data type received on a topic.
+pub struct TypeTracker {
+    expected: DataTypeInfo,
+    policy: RwLock<CompatibilityPolicy>,
+    on_event: RwLock<Option<EventCallback>>,
+    state: Mutex<TrackerState>,
+}
+
+impl TypeTracker {
+    /// Creates a tracker for a subscriber expecting `expected`.
+    pub fn new(expected: DataTypeInfo) -> Self {
+        Self {
+            expected,
+            policy: RwLock::new(CompatibilityPolicy::default()),
+            on_event: RwLock::new(None),
+            state: Mutex::new(TrackerState::default()),
+        }
+    }
+
+    /// Replaces the compatibility policy and re-evaluates the current type.
+    pub fn set_policy(&self, policy: CompatibilityPolicy) {
+        let mut state = self.state.lock().unwrap();
+        if let Some(current) = &state.current {
+            state.compatible = policy.is_compatible(&self.expected, current);
+        }
+        *self.policy.write().unwrap() = policy;
+    }
+
+    /// Registers the callback receiving [`SubscriberEvent`]s, replacing the previous one.
+    pub fn set_event_callback<F>(&self, callback: F)
+    where
+        F: Fn(&SubscriberEvent) + Send + Sync + 'static,
+    {
+        *self.on_event.write().unwrap() = Some(Arc::new(callback));
+    }
+
+    /// Returns the data type the subscriber expects.
+    pub fn expected(&self) -> &DataTypeInfo {
+        &self.expected
+    }
+
+    /// Returns the data type seen last, if any message was received.
+    pub fn current(&self) -> Option<DataTypeInfo> {
+        self.state.lock().unwrap().current.clone()
+    }
+
+    /// Feeds the data type of a received message into the tracker.
+    ///
+    /// The first type seen is only checked against the policy; every later
+    /// change additionally raises [`SubscriberEvent::TypeChanged`].
+    ///
+    /// Returns `true` if the message should be decoded.
+    pub fn observe(&self, topic_name: &str, received: &DataTypeInfo) -> bool {
+        let mut state = self.state.lock().unwrap();
+        if state.current.as_ref() == Some(received) {
+            return state.compatible;
+        }
+
+        let compatible = self
+            .policy
+            .read()
+            .unwrap()
+            .is_compatible(&self.expected, received);
+        let previous = state.current.replace(received.clone());
+        state.compatible = compatible;
+        drop(state);
+
+        if let Some(previous) = previous {
+            let callback = self.on_event.read().unwrap().clone();
+            if let Some(callback) = callback {
+                callback(&SubscriberEvent::TypeChanged {
+                    topic_name: topic_name.to_string(),
+                    previous,
+                    current: received.clone(),
+                    compatible,
+                });
+            }
+        }
+        compatible
+    }
+}
@@ -1,10 +1,22 @@
 use crate::{
-    payload_writer::PayloadWriter,
-    publisher::{Publisher, Timestamp},
+    clock_source::ClockSource,
+    error::{PubSubError, SendOutcome},
+    events::PublisherEvent,
+    payload_writer::{InPlaceWriter, PayloadWriter},
+    publisher::{Publisher, SendOptions, Timestamp},
+    publisher_builder::PublisherBuilder,
+    statistics::PublisherStatistics,
     types::TopicId,
 };
 use rustecal_core::types::DataTypeInfo;
-use std::{marker::PhantomData, sync::Arc};
+use std::{cell::RefCell, marker::PhantomData, sync::Arc, time::Duration};
+
+/// Capacity up to which [`TypedPublisher`] keeps its send buffer between sends.
+///
+/// Small messages (control messages, poses, commands) are serialized into
+/// the retained buffer without any allocation; the buffer of a larger
+/// message is released after sending.
+pub const SEND_BUFFER_RETAIN_LIMIT: usize = 4096;
 
 /// A trait for message types that can be published via [`TypedPublisher`].
 ///
@@ -16,6 +28,26 @@ pub trait PublisherMessage {
 
     /// Serializes the message into a shared, reference-counted byte buffer.
     fn to_bytes(&self) -> Arc<[u8]>;
+
+    /// Appends the serialized message to `buffer`.
+    ///
+    /// [`TypedPublisher::send`] serializes through this method into a
+    /// reused buffer. The default implementation copies the result of
+    /// [`PublisherMessage::to_bytes`]; implementations should override it to
+    /// write directly and avoid the per-message `Arc` allocation.
+    fn write_bytes(&self, buffer: &mut Vec<u8>) {
+        buffer.extend_from_slice(&self.to_bytes());
+    }
+
+    /// Returns the serialized message if it is already available as a
+    /// contiguous buffer, e.g. a raw byte payload.
+    ///
+    /// [`TypedPublisher::send`] hands such a payload to eCAL directly,
+    /// skipping [`PublisherMessage::write_bytes`] and the send buffer. The
+    /// default implementation returns `None`.
+    fn as_payload(&self) -> Option<&[u8]> {
+        None
+    }
 }
 
 /// A type-safe, high-level wrapper over an eCAL publisher for messages of type `T`.
@@ -24,6 +56,7 @@ pub trait PublisherMessage {
 /// (implementing [`PublisherMessage`]) are published.
 pub struct TypedPublisher<T: PublisherMessage> {
     publisher: Publisher,
+    buffer: RefCell<Vec<u8>>,
     _phantom: PhantomData<T>,
 }
 
@@ -40,17 +73,30 @@ impl<T: PublisherMessage> TypedPublisher<T> {
     pub fn new(topic_name: &str) -> Result<Self, String> {
         let datatype = T::datatype();
         let publisher = Publisher::new(topic_name, datatype)?;
+        Ok(Self::from_publisher(publisher))
+    }
 
-        Ok(Self {
+    /// Starts a [`PublisherBuilder`] for a typed publisher on `topic_name`;
+    /// finish it with [`PublisherBuilder::create_typed`].
+    pub fn builder(topic_name: &str) -> PublisherBuilder {
+        PublisherBuilder::new(topic_name)
+    }
+
+    pub(crate) fn from_publisher(publisher: Publisher) -> Self {
+        Self {
             publisher,
+            buffer: RefCell::new(Vec::new()),
             _phantom: PhantomData,
-        })
+        }
     }
 
     /// Sends a message of type `T` to all connected subscribers.
     ///
-    /// Serializes the message via [`PublisherMessage::to_bytes()`], and
-    /// specifies when to timestamp (auto or custom).
+    /// Serializes the message via [`PublisherMessage::write_bytes()`] into a
+    /// buffer reused across sends (see [`SEND_BUFFER_RETAIN_LIMIT`]), and
+    /// specifies when to timestamp (auto or custom). Messages providing
+    /// [`PublisherMessage::as_payload()`] are sent from that slice without
+    /// copying.
     ///
     /// # Arguments
     ///
@@ -59,10 +105,75 @@ impl<T: PublisherMessage> TypedPublisher<T> {
     ///
     /// # Returns
     ///
-    /// `true` on success, `false` on failure.
-    pub fn send(&self, message: &T, timestamp: Timestamp) -> bool {
-        let bytes = message.to_bytes();
-        self.publisher.send(&bytes, timestamp)
+    /// The [`SendOutcome`] on success.
+    ///
+    /// # Errors
+    ///
+    /// See [`Publisher::send`].
+    pub fn send(&self, message: &T, timestamp: Timestamp) -> Result<SendOutcome, PubSubError> {
+        if let Some(payload) = message.as_payload() {
+            return self.publisher.send(payload, timestamp);
+        }
+        self.with_serialized(message, |data| self.publisher.send(data, timestamp))
+    }
+
+    /// Sends a message of type `T` with per-message [`SendOptions`].
+    ///
+    /// See [`Publisher::send_with`].
+    pub fn send_with(&self, message: &T, options: SendOptions) -> Result<SendOutcome, PubSubError> {
+        if let Some(payload) = message.as_payload() {
+            return self.publisher.send_with(payload, options);
+        }
+        self.with_serialized(message, |data| self.publisher.send_with(data, options))
+    }
+
+    /// Serializes `message` into the send buffer and passes it to `send`.
+    ///
+    /// A send nested in another one on the same publisher, e.g. from
+    /// `write_bytes` or a subscriber callback run by the send, finds the
+    /// buffer in use and serializes into a temporary buffer instead.
+    fn with_serialized<R>(&self, message: &T, send: impl FnOnce(&[u8]) -> R) -> R {
+        let Ok(mut buffer) = self.buffer.try_borrow_mut() else {
+            let mut buffer = Vec::new();
+            message.write_bytes(&mut buffer);
+            return send(&buffer);
+        };
+        buffer.clear();
+        message.write_bytes(&mut buffer);
+        let sent = send(&buffer);
+        if buffer.capacity() > SEND_BUFFER_RETAIN_LIMIT {
+            *buffer = Vec::new();
+        }
+        sent
+    }
+
+    /// Creates the publisher for local-only messages in advance (see
+    /// [`Publisher::prepare_local_only`]).
+    ///
+    /// # Errors
+    ///
+    /// See [`Publisher::prepare_local_only`].
+    pub fn prepare_local_only(&self) -> Result<(), String> {
+        self.publisher.prepare_local_only()
+    }
+
+    /// Sends already serialized bytes, e.g. relayed from another system.
+    ///
+    /// The bytes are sent as they are while the topic keeps advertising
+    /// `T`'s data type. The caller guarantees that `data` is a valid encoding
+    /// of `T`: subscribers decode it as such, and nothing is checked here.
+    ///
+    /// # Errors
+    ///
+    /// See [`Publisher::send`].
+    pub fn send_raw(&self, data: &[u8], timestamp: Timestamp) -> Result<SendOutcome, PubSubError> {
+        self.publisher.send(data, timestamp)
+    }
+
+    /// Sends a message of type `T` and returns `true` on success.
+    #[deprecated(note = "use `send`, which reports why a send failed")]
+    pub fn send_bool(&self, message: &T, timestamp: Timestamp) -> bool {
+        self.send(message, timestamp).is_ok()
     }
 
     /// Performs a zero-copy send using a [`PayloadWriter`].
@@ -77,20 +188,129 @@ impl<T: PublisherMessage> TypedPublisher<T> {
     ///
     /// # Returns
     ///
-    /// `true` on success, `false` on failure.
+    /// The [`SendOutcome`] on success.
+    ///
+    /// # Errors
+    ///
+    /// See [`Publisher::send`].
     pub fn send_payload_writer<W: PayloadWriter>(
         &self,
         writer: &mut W,
         timestamp: Timestamp,
-    ) -> bool {
+    ) -> Result<SendOutcome, PubSubError> {
         self.publisher.send_payload_writer(writer, timestamp)
     }
 
+    /// Serializes a message of type `T` directly into the shared memory buffer.
+    ///
+    /// `fill` receives a buffer of exactly `size` bytes and returns the number
+    /// of bytes it wrote, e.g. for a protobuf message:
+    ///
+    /// ```ignore
+    /// let size = message.encoded_len();
+    /// publisher.send_in_place(size, |mut buf| {
+    ///     message.encode(&mut buf).map_or(0, |_| size)
+    /// }, Timestamp::Auto)?;
+    /// ```
+    ///
+    /// The topic keeps advertising `T`'s data type, but no intermediate buffer
+    /// is filled, which saves a copy of large messages. On transports other
+    /// than shared memory eCAL provides the buffer itself.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`PubSubError::PayloadWrite`] if `fill` reports a length other
+    /// than `size`; otherwise see [`Publisher::send`].
+    pub fn send_in_place<F>(
+        &self,
+        size: usize,
+        fill: F,
+        timestamp: Timestamp,
+    ) -> Result<SendOutcome, PubSubError>
+    where
+        F: FnMut(&mut [u8]) -> usize,
+    {
+        let mut writer = InPlaceWriter::new(size, fill);
+        self.publisher.send_payload_writer(&mut writer, timestamp)
+    }
+
+    /// Performs a zero-copy send and returns `true` on success.
+    #[deprecated(note = "use `send_payload_writer`, which reports why a send failed")]
+    pub fn send_payload_writer_bool<W: PayloadWriter>(
+        &self,
+        writer: &mut W,
+        timestamp: Timestamp,
+    ) -> bool {
+        self.send_payload_writer(writer, timestamp).is_ok()
+    }
+
+    /// Overrides the clock source used for messages sent with [`Timestamp::Auto`].
+    ///
+    /// Passing `None` reverts to the process-wide default.
+    pub fn set_clock_source(&mut self, clock_source: Option<ClockSource>) {
+        self.publisher.set_clock_source(clock_source);
+    }
+
+    /// Returns the clock source currently in effect for this publisher.
+    pub fn clock_source(&self) -> ClockSource {
+        self.publisher.clock_source()
+    }
+
+    /// Returns how long a send waits for acknowledgements, if at all.
+    ///
+    /// See [`Publisher::acknowledge_timeout`].
+    pub fn acknowledge_timeout(&self) -> Option<Duration> {
+        self.publisher.acknowledge_timeout()
+    }
+
     /// Returns the number of currently connected subscribers.
     pub fn get_subscriber_count(&self) -> usize {
         self.publisher.get_subscriber_count()
     }
 
+    /// Registers a callback for [`PublisherEvent`]s, replacing a previous one.
+    ///
+    /// See [`Publisher::set_event_callback`].
+    pub fn set_event_callback<F>(&mut self, callback: F)
+    where
+        F: Fn(&PublisherEvent) + Send + Sync + 'static,
+    {
+        self.publisher.set_event_callback(callback);
+    }
+
+    /// Makes the publisher re-deliver its last message to subscribers that
+    /// connect later.
+    ///
+    /// See [`Publisher::set_latched`].
+    pub fn set_latched(&mut self, latched: bool) {
+        self.publisher.set_latched(latched);
+    }
+
+    /// Returns `true` if the publisher re-delivers its last message.
+    pub fn is_latched(&self) -> bool {
+        self.publisher.is_latched()
+    }
+
+    /// Returns the message and failure counters of this publisher.
+    pub fn get_statistics(&self) -> PublisherStatistics {
+        self.publisher.get_statistics()
+    }
+
+    /// Blocks until at least `count` subscribers are connected, or `timeout` has elapsed.
+    ///
+    /// Returns `true` if enough subscribers are connected.
+    pub fn wait_for_subscribers(&self, count: usize, timeout: Duration) -> bool {
+        self.publisher.wait_for_subscribers(count, timeout)
+    }
+
+    /// Async variant of [`TypedPublisher::wait_for_subscribers`].
+    #[cfg(feature = "tokio")]
+    pub async fn wait_for_subscribers_async(&self, count: usize, timeout: Duration) -> bool {
+        self.publisher
+            .wait_for_subscribers_async(count, timeout)
+            .await
+    }
+
     /// Returns the name of the topic this publisher is bound to.
     pub fn get_topic_name(&self) -> Option<String> {
         self.publisher.get_topic_name()
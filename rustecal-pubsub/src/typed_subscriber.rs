@@ -1,13 +1,44 @@
+use crate::decimation::{Decimation, Decimator};
+use crate::executor::Executor;
+use crate::introspection::hexdump;
+use crate::pressure::{Pressure, PressureMonitor};
+use crate::silence::{SilenceEvent, SilenceWatch};
+use crate::statistics::{ClockGapTracker, SampleLoss, SubscriberStatistics};
 use crate::subscriber::Subscriber;
+use crate::subscriber_builder::SubscriberBuilder;
+use crate::type_tracking::{
+    CompatibilityPolicy, MismatchReason, SubscriberEvent, TypeMismatch, TypeTracker,
+};
 use crate::types::TopicId;
-use rustecal_core::types::DataTypeInfo;
-use rustecal_sys::{eCAL_SDataTypeInformation, eCAL_SReceiveCallbackData, eCAL_STopicId};
+use crossbeam_channel::{Receiver, RecvTimeoutError};
+use rustecal_core::Ecal;
+use rustecal_core::callback_budget::CallbackBudget;
+use rustecal_core::error_hook::guard;
+use rustecal_core::time::Time;
+use rustecal_core::topic_remap::TopicRemap;
+use rustecal_core::types::{DataTypeInfo, EntityId};
+use rustecal_sys::{
+    eCAL_SDataTypeInformation, eCAL_SReceiveCallbackData, eCAL_STopicId,
+    eCAL_Subscriber_Configuration,
+};
 use std::{
     ffi::{CStr, c_void},
+    fmt,
     marker::PhantomData,
     slice,
+    sync::{Arc, Mutex, PoisonError, RwLock},
+    time::{Duration, Instant},
 };
 
+/// Capacity of the receive queue installed by the first `recv*` call.
+pub const DEFAULT_QUEUE_CAPACITY: usize = 1024;
+
+/// Most recent message kept by [`TypedSubscriber::keep_latest`].
+type LatestSlot<T> = Arc<RwLock<Option<Arc<Received<T>>>>>;
+
+/// Interval at which a blocking [`TypedSubscriber::recv`] checks for eCAL shutdown.
+const RECV_SHUTDOWN_POLL: Duration = Duration::from_millis(100);
+
 /// A trait for message types that can be deserialized by [`TypedSubscriber`].
 ///
 /// Implement this trait for any type `T` that needs to be reconstructed
@@ -29,6 +60,19 @@ pub trait SubscriberMessage<'a>: Sized {
     fn from_bytes(bytes: &'a [u8], data_type_info: &DataTypeInfo) -> Option<Self>;
 }
 
+/// A message type whose values can be kept after the receive callback returned.
+///
+/// A message decoded with [`SubscriberMessage::from_bytes`] may borrow the
+/// receive buffer, which eCAL reuses once the callback returns. Queues,
+/// channels, streams, executors and the cache of
+/// [`TypedSubscriber::keep_latest`] keep messages longer, so they detach
+/// every message with [`OwnedMessage::into_owned`] first.
+pub trait OwnedMessage: Send + 'static {
+    /// Copies any data borrowed from the receive buffer; messages owning
+    /// their data return themselves.
+    fn into_owned(self) -> Self;
+}
+
 /// A received message, with payload and metadata.
 pub struct Received<T> {
     /// The deserialized payload of type `T`.
@@ -43,27 +87,255 @@ pub struct Received<T> {
     pub timestamp: i64,
     /// The publisher's logical clock at send time.
     pub clock: i64,
+    /// The size of the serialized payload in bytes.
+    pub size: usize,
+    /// Entity ID of the sending publisher, resolvable with `Monitoring::process_of`.
+    pub publisher_id: u64,
+    /// Host name of the sending process.
+    pub host_name: String,
+    /// Process ID of the sending process.
+    pub process_id: i32,
+}
+
+impl<T> Received<T> {
+    /// Returns the time between sending and now in microseconds.
+    ///
+    /// Measured against eCAL time, so it is only meaningful on a single host
+    /// or on time-synchronized hosts.
+    pub fn latency_us(&self) -> i64 {
+        Time::microseconds() - self.timestamp
+    }
+
+    /// Converts the payload with `f`, keeping the metadata.
+    pub fn map<U>(self, f: impl FnOnce(T) -> U) -> Received<U> {
+        Received {
+            payload: f(self.payload),
+            topic_name: self.topic_name,
+            encoding: self.encoding,
+            type_name: self.type_name,
+            timestamp: self.timestamp,
+            clock: self.clock,
+            size: self.size,
+            publisher_id: self.publisher_id,
+            host_name: self.host_name,
+            process_id: self.process_id,
+        }
+    }
+
+    /// Detaches the payload from the receive buffer (see [`OwnedMessage`]).
+    pub fn into_owned(self) -> Self
+    where
+        T: OwnedMessage,
+    {
+        self.map(OwnedMessage::into_owned)
+    }
+
+    /// Returns the entity ID of the sending publisher.
+    ///
+    /// eCAL does not transmit the unit name with a message; look it up with
+    /// `Monitoring::process_of(msg.publisher_id)` if needed.
+    pub fn publisher(&self) -> EntityId {
+        EntityId {
+            entity_id: self.publisher_id,
+            process_id: self.process_id,
+            host_name: self.host_name.clone(),
+        }
+    }
+
+    /// Returns a one-line description of the message metadata:
+    /// topic, encoding and type, size, clock and latency.
+    pub fn summary(&self) -> String {
+        format!(
+            "topic '{}' [{}:{}] {} bytes, clock {}, latency {} µs",
+            self.topic_name,
+            self.encoding,
+            self.type_name,
+            self.size,
+            self.clock,
+            self.latency_us()
+        )
+    }
+}
+
+impl<T: AsRef<[u8]>> Received<T> {
+    /// Formats up to `limit` bytes of the payload as a hexdump.
+    ///
+    /// See [`hexdump`] for the format.
+    pub fn hexdump(&self, limit: usize) -> String {
+        hexdump(self.payload.as_ref(), limit)
+    }
+}
+
+impl<T> fmt::Display for Received<T> {
+    /// Displays the [`Received::summary`].
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.summary())
+    }
+}
+
+/// Restricts a subscriber to the messages of matching publishers.
+///
+/// A publisher matches if it is on one of the listed hosts, in one of the
+/// listed processes and one of the listed publisher entities; an empty list
+/// matches every publisher. This tells apart publishers of the same topic,
+/// e.g. a simulator and the real hardware on a multi-ECU test bench.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SourceFilter {
+    /// Host names of the publishing processes.
+    pub host_names: Vec<String>,
+    /// Process IDs of the publishing processes.
+    pub process_ids: Vec<i32>,
+    /// Entity IDs of the publishers (see `Received::publisher_id`).
+    pub publisher_ids: Vec<u64>,
+}
+
+impl SourceFilter {
+    /// Creates a filter matching every publisher.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Accepts publishers on `host_name`.
+    pub fn host(mut self, host_name: &str) -> Self {
+        self.host_names.push(host_name.to_string());
+        self
+    }
+
+    /// Accepts publishers in process `process_id`.
+    pub fn process_id(mut self, process_id: i32) -> Self {
+        self.process_ids.push(process_id);
+        self
+    }
+
+    /// Accepts the publisher with entity ID `publisher_id`.
+    pub fn publisher(mut self, publisher_id: u64) -> Self {
+        self.publisher_ids.push(publisher_id);
+        self
+    }
+
+    /// Returns `true` if the publisher `publisher_id` in process
+    /// `process_id` on `host_name` matches.
+    pub fn matches(&self, host_name: &str, process_id: i32, publisher_id: u64) -> bool {
+        (self.host_names.is_empty() || self.host_names.iter().any(|h| h == host_name))
+            && (self.process_ids.is_empty() || self.process_ids.contains(&process_id))
+            && (self.publisher_ids.is_empty() || self.publisher_ids.contains(&publisher_id))
+    }
 }
 
+/// Check on the raw payload and data type of a message, see
+/// [`TypedSubscriber::set_payload_filter`].
+pub type PayloadFilter = Arc<dyn Fn(&[u8], &DataTypeInfo) -> bool + Send + Sync + 'static>;
+
+/// Returns the number of queued messages and the capacity of the queue a
+/// queueing receive path feeds, for the pressure signal.
+pub(crate) type QueueGauge = Box<dyn Fn() -> (usize, usize) + Send + Sync + 'static>;
+
+type SampleLossCallback = Arc<dyn Fn(&SampleLoss) + Send + Sync + 'static>;
+type TypeMismatchCallback = Arc<dyn Fn(&TypeMismatch) + Send + Sync + 'static>;
+
+/// Optional per-subscriber hooks, shared between the subscriber and its callback wrapper.
+struct Hooks {
+    budget: RwLock<Option<CallbackBudget>>,
+    pressure: RwLock<Option<Arc<PressureMonitor>>>,
+    queue: RwLock<Option<QueueGauge>>,
+    types: TypeTracker,
+    statistics: ClockGapTracker,
+    source: RwLock<Option<SourceFilter>>,
+    payload_filter: RwLock<Option<PayloadFilter>>,
+    decimation: Mutex<Option<Decimator>>,
+    silence: RwLock<Option<SilenceWatch>>,
+    on_loss: RwLock<Option<SampleLossCallback>>,
+    on_type_mismatch: RwLock<Option<TypeMismatchCallback>>,
+}
+
+impl Hooks {
+    fn new(expected: DataTypeInfo) -> Self {
+        Self {
+            budget: RwLock::new(None),
+            pressure: RwLock::new(None),
+            queue: RwLock::new(None),
+            types: TypeTracker::new(expected),
+            statistics: ClockGapTracker::new(),
+            source: RwLock::new(None),
+            payload_filter: RwLock::new(None),
+            decimation: Mutex::new(None),
+            silence: RwLock::new(None),
+            on_loss: RwLock::new(None),
+            on_type_mismatch: RwLock::new(None),
+        }
+    }
+
+    /// Counts a message discarded because of its data type and reports it.
+    fn type_mismatch(
+        &self,
+        topic_name: &str,
+        publisher_id: u64,
+        received: &DataTypeInfo,
+        reason: MismatchReason,
+    ) {
+        self.statistics.record_type_mismatch();
+        if let Some(callback) = &*self.on_type_mismatch.read().unwrap() {
+            callback(&TypeMismatch {
+                topic_name: topic_name.to_string(),
+                publisher_id,
+                expected: self.types.expected().clone(),
+                received: received.clone(),
+                reason,
+            });
+        }
+    }
+}
+
+type ReceiveCallback<T> = Box<dyn FnMut(Received<T>) + Send + 'static>;
+
 /// Wrapper to store a boxed callback for `Received<T>`
 struct CallbackWrapper<'buf, T: SubscriberMessage<'buf>> {
-    callback: Box<dyn Fn(Received<T>) + Send + Sync + 'static>,
+    /// Locked per message: eCAL may deliver from several transport threads at once.
+    callback: Mutex<ReceiveCallback<T>>,
+    hooks: Arc<Hooks>,
     _phantom: PhantomData<&'buf T>,
 }
 
 impl<'buf, T: SubscriberMessage<'buf>> CallbackWrapper<'buf, T> {
-    fn new<F>(f: F) -> Self
+    fn new<F>(f: F, hooks: Arc<Hooks>) -> Self
     where
-        F: Fn(Received<T>) + Send + Sync + 'static,
+        F: FnMut(Received<T>) + Send + 'static,
     {
         Self {
-            callback: Box::new(f),
+            callback: Mutex::new(Box::new(f)),
+            hooks,
             _phantom: PhantomData,
         }
     }
 
-    fn call(&self, received: Received<T>) {
-        (self.callback)(received);
+    fn call(&self, received: Received<T>, publisher_id: u64) {
+        let clock = received.clock;
+        let timestamp = received.timestamp;
+        let start = Instant::now();
+
+        // a panic in an earlier invocation must not silence the subscriber
+        let mut callback = self.callback.lock().unwrap_or_else(PoisonError::into_inner);
+        // cloned so that the callback may replace the budget
+        let budget = self.hooks.budget.read().unwrap().clone();
+        match budget {
+            Some(budget) => {
+                let topic_name = received.topic_name.clone();
+                budget.measure(&topic_name, || callback(received));
+            }
+            None => callback(received),
+        }
+        drop(callback);
+
+        if let Some(monitor) = &*self.hooks.pressure.read().unwrap() {
+            let queue = self
+                .hooks
+                .queue
+                .read()
+                .unwrap()
+                .as_ref()
+                .map(|gauge| gauge());
+            monitor.record(publisher_id, clock, timestamp, start.elapsed(), queue);
+        }
     }
 }
 
@@ -74,6 +346,10 @@ impl<'buf, T: SubscriberMessage<'buf>> CallbackWrapper<'buf, T> {
 pub struct TypedSubscriber<'buf, T: SubscriberMessage<'buf>> {
     subscriber: Subscriber,
     user_data: *mut CallbackWrapper<'buf, T>,
+    hooks: Arc<Hooks>,
+    paused: bool,
+    queue: Option<Receiver<Received<T>>>,
+    latest: Option<LatestSlot<T>>,
     _phantom: PhantomData<&'buf T>,
 }
 
@@ -87,32 +363,97 @@ impl<'buf, T: SubscriberMessage<'buf>> TypedSubscriber<'buf, T> {
     /// # Returns
     ///
     /// `Ok(Self)` if the subscriber was created successfully, or `Err` with a description.
+    ///
+    /// The topic name is renamed by the process-wide [`TopicRemap`] first.
     pub fn new(topic_name: &str) -> Result<Self, String> {
+        Self::with_configuration(&TopicRemap::resolve(topic_name), None)
+    }
+
+    /// Creates a subscriber on `topic_name` as given, without applying the [`TopicRemap`].
+    pub(crate) fn with_configuration(
+        topic_name: &str,
+        configuration: Option<eCAL_Subscriber_Configuration>,
+    ) -> Result<Self, String> {
         let datatype = T::datatype();
 
         // dummy callback for construction
-        let boxed = Box::new(CallbackWrapper::new(|_| {}));
+        let hooks = Arc::new(Hooks::new(datatype.clone()));
+        let boxed = Box::new(CallbackWrapper::new(|_| {}, hooks.clone()));
         let user_data = Box::into_raw(boxed);
 
-        let subscriber = Subscriber::new(topic_name, datatype, trampoline::<'buf, T>)?;
+        let subscriber = Subscriber::with_configuration(
+            topic_name,
+            datatype,
+            trampoline::<'buf, T>,
+            configuration,
+        )?;
         Ok(Self {
             subscriber,
             user_data,
+            hooks,
+            paused: false,
+            queue: None,
+            latest: None,
             _phantom: PhantomData,
         })
     }
 
     /// Registers a user callback that receives a deserialized message with metadata.
+    ///
+    /// The callback may keep state (e.g. counters) in its captures: it is
+    /// never invoked concurrently with itself, even if eCAL delivers on
+    /// several transport threads.
+    ///
+    /// Replaces a receive queue set up by [`TypedSubscriber::set_queue_capacity`]
+    /// and the cache of [`TypedSubscriber::keep_latest`].
     pub fn set_callback<F>(&mut self, callback: F)
     where
-        F: Fn(Received<T>) + Send + Sync + 'static,
+        F: FnMut(Received<T>) + Send + 'static,
     {
-        // drop the old callback
+        self.queue = None;
+        self.latest = None;
+        *self.hooks.queue.write().unwrap() = None;
+        let boxed = Box::new(CallbackWrapper::new(callback, self.hooks.clone()));
+        let old = std::mem::replace(&mut self.user_data, Box::into_raw(boxed));
+        // a paused subscriber attaches the new callback on `resume`
+        if !self.paused {
+            self.attach_callback();
+        }
+        // eCAL swaps callbacks under the lock it holds while delivering, so
+        // the old callback is no longer running once the new one is attached
         unsafe {
-            let _ = Box::from_raw(self.user_data);
+            let _ = Box::from_raw(old);
+        }
+    }
+
+    /// Temporarily detaches the receive callback at the eCAL level.
+    ///
+    /// The subscriber stays registered and connected to its publishers, so
+    /// [`TypedSubscriber::resume`] continues without reconnect delays. Messages
+    /// arriving while paused are discarded.
+    pub fn pause(&mut self) {
+        if !self.paused {
+            self.paused = true;
+            unsafe {
+                rustecal_sys::eCAL_Subscriber_RemoveReceiveCallback(self.subscriber.raw_handle());
+            }
+        }
+    }
+
+    /// Re-attaches the receive callback after [`TypedSubscriber::pause`].
+    pub fn resume(&mut self) {
+        if self.paused {
+            self.paused = false;
+            self.attach_callback();
         }
-        let boxed = Box::new(CallbackWrapper::new(callback));
-        self.user_data = Box::into_raw(boxed);
+    }
+
+    /// Returns `true` if the subscriber is paused.
+    pub fn is_paused(&self) -> bool {
+        self.paused
+    }
+
+    fn attach_callback(&self) {
         unsafe {
             rustecal_sys::eCAL_Subscriber_SetReceiveCallback(
                 self.subscriber.raw_handle(),
@@ -122,14 +463,191 @@ impl<'buf, T: SubscriberMessage<'buf>> TypedSubscriber<'buf, T> {
         }
     }
 
+    /// Sets an execution time budget for the receive callback.
+    ///
+    /// Every callback invocation taking longer than the budget is reported
+    /// (see [`CallbackBudget`]). Passing `None` disables the measurement.
+    pub fn set_callback_budget(&mut self, budget: Option<CallbackBudget>) {
+        *self.hooks.budget.write().unwrap() = budget;
+    }
+
+    /// Attaches a [`PressureMonitor`] that tracks whether this subscriber keeps up.
+    ///
+    /// Passing `None` removes the monitor.
+    pub fn set_pressure_monitor(&mut self, monitor: Option<PressureMonitor>) {
+        *self.hooks.pressure.write().unwrap() = monitor.map(Arc::new);
+    }
+
+    /// Reports the fill level of the queue fed by the callback to the
+    /// pressure monitor; cleared by [`TypedSubscriber::set_callback`].
+    pub(crate) fn set_queue_gauge(&self, gauge: QueueGauge) {
+        *self.hooks.queue.write().unwrap() = Some(gauge);
+    }
+
+    /// Returns the current pressure signal, if a monitor is attached.
+    pub fn pressure(&self) -> Option<Pressure> {
+        self.hooks
+            .pressure
+            .read()
+            .unwrap()
+            .as_ref()
+            .map(|monitor| monitor.pressure())
+    }
+
+    /// Only delivers messages of publishers matching `filter`; messages of
+    /// other publishers are discarded before decoding and not counted in
+    /// the statistics. Passing `None` accepts all publishers.
+    pub fn set_source_filter(&mut self, filter: Option<SourceFilter>) {
+        *self.hooks.source.write().unwrap() = filter;
+    }
+
+    /// Discards messages for which `filter` returns `false` before they are decoded.
+    ///
+    /// The filter sees the serialized payload and its data type, so
+    /// high-rate topics can drop unwanted samples cheaply, e.g. by checking
+    /// a frame ID at a known byte offset. It runs on the receive thread for
+    /// every message and replaces a previous filter; discarded messages
+    /// still count as received in the statistics.
+    pub fn set_payload_filter<F>(&mut self, filter: F)
+    where
+        F: Fn(&[u8], &DataTypeInfo) -> bool + Send + Sync + 'static,
+    {
+        *self.hooks.payload_filter.write().unwrap() = Some(Arc::new(filter));
+    }
+
+    /// Removes the filter set with [`TypedSubscriber::set_payload_filter`].
+    pub fn clear_payload_filter(&mut self) {
+        *self.hooks.payload_filter.write().unwrap() = None;
+    }
+
+    /// Only delivers a subset of the messages, see [`crate::decimation`].
+    ///
+    /// The skipped messages are discarded before they are decoded. Passing
+    /// `None` delivers all messages.
+    pub fn set_decimation(&mut self, decimation: Option<Decimation>) {
+        *self.hooks.decimation.lock().unwrap() = decimation.map(Decimator::new);
+    }
+
+    /// Returns the decimation set with [`TypedSubscriber::set_decimation`].
+    pub fn decimation(&self) -> Option<Decimation> {
+        self.hooks
+            .decimation
+            .lock()
+            .unwrap()
+            .as_ref()
+            .map(Decimator::decimation)
+    }
+
+    /// Registers a callback invoked when no message arrives within `window`,
+    /// and again when messages resume, replacing a previous one.
+    ///
+    /// The window starts now. Messages discarded by the source filter do not
+    /// end a silence; see [`crate::silence`] for the threads the callback runs on.
+    pub fn on_timeout<F>(&mut self, window: Duration, callback: F)
+    where
+        F: Fn(&SilenceEvent) + Send + Sync + 'static,
+    {
+        let topic_name = self.get_topic_name().unwrap_or_default();
+        *self.hooks.silence.write().unwrap() =
+            Some(SilenceWatch::new(&topic_name, window, callback));
+    }
+
+    /// Removes the callback set with [`TypedSubscriber::on_timeout`].
+    pub fn clear_timeout(&mut self) {
+        *self.hooks.silence.write().unwrap() = None;
+    }
+
+    /// Registers a callback for gaps and reorderings in the send clocks of
+    /// the publishers, replacing a previous one.
+    ///
+    /// The callback runs on the receive thread before the message is
+    /// delivered; the same losses are counted in [`TypedSubscriber::get_statistics`].
+    pub fn set_sample_loss_callback<F>(&mut self, callback: F)
+    where
+        F: Fn(&SampleLoss) + Send + Sync + 'static,
+    {
+        *self.hooks.on_loss.write().unwrap() = Some(Arc::new(callback));
+    }
+
+    /// Registers a callback for messages discarded because of their data
+    /// type, replacing a previous one.
+    ///
+    /// The callback runs on the receive thread for every message the
+    /// [`CompatibilityPolicy`] rejects or [`SubscriberMessage::from_bytes`]
+    /// fails to decode; the same messages are counted as `type_mismatches`
+    /// in [`TypedSubscriber::get_statistics`].
+    pub fn set_type_mismatch_callback<F>(&mut self, callback: F)
+    where
+        F: Fn(&TypeMismatch) + Send + Sync + 'static,
+    {
+        *self.hooks.on_type_mismatch.write().unwrap() = Some(Arc::new(callback));
+    }
+
+    /// Returns the message and loss counters of this subscriber.
+    ///
+    /// Lost messages are detected as gaps in the publishers' send clocks;
+    /// messages discarded while paused count as lost.
+    pub fn get_statistics(&self) -> SubscriberStatistics {
+        self.hooks.statistics.statistics()
+    }
+
+    /// Sets the policy deciding which publisher data types are decoded.
+    ///
+    /// The policy is re-evaluated whenever the received data type changes;
+    /// messages of an incompatible type are dropped without decoding.
+    pub fn set_compatibility_policy(&mut self, policy: CompatibilityPolicy) {
+        self.hooks.types.set_policy(policy);
+    }
+
+    /// Registers a callback for subscriber events: data type changes
+    /// ([`SubscriberEvent::TypeChanged`]) and the connection events of
+    /// [`Subscriber::set_event_callback`].
+    pub fn set_event_callback<F>(&mut self, callback: F)
+    where
+        F: Fn(&SubscriberEvent) + Send + Sync + 'static,
+    {
+        let callback = Arc::new(callback);
+        let on_type_change = callback.clone();
+        self.hooks
+            .types
+            .set_event_callback(move |event| on_type_change(event));
+        self.subscriber
+            .set_event_callback(move |event| callback(event));
+    }
+
+    /// Returns the data type of the most recently received message.
+    ///
+    /// Unlike [`TypedSubscriber::get_data_type_information`], which returns
+    /// the subscriber's own declaration, this reflects what the publishers
+    /// actually send, including schema updates after a publisher restart.
+    pub fn get_publisher_data_type(&self) -> Option<DataTypeInfo> {
+        self.hooks.types.current()
+    }
+
     /// Returns the number of currently connected publishers.
     pub fn get_publisher_count(&self) -> usize {
         self.subscriber.get_publisher_count()
     }
 
+    /// Blocks until at least `count` publishers are connected, or `timeout` has elapsed.
+    ///
+    /// Returns `true` if enough publishers are connected.
+    pub fn wait_for_publishers(&self, count: usize, timeout: Duration) -> bool {
+        self.subscriber.wait_for_publishers(count, timeout)
+    }
+
+    /// Async variant of [`TypedSubscriber::wait_for_publishers`].
+    #[cfg(feature = "tokio")]
+    pub async fn wait_for_publishers_async(&self, count: usize, timeout: Duration) -> bool {
+        self.subscriber
+            .wait_for_publishers_async(count, timeout)
+            .await
+    }
+
     /// Returns the name of the subscribed topic.
     ///
-    /// This is the same topic name passed to [`TypedSubscriber::new`].
+    /// This is the topic name passed to [`TypedSubscriber::new`], renamed by the
+    /// process-wide [`TopicRemap`].
     pub fn get_topic_name(&self) -> Option<String> {
         self.subscriber.get_topic_name()
     }
@@ -150,6 +668,106 @@ impl<'buf, T: SubscriberMessage<'buf>> TypedSubscriber<'buf, T> {
     }
 }
 
+impl<T: SubscriberMessage<'static> + OwnedMessage> TypedSubscriber<'static, T> {
+    /// Starts a [`SubscriberBuilder`] for a subscriber on `topic_name`.
+    pub fn builder(topic_name: &str) -> SubscriberBuilder<T> {
+        SubscriberBuilder::new(topic_name)
+    }
+
+    /// Runs `callback` on the worker threads of `executor` instead of eCAL's
+    /// receive thread, replacing the callback.
+    ///
+    /// The receive thread only decodes the message and enqueues it, so heavy
+    /// processing does not delay other messages. The callback may run on
+    /// several workers at once; what happens while the executor's queue is
+    /// full is decided by its [`DropPolicy`](crate::channel::DropPolicy).
+    pub fn set_callback_on<F>(&mut self, executor: &Executor, callback: F)
+    where
+        F: Fn(Received<T>) + Send + Sync + 'static,
+    {
+        let submit = executor.submitter();
+        let callback = Arc::new(callback);
+        self.set_callback(move |msg| {
+            let msg = msg.into_owned();
+            let callback = callback.clone();
+            submit(Box::new(move || callback(msg)));
+        });
+    }
+
+    /// Receives messages into a queue holding up to `capacity` messages,
+    /// replacing the callback.
+    ///
+    /// The queue is read with [`TypedSubscriber::recv`],
+    /// [`TypedSubscriber::try_recv`] and [`TypedSubscriber::recv_timeout`].
+    /// While it is full, newly arriving messages are dropped. The `recv*`
+    /// methods set up a queue of [`DEFAULT_QUEUE_CAPACITY`] if none exists.
+    pub fn set_queue_capacity(&mut self, capacity: usize) {
+        let (sender, receiver) = crossbeam_channel::bounded(capacity);
+        self.set_callback(move |msg| {
+            let _ = sender.try_send(msg.into_owned());
+        });
+        let gauge = receiver.clone();
+        self.set_queue_gauge(Box::new(move || (gauge.len(), capacity)));
+        self.queue = Some(receiver);
+    }
+
+    /// Keeps only the most recent message for [`TypedSubscriber::get_latest`],
+    /// replacing the callback.
+    ///
+    /// Lets a loop sample a topic at its own rate, e.g. a 100 Hz controller
+    /// reading a 10 Hz sensor, without sharing state with a callback.
+    pub fn keep_latest(&mut self)
+    where
+        T: Sync,
+    {
+        let slot: LatestSlot<T> = Arc::new(RwLock::new(None));
+        let latest = slot.clone();
+        self.set_callback(move |msg| {
+            *latest.write().unwrap() = Some(Arc::new(msg.into_owned()));
+        });
+        self.latest = Some(slot);
+    }
+
+    /// Returns the most recent message, or `None` if none arrived yet or
+    /// [`TypedSubscriber::keep_latest`] was not called.
+    ///
+    /// The message stays cached; check [`Received::timestamp`] to detect stale data.
+    pub fn get_latest(&self) -> Option<Arc<Received<T>>> {
+        self.latest.as_ref()?.read().unwrap().clone()
+    }
+
+    fn queue(&mut self) -> &Receiver<Received<T>> {
+        if self.queue.is_none() {
+            self.set_queue_capacity(DEFAULT_QUEUE_CAPACITY);
+        }
+        self.queue.as_ref().unwrap()
+    }
+
+    /// Blocks until a message arrives.
+    ///
+    /// Returns `None` once eCAL is shutting down ([`Ecal::ok`] returns `false`).
+    pub fn recv(&mut self) -> Option<Received<T>> {
+        let queue = self.queue();
+        loop {
+            match queue.recv_timeout(RECV_SHUTDOWN_POLL) {
+                Ok(msg) => return Some(msg),
+                Err(RecvTimeoutError::Timeout) if Ecal::ok() => continue,
+                Err(_) => return None,
+            }
+        }
+    }
+
+    /// Returns the next queued message without blocking.
+    pub fn try_recv(&mut self) -> Option<Received<T>> {
+        self.queue().try_recv().ok()
+    }
+
+    /// Blocks until a message arrives or `timeout` has elapsed.
+    pub fn recv_timeout(&mut self, timeout: Duration) -> Option<Received<T>> {
+        self.queue().recv_timeout(timeout).ok()
+    }
+}
+
 impl<'buf, T: SubscriberMessage<'buf>> Drop for TypedSubscriber<'buf, T> {
     /// Cleans up and removes the callback, releasing any boxed closures.
     fn drop(&mut self) {
@@ -191,21 +809,87 @@ extern "C" fn trampoline<'buf, T: SubscriberMessage<'buf> + 'buf>(
             descriptor,
         };
 
-        // direct-borrow deserialization
-        if let Some(decoded) = T::from_bytes(payload, &dt_info) {
-            let cb_wrapper = &*(user_data as *const CallbackWrapper<'buf, T>);
-            let topic_name = CStr::from_ptr((*topic_id).topic_name)
+        let topic_name = CStr::from_ptr((*topic_id).topic_name)
+            .to_string_lossy()
+            .into_owned();
+        let publisher = &(*topic_id).topic_id;
+        let publisher_id = publisher.entity_id;
+        let cb_wrapper = &*(user_data as *const CallbackWrapper<'buf, T>);
+
+        let host_name = if publisher.host_name.is_null() {
+            String::new()
+        } else {
+            CStr::from_ptr(publisher.host_name)
                 .to_string_lossy()
-                .into_owned();
-            let received = Received {
-                payload: decoded,
-                topic_name,
-                encoding: encoding.clone(),
-                type_name: type_name.clone(),
-                timestamp: rd.send_timestamp,
-                clock: rd.send_clock,
-            };
-            cb_wrapper.call(received);
+                .into_owned()
+        };
+        if let Some(filter) = &*cb_wrapper.hooks.source.read().unwrap()
+            && !filter.matches(&host_name, publisher.process_id, publisher_id)
+        {
+            return;
+        }
+        if let Some(watch) = &*cb_wrapper.hooks.silence.read().unwrap() {
+            watch.record_sample();
         }
+
+        let loss = cb_wrapper
+            .hooks
+            .statistics
+            .observe(publisher_id, rd.send_clock, rd.buffer_size);
+
+        // deserialization and user callback must not unwind into eCAL
+        guard(
+            || format!("subscriber '{topic_name}'"),
+            || {
+                if let Some(loss) = &loss
+                    && let Some(on_loss) = &*cb_wrapper.hooks.on_loss.read().unwrap()
+                {
+                    on_loss(loss);
+                }
+                if !cb_wrapper.hooks.types.observe(&topic_name, &dt_info) {
+                    cb_wrapper.hooks.type_mismatch(
+                        &topic_name,
+                        publisher_id,
+                        &dt_info,
+                        MismatchReason::Incompatible,
+                    );
+                    return;
+                }
+                if let Some(filter) = &*cb_wrapper.hooks.payload_filter.read().unwrap()
+                    && !filter(payload, &dt_info)
+                {
+                    return;
+                }
+                if let Some(decimator) = &mut *cb_wrapper.hooks.decimation.lock().unwrap()
+                    && !decimator.admit()
+                {
+                    return;
+                }
+
+                // direct-borrow deserialization
+                if let Some(decoded) = T::from_bytes(payload, &dt_info) {
+                    let received = Received {
+                        payload: decoded,
+                        topic_name: topic_name.clone(),
+                        encoding,
+                        type_name,
+                        timestamp: rd.send_timestamp,
+                        clock: rd.send_clock,
+                        size: rd.buffer_size,
+                        publisher_id,
+                        host_name,
+                        process_id: publisher.process_id,
+                    };
+                    cb_wrapper.call(received, publisher_id);
+                } else {
+                    cb_wrapper.hooks.type_mismatch(
+                        &topic_name,
+                        publisher_id,
+                        &dt_info,
+                        MismatchReason::DecodeFailed,
+                    );
+                }
+            },
+        );
     }
 }
@@ -0,0 +1,30 @@
+use rustecal_pubsub::typed_subscriber::Received;
+
+#[derive(Debug, PartialEq)]
+enum Sensor {
+    Speed(u32),
+}
+
+#[test]
+fn map_converts_payload_and_keeps_metadata() {
+    let received = Received {
+        payload: 42u32,
+        topic_name: "speed".into(),
+        encoding: "raw".into(),
+        type_name: "u32".into(),
+        timestamp: 1_000,
+        clock: 7,
+        size: 4,
+        publisher_id: 99,
+        host_name: "cam-ecu".into(),
+        process_id: 1234,
+    };
+
+    let mapped = received.map(Sensor::Speed);
+    assert_eq!(mapped.payload, Sensor::Speed(42));
+    assert_eq!(mapped.topic_name, "speed");
+    assert_eq!((mapped.timestamp, mapped.clock, mapped.size), (1_000, 7, 4));
+    assert_eq!(mapped.publisher_id, 99);
+    assert_eq!(mapped.publisher().host_name, "cam-ecu");
+    assert_eq!(mapped.publisher().process_id, 1234);
+}
@@ -0,0 +1,79 @@
+//! These tests publish within the process and need an eCAL runtime:
+//! `cargo test -p rustecal-pubsub --test callback_budget -- --ignored`.
+
+use rustecal_core::callback_budget::CallbackBudget;
+use rustecal_core::types::DataTypeInfo;
+use rustecal_pubsub::publisher::Timestamp;
+use rustecal_pubsub::typed_publisher::PublisherMessage;
+use rustecal_pubsub::typed_subscriber::SubscriberMessage;
+use rustecal_pubsub::{Ecal, EcalComponents, TypedPublisher, TypedSubscriber};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::Duration;
+
+const TIMEOUT: Duration = Duration::from_secs(5);
+
+struct Ping;
+
+impl PublisherMessage for Ping {
+    fn datatype() -> DataTypeInfo {
+        DataTypeInfo {
+            encoding: "raw".into(),
+            type_name: "ping".into(),
+            descriptor: Vec::new(),
+        }
+    }
+
+    fn to_bytes(&self) -> Arc<[u8]> {
+        Arc::from([0u8])
+    }
+}
+
+impl SubscriberMessage<'_> for Ping {
+    fn datatype() -> DataTypeInfo {
+        <Ping as PublisherMessage>::datatype()
+    }
+
+    fn from_bytes(_bytes: &[u8], _data_type_info: &DataTypeInfo) -> Option<Self> {
+        Some(Ping)
+    }
+}
+
+/// The subscriber shared with its own callback.
+struct Shared(Mutex<TypedSubscriber<'static, Ping>>);
+
+// the subscriber is only used under the mutex
+unsafe impl Send for Shared {}
+unsafe impl Sync for Shared {}
+
+#[test]
+#[ignore = "needs an eCAL runtime"]
+fn callback_can_replace_its_budget() {
+    Ecal::initialize(
+        Some("callback_budget"),
+        EcalComponents::PUBLISHER | EcalComponents::SUBSCRIBER,
+        None,
+    )
+    .unwrap();
+    let budget = CallbackBudget::new(Duration::ZERO).log_level(None);
+    let subscriber = Arc::new(Shared(Mutex::new(
+        TypedSubscriber::<Ping>::new("callback_budget/topic").unwrap(),
+    )));
+    {
+        let shared = subscriber.clone();
+        let mut locked = subscriber.0.lock().unwrap();
+        locked.set_callback_budget(Some(budget.clone()));
+        locked.set_callback(move |_| {
+            shared.0.lock().unwrap().set_callback_budget(None);
+            thread::sleep(Duration::from_millis(1));
+        });
+    }
+    let publisher = TypedPublisher::<Ping>::new("callback_budget/topic").unwrap();
+    assert!(publisher.wait_for_subscribers(1, TIMEOUT));
+
+    publisher.send(&Ping, Timestamp::Auto).unwrap();
+    publisher.send(&Ping, Timestamp::Auto).unwrap();
+
+    // the first call ran with the budget and removed it for the second one
+    assert_eq!(budget.exceeded_count(), 1);
+}
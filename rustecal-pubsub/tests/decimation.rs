@@ -0,0 +1,35 @@
+use rustecal_pubsub::{Decimation, Decimator};
+use std::time::{Duration, Instant};
+
+#[test]
+fn every_nth_delivers_first_and_then_every_nth() {
+    let mut decimator = Decimator::new(Decimation::EveryNth(3));
+    let delivered: Vec<usize> = (0..10).filter(|_| decimator.admit()).collect();
+    assert_eq!(delivered, vec![0, 3, 6, 9]);
+}
+
+#[test]
+fn every_first_and_zero_deliver_everything() {
+    for n in [0, 1] {
+        let mut decimator = Decimator::new(Decimation::EveryNth(n));
+        assert!((0..5).all(|_| decimator.admit()));
+    }
+}
+
+#[test]
+fn max_rate_caps_deliveries() {
+    assert!(Decimation::max_rate(0.0).is_err());
+    let decimation = Decimation::max_rate(10.0).unwrap();
+    assert_eq!(
+        decimation,
+        Decimation::MinInterval(Duration::from_millis(100))
+    );
+
+    // 1 kHz for one second
+    let mut decimator = Decimator::new(decimation);
+    let start = Instant::now();
+    let delivered = (0..1_000)
+        .filter(|i| decimator.admit_at(start + Duration::from_millis(*i)))
+        .count();
+    assert_eq!(delivered, 10);
+}
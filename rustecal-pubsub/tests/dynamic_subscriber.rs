@@ -0,0 +1,18 @@
+use rustecal_pubsub::SampleMeta;
+
+#[test]
+fn sample_meta_identifies_the_publisher() {
+    let meta = SampleMeta {
+        topic_name: "camera/front".to_string(),
+        timestamp: 0,
+        clock: 7,
+        size: 1024,
+        publisher_id: 42,
+        host_name: "ecu-1".to_string(),
+        process_id: 1234,
+    };
+    let publisher = meta.publisher();
+    assert_eq!(publisher.entity_id, 42);
+    assert_eq!(publisher.process_id, 1234);
+    assert_eq!(publisher.host_name, "ecu-1");
+}
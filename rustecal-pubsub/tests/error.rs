@@ -0,0 +1,69 @@
+use rustecal_pubsub::error::{
+    PayloadError, PubSubError, SendOutcome, check_acknowledged_send, check_send,
+};
+use std::time::Duration;
+
+#[test]
+fn successful_send_reports_outcome() {
+    assert_eq!(
+        check_send(0, 128, 2),
+        Ok(SendOutcome {
+            bytes_written: 128,
+            subscriber_count: 2,
+            acknowledged: None,
+        })
+    );
+}
+
+#[test]
+fn acknowledged_sends_report_acknowledgements() {
+    let timeout = Duration::from_millis(50);
+    assert_eq!(
+        check_acknowledged_send(0, 64, 2, Some(timeout), Duration::ZERO).map(|o| o.acknowledged),
+        Ok(Some(2))
+    );
+    assert_eq!(
+        check_acknowledged_send(-1, 64, 2, Some(timeout), timeout),
+        Err(PubSubError::NotAcknowledged {
+            subscriber_count: 2,
+            timeout,
+        })
+    );
+    assert_eq!(
+        check_acknowledged_send(-1, 64, 0, Some(timeout), timeout),
+        Err(PubSubError::NoSubscribers)
+    );
+}
+
+#[test]
+fn failure_before_acknowledge_timeout_is_no_acknowledgement_error() {
+    let timeout = Duration::from_millis(50);
+    assert_eq!(
+        check_acknowledged_send(-1, 64, 2, Some(timeout), Duration::from_millis(1)),
+        Err(PubSubError::SendFailed {
+            size: 64,
+            subscriber_count: 2,
+        })
+    );
+}
+
+#[test]
+fn failed_send_tells_missing_subscribers_apart() {
+    assert_eq!(check_send(-1, 128, 0), Err(PubSubError::NoSubscribers));
+    assert_eq!(
+        check_send(-1, 128, 3),
+        Err(PubSubError::SendFailed {
+            size: 128,
+            subscriber_count: 3,
+        })
+    );
+}
+
+#[test]
+fn payload_errors_keep_their_reason() {
+    let err = PubSubError::PayloadWrite(PayloadError::buffer_too_small(1024, 512));
+    assert_eq!(
+        err.to_string(),
+        "payload writer failed: buffer of 512 bytes too small for 1024 bytes"
+    );
+}
@@ -0,0 +1,60 @@
+use rustecal_pubsub::{DropPolicy, Executor};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, mpsc};
+use std::thread;
+use std::time::Duration;
+
+#[test]
+fn jobs_run_on_worker_threads() {
+    let executor = Executor::new(2, 16, DropPolicy::Block);
+    assert_eq!(executor.threads(), 2);
+
+    let (sender, receiver) = mpsc::channel();
+    let caller = thread::current().id();
+    for i in 0..8 {
+        let sender = sender.clone();
+        executor.spawn(move || sender.send((i, thread::current().id())).unwrap());
+    }
+
+    let mut done: Vec<i32> = (0..8)
+        .map(|_| {
+            let (i, worker) = receiver.recv_timeout(Duration::from_secs(5)).unwrap();
+            assert_ne!(worker, caller);
+            i
+        })
+        .collect();
+    done.sort();
+    assert_eq!(done, (0..8).collect::<Vec<_>>());
+}
+
+#[test]
+fn panicking_job_keeps_worker_alive() {
+    let executor = Executor::new(1, 4, DropPolicy::Block);
+    executor.spawn(|| panic!("job failed"));
+
+    let (sender, receiver) = mpsc::channel();
+    executor.spawn(move || sender.send(()).unwrap());
+    assert!(receiver.recv_timeout(Duration::from_secs(5)).is_ok());
+}
+
+#[test]
+fn drop_discards_queued_jobs() {
+    let executor = Executor::new(1, 8, DropPolicy::Block);
+    let (started, wait_started) = mpsc::channel();
+    executor.spawn(move || {
+        started.send(()).unwrap();
+        thread::sleep(Duration::from_millis(100));
+    });
+    wait_started.recv_timeout(Duration::from_secs(5)).unwrap();
+
+    let ran = Arc::new(AtomicUsize::new(0));
+    for _ in 0..4 {
+        let ran = ran.clone();
+        executor.spawn(move || {
+            ran.fetch_add(1, Ordering::SeqCst);
+        });
+    }
+    // waits for the running job only
+    drop(executor);
+    assert_eq!(ran.load(Ordering::SeqCst), 0);
+}
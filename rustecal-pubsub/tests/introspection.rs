@@ -0,0 +1,18 @@
+use rustecal_pubsub::introspection::hexdump;
+
+#[test]
+fn hexdump_formats_lines_and_truncates() {
+    let bytes: Vec<u8> = (0x41..0x41 + 20).collect();
+
+    assert_eq!(
+        hexdump(&bytes, 20),
+        "00000000  41 42 43 44 45 46 47 48 49 4a 4b 4c 4d 4e 4f 50  ABCDEFGHIJKLMNOP\n\
+         00000010  51 52 53 54                                      QRST"
+    );
+    assert_eq!(
+        hexdump(&bytes, 4),
+        "00000000  41 42 43 44  ABCD\n... (16 more bytes)"
+    );
+    assert_eq!(hexdump(&[0x00, 0x7f], 16), "00000000  00 7f  ..");
+    assert_eq!(hexdump(&[], 16), "");
+}
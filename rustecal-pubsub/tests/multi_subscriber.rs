@@ -0,0 +1,19 @@
+#![cfg(feature = "monitoring")]
+
+use rustecal_pubsub::TopicPattern;
+
+#[test]
+fn glob_pattern_matches_across_levels() {
+    let pattern = TopicPattern::glob("camera/*/image");
+    assert!(pattern.matches("camera/front/image"));
+    assert!(pattern.matches("camera/rear/left/image"));
+    assert!(!pattern.matches("camera/front/depth"));
+}
+
+#[test]
+fn regex_pattern_matches() {
+    let pattern = TopicPattern::regex(r"^lidar_\d+$").unwrap();
+    assert!(pattern.matches("lidar_1"));
+    assert!(!pattern.matches("lidar_front"));
+    assert!(TopicPattern::regex("(").is_err());
+}
@@ -0,0 +1,55 @@
+use rustecal_pubsub::payload_writer::write_modified_ranges;
+use rustecal_pubsub::{PayloadError, PayloadWriter};
+use std::ops::Range;
+
+struct Sparse {
+    ranges: Option<Vec<Range<usize>>>,
+}
+
+impl PayloadWriter for Sparse {
+    fn write_full(&mut self, buf: &mut [u8]) -> Result<(), PayloadError> {
+        buf.fill(1);
+        Ok(())
+    }
+
+    fn modified_ranges(&self) -> Option<Vec<Range<usize>>> {
+        self.ranges.clone()
+    }
+
+    fn write_range(&mut self, _range: Range<usize>, chunk: &mut [u8]) -> Result<(), PayloadError> {
+        chunk.fill(9);
+        Ok(())
+    }
+
+    fn get_size(&self) -> usize {
+        8
+    }
+}
+
+#[test]
+fn only_modified_ranges_are_written() {
+    let mut writer = Sparse {
+        ranges: Some(vec![1..3, 6..7]),
+    };
+    let mut buf = [0u8; 8];
+    write_modified_ranges(&mut writer, &mut buf).unwrap();
+    assert_eq!(buf, [0, 9, 9, 0, 0, 0, 9, 0]);
+}
+
+#[test]
+fn without_ranges_write_modified_is_used() {
+    let mut writer = Sparse { ranges: None };
+    let mut buf = [0u8; 8];
+    write_modified_ranges(&mut writer, &mut buf).unwrap();
+    assert_eq!(buf, [1; 8]);
+}
+
+#[test]
+fn ranges_beyond_the_buffer_fail() {
+    let mut writer = Sparse {
+        ranges: Some(vec![0..2, 4..12]),
+    };
+    let mut buf = [0u8; 8];
+    assert!(write_modified_ranges(&mut writer, &mut buf).is_err());
+    assert_eq!(buf, [9, 9, 0, 0, 0, 0, 0, 0]);
+}
@@ -0,0 +1,67 @@
+use rustecal_pubsub::pressure::{PressureConfig, PressureMonitor, PressureState};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::time::Duration;
+
+#[test]
+fn limits_must_be_positive() {
+    let config = PressureConfig {
+        drop_ratio_limit: 0.0,
+        ..PressureConfig::default()
+    };
+    assert!(PressureMonitor::new(config).is_err());
+
+    let config = PressureConfig {
+        queue_fill_limit: f64::NAN,
+        ..PressureConfig::default()
+    };
+    assert!(PressureMonitor::new(config).is_err());
+
+    let config = PressureConfig {
+        callback_time_limit: Duration::ZERO,
+        ..PressureConfig::default()
+    };
+    assert!(PressureMonitor::new(config).is_err());
+
+    let config = PressureConfig {
+        lag_limit: Some(Duration::ZERO),
+        ..PressureConfig::default()
+    };
+    assert!(PressureMonitor::new(config).is_err());
+
+    assert!(PressureMonitor::new(PressureConfig::default()).is_ok());
+}
+
+#[test]
+fn filling_queue_raises_pressure() {
+    let high = Arc::new(AtomicU32::new(0));
+    let fired = high.clone();
+    let monitor = PressureMonitor::new(PressureConfig::default())
+        .unwrap()
+        .on_pressure_high(move |_| {
+            fired.fetch_add(1, Ordering::Relaxed);
+        });
+
+    // fast callbacks without drops, but the consumer does not drain the queue
+    for clock in 1..=8 {
+        monitor.record(1, clock, 0, Duration::ZERO, Some((clock as usize, 16)));
+    }
+
+    let pressure = monitor.pressure();
+    assert_eq!(pressure.queue_depth, 8);
+    assert_eq!(pressure.level, 1.0);
+    assert_eq!(pressure.state, PressureState::High);
+    assert_eq!(high.load(Ordering::Relaxed), 1);
+}
+
+#[test]
+fn subscriber_without_queue_has_no_queue_pressure() {
+    let monitor = PressureMonitor::new(PressureConfig::default()).unwrap();
+    monitor.record(1, 1, 0, Duration::ZERO, None);
+    monitor.record(1, 2, 0, Duration::ZERO, Some((0, 0)));
+
+    let pressure = monitor.pressure();
+    assert_eq!(pressure.queue_depth, 0);
+    assert_eq!(pressure.level, 0.0);
+    assert_eq!(pressure.state, PressureState::Normal);
+}
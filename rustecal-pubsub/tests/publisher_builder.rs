@@ -0,0 +1,12 @@
+use rustecal_pubsub::Publisher;
+
+#[test]
+fn create_without_data_type_fails() {
+    let err = Publisher::builder("builder/topic")
+        .shm_buffer_count(2)
+        .zero_copy(true)
+        .create()
+        .err()
+        .unwrap();
+    assert!(err.contains("builder/topic"));
+}
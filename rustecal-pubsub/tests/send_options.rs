@@ -0,0 +1,84 @@
+//! The tests sending messages publish within the process and need an eCAL
+//! runtime: `cargo test -p rustecal-pubsub --test send_options -- --ignored`.
+
+use rustecal_core::types::DataTypeInfo;
+use rustecal_pubsub::publisher::Timestamp;
+use rustecal_pubsub::typed_publisher::PublisherMessage;
+use rustecal_pubsub::typed_subscriber::SubscriberMessage;
+use rustecal_pubsub::{Ecal, EcalComponents, SendOptions, TypedPublisher, TypedSubscriber};
+use std::sync::{Arc, mpsc};
+use std::time::Duration;
+
+const TIMEOUT: Duration = Duration::from_secs(5);
+
+struct Sample(u8);
+
+impl PublisherMessage for Sample {
+    fn datatype() -> DataTypeInfo {
+        DataTypeInfo {
+            encoding: "raw".into(),
+            type_name: "sample".into(),
+            descriptor: Vec::new(),
+        }
+    }
+
+    fn to_bytes(&self) -> Arc<[u8]> {
+        Arc::from([self.0])
+    }
+}
+
+impl SubscriberMessage<'_> for Sample {
+    fn datatype() -> DataTypeInfo {
+        <Sample as PublisherMessage>::datatype()
+    }
+
+    fn from_bytes(bytes: &[u8], _data_type_info: &DataTypeInfo) -> Option<Self> {
+        bytes.first().copied().map(Sample)
+    }
+}
+
+#[test]
+fn default_options_timestamp_automatically() {
+    let options = SendOptions::new();
+    assert!(matches!(options.timestamp, Timestamp::Auto));
+    assert!(!options.local_only);
+}
+
+#[test]
+fn timestamp_is_set_per_message() {
+    let options = SendOptions::new().timestamp(Timestamp::Custom(42));
+    assert!(matches!(options.timestamp, Timestamp::Custom(42)));
+}
+
+#[test]
+fn local_only_is_set_per_message() {
+    let options = SendOptions::new().local_only();
+    assert!(options.local_only);
+}
+
+#[test]
+#[ignore = "needs an eCAL runtime"]
+fn local_only_messages_reach_local_subscribers() {
+    Ecal::initialize(
+        Some("send_options"),
+        EcalComponents::PUBLISHER | EcalComponents::SUBSCRIBER,
+        None,
+    )
+    .unwrap();
+    let (sender, receiver) = mpsc::channel();
+    let mut subscriber = TypedSubscriber::<Sample>::new("send_options/local").unwrap();
+    subscriber.set_callback(move |msg| sender.send(msg.payload.0).unwrap());
+    let publisher = TypedPublisher::<Sample>::new("send_options/local").unwrap();
+    publisher.prepare_local_only().unwrap();
+    // the subscriber sees the main and the local-only publisher
+    assert!(subscriber.wait_for_publishers(2, TIMEOUT));
+
+    publisher.send(&Sample(1), Timestamp::Auto).unwrap();
+    publisher
+        .send_with(&Sample(2), SendOptions::new().local_only())
+        .unwrap();
+
+    assert_eq!(receiver.recv_timeout(TIMEOUT).unwrap(), 1);
+    assert_eq!(receiver.recv_timeout(TIMEOUT).unwrap(), 2);
+    assert_eq!(publisher.get_statistics().messages_sent, 2);
+}
@@ -0,0 +1,68 @@
+use rustecal_pubsub::{SilenceEvent, SilenceWatch};
+use std::sync::mpsc;
+use std::time::Duration;
+
+const TIMEOUT: Duration = Duration::from_secs(5);
+
+fn watch(topic_name: &str, window: Duration) -> (SilenceWatch, mpsc::Receiver<SilenceEvent>) {
+    let (sender, receiver) = mpsc::channel();
+    let sender = std::sync::Mutex::new(sender);
+    let watch = SilenceWatch::new(topic_name, window, move |event| {
+        let _ = sender.lock().unwrap().send(event.clone());
+    });
+    (watch, receiver)
+}
+
+#[test]
+fn reports_timeout_and_resume() {
+    let window = Duration::from_millis(50);
+    let (watch, events) = watch("sensor", window);
+
+    assert_eq!(
+        events.recv_timeout(TIMEOUT).unwrap(),
+        SilenceEvent::TimedOut {
+            topic_name: "sensor".into(),
+            window,
+        }
+    );
+    assert!(watch.is_silent());
+
+    watch.record_sample();
+    assert!(!watch.is_silent());
+    match events.recv_timeout(TIMEOUT).unwrap() {
+        SilenceEvent::Resumed {
+            topic_name,
+            silent_for,
+        } => {
+            assert_eq!(topic_name, "sensor");
+            assert!(silent_for >= window);
+        }
+        event => panic!("unexpected {event:?}"),
+    }
+
+    // the window restarts with the resumed sample
+    assert!(matches!(
+        events.recv_timeout(TIMEOUT).unwrap(),
+        SilenceEvent::TimedOut { .. }
+    ));
+}
+
+#[test]
+fn samples_within_the_window_keep_the_topic_alive() {
+    let (watch, events) = watch("busy", Duration::from_millis(500));
+    for _ in 0..10 {
+        std::thread::sleep(Duration::from_millis(20));
+        watch.record_sample();
+    }
+    assert!(events.try_recv().is_err());
+    assert!(!watch.is_silent());
+}
+
+#[test]
+fn watches_share_the_monitor() {
+    let (_slow, slow_events) = watch("slow", Duration::from_millis(300));
+    let (_fast, fast_events) = watch("fast", Duration::from_millis(20));
+    assert!(fast_events.recv_timeout(TIMEOUT).is_ok());
+    assert!(slow_events.try_recv().is_err());
+    assert!(slow_events.recv_timeout(TIMEOUT).is_ok());
+}
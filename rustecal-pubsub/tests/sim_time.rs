@@ -0,0 +1,42 @@
+use rustecal_pubsub::sim_time::{SimTimeMessage, SimulationState};
+
+#[test]
+fn sim_time_message_round_trips() {
+    let message = SimTimeMessage {
+        simulation_state: SimulationState::Playing,
+        simulation_time_nsecs: 12_345_678_000,
+        real_time_factor: 0.5,
+        local_time_nsecs: 1_700_000_000_000_000_000,
+        host_name: "sim-host".into(),
+        process_id: -1,
+    };
+    assert_eq!(SimTimeMessage::decode(&message.encode()), Some(message));
+}
+
+#[test]
+fn sim_time_message_matches_protobuf_encoding() {
+    let message = SimTimeMessage {
+        simulation_state: SimulationState::Paused,
+        simulation_time_nsecs: 300,
+        host_name: "h".into(),
+        process_id: 7,
+        ..Default::default()
+    };
+    // field 1 = 2, field 2 = 300, field 3 = 0.0, field 4 = 0, field 5 = "h", field 6 = 7
+    let expected = [
+        0x08, 0x02, 0x10, 0xac, 0x02, 0x19, 0, 0, 0, 0, 0, 0, 0, 0, 0x20, 0x00, 0x2a, 0x01, b'h',
+        0x30, 0x07,
+    ];
+    assert_eq!(message.encode(), expected);
+}
+
+#[test]
+fn sim_time_decoding_skips_unknown_fields() {
+    // field 7 (varint) and field 8 (length-delimited) precede field 2 = 5
+    let bytes = [0x38, 0x01, 0x42, 0x02, 0xaa, 0xbb, 0x10, 0x05];
+    let message = SimTimeMessage::decode(&bytes).unwrap();
+    assert_eq!(message.simulation_time_nsecs, 5);
+    assert_eq!(message.simulation_state, SimulationState::Stopped);
+
+    assert!(SimTimeMessage::decode(&[0x2a, 0x05, b'a']).is_none());
+}
@@ -0,0 +1,76 @@
+use rustecal_pubsub::statistics::{ClockGapTracker, SampleLoss, SubscriberStatistics};
+
+#[test]
+fn consecutive_clocks_lose_nothing() {
+    let tracker = ClockGapTracker::new();
+    for clock in 1..=5 {
+        assert_eq!(tracker.record(7, clock, 10), 0);
+    }
+    assert_eq!(
+        tracker.statistics(),
+        SubscriberStatistics {
+            messages_received: 5,
+            bytes_received: 50,
+            messages_lost: 0,
+            messages_out_of_order: 0,
+            type_mismatches: 0,
+        }
+    );
+}
+
+#[test]
+fn clock_gaps_count_as_lost_per_publisher() {
+    let tracker = ClockGapTracker::new();
+    tracker.record(1, 10, 0);
+    tracker.record(2, 100, 0);
+    assert_eq!(tracker.record(1, 14, 0), 3);
+    assert_eq!(tracker.record(2, 101, 0), 0);
+    assert_eq!(tracker.statistics().messages_lost, 3);
+}
+
+#[test]
+fn clock_going_backwards_is_out_of_order() {
+    let tracker = ClockGapTracker::new();
+    tracker.record(1, 50, 0);
+    // a restarted publisher starts over
+    assert_eq!(tracker.record(1, 1, 0), 0);
+    assert_eq!(tracker.record(1, 2, 0), 0);
+    let statistics = tracker.statistics();
+    assert_eq!(statistics.messages_out_of_order, 1);
+    assert_eq!(statistics.messages_lost, 0);
+}
+
+#[test]
+fn observe_reports_gaps_and_reordering() {
+    let tracker = ClockGapTracker::new();
+    assert_eq!(tracker.observe(1, 10, 0), None);
+    assert_eq!(
+        tracker.observe(1, 13, 0),
+        Some(SampleLoss::Gap {
+            publisher_id: 1,
+            previous_clock: 10,
+            clock: 13,
+            lost: 2,
+        })
+    );
+    assert_eq!(
+        tracker.observe(1, 12, 0),
+        Some(SampleLoss::OutOfOrder {
+            publisher_id: 1,
+            previous_clock: 13,
+            clock: 12,
+        })
+    );
+    assert_eq!(tracker.statistics().messages_lost, 2);
+    assert_eq!(tracker.statistics().messages_out_of_order, 1);
+}
+
+#[test]
+fn type_mismatches_are_counted() {
+    let tracker = ClockGapTracker::new();
+    tracker.record(1, 1, 0);
+    tracker.record_type_mismatch();
+    let statistics = tracker.statistics();
+    assert_eq!(statistics.messages_received, 1);
+    assert_eq!(statistics.type_mismatches, 1);
+}
@@ -0,0 +1,36 @@
+use rustecal_pubsub::sim_time::SimTimeMessage;
+use rustecal_pubsub::{SourceFilter, TypedSubscriber};
+
+#[test]
+fn callback_and_queue_are_exclusive() {
+    let err = TypedSubscriber::<SimTimeMessage>::builder("builder/topic")
+        .callback(|_| {})
+        .queue_depth(16)
+        .build()
+        .err()
+        .unwrap();
+    assert!(err.contains("builder/topic"));
+}
+
+#[test]
+fn source_filter_matches_host_and_process() {
+    let any = SourceFilter::new();
+    assert!(any.matches("cam-ecu", 7, 1));
+
+    let host = SourceFilter::new().host("cam-ecu").host("sim");
+    assert!(host.matches("cam-ecu", 7, 1));
+    assert!(host.matches("sim", 7, 1));
+    assert!(!host.matches("lidar-ecu", 7, 1));
+
+    let process = host.process_id(7);
+    assert!(process.matches("cam-ecu", 7, 1));
+    assert!(!process.matches("cam-ecu", 8, 1));
+}
+
+#[test]
+fn source_filter_matches_publisher_ids() {
+    let filter = SourceFilter::new().publisher(10).publisher(11);
+    assert!(filter.matches("any", 1, 10));
+    assert!(filter.matches("any", 2, 11));
+    assert!(!filter.matches("any", 1, 12));
+}
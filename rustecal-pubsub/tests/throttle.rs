@@ -0,0 +1,40 @@
+use rustecal_pubsub::RateLimiter;
+use std::time::{Duration, Instant};
+
+#[test]
+fn rejects_invalid_rates() {
+    assert!(RateLimiter::new(0.0).is_err());
+    assert!(RateLimiter::new(-5.0).is_err());
+    assert!(RateLimiter::new(f64::NAN).is_err());
+    assert!(RateLimiter::new(f64::INFINITY).is_err());
+    assert_eq!(
+        RateLimiter::new(10.0).unwrap().interval(),
+        Duration::from_millis(100)
+    );
+}
+
+#[test]
+fn admits_one_event_per_interval() {
+    let mut limiter = RateLimiter::with_interval(Duration::from_millis(100));
+    let start = Instant::now();
+
+    assert!(limiter.try_acquire_at(start));
+    assert!(!limiter.try_acquire_at(start + Duration::from_millis(1)));
+    assert!(!limiter.try_acquire_at(start + Duration::from_millis(99)));
+    assert!(limiter.is_ready_at(start + Duration::from_millis(100)));
+    assert!(limiter.try_acquire_at(start + Duration::from_millis(150)));
+    // the interval restarts at the last admitted event
+    assert!(!limiter.try_acquire_at(start + Duration::from_millis(200)));
+    assert!(limiter.try_acquire_at(start + Duration::from_millis(250)));
+}
+
+#[test]
+fn caps_a_burst() {
+    let mut limiter = RateLimiter::new(1_000.0).unwrap();
+    let start = Instant::now();
+    // 10 kHz for one second
+    let admitted = (0..10_000)
+        .filter(|i| limiter.try_acquire_at(start + Duration::from_micros(i * 100)))
+        .count();
+    assert_eq!(admitted, 1_000);
+}
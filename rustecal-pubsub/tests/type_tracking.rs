@@ -0,0 +1,79 @@
+use rustecal_core::types::DataTypeInfo;
+use rustecal_pubsub::type_tracking::{CompatibilityPolicy, SubscriberEvent, TypeTracker};
+use std::sync::{Arc, Mutex};
+
+fn data_type(type_name: &str, descriptor: &[u8]) -> DataTypeInfo {
+    DataTypeInfo {
+        type_name: type_name.into(),
+        encoding: "proto".into(),
+        descriptor: descriptor.to_vec(),
+    }
+}
+
+#[test]
+fn type_change_raises_event_once() {
+    let tracker = TypeTracker::new(data_type("pb.Pose", &[]));
+    let events = Arc::new(Mutex::new(Vec::new()));
+    let sink = events.clone();
+    tracker.set_event_callback(move |event| sink.lock().unwrap().push(event.clone()));
+
+    // the first type seen is not a change
+    assert!(tracker.observe("pose", &data_type("pb.Pose", &[1])));
+    assert!(tracker.observe("pose", &data_type("pb.Pose", &[1])));
+    assert!(events.lock().unwrap().is_empty());
+
+    // schema update after a publisher restart
+    assert!(tracker.observe("pose", &data_type("pb.Pose", &[2])));
+    assert!(tracker.observe("pose", &data_type("pb.Pose", &[2])));
+
+    let events = events.lock().unwrap();
+    assert_eq!(events.len(), 1);
+    let SubscriberEvent::TypeChanged {
+        topic_name,
+        previous,
+        current,
+        compatible,
+    } = &events[0]
+    else {
+        panic!("expected a type change, got {:?}", events[0]);
+    };
+    assert_eq!(topic_name, "pose");
+    assert_eq!(previous.descriptor, vec![1]);
+    assert_eq!(current.descriptor, vec![2]);
+    assert!(compatible);
+    assert_eq!(tracker.current(), Some(data_type("pb.Pose", &[2])));
+}
+
+#[test]
+fn policy_is_rerun_on_change() {
+    let tracker = TypeTracker::new(data_type("pb.Pose", &[]));
+    tracker.set_policy(CompatibilityPolicy::SameType);
+
+    assert!(tracker.observe("pose", &data_type("pb.Pose", &[1])));
+    assert!(!tracker.observe("pose", &data_type("pb.PoseV2", &[1])));
+
+    // replacing the policy re-evaluates the current type
+    tracker.set_policy(CompatibilityPolicy::Custom(Arc::new(|_, received| {
+        received.type_name.starts_with("pb.Pose")
+    })));
+    assert!(tracker.observe("pose", &data_type("pb.PoseV2", &[1])));
+}
+
+#[test]
+fn strict_and_encoding_only_policies() {
+    let expected = data_type("pb.Pose", &[1]);
+    let renamed = data_type("Pose", &[1]);
+    let mut raw = data_type("pb.Pose", &[1]);
+    raw.encoding = "raw".into();
+
+    let strict = CompatibilityPolicy::Strict;
+    assert!(strict.is_compatible(&expected, &data_type("pb.Pose", &[1])));
+    assert!(!strict.is_compatible(&expected, &data_type("pb.Pose", &[2])));
+    // a side without descriptor is not compared
+    assert!(strict.is_compatible(&expected, &data_type("pb.Pose", &[])));
+    assert!(!strict.is_compatible(&expected, &renamed));
+
+    let encoding_only = CompatibilityPolicy::EncodingOnly;
+    assert!(encoding_only.is_compatible(&expected, &renamed));
+    assert!(!encoding_only.is_compatible(&expected, &raw));
+}
@@ -0,0 +1,127 @@
+//! These tests publish within the process and need an eCAL runtime:
+//! `cargo test -p rustecal-pubsub --test typed_publisher -- --ignored`.
+
+use rustecal_core::types::DataTypeInfo;
+use rustecal_pubsub::publisher::Timestamp;
+use rustecal_pubsub::typed_publisher::PublisherMessage;
+use rustecal_pubsub::typed_subscriber::SubscriberMessage;
+use rustecal_pubsub::{Ecal, EcalComponents, SendOptions, TypedPublisher, TypedSubscriber};
+use std::sync::{Arc, mpsc};
+use std::time::Duration;
+
+const TIMEOUT: Duration = Duration::from_secs(5);
+
+/// A message that is its own serialized payload and must never be copied.
+struct Frame<'a>(&'a [u8]);
+
+impl PublisherMessage for Frame<'_> {
+    fn datatype() -> DataTypeInfo {
+        DataTypeInfo {
+            encoding: "raw".into(),
+            type_name: "frame".into(),
+            descriptor: Vec::new(),
+        }
+    }
+
+    fn to_bytes(&self) -> Arc<[u8]> {
+        unreachable!("frame was copied")
+    }
+
+    fn write_bytes(&self, _buffer: &mut Vec<u8>) {
+        unreachable!("frame was copied into the send buffer")
+    }
+
+    fn as_payload(&self) -> Option<&[u8]> {
+        Some(self.0)
+    }
+}
+
+impl<'a> SubscriberMessage<'a> for Frame<'a> {
+    fn datatype() -> DataTypeInfo {
+        <Frame as PublisherMessage>::datatype()
+    }
+
+    fn from_bytes(bytes: &'a [u8], _data_type_info: &DataTypeInfo) -> Option<Self> {
+        Some(Frame(bytes))
+    }
+}
+
+/// A message that publishes a follow-up on its own publisher while it is serialized.
+struct Chained<'a> {
+    value: u8,
+    publisher: Option<&'a TypedPublisher<Chained<'a>>>,
+}
+
+impl PublisherMessage for Chained<'_> {
+    fn datatype() -> DataTypeInfo {
+        <Frame as PublisherMessage>::datatype()
+    }
+
+    fn to_bytes(&self) -> Arc<[u8]> {
+        Arc::from([self.value])
+    }
+
+    fn write_bytes(&self, buffer: &mut Vec<u8>) {
+        if let Some(publisher) = self.publisher {
+            let next = Chained {
+                value: self.value + 1,
+                publisher: None,
+            };
+            publisher.send(&next, Timestamp::Auto).unwrap();
+        }
+        buffer.push(self.value);
+    }
+}
+
+#[test]
+#[ignore = "needs an eCAL runtime"]
+fn payload_is_sent_without_send_buffer() {
+    Ecal::initialize(
+        Some("typed_publisher"),
+        EcalComponents::PUBLISHER | EcalComponents::SUBSCRIBER,
+        None,
+    )
+    .unwrap();
+    let (sender, receiver) = mpsc::channel();
+    let mut subscriber = TypedSubscriber::<Frame>::new("typed_publisher/frame").unwrap();
+    subscriber.set_callback(move |msg| sender.send(msg.payload.0.to_vec()).unwrap());
+    let publisher = TypedPublisher::<Frame>::new("typed_publisher/frame").unwrap();
+    assert!(publisher.wait_for_subscribers(1, TIMEOUT));
+    assert!(subscriber.wait_for_publishers(1, TIMEOUT));
+
+    publisher.send(&Frame(&[1; 64]), Timestamp::Auto).unwrap();
+    publisher
+        .send_with(&Frame(&[2; 64]), SendOptions::new())
+        .unwrap();
+
+    assert_eq!(receiver.recv_timeout(TIMEOUT).unwrap(), [1; 64]);
+    assert_eq!(receiver.recv_timeout(TIMEOUT).unwrap(), [2; 64]);
+    assert_eq!(publisher.get_statistics().bytes_sent, 128);
+}
+
+#[test]
+#[ignore = "needs an eCAL runtime"]
+fn nested_send_uses_temporary_buffer() {
+    Ecal::initialize(
+        Some("typed_publisher"),
+        EcalComponents::PUBLISHER | EcalComponents::SUBSCRIBER,
+        None,
+    )
+    .unwrap();
+    let (sender, receiver) = mpsc::channel();
+    let mut subscriber = TypedSubscriber::<Frame>::new("typed_publisher/chained").unwrap();
+    subscriber.set_callback(move |msg| sender.send(msg.payload.0.to_vec()).unwrap());
+    let publisher = TypedPublisher::<Chained>::new("typed_publisher/chained").unwrap();
+    assert!(publisher.wait_for_subscribers(1, TIMEOUT));
+    assert!(subscriber.wait_for_publishers(1, TIMEOUT));
+
+    let first = Chained {
+        value: 1,
+        publisher: Some(&publisher),
+    };
+    publisher.send(&first, Timestamp::Auto).unwrap();
+
+    // the nested message is sent while the first one is being serialized
+    assert_eq!(receiver.recv_timeout(TIMEOUT).unwrap(), [2]);
+    assert_eq!(receiver.recv_timeout(TIMEOUT).unwrap(), [1]);
+}
@@ -4,9 +4,7 @@
 use rustecal::pubsub::typed_subscriber::Received;
 use rustecal::{Ecal, EcalComponents, TypedSubscriber};
 use rustecal_types_bytes::BytesMessage;
-use std::thread::sleep;
 use std::{
-    sync::{Arc, Mutex, atomic::Ordering},
     thread,
     time::{Duration, Instant},
 };
@@ -24,68 +22,53 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     let mut subscriber: TypedSubscriber<'_, BytesMessage<'_>> =
         TypedSubscriber::new("Performance")?;
 
-    // shared counters & timer
-    let msgs = Arc::new(std::sync::atomic::AtomicU64::new(0));
-    let bytes = Arc::new(std::sync::atomic::AtomicU64::new(0));
-    let start = Arc::new(Mutex::new(Instant::now()));
+    // counters & timer, owned by the callback
+    let mut msgs: u64 = 0;
+    let mut bytes: u64 = 0;
+    let mut start = Instant::now();
 
     // register the receive-callback
-    {
-        let msgs = Arc::clone(&msgs);
-        let bytes = Arc::clone(&bytes);
-        let start = Arc::clone(&start);
+    subscriber.set_callback(move |msg: Received<BytesMessage>| {
+        let buffer: &[u8] = msg.payload.data();
+        if buffer.is_empty() {
+            // nothing to do
+            return;
+        }
 
-        subscriber.set_callback(move |msg: Received<BytesMessage>| {
-            let buffer: &[u8] = msg.payload.data.as_ref();
-            if buffer.is_empty() {
-                // nothing to do
-                return;
-            }
+        // update counters
+        msgs += 1;
+        bytes += buffer.len() as u64;
 
-            // update counters
-            msgs.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
-            bytes.fetch_add(buffer.len() as u64, std::sync::atomic::Ordering::Relaxed);
+        // compute & maybe print
+        let elapsed = start.elapsed();
+        if elapsed >= Duration::from_secs(1) {
+            let secs = elapsed.as_secs_f64();
+            let kbyte_s = (bytes as f64 / 1024.0) / secs;
+            let mbyte_s = kbyte_s / 1024.0;
+            let gbyte_s = mbyte_s / 1024.0;
+            let msg_s = (msgs as f64) / secs;
+            let latency_us = (secs * 1e6) / (msgs as f64);
 
-            // lock the timer, compute & maybe print
-            let mut start_lock = start.lock().unwrap();
-            let elapsed = start_lock.elapsed();
-            if elapsed >= Duration::from_secs(1) {
-                let m = msgs.swap(0, Ordering::Relaxed);
-                let b = bytes.swap(0, Ordering::Relaxed);
+            println!("Topic name          : {}", msg.topic_name);
+            println!("Message [0 - 15]    : {}", msg.hexdump(16));
+            println!("Payload size (kB)   : {:.0}", buffer.len() / 1024);
+            println!("Throughput   (kB/s) : {kbyte_s:.0}");
+            println!("Throughput   (MB/s) : {mbyte_s:.2}");
+            println!("Throughput   (GB/s) : {gbyte_s:.2}");
+            println!("Messages     (1/s)  : {msg_s:.0}");
+            println!("Latency      (µs)   : {latency_us:.2}");
+            println!();
 
-                let secs = elapsed.as_secs_f64();
-                let kbyte_s = (b as f64 / 1024.0) / secs;
-                let mbyte_s = kbyte_s / 1024.0;
-                let gbyte_s = mbyte_s / 1024.0;
-                let msg_s = (m as f64) / secs;
-                let latency_us = (secs * 1e6) / (m as f64);
-
-                println!("Topic name          : {}", msg.topic_name);
-                let slice = &buffer[..16];
-                let spaced = slice
-                    .iter()
-                    .map(|&b| (b as char).to_string())
-                    .collect::<Vec<String>>()
-                    .join(" ");
-                println!("Message [0 - 15]    : {spaced:?}");
-                println!("Payload size (kB)   : {:.0}", buffer.len() / 1024);
-                println!("Throughput   (kB/s) : {kbyte_s:.0}");
-                println!("Throughput   (MB/s) : {mbyte_s:.2}");
-                println!("Throughput   (GB/s) : {gbyte_s:.2}");
-                println!("Messages     (1/s)  : {msg_s:.0}");
-                println!("Latency      (µs)   : {latency_us:.2}");
-                println!();
-
-                // reset the timer
-                *start_lock = Instant::now();
-            }
-        });
-    }
+            // reset counters & timer
+            msgs = 0;
+            bytes = 0;
+            start = Instant::now();
+        }
+    });
 
     // wait for publisher
-    while subscriber.get_publisher_count() == 0 {
+    while Ecal::ok() && !subscriber.wait_for_publishers(1, Duration::from_secs(1)) {
         println!("Waiting for publisher …");
-        sleep(Duration::from_secs(1));
     }
     println!();
 
@@ -5,7 +5,7 @@
 //! - `write_modified` updates a single byte per invocation to simulate changing data.
 //! - `get_size` reports the exact buffer size needed.
 
-use rustecal_pubsub::PayloadWriter;
+use rustecal_pubsub::{PayloadError, PayloadWriter};
 
 /// A direct-write binary payload that writes into shared memory without copying.
 pub struct BinaryPayload {
@@ -34,25 +34,25 @@ impl PayloadWriter for BinaryPayload {
     /// Fill the entire buffer with the constant byte `0x2A`.
     ///
     /// This is called by eCAL when the shared-memory region is first allocated
-    /// or its size changes. Returning `false` indicates an allocation error.
-    fn write_full(&mut self, buf: &mut [u8]) -> bool {
+    /// or its size changes. An error indicates an allocation problem.
+    fn write_full(&mut self, buf: &mut [u8]) -> Result<(), PayloadError> {
         if buf.len() < self.size {
             // Buffer too small: cannot satisfy payload size
-            return false;
+            return Err(PayloadError::buffer_too_small(self.size, buf.len()));
         }
         // Fast-path fill: every byte set to 42 (0x2A)
         buf[..self.size].fill(42);
-        true
+        Ok(())
     }
 
     /// Modify a single byte in the existing buffer to simulate an update.
     ///
     /// This is called after the first full write when zero-copy mode is enabled.
     /// It only changes one byte per call for maximum performance.
-    fn write_modified(&mut self, buf: &mut [u8]) -> bool {
+    fn write_modified(&mut self, buf: &mut [u8]) -> Result<(), PayloadError> {
         if buf.len() < self.size {
             // Buffer too small: cannot satisfy payload size
-            return false;
+            return Err(PayloadError::buffer_too_small(self.size, buf.len()));
         }
         // Compute an index that cycles through the first 1024 bytes, then wraps
         let idx = ((self.clock as usize) % 1024) % self.size;
@@ -60,7 +60,7 @@ impl PayloadWriter for BinaryPayload {
         buf[idx] = b'0' + (self.clock % 10) as u8;
         // Advance the clock for next iteration
         self.clock = self.clock.wrapping_add(1);
-        true
+        Ok(())
     }
 
     /// Report the exact payload size that this writer will produce.
@@ -5,7 +5,6 @@
 
 use rustecal::{Configuration, Ecal, EcalComponents, TypedPublisher};
 use rustecal_types_bytes::BytesMessage;
-use std::thread::sleep;
 use std::{
     env,
     time::{Duration, Instant},
@@ -62,16 +61,15 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     let mut last_log = Instant::now();
 
     // wait for subscriber
-    while publisher.get_subscriber_count() == 0 {
+    while Ecal::ok() && !publisher.wait_for_subscribers(1, Duration::from_secs(1)) {
         println!("Waiting for receiver …");
-        sleep(Duration::from_secs(1));
     }
     println!();
 
     // send loop
     while Ecal::ok() {
         // zero-copy send via PayloadWriter
-        publisher.send_payload_writer(&mut payload, Timestamp::Auto);
+        let _ = publisher.send_payload_writer(&mut payload, Timestamp::Auto);
 
         msgs_sent += 1;
         bytes_sent += payload_size as u64;
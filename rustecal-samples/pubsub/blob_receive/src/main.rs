@@ -10,7 +10,7 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     let mut subscriber = TypedSubscriber::<BytesMessage>::new("blob")?;
 
     subscriber.set_callback(|msg: Received<BytesMessage>| {
-        let buffer = &msg.payload.data;
+        let buffer = msg.payload.data();
         if buffer.is_empty() {
             return;
         }
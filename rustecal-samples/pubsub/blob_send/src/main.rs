@@ -15,10 +15,8 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
         let buffer = vec![counter; 1024];
         counter = counter.wrapping_add(1);
 
-        let wrapped = BytesMessage {
-            data: buffer.into(),
-        };
-        publisher.send(&wrapped, Timestamp::Auto);
+        let wrapped = BytesMessage::borrowed(&buffer);
+        let _ = publisher.send(&wrapped, Timestamp::Auto);
 
         println!("Sent buffer filled with {counter}");
 
@@ -15,9 +15,13 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
         let msg = format!("HELLO WORLD FROM RUST ({count})");
 
         let wrapped = StringMessage { data: msg.into() };
-        publisher.send(&wrapped, Timestamp::Auto);
-
-        println!("Sent: {}", wrapped.data);
+        match publisher.send(&wrapped, Timestamp::Auto) {
+            Ok(outcome) => println!(
+                "Sent: {} (to {} subscribers)",
+                wrapped.data, outcome.subscriber_count
+            ),
+            Err(err) => println!("Not sent: {} ({err})", wrapped.data),
+        }
 
         std::thread::sleep(std::time::Duration::from_millis(500));
     }
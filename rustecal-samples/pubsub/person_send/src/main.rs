@@ -67,7 +67,7 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
         let wrapped = ProtobufMessage {
             data: person.into(),
         };
-        publisher.send(&wrapped, Timestamp::Auto);
+        let _ = publisher.send(&wrapped, Timestamp::Auto);
 
         std::thread::sleep(std::time::Duration::from_millis(500));
     }
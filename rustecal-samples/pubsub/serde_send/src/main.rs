@@ -27,7 +27,7 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
         let wrapped = JsonMessage::new(payload.clone());
 
         // send over eCAL pub/sub
-        publisher.send(&wrapped, Timestamp::Auto);
+        let _ = publisher.send(&wrapped, Timestamp::Auto);
         println!(
             "Sent: message = {}, count = {}",
             wrapped.data.message, wrapped.data.count
@@ -1,5 +1,5 @@
-use rustecal::{CallState, ServiceClient, ServiceRequest};
 use rustecal::{Ecal, EcalComponents};
+use rustecal::{ServiceClient, ServiceError, ServiceRequest};
 use std::thread;
 use std::time::Duration;
 
@@ -11,9 +11,8 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     let client = ServiceClient::new("mirror")?;
 
     // wait until connected
-    while client.get_client_instances().is_empty() {
+    while Ecal::ok() && !client.wait_for_server(Duration::from_secs(1)) {
         println!("Waiting for a service ..");
-        thread::sleep(Duration::from_secs(1));
     }
 
     let methods = ["echo", "reverse"];
@@ -34,25 +33,22 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
             println!("Method '{method_name}' called with message: stressed");
 
             match response {
-                Some(res) => match CallState::from(res.success as i32) {
-                    CallState::Executed => {
-                        let text = String::from_utf8_lossy(&res.payload);
-                        println!(
-                            "Received response: {} from service id {:?}",
-                            text, res.server_id.service_id.entity_id
-                        );
-                    }
-                    CallState::Failed => {
-                        println!(
-                            "Received error: {} from service id {:?}",
-                            res.error_msg.unwrap_or_else(|| "Unknown".into()),
-                            res.server_id.service_id.entity_id
-                        );
-                    }
-                    _ => {}
-                },
-                None => {
-                    println!("Method blocking call failed ..");
+                Ok(res) => {
+                    let text = String::from_utf8_lossy(&res.payload);
+                    println!(
+                        "Received response: {} from service id {:?}",
+                        text, res.server_id.service_id.entity_id
+                    );
+                }
+                Err(ServiceError::Failed(message)) => {
+                    println!(
+                        "Received error: {} from service id {:?}",
+                        message,
+                        instance.server_id().service_id.entity_id
+                    );
+                }
+                Err(err) => {
+                    println!("Method blocking call failed: {err}");
                 }
             }
         }
@@ -24,37 +24,25 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
             payload: b"stressed".to_vec(),
         };
 
-        let instances = client.get_client_instances();
+        let results = client.call_each(method, request, Some(1000));
 
-        if instances.is_empty() {
+        if results.is_empty() {
             println!("No service instances available.");
-        } else {
-            for instance in instances {
-                let response = instance.call(method, request.clone(), Some(1000));
-
-                println!();
-                println!("Method '{method}' called with message: stressed");
-
-                match response {
-                    Some(res) => {
-                        if res.success {
-                            println!(
-                                "Received response: {} from service id {:?}",
-                                String::from_utf8_lossy(&res.payload),
-                                res.server_id.service_id.entity_id
-                            );
-                        } else {
-                            println!(
-                                "Received error: {} from service id {:?}",
-                                res.error_msg.unwrap_or_else(|| "Unknown".into()),
-                                res.server_id.service_id.entity_id
-                            );
-                        }
-                    }
-                    None => {
-                        println!("Call failed or timed out.");
-                    }
-                }
+        }
+        for (server_id, result) in results {
+            println!();
+            println!("Method '{method}' called with message: stressed");
+
+            match result {
+                Ok(res) => println!(
+                    "Received response: {} from service id {:?}",
+                    String::from_utf8_lossy(&res.payload),
+                    server_id.service_id.entity_id
+                ),
+                Err(err) => println!(
+                    "Received error: {} from service id {:?}",
+                    err, server_id.service_id.entity_id
+                ),
             }
         }
 
@@ -0,0 +1,304 @@
+//! # rustecal-service-build
+//!
+//! Generates typed eCAL services from Protobuf `service` definitions at build time.
+//!
+//! ## Functionality
+//! - A trait per service, implemented by the application.
+//! - A server hosting a trait implementation over `rustecal-service`.
+//! - A client stub with one typed method per RPC.
+//!
+//! For a definition like
+//!
+//! ```proto
+//! service MathService {
+//!   rpc Add (AddRequest) returns (AddResponse);
+//! }
+//! ```
+//!
+//! the build script
+//!
+//! ```ignore
+//! fn main() {
+//!     rustecal_service_build::compile_protos(&["proto/math.proto"], &["proto"]).unwrap();
+//! }
+//! ```
+//!
+//! generates, next to the prost messages:
+//!
+//! ```ignore
+//! pub trait MathService: Send + Sync + 'static {
+//!     fn add(&self, request: AddRequest) -> Result<AddResponse, ServiceError>;
+//! }
+//! pub struct MathServiceServer { .. } // MathServiceServer::new(impl MathService)
+//! pub struct MathServiceClient { .. } // MathServiceClient::new()?.add(&request, timeout_ms)
+//! ```
+//!
+//! The service is named after its full Protobuf name (e.g. `math.MathService`)
+//! and its methods after the RPC names, as with the protobuf services of the
+//! eCAL C++ API, so generated clients and servers interoperate with those.
+//! The server advertises the request and response types of each method by
+//! their full Protobuf names (e.g. `math.AddRequest`) with encoding `proto`.
+//! The generated code uses `prost` and `rustecal-service`, which the crate
+//! including it must depend on. Streaming RPCs are not supported.
+
+use prost_build::{Config, Service};
+use std::fmt::Write;
+use std::io;
+use std::path::Path;
+
+/// Default path of the `rustecal-service` crate in generated code.
+pub const DEFAULT_CRATE_PATH: &str = "::rustecal_service";
+
+/// A [`prost_build::ServiceGenerator`] emitting typed eCAL services.
+#[derive(Debug, Clone)]
+pub struct ServiceGenerator {
+    crate_path: String,
+}
+
+impl Default for ServiceGenerator {
+    fn default() -> Self {
+        Self {
+            crate_path: DEFAULT_CRATE_PATH.to_string(),
+        }
+    }
+}
+
+impl ServiceGenerator {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the path of the `rustecal-service` crate used by the generated
+    /// code, e.g. `"::rustecal::service"` when depending on the `rustecal`
+    /// crate only.
+    pub fn crate_path(mut self, path: &str) -> Self {
+        self.crate_path = path.to_string();
+        self
+    }
+
+    /// Returns the expression of the `DataTypeInfo` advertised for a message,
+    /// named by its full Protobuf name as in eCAL's protobuf services.
+    fn data_type(&self, proto_type: &str) -> String {
+        format!(
+            "{}::DataTypeInfo {{ encoding: \"proto\".into(), type_name: \"{}\".into(), descriptor: ::std::vec::Vec::new() }}",
+            self.crate_path,
+            proto_type.trim_start_matches('.')
+        )
+    }
+
+    fn write_service(&self, service: &Service, buf: &mut String) -> std::fmt::Result {
+        if let Some(method) = service
+            .methods
+            .iter()
+            .find(|method| method.client_streaming || method.server_streaming)
+        {
+            panic!(
+                "streaming RPC '{}.{}' is not supported by eCAL services",
+                service.proto_name, method.proto_name
+            );
+        }
+
+        let krate = &self.crate_path;
+        let name = &service.name;
+        let full_name = if service.package.is_empty() {
+            service.proto_name.clone()
+        } else {
+            format!("{}.{}", service.package, service.proto_name)
+        };
+
+        // trait implemented by the application
+        service.comments.append_with_indent(0, buf);
+        writeln!(buf, "pub trait {name}: Send + Sync + 'static {{")?;
+        for method in &service.methods {
+            method.comments.append_with_indent(1, buf);
+            writeln!(
+                buf,
+                "    fn {}(&self, request: {}) -> ::core::result::Result<{}, {krate}::ServiceError>;",
+                method.name, method.input_type, method.output_type
+            )?;
+        }
+        writeln!(buf, "}}")?;
+
+        // server
+        writeln!(
+            buf,
+            "/// Hosts a [`{name}`] implementation as eCAL service."
+        )?;
+        writeln!(buf, "pub struct {name}Server {{")?;
+        writeln!(buf, "    server: {krate}::ServiceServer,")?;
+        writeln!(buf, "}}")?;
+        writeln!(buf, "impl {name}Server {{")?;
+        writeln!(buf, "    /// Name of the eCAL service.")?;
+        writeln!(
+            buf,
+            "    pub const SERVICE_NAME: &'static str = \"{full_name}\";"
+        )?;
+        writeln!(buf, "    /// Hosts `service` under [`Self::SERVICE_NAME`].")?;
+        writeln!(
+            buf,
+            "    pub fn new<S: {name}>(service: S) -> ::core::result::Result<Self, ::std::string::String> {{"
+        )?;
+        writeln!(buf, "        Self::with_name(Self::SERVICE_NAME, service)")?;
+        writeln!(buf, "    }}")?;
+        writeln!(buf, "    /// Hosts `service` under `service_name`.")?;
+        writeln!(
+            buf,
+            "    pub fn with_name<S: {name}>(service_name: &str, service: S) -> ::core::result::Result<Self, ::std::string::String> {{"
+        )?;
+        writeln!(buf, "        let service = ::std::sync::Arc::new(service);")?;
+        writeln!(
+            buf,
+            "        let mut server = {krate}::ServiceServer::new(service_name)?;"
+        )?;
+        for method in &service.methods {
+            writeln!(buf, "        {{")?;
+            writeln!(buf, "            let service = service.clone();")?;
+            writeln!(buf, "            server.add_typed_method(")?;
+            writeln!(buf, "                \"{}\",", method.proto_name)?;
+            writeln!(
+                buf,
+                "                {},",
+                self.data_type(&method.input_proto_type)
+            )?;
+            writeln!(
+                buf,
+                "                {},",
+                self.data_type(&method.output_proto_type)
+            )?;
+            writeln!(
+                buf,
+                "                ::std::boxed::Box::new(move |_info, request| {{"
+            )?;
+            writeln!(
+                buf,
+                "                    let request = <{} as ::prost::Message>::decode(request)",
+                method.input_type
+            )?;
+            writeln!(
+                buf,
+                "                        .map_err(|e| {krate}::ServiceError::Serialization(e.to_string()))?;"
+            )?;
+            writeln!(
+                buf,
+                "                    service.{}(request).map(|response| ::prost::Message::encode_to_vec(&response))",
+                method.name
+            )?;
+            writeln!(buf, "                }}),")?;
+            writeln!(buf, "            )?;")?;
+            writeln!(buf, "        }}")?;
+        }
+        writeln!(buf, "        Ok(Self {{ server }})")?;
+        writeln!(buf, "    }}")?;
+        writeln!(
+            buf,
+            "    /// Returns the underlying server, e.g. to set a worker pool."
+        )?;
+        writeln!(
+            buf,
+            "    pub fn server_mut(&mut self) -> &mut {krate}::ServiceServer {{"
+        )?;
+        writeln!(buf, "        &mut self.server")?;
+        writeln!(buf, "    }}")?;
+        writeln!(buf, "}}")?;
+
+        // client
+        writeln!(buf, "/// Typed client of the [`{name}`] eCAL service.")?;
+        writeln!(buf, "pub struct {name}Client {{")?;
+        writeln!(buf, "    client: {krate}::ServiceClient,")?;
+        writeln!(buf, "}}")?;
+        writeln!(buf, "impl {name}Client {{")?;
+        writeln!(
+            buf,
+            "    /// Connects to the service named [`{name}Server::SERVICE_NAME`]."
+        )?;
+        writeln!(
+            buf,
+            "    pub fn new() -> ::core::result::Result<Self, ::std::string::String> {{"
+        )?;
+        writeln!(buf, "        Self::with_name({name}Server::SERVICE_NAME)")?;
+        writeln!(buf, "    }}")?;
+        writeln!(buf, "    /// Connects to the service named `service_name`.")?;
+        writeln!(
+            buf,
+            "    pub fn with_name(service_name: &str) -> ::core::result::Result<Self, ::std::string::String> {{"
+        )?;
+        writeln!(
+            buf,
+            "        Ok(Self {{ client: {krate}::ServiceClient::new(service_name)? }})"
+        )?;
+        writeln!(buf, "    }}")?;
+        for method in &service.methods {
+            method.comments.append_with_indent(1, buf);
+            writeln!(
+                buf,
+                "    pub fn {}(&self, request: &{}, timeout_ms: ::core::option::Option<i32>) -> ::core::result::Result<{}, {krate}::ServiceError> {{",
+                method.name, method.input_type, method.output_type
+            )?;
+            writeln!(
+                buf,
+                "        let request = {krate}::ServiceRequest {{ payload: ::prost::Message::encode_to_vec(request) }};"
+            )?;
+            writeln!(
+                buf,
+                "        let response = self.client.call(\"{}\", request, timeout_ms)?;",
+                method.proto_name
+            )?;
+            writeln!(
+                buf,
+                "        <{} as ::prost::Message>::decode(response.payload.as_slice())",
+                method.output_type
+            )?;
+            writeln!(
+                buf,
+                "            .map_err(|e| {krate}::ServiceError::Serialization(e.to_string()))"
+            )?;
+            writeln!(buf, "    }}")?;
+        }
+        writeln!(
+            buf,
+            "    /// Returns the underlying client, e.g. to wait for a server."
+        )?;
+        writeln!(
+            buf,
+            "    pub fn client(&self) -> &{krate}::ServiceClient {{"
+        )?;
+        writeln!(buf, "        &self.client")?;
+        writeln!(buf, "    }}")?;
+        writeln!(
+            buf,
+            "    /// Returns the underlying client mutably, e.g. to set a retry policy."
+        )?;
+        writeln!(
+            buf,
+            "    pub fn client_mut(&mut self) -> &mut {krate}::ServiceClient {{"
+        )?;
+        writeln!(buf, "        &mut self.client")?;
+        writeln!(buf, "    }}")?;
+        writeln!(buf, "}}")
+    }
+}
+
+impl prost_build::ServiceGenerator for ServiceGenerator {
+    fn generate(&mut self, service: Service, buf: &mut String) {
+        // writing to a String cannot fail
+        let _ = self.write_service(&service, buf);
+    }
+}
+
+/// Compiles `protos` with prost and generates typed eCAL services for their
+/// `service` definitions, see the [crate documentation](crate).
+///
+/// Use [`ServiceGenerator`] with a custom [`prost_build::Config`] for more control.
+///
+/// # Errors
+///
+/// Returns the error of [`prost_build::Config::compile_protos`], e.g. when
+/// `protoc` is not found.
+pub fn compile_protos(
+    protos: &[impl AsRef<Path>],
+    includes: &[impl AsRef<Path>],
+) -> io::Result<()> {
+    Config::new()
+        .service_generator(Box::new(ServiceGenerator::new()))
+        .compile_protos(protos, includes)
+}
@@ -0,0 +1,76 @@
+use prost_build::{Comments, Method, Service, ServiceGenerator as _};
+use rustecal_service_build::ServiceGenerator;
+
+fn method(name: &str, proto_name: &str, input: &str, output: &str) -> Method {
+    Method {
+        name: name.into(),
+        proto_name: proto_name.into(),
+        comments: Comments::default(),
+        input_type: input.into(),
+        output_type: output.into(),
+        input_proto_type: format!(".math.{input}"),
+        output_proto_type: format!(".math.{output}"),
+        options: Default::default(),
+        client_streaming: false,
+        server_streaming: false,
+    }
+}
+
+fn math_service() -> Service {
+    Service {
+        name: "MathService".into(),
+        proto_name: "MathService".into(),
+        package: "math".into(),
+        comments: Comments::default(),
+        methods: vec![method("add", "Add", "AddRequest", "AddResponse")],
+        options: Default::default(),
+    }
+}
+
+fn generate(generator: &mut ServiceGenerator, service: Service) -> String {
+    let mut buf = String::new();
+    generator.generate(service, &mut buf);
+    buf
+}
+
+#[test]
+fn generates_trait_server_and_client() {
+    let code = generate(&mut ServiceGenerator::new(), math_service());
+    assert!(code.contains("pub trait MathService: Send + Sync + 'static {"));
+    assert!(code.contains(
+        "fn add(&self, request: AddRequest) -> ::core::result::Result<AddResponse, ::rustecal_service::ServiceError>;"
+    ));
+    assert!(code.contains("pub struct MathServiceServer {"));
+    assert!(code.contains("pub const SERVICE_NAME: &'static str = \"math.MathService\";"));
+    assert!(code.contains("server.add_typed_method(\n                \"Add\","));
+    assert!(code.contains(
+        "::rustecal_service::DataTypeInfo { encoding: \"proto\".into(), type_name: \"math.AddRequest\".into(), descriptor: ::std::vec::Vec::new() },"
+    ));
+    assert!(code.contains("type_name: \"math.AddResponse\".into()"));
+    assert!(code.contains("pub struct MathServiceClient {"));
+    assert!(code.contains("self.client.call(\"Add\", request, timeout_ms)?"));
+}
+
+#[test]
+fn uses_configured_crate_path() {
+    let mut generator = ServiceGenerator::new().crate_path("::rustecal::service");
+    let code = generate(&mut generator, math_service());
+    assert!(code.contains("::rustecal::service::ServiceServer::new(service_name)?"));
+    assert!(!code.contains("::rustecal_service::"));
+}
+
+#[test]
+fn names_service_without_package_by_its_proto_name() {
+    let mut service = math_service();
+    service.package.clear();
+    let code = generate(&mut ServiceGenerator::new(), service);
+    assert!(code.contains("SERVICE_NAME: &'static str = \"MathService\";"));
+}
+
+#[test]
+#[should_panic(expected = "streaming RPC 'MathService.Add'")]
+fn rejects_streaming_methods() {
+    let mut service = math_service();
+    service.methods[0].server_streaming = true;
+    generate(&mut ServiceGenerator::new(), service);
+}
@@ -0,0 +1,195 @@
+//! Cancelling asynchronous service calls.
+//!
+//! [`ClientInstance::call_with_callback`] returns a [`CancelToken`];
+//! cancelling it before the response arrives drops the callback without
+//! running it. [`ClientInstance::call_async`] returns a [`ResponseFuture`],
+//! which cancels its call when it is dropped, e.g. when a
+//! `tokio::time::timeout` or `select!` around it gives up:
+//!
+//! ```ignore
+//! let response = instance.call_async("echo", request, Some(500)).await?;
+//! ```
+//!
+//! A call with a timeout completes with [`ServiceError::Timeout`] once the
+//! timeout expires, also if the server never answers. All pending calls of
+//! the process share one timer thread, which sleeps until the earliest
+//! deadline and runs the callbacks of expired calls.
+//!
+//! eCAL offers no way to withdraw a request already sent, so cancelling
+//! only stops the client from waiting. Servers learn when the client stops
+//! waiting from the deadline passed along with the call, see
+//! [`MethodInfo::deadline`](crate::types::MethodInfo::deadline).
+//!
+//! [`ClientInstance::call_with_callback`]: crate::client_instance::ClientInstance::call_with_callback
+//! [`ClientInstance::call_async`]: crate::client_instance::ClientInstance::call_async
+
+use crate::error::ServiceError;
+use crate::response::ServiceResponse;
+use rustecal_core::error_hook::guard;
+use std::cmp::{Ordering as Order, Reverse};
+use std::collections::BinaryHeap;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Condvar, Mutex, Once};
+use std::task::{Context, Poll, Waker};
+use std::thread;
+use std::time::Instant;
+
+/// Cancels an asynchronous call; clones cancel the same call.
+#[derive(Debug, Clone, Default)]
+pub struct CancelToken {
+    cancelled: Arc<AtomicBool>,
+}
+
+impl CancelToken {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Cancels the call; its response is discarded when it arrives.
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::Release);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::Acquire)
+    }
+}
+
+/// Result of a call and the task waiting for it.
+#[derive(Default)]
+pub(crate) struct Slot {
+    result: Option<Result<ServiceResponse, ServiceError>>,
+    waker: Option<Waker>,
+}
+
+// the slot is filled on an eCAL thread and taken by the awaiting task; the
+// host name pointers of server ids are not dereferenced by either of them
+unsafe impl Send for Slot {}
+
+/// Shared end of a [`ResponseFuture`], completed by the response callback.
+pub(crate) type SharedSlot = Arc<Mutex<Slot>>;
+
+/// Stores `result` in `slot` and wakes the task awaiting it.
+pub(crate) fn complete(slot: &SharedSlot, result: Result<ServiceResponse, ServiceError>) {
+    let waker = {
+        let mut slot = slot.lock().unwrap_or_else(|e| e.into_inner());
+        slot.result = Some(result);
+        slot.waker.take()
+    };
+    if let Some(waker) = waker {
+        waker.wake();
+    }
+}
+
+/// The response of a call issued with
+/// [`ClientInstance::call_async`](crate::ClientInstance::call_async).
+///
+/// Dropping the future before it completes cancels the call.
+pub struct ResponseFuture {
+    slot: SharedSlot,
+    token: CancelToken,
+}
+
+impl ResponseFuture {
+    pub(crate) fn new(slot: SharedSlot, token: CancelToken) -> Self {
+        Self { slot, token }
+    }
+}
+
+impl Future for ResponseFuture {
+    type Output = Result<ServiceResponse, ServiceError>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let mut slot = self.slot.lock().unwrap_or_else(|e| e.into_inner());
+        match slot.result.take() {
+            Some(result) => Poll::Ready(result),
+            None => {
+                slot.waker = Some(cx.waker().clone());
+                Poll::Pending
+            }
+        }
+    }
+}
+
+impl Drop for ResponseFuture {
+    fn drop(&mut self) {
+        self.token.cancel();
+    }
+}
+
+/// An action scheduled on the timer thread.
+struct Expiry {
+    deadline: Instant,
+    action: Box<dyn FnOnce() + Send + 'static>,
+}
+
+// expiries are ordered by their deadline only
+impl PartialEq for Expiry {
+    fn eq(&self, other: &Self) -> bool {
+        self.deadline == other.deadline
+    }
+}
+
+impl Eq for Expiry {}
+
+impl PartialOrd for Expiry {
+    fn partial_cmp(&self, other: &Self) -> Option<Order> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Expiry {
+    fn cmp(&self, other: &Self) -> Order {
+        self.deadline.cmp(&other.deadline)
+    }
+}
+
+static EXPIRIES: Mutex<BinaryHeap<Reverse<Expiry>>> = Mutex::new(BinaryHeap::new());
+static WAKE: Condvar = Condvar::new();
+static TIMER: Once = Once::new();
+
+/// Runs `action` on the timer thread once `deadline` has passed.
+pub(crate) fn at_deadline<F>(deadline: Instant, action: F)
+where
+    F: FnOnce() + Send + 'static,
+{
+    TIMER.call_once(|| {
+        thread::spawn(timer);
+    });
+    EXPIRIES
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .push(Reverse(Expiry {
+            deadline,
+            action: Box::new(action),
+        }));
+    WAKE.notify_all();
+}
+
+/// Body of the timer thread shared by all calls with a timeout.
+fn timer() {
+    let mut expiries = EXPIRIES.lock().unwrap_or_else(|e| e.into_inner());
+    loop {
+        let now = Instant::now();
+        let next = expiries.peek().map(|Reverse(expiry)| expiry.deadline);
+        expiries = match next {
+            Some(deadline) if deadline <= now => {
+                let Some(Reverse(expiry)) = expiries.pop() else {
+                    continue;
+                };
+                // callbacks may issue calls, so they run without the lock
+                drop(expiries);
+                guard(|| "service call timeout".to_string(), expiry.action);
+                EXPIRIES.lock().unwrap_or_else(|e| e.into_inner())
+            }
+            Some(deadline) => {
+                WAKE.wait_timeout(expiries, deadline - now)
+                    .unwrap_or_else(|e| e.into_inner())
+                    .0
+            }
+            None => WAKE.wait(expiries).unwrap_or_else(|e| e.into_inner()),
+        };
+    }
+}
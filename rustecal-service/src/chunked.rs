@@ -0,0 +1,254 @@
+//! Transferring large responses in chunks.
+//!
+//! A method registered with [`ServiceServer::add_chunked_method`] answers
+//! with a chunk of at most `chunk_size` bytes at a time.
+//! [`ServiceClient::call_chunked`] fetches the chunks one call after the
+//! other from the same server and reassembles the response:
+//!
+//! ```ignore
+//! server.add_chunked_method("map", 1 << 20, Box::new(|_, _| Ok(load_map())))?;
+//! let map: Vec<u8> = client.call_chunked("map", request, Some(1000))?;
+//! ```
+//!
+//! The first call carries the request and runs the handler; the server
+//! keeps the response as a transfer and returns its first chunk with the
+//! transfer id and the number of chunks. Subsequent calls ask for the next
+//! chunk of the transfer. Every chunk names its index and the chunk count,
+//! so the client detects missing, reordered and final chunks. The server
+//! drops a transfer when its last chunk was fetched, or when no chunk was
+//! fetched for [`TRANSFER_TIMEOUT`].
+//!
+//! [`ServiceServer::add_chunked_method`]: crate::server::ServiceServer::add_chunked_method
+//! [`ServiceClient::call_chunked`]: crate::client::ServiceClient::call_chunked
+
+use crate::error::ServiceError;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// Time after which a server drops a transfer no chunk was fetched of.
+pub const TRANSFER_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Position of a chunk within its transfer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ChunkHeader {
+    pub transfer_id: u64,
+    /// Index of the chunk, starting at 0.
+    pub index: u32,
+    /// Number of chunks of the transfer, at least 1.
+    pub count: u32,
+}
+
+/// Encodes a chunk: transfer id (`u64`), index and count (`u32`), all
+/// little-endian, then the chunk data.
+pub fn encode_chunk(header: ChunkHeader, data: &[u8]) -> Vec<u8> {
+    let mut chunk = Vec::with_capacity(16 + data.len());
+    chunk.extend_from_slice(&header.transfer_id.to_le_bytes());
+    chunk.extend_from_slice(&header.index.to_le_bytes());
+    chunk.extend_from_slice(&header.count.to_le_bytes());
+    chunk.extend_from_slice(data);
+    chunk
+}
+
+/// Splits a chunk encoded by [`encode_chunk`] into header and data.
+pub fn decode_chunk(chunk: &[u8]) -> Option<(ChunkHeader, &[u8])> {
+    let header = ChunkHeader {
+        transfer_id: u64::from_le_bytes(chunk.get(..8)?.try_into().ok()?),
+        index: u32::from_le_bytes(chunk.get(8..12)?.try_into().ok()?),
+        count: u32::from_le_bytes(chunk.get(12..16)?.try_into().ok()?),
+    };
+    Some((header, &chunk[16..]))
+}
+
+/// Encodes a request for chunk `index` of `transfer_id`: both little-endian,
+/// then the payload. Transfer id `0` starts a new transfer with `payload`
+/// as request of the handler.
+pub fn encode_chunk_request(transfer_id: u64, index: u32, payload: &[u8]) -> Vec<u8> {
+    let mut request = Vec::with_capacity(12 + payload.len());
+    request.extend_from_slice(&transfer_id.to_le_bytes());
+    request.extend_from_slice(&index.to_le_bytes());
+    request.extend_from_slice(payload);
+    request
+}
+
+/// Splits a request encoded by [`encode_chunk_request`] into transfer id,
+/// chunk index and payload.
+pub fn decode_chunk_request(request: &[u8]) -> Option<(u64, u32, &[u8])> {
+    let transfer_id = u64::from_le_bytes(request.get(..8)?.try_into().ok()?);
+    let index = u32::from_le_bytes(request.get(8..12)?.try_into().ok()?);
+    Some((transfer_id, index, &request[12..]))
+}
+
+/// A response kept by the server until its chunks are fetched.
+struct Transfer {
+    response: Arc<Vec<u8>>,
+    last_access: Instant,
+}
+
+/// The server side of chunked methods: runs the handler for new transfers
+/// and answers chunk requests from the kept responses.
+pub struct ChunkStore {
+    chunk_size: usize,
+    next_id: AtomicU64,
+    transfers: Mutex<HashMap<u64, Transfer>>,
+}
+
+impl ChunkStore {
+    /// Creates a store splitting responses into chunks of `chunk_size` bytes
+    /// (at least 1).
+    pub fn new(chunk_size: usize) -> Self {
+        Self {
+            chunk_size: chunk_size.max(1),
+            next_id: AtomicU64::new(1),
+            transfers: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Answers a request encoded by [`encode_chunk_request`], calling
+    /// `handler` with the payload to start a new transfer.
+    ///
+    /// # Errors
+    ///
+    /// Returns the error of `handler`, or a [`ServiceError::Failed`] for
+    /// malformed requests and unknown transfers or chunks.
+    pub fn handle<F>(&self, request: &[u8], handler: F) -> Result<Vec<u8>, ServiceError>
+    where
+        F: FnOnce(&[u8]) -> Result<Vec<u8>, ServiceError>,
+    {
+        let (transfer_id, index, payload) = decode_chunk_request(request)
+            .ok_or_else(|| ServiceError::new("request is no chunk request"))?;
+
+        let mut transfers = self.transfers.lock().unwrap_or_else(|e| e.into_inner());
+        transfers.retain(|_, transfer| transfer.last_access.elapsed() < TRANSFER_TIMEOUT);
+
+        let (transfer_id, response) = if transfer_id == 0 {
+            // the handler may take long, so it runs without the lock
+            drop(transfers);
+            let response = handler(payload)?;
+            if response.len() <= self.chunk_size {
+                let header = ChunkHeader {
+                    transfer_id: 0,
+                    index: 0,
+                    count: 1,
+                };
+                return Ok(encode_chunk(header, &response));
+            }
+            let transfer_id = self.next_id.fetch_add(1, Ordering::Relaxed);
+            let response = Arc::new(response);
+            transfers = self.transfers.lock().unwrap_or_else(|e| e.into_inner());
+            transfers.insert(
+                transfer_id,
+                Transfer {
+                    response: response.clone(),
+                    last_access: Instant::now(),
+                },
+            );
+            (transfer_id, response)
+        } else {
+            let transfer = transfers
+                .get_mut(&transfer_id)
+                .ok_or_else(|| ServiceError::new(format!("unknown transfer {transfer_id}")))?;
+            transfer.last_access = Instant::now();
+            (transfer_id, transfer.response.clone())
+        };
+
+        let count = response.len().div_ceil(self.chunk_size).max(1);
+        let start = index as usize * self.chunk_size;
+        if index as usize >= count {
+            return Err(ServiceError::new(format!(
+                "transfer {transfer_id} has no chunk {index}"
+            )));
+        }
+        if index as usize + 1 == count {
+            transfers.remove(&transfer_id);
+        }
+        drop(transfers);
+
+        let end = (start + self.chunk_size).min(response.len());
+        let header = ChunkHeader {
+            transfer_id,
+            index,
+            count: count as u32,
+        };
+        Ok(encode_chunk(header, &response[start..end]))
+    }
+
+    /// Returns the number of transfers whose chunks are not all fetched yet.
+    pub fn pending(&self) -> usize {
+        self.transfers
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .len()
+    }
+}
+
+/// The client side of chunked methods: checks the order of received chunks
+/// and reassembles the response.
+#[derive(Debug, Default)]
+pub struct ChunkAssembler {
+    transfer_id: u64,
+    count: Option<u32>,
+    next_index: u32,
+    data: Vec<u8>,
+}
+
+impl ChunkAssembler {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the request fetching the next chunk; `payload` is only sent
+    /// with the first one.
+    pub fn next_request(&self, payload: &[u8]) -> Vec<u8> {
+        if self.count.is_none() {
+            encode_chunk_request(0, 0, payload)
+        } else {
+            encode_chunk_request(self.transfer_id, self.next_index, &[])
+        }
+    }
+
+    /// Adds a chunk encoded by [`encode_chunk`] and returns `true` once the
+    /// response is complete.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`ServiceError::Transport`] for malformed chunks and chunks
+    /// of another transfer or out of order.
+    pub fn push(&mut self, chunk: &[u8]) -> Result<bool, ServiceError> {
+        let (header, data) =
+            decode_chunk(chunk).ok_or_else(|| ServiceError::Transport("malformed chunk".into()))?;
+        if header.count == 0 || header.index != self.next_index {
+            return Err(ServiceError::Transport(format!(
+                "expected chunk {}, received {} of {}",
+                self.next_index, header.index, header.count
+            )));
+        }
+        match self.count {
+            None => {
+                self.transfer_id = header.transfer_id;
+                self.count = Some(header.count);
+            }
+            Some(count) if count != header.count || self.transfer_id != header.transfer_id => {
+                return Err(ServiceError::Transport(format!(
+                    "chunk {} belongs to another transfer",
+                    header.index
+                )));
+            }
+            Some(_) => {}
+        }
+        self.data.extend_from_slice(data);
+        self.next_index += 1;
+        Ok(self.is_complete())
+    }
+
+    /// Returns `true` once all chunks were added.
+    pub fn is_complete(&self) -> bool {
+        self.count == Some(self.next_index)
+    }
+
+    /// Returns the reassembled response.
+    pub fn into_inner(self) -> Vec<u8> {
+        self.data
+    }
+}
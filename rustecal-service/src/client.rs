@@ -1,43 +1,232 @@
 use crate::client_instance::ClientInstance;
+use crate::error::ServiceError;
+use crate::events::{CLIENT_EVENTS, ServiceEvent, client_event_trampoline};
+use crate::intercept::{ClientInterceptor, ClientNext};
 use crate::response::ServiceResponse;
-use crate::types::ServiceRequest;
+use crate::retry::RetryPolicy;
+use crate::types::{ServiceId, ServiceRequest, encode_path_request, with_deadline};
+use crate::versioning::{
+    METHODS_METHOD, NegotiationError, VERSION_METHOD, check_versions, decode_methods,
+    decode_version,
+};
+use rustecal_core::discovery::wait_until;
+use rustecal_core::handle_registry::{HandleKind, HandleRegistry, TrackedHandle};
+use rustecal_core::types::EntityId;
 use rustecal_sys::*;
 use std::ffi::CString;
 use std::os::raw::c_void;
 use std::ptr;
+use std::sync::Arc;
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// Result of calling one server instance, see [`ServiceClient::call_each`].
+pub type InstanceResult = (ServiceId, Result<ServiceResponse, ServiceError>);
+
+/// A client instance or call result passed between calling threads.
+struct Sendable<T>(T);
+
+// eCAL client instances may be called from any thread, and each one is used
+// by a single thread while the calling `ServiceClient` is borrowed; the host
+// name pointers of server ids are not dereferenced by the calling threads.
+unsafe impl<T> Send for Sendable<T> {}
+
+impl<T> Sendable<T> {
+    fn get(self) -> T {
+        self.0
+    }
+}
 
 pub struct ServiceClient {
     pub(crate) handle: *mut eCAL_ServiceClient,
+    service_name: String,
+    event_token: Option<u64>,
+    retry: Option<RetryPolicy>,
+    propagate_deadline: bool,
+    interceptors: Vec<ClientInterceptor>,
+    _tracked: Option<TrackedHandle>,
 }
 
 impl ServiceClient {
     pub fn new(service_name: &str) -> Result<Self, String> {
         let c_service = CString::new(service_name).map_err(|_| "Invalid service name")?;
-        let handle = unsafe { eCAL_ServiceClient_New(c_service.as_ptr(), ptr::null(), 0, None) };
+        let handle = unsafe {
+            eCAL_ServiceClient_New(
+                c_service.as_ptr(),
+                ptr::null(),
+                0,
+                Some(client_event_trampoline),
+            )
+        };
 
         if handle.is_null() {
             Err("Failed to create eCAL_ServiceClient".into())
         } else {
-            Ok(Self { handle })
+            Ok(Self {
+                handle,
+                service_name: service_name.to_string(),
+                event_token: None,
+                retry: None,
+                propagate_deadline: false,
+                interceptors: Vec::new(),
+                _tracked: HandleRegistry::track(HandleKind::ServiceClient, service_name),
+            })
         }
     }
 
+    /// Calls `method` and waits for a response.
+    ///
+    /// If several servers are connected, all of them are called and the
+    /// first successful response is returned. Failed calls are repeated
+    /// according to the retry policy, if set.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`ServiceError`] if no server produced a response; with
+    /// several servers, the error of the last one.
     pub fn call(
         &self,
         method: &str,
         request: ServiceRequest,
         timeout_ms: Option<i32>,
-    ) -> Option<ServiceResponse> {
-        self.call_all(method, request, timeout_ms)?.pop()
+    ) -> Result<ServiceResponse, ServiceError> {
+        match &self.retry {
+            Some(policy) => policy.run(|| self.call_once(method, request.clone(), timeout_ms)),
+            None => self.call_once(method, request, timeout_ms),
+        }
+    }
+
+    fn call_once(
+        &self,
+        method: &str,
+        request: ServiceRequest,
+        timeout_ms: Option<i32>,
+    ) -> Result<ServiceResponse, ServiceError> {
+        let call = |method: &str, request| {
+            let mut result = Err(ServiceError::NoInstance);
+            for response in self.call_all(method, request, timeout_ms)? {
+                result = response.into_result();
+                if result.is_ok() {
+                    break;
+                }
+            }
+            result
+        };
+        ClientNext::new(&self.interceptors, &call).run(method, request)
+    }
+
+    /// Adds an interceptor wrapping every attempt of [`call`](Self::call)
+    /// and the calls built on it.
+    ///
+    /// `interceptor` receives the method and request with the rest of the
+    /// chain and passes them on with [`ClientNext::run`], e.g. after
+    /// attaching an auth token with
+    /// [`encode_header_request`](crate::types::encode_header_request).
+    /// Interceptors run in the order they were added. See [`crate::intercept`].
+    pub fn add_interceptor<F>(&mut self, interceptor: F)
+    where
+        F: Fn(&str, ServiceRequest, ClientNext<'_>) -> Result<ServiceResponse, ServiceError>
+            + Send
+            + Sync
+            + 'static,
+    {
+        self.interceptors.push(Arc::new(interceptor));
+    }
+
+    /// Sets the policy for repeating failed calls of [`call`](Self::call)
+    /// and the methods built on it; `None` disables retries (the default).
+    ///
+    /// See [`crate::retry`].
+    pub fn set_retry_policy(&mut self, policy: Option<RetryPolicy>) {
+        self.retry = policy;
+    }
+
+    /// Passes the timeout of each call to the servers, which see it as
+    /// [`MethodInfo::deadline`](crate::types::MethodInfo::deadline) and can
+    /// abort handlers the client no longer waits for. Disabled by default.
+    ///
+    /// The deadline is encoded into the request (see
+    /// [`encode_deadline_request`](crate::types::encode_deadline_request)),
+    /// so only enable it for servers built with rustecal.
+    pub fn set_deadline_propagation(&mut self, enabled: bool) {
+        self.propagate_deadline = enabled;
+    }
+
+    /// Calls the path `path` below a prefix method registered with
+    /// `ServiceServer::add_prefix_handler`.
+    pub fn call_path(
+        &self,
+        prefix: &str,
+        path: &str,
+        request: ServiceRequest,
+        timeout_ms: Option<i32>,
+    ) -> Result<ServiceResponse, ServiceError> {
+        let request = ServiceRequest {
+            payload: encode_path_request(path, &request.payload),
+        };
+        self.call(prefix, request, timeout_ms)
+    }
+
+    /// Checks that every reachable server implements at least `min_version`
+    /// of the interface (see [`versioning`](crate::versioning)).
+    ///
+    /// Returns the lowest version reported by the servers.
+    pub fn negotiate(
+        &self,
+        min_version: u32,
+        timeout_ms: Option<i32>,
+    ) -> Result<u32, NegotiationError> {
+        let request = ServiceRequest {
+            payload: Vec::new(),
+        };
+        let responses = self
+            .call_all(VERSION_METHOD, request, timeout_ms)
+            .map_err(|_| NegotiationError::NoServer)?;
+        let versions: Vec<Option<u32>> = responses
+            .iter()
+            .map(|response| {
+                response
+                    .success
+                    .then(|| decode_version(&response.payload))
+                    .flatten()
+            })
+            .collect();
+        check_versions(&versions, min_version)
+    }
+
+    /// Returns the methods registered by the first responding server.
+    ///
+    /// # Errors
+    ///
+    /// See [`call`](Self::call).
+    pub fn get_methods(&self, timeout_ms: Option<i32>) -> Result<Vec<String>, ServiceError> {
+        let request = ServiceRequest {
+            payload: Vec::new(),
+        };
+        let response = self.call(METHODS_METHOD, request, timeout_ms)?;
+        Ok(decode_methods(&response.payload))
     }
 
+    /// Calls `method` on all connected servers and returns their responses,
+    /// including unsuccessful ones (see [`ServiceResponse::into_result`]).
+    ///
+    /// eCAL returns the responses together once all servers answered, so
+    /// their round-trip times are those of the slowest server; use
+    /// [`call_each_parallel`](Self::call_each_parallel) to measure each one.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ServiceError::NoInstance`] if no server is connected,
+    /// [`ServiceError::Transport`] if none of the connected servers answered,
+    /// or [`ServiceError::InvalidMethod`] for a method name containing a NUL byte.
     pub fn call_all(
         &self,
         method: &str,
         request: ServiceRequest,
         timeout_ms: Option<i32>,
-    ) -> Option<Vec<ServiceResponse>> {
-        let c_method = CString::new(method).ok()?;
+    ) -> Result<Vec<ServiceResponse>, ServiceError> {
+        let c_method =
+            CString::new(method).map_err(|_| ServiceError::InvalidMethod(method.to_string()))?;
 
         let mut response_ptr: *mut eCAL_SServiceResponse = ptr::null_mut();
         let mut response_len: usize = 0;
@@ -46,6 +235,8 @@ impl ServiceClient {
             .as_ref()
             .map(|t| t as *const i32)
             .unwrap_or(ptr::null());
+        let request = with_deadline(request, timeout_ms, self.propagate_deadline);
+        let start = Instant::now();
 
         let result = unsafe {
             eCAL_ServiceClient_CallWithResponse(
@@ -59,8 +250,13 @@ impl ServiceClient {
             )
         };
 
-        if result != 0 || response_ptr.is_null() || response_len == 0 {
-            return None;
+        // with responses, a failing result only means that some calls failed
+        if response_ptr.is_null() || response_len == 0 {
+            return Err(if result != 0 && self.is_connected() {
+                ServiceError::Transport(format!("no response to method '{method}'"))
+            } else {
+                ServiceError::NoInstance
+            });
         }
 
         let mut responses = Vec::with_capacity(response_len);
@@ -68,13 +264,127 @@ impl ServiceClient {
         unsafe {
             for i in 0..response_len {
                 let item = &*response_ptr.add(i);
-                responses.push(ServiceResponse::from_struct(item));
+                responses.push(ServiceResponse::from_struct(item).timed(start));
             }
 
             eCAL_Free(response_ptr as *mut c_void);
         }
 
-        Some(responses)
+        Ok(responses)
+    }
+
+    /// Calls a method registered with `ServiceServer::add_chunked_method`
+    /// and returns the reassembled response.
+    ///
+    /// All chunks are fetched from the same server; if that fails, the
+    /// transfer is repeated with the next connected server. See
+    /// [`crate::chunked`].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ServiceError::NoInstance`] if no server is connected, or the
+    /// error of the last server tried.
+    pub fn call_chunked(
+        &self,
+        method: &str,
+        request: ServiceRequest,
+        timeout_ms: Option<i32>,
+    ) -> Result<Vec<u8>, ServiceError> {
+        let mut result = Err(ServiceError::NoInstance);
+        for instance in self.get_client_instances() {
+            result = instance.call_chunked(method, request.clone(), timeout_ms);
+            if result.is_ok() {
+                break;
+            }
+        }
+        result
+    }
+
+    /// Returns the instance of the first connected server running on `host_name`.
+    pub fn instance_on_host(&self, host_name: &str) -> Option<ClientInstance> {
+        self.get_client_instances()
+            .into_iter()
+            .find(|instance| EntityId::from(instance.server_id().service_id).host_name == host_name)
+    }
+
+    /// Returns the instance of the connected server with entity id `entity_id`
+    /// (see [`ServiceId`]).
+    pub fn instance_by_id(&self, entity_id: u64) -> Option<ClientInstance> {
+        self.get_client_instances()
+            .into_iter()
+            .find(|instance| instance.server_id().service_id.entity_id == entity_id)
+    }
+
+    /// Calls `method` on every connected server instance, one after the other.
+    ///
+    /// Unlike [`call_all`](Self::call_all), every instance is reported with
+    /// its server id, including those whose call failed or timed out, with
+    /// the result of [`ClientInstance::call`].
+    pub fn call_each(
+        &self,
+        method: &str,
+        request: ServiceRequest,
+        timeout_ms: Option<i32>,
+    ) -> Vec<InstanceResult> {
+        self.get_client_instances()
+            .into_iter()
+            .map(|instance| call_instance(&instance, method, request.clone(), timeout_ms))
+            .collect()
+    }
+
+    /// Like [`call_each`](Self::call_each), but calls all instances at the
+    /// same time, one thread per instance, so a slow server does not delay
+    /// the others.
+    pub fn call_each_parallel(
+        &self,
+        method: &str,
+        request: ServiceRequest,
+        timeout_ms: Option<i32>,
+    ) -> Vec<InstanceResult> {
+        let instances = self.get_client_instances();
+        thread::scope(|scope| {
+            let calls: Vec<_> = instances
+                .into_iter()
+                .map(|instance| {
+                    let instance = Sendable(instance);
+                    let request = request.clone();
+                    scope.spawn(move || {
+                        Sendable(call_instance(&instance.get(), method, request, timeout_ms))
+                    })
+                })
+                .collect();
+            calls
+                .into_iter()
+                .map(|call| call.join().expect("service call panicked").get())
+                .collect()
+        })
+    }
+
+    /// Registers a callback for [`ServiceEvent`]s, replacing a previous one.
+    ///
+    /// The callback runs on an eCAL thread whenever a server connects,
+    /// disconnects or times out. See [`crate::events`] for how events of
+    /// clients sharing a service name within one process are dispatched.
+    pub fn set_event_callback<F>(&mut self, callback: F)
+    where
+        F: Fn(&ServiceEvent) + Send + Sync + 'static,
+    {
+        if let Some(token) = self.event_token.take() {
+            CLIENT_EVENTS.unregister(token);
+        }
+        self.event_token = Some(CLIENT_EVENTS.register(&self.service_name, Arc::new(callback)));
+    }
+
+    /// Returns `true` if at least one service server is connected.
+    pub fn is_connected(&self) -> bool {
+        unsafe { eCAL_ServiceClient_IsConnected(self.handle) != 0 }
+    }
+
+    /// Blocks until at least one service server is connected, or `timeout` has elapsed.
+    ///
+    /// Returns `true` if a server is connected.
+    pub fn wait_for_server(&self, timeout: Duration) -> bool {
+        wait_until(timeout, || self.is_connected())
     }
 
     pub fn get_client_instances(&self) -> Vec<ClientInstance> {
@@ -93,7 +403,10 @@ impl ServiceClient {
                     break;
                 }
 
-                result.push(ClientInstance::from_raw(instance_ptr));
+                result.push(
+                    ClientInstance::from_raw(instance_ptr)
+                        .with_deadline_propagation(self.propagate_deadline),
+                );
                 offset += 1;
             }
         }
@@ -102,6 +415,18 @@ impl ServiceClient {
     }
 }
 
+fn call_instance(
+    instance: &ClientInstance,
+    method: &str,
+    request: ServiceRequest,
+    timeout_ms: Option<i32>,
+) -> InstanceResult {
+    (
+        instance.server_id(),
+        instance.call(method, request, timeout_ms),
+    )
+}
+
 impl Drop for ServiceClient {
     fn drop(&mut self) {
         unsafe {
@@ -110,5 +435,8 @@ impl Drop for ServiceClient {
 
             eCAL_ServiceClient_Delete(self.handle);
         }
+        if let Some(token) = self.event_token.take() {
+            CLIENT_EVENTS.unregister(token);
+        }
     }
 }
@@ -1,31 +1,72 @@
+use crate::cancel::{self, CancelToken, ResponseFuture, SharedSlot};
+use crate::chunked::ChunkAssembler;
+use crate::error::ServiceError;
 use crate::response::ServiceResponse;
-use crate::types::{ServiceId, ServiceRequest};
+use crate::types::{ServiceId, ServiceRequest, with_deadline};
+use rustecal_core::error_hook::guard;
 use rustecal_sys::*;
 use std::ffi::CString;
 use std::os::raw::c_void;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// Handler of a response to a call issued with [`ClientInstance::call_with_callback`].
+type ResponseHandler = Box<dyn FnOnce(Result<ServiceResponse, ServiceError>) + Send + 'static>;
 
 #[derive(Debug)]
 pub struct ClientInstance {
     pub(crate) instance: *mut eCAL_ClientInstance,
+    propagate_deadline: bool,
 }
 
 impl ClientInstance {
     pub fn from_raw(raw: *mut eCAL_ClientInstance) -> Self {
-        Self { instance: raw }
+        Self {
+            instance: raw,
+            propagate_deadline: false,
+        }
+    }
+
+    /// Passes the timeout of calls to the server as deadline, see
+    /// `ServiceClient::set_deadline_propagation`.
+    pub fn with_deadline_propagation(mut self, enabled: bool) -> Self {
+        self.propagate_deadline = enabled;
+        self
+    }
+
+    /// Returns the id of the server this instance calls.
+    pub fn server_id(&self) -> ServiceId {
+        let id = unsafe { eCAL_ClientInstance_GetClientID(self.instance) };
+        ServiceId {
+            service_id: if id.is_null() {
+                unsafe { std::mem::zeroed() }
+            } else {
+                unsafe { *id }
+            },
+        }
     }
 
+    /// Calls `method` on the server of this instance and waits for the response.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`ServiceError`] if the call did not produce a response,
+    /// e.g. [`ServiceError::Timeout`] or [`ServiceError::Failed`].
     pub fn call(
         &self,
         method: &str,
         request: ServiceRequest,
         timeout_ms: Option<i32>,
-    ) -> Option<ServiceResponse> {
-        let c_method = CString::new(method).ok()?;
+    ) -> Result<ServiceResponse, ServiceError> {
+        let c_method =
+            CString::new(method).map_err(|_| ServiceError::InvalidMethod(method.to_string()))?;
         let timeout_ptr = timeout_ms
             .as_ref()
             .map(|t| t as *const i32)
             .unwrap_or(std::ptr::null());
+        let request = with_deadline(request, timeout_ms, self.propagate_deadline);
 
+        let start = Instant::now();
         let response_ptr = unsafe {
             eCAL_ClientInstance_CallWithResponse(
                 self.instance,
@@ -37,21 +78,219 @@ impl ClientInstance {
         };
 
         if response_ptr.is_null() {
-            return Some(ServiceResponse {
-                success: false,
-                server_id: ServiceId {
-                    service_id: unsafe { std::mem::zeroed() },
-                },
-                error_msg: Some("call failed".into()),
-                payload: vec![],
-            });
+            return Err(ServiceError::Transport(format!(
+                "no response to method '{method}'"
+            )));
         }
 
-        unsafe {
-            let response = &*response_ptr;
-            let result = ServiceResponse::from_struct(response);
+        let response = unsafe {
+            let response = ServiceResponse::from_struct(&*response_ptr).timed(start);
             eCAL_Free(response_ptr as *mut c_void);
-            Some(result)
+            response
+        };
+        response.into_result()
+    }
+
+    /// Calls a method registered with `ServiceServer::add_chunked_method`
+    /// and returns the reassembled response.
+    ///
+    /// Every chunk is fetched with a call of its own, each with `timeout_ms`.
+    ///
+    /// # Errors
+    ///
+    /// Returns the [`ServiceError`] of a failed chunk call, or a
+    /// [`ServiceError::Transport`] if the chunks arrive out of order.
+    pub fn call_chunked(
+        &self,
+        method: &str,
+        request: ServiceRequest,
+        timeout_ms: Option<i32>,
+    ) -> Result<Vec<u8>, ServiceError> {
+        let mut assembler = ChunkAssembler::new();
+        loop {
+            let chunk_request = ServiceRequest {
+                payload: assembler.next_request(&request.payload),
+            };
+            let response = self.call(method, chunk_request, timeout_ms)?;
+            if assembler.push(&response.payload)? {
+                return Ok(assembler.into_inner());
+            }
         }
     }
+
+    /// Calls `method` without waiting for the response.
+    ///
+    /// Returns as soon as the request is sent; `callback` is invoked once
+    /// with the result of the call, as returned by [`call`](Self::call).
+    /// Responses are delivered on an eCAL thread; a call without response
+    /// within `timeout_ms` fails with [`ServiceError::Timeout`] on the timer
+    /// thread of the client (see [`crate::cancel`]). Callbacks must not
+    /// block, since they delay other responses.
+    ///
+    /// `timeout_ms` is passed to the server as deadline, if enabled with
+    /// [`with_deadline_propagation`](Self::with_deadline_propagation).
+    ///
+    /// The returned token cancels the call: a response arriving after
+    /// [`CancelToken::cancel`] is discarded without invoking `callback`.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`ServiceError`] if the method name is invalid or eCAL could
+    /// not issue the call; `callback` is not invoked then.
+    pub fn call_with_callback<F>(
+        &self,
+        method: &str,
+        request: ServiceRequest,
+        timeout_ms: Option<i32>,
+        callback: F,
+    ) -> Result<CancelToken, ServiceError>
+    where
+        F: FnOnce(Result<ServiceResponse, ServiceError>) + Send + 'static,
+    {
+        let token = CancelToken::new();
+        let cancelled = token.clone();
+        self.issue(
+            method,
+            request,
+            timeout_ms,
+            Box::new(move |result| {
+                if !cancelled.is_cancelled() {
+                    callback(result);
+                }
+            }),
+        )?;
+        Ok(token)
+    }
+
+    /// Calls `method` and returns a future resolving to the result of the
+    /// call, as returned by [`call`](Self::call).
+    ///
+    /// The future does not block a thread while waiting and can be awaited
+    /// on any executor. Dropping it cancels the call (see [`crate::cancel`]).
+    /// Without response within `timeout_ms` the future resolves to
+    /// [`ServiceError::Timeout`], also if the server never answers.
+    /// `timeout_ms` is passed to the server as deadline, if enabled with
+    /// [`with_deadline_propagation`](Self::with_deadline_propagation).
+    pub fn call_async(
+        &self,
+        method: &str,
+        request: ServiceRequest,
+        timeout_ms: Option<i32>,
+    ) -> ResponseFuture {
+        let token = CancelToken::new();
+        let slot = SharedSlot::default();
+
+        let (cancelled, completed) = (token.clone(), slot.clone());
+        let issued = self.issue(
+            method,
+            request,
+            timeout_ms,
+            Box::new(move |result| {
+                if !cancelled.is_cancelled() {
+                    cancel::complete(&completed, result);
+                }
+            }),
+        );
+        if let Err(error) = issued {
+            cancel::complete(&slot, Err(error));
+        }
+        ResponseFuture::new(slot, token)
+    }
+
+    /// Issues an asynchronous call of `method`, passing its result to `handler`.
+    ///
+    /// With a timeout, `handler` runs with the response or with
+    /// [`ServiceError::Timeout`] at the deadline, whichever comes first.
+    /// eCAL keeps the callback data of a call that is never answered, so the
+    /// handler is then dropped by the timer and eCAL only holds a weak
+    /// reference to it.
+    fn issue(
+        &self,
+        method: &str,
+        request: ServiceRequest,
+        timeout_ms: Option<i32>,
+        handler: ResponseHandler,
+    ) -> Result<(), ServiceError> {
+        let c_method =
+            CString::new(method).map_err(|_| ServiceError::InvalidMethod(method.to_string()))?;
+        let request = with_deadline(request, timeout_ms, self.propagate_deadline);
+        let start = Instant::now();
+        let deadline = timeout_ms
+            .filter(|timeout| *timeout > 0)
+            .map(|timeout| start + Duration::from_millis(timeout as u64));
+
+        // taken by the response or the timer, whichever comes first
+        let pending = Arc::new(Mutex::new(Some(handler)));
+        let response_handler: ResponseHandler = match deadline {
+            Some(deadline) => {
+                let pending = Arc::downgrade(&pending);
+                Box::new(move |result| {
+                    let result = match result {
+                        _ if Instant::now() > deadline => Err(ServiceError::Timeout),
+                        result => result.map(|response| response.timed(start)),
+                    };
+                    if let Some(handler) = pending.upgrade().and_then(|pending| take(&pending)) {
+                        handler(result);
+                    }
+                })
+            }
+            None => {
+                let pending = pending.clone();
+                Box::new(move |result| {
+                    if let Some(handler) = take(&pending) {
+                        handler(result.map(|response| response.timed(start)));
+                    }
+                })
+            }
+        };
+        let user_data = Box::into_raw(Box::new(response_handler)) as *mut c_void;
+
+        let result = unsafe {
+            eCAL_ClientInstance_CallWithCallbackAsync(
+                self.instance,
+                c_method.as_ptr(),
+                request.payload.as_ptr() as *const c_void,
+                request.payload.len(),
+                Some(Self::response_trampoline),
+                user_data,
+            )
+        };
+
+        if result != 0 {
+            // the call was not issued, so the handler will not run
+            drop(unsafe { Box::from_raw(user_data as *mut ResponseHandler) });
+            return Err(ServiceError::Transport(format!(
+                "failed to call method '{method}'"
+            )));
+        }
+        if let Some(deadline) = deadline {
+            cancel::at_deadline(deadline, move || {
+                if let Some(handler) = take(&pending) {
+                    handler(Err(ServiceError::Timeout));
+                }
+            });
+        }
+        Ok(())
+    }
+
+    unsafe extern "C" fn response_trampoline(
+        response: *const eCAL_SServiceResponse,
+        user_data: *mut c_void,
+    ) {
+        let handler = unsafe { Box::from_raw(user_data as *mut ResponseHandler) };
+        let response = if response.is_null() {
+            Err(ServiceError::Transport("no response".into()))
+        } else {
+            ServiceResponse::from_struct(unsafe { &*response }).into_result()
+        };
+        guard(
+            || "service response callback".to_string(),
+            move || handler(response),
+        );
+    }
+}
+
+/// Takes the handler of a call that has not completed yet.
+fn take(pending: &Mutex<Option<ResponseHandler>>) -> Option<ResponseHandler> {
+    pending.lock().unwrap_or_else(|e| e.into_inner()).take()
 }
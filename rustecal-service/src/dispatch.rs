@@ -0,0 +1,162 @@
+//! Running method handlers on a worker pool.
+//!
+//! By default a handler runs on the eCAL thread that delivered the call.
+//! With [`ServiceServer::set_worker_pool`], handlers run on the threads of a
+//! [`WorkerPool`] instead, which bounds how many handlers run at the same
+//! time and can be shared between servers:
+//!
+//! ```ignore
+//! let pool = Arc::new(WorkerPool::new(4)?);
+//! server.set_worker_pool(Some(pool.clone()));
+//! server.set_method_concurrency("write", Some(1)); // not reentrant
+//! ```
+//!
+//! eCAL's C API expects the response when its callback returns, so the
+//! delivering eCAL thread waits for the handler in either case. The pool
+//! therefore adds no concurrency beyond eCAL's own delivery threads: if eCAL
+//! delivers the calls of a server on one thread, a slow method still holds
+//! up the calls of every other method of that server. What the pool adds is
+//! a bound on the handlers running at the same time, shared between the
+//! servers using it.
+//!
+//! [`ServiceServer::set_method_concurrency`] limits how many calls of one
+//! method run at the same time, e.g. `1` for a handler that is not
+//! reentrant. Calls beyond the limit wait for a running one to finish
+//! before they are handed to the pool, so they do not occupy a worker.
+//!
+//! [`ServiceServer::set_worker_pool`]: crate::server::ServiceServer::set_worker_pool
+//! [`ServiceServer::set_method_concurrency`]: crate::server::ServiceServer::set_method_concurrency
+
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::sync::{Arc, Condvar, Mutex};
+use std::thread::{self, JoinHandle};
+
+type Job = Box<dyn FnOnce() + Send + 'static>;
+
+/// A fixed number of threads running method handlers.
+///
+/// Dropping the pool lets the workers finish the queued handlers and joins them.
+pub struct WorkerPool {
+    sender: Mutex<Option<Sender<Job>>>,
+    workers: Vec<JoinHandle<()>>,
+}
+
+impl WorkerPool {
+    /// Starts a pool of `threads` workers.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `Err(String)` if `threads` is zero or a thread could not be spawned.
+    pub fn new(threads: usize) -> Result<Self, String> {
+        if threads == 0 {
+            return Err("a worker pool needs at least one thread".into());
+        }
+        let (sender, receiver) = mpsc::channel::<Job>();
+        let receiver = Arc::new(Mutex::new(receiver));
+        let workers = (0..threads)
+            .map(|index| {
+                let receiver = receiver.clone();
+                thread::Builder::new()
+                    .name(format!("rustecal-service-{index}"))
+                    .spawn(move || Self::work(&receiver))
+                    .map_err(|e| format!("Failed to spawn service worker: {e}"))
+            })
+            .collect::<Result<_, _>>()?;
+        Ok(Self {
+            sender: Mutex::new(Some(sender)),
+            workers,
+        })
+    }
+
+    fn work(receiver: &Mutex<Receiver<Job>>) {
+        loop {
+            // the lock is released before the job runs
+            let job = receiver.lock().unwrap_or_else(|e| e.into_inner()).recv();
+            match job {
+                // a panicking job drops its result sender; the worker keeps running
+                Ok(job) => {
+                    let _ = panic::catch_unwind(AssertUnwindSafe(job));
+                }
+                Err(_) => break,
+            }
+        }
+    }
+
+    /// Returns the number of worker threads.
+    pub fn threads(&self) -> usize {
+        self.workers.len()
+    }
+
+    /// Runs `f` on a worker and waits for its result.
+    ///
+    /// Returns `None` if `f` panicked or the pool is shutting down.
+    pub fn run<R, F>(&self, f: F) -> Option<R>
+    where
+        R: Send + 'static,
+        F: FnOnce() -> R + Send + 'static,
+    {
+        let (result_sender, result) = mpsc::sync_channel(1);
+        let job: Job = Box::new(move || {
+            let _ = result_sender.send(f());
+        });
+        self.sender
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .as_ref()?
+            .send(job)
+            .ok()?;
+        result.recv().ok()
+    }
+}
+
+impl Drop for WorkerPool {
+    fn drop(&mut self) {
+        self.sender.lock().unwrap_or_else(|e| e.into_inner()).take();
+        for worker in self.workers.drain(..) {
+            let _ = worker.join();
+        }
+    }
+}
+
+/// Limits the number of concurrent calls of one method.
+pub(crate) struct ConcurrencyLimit {
+    limit: usize,
+    running: Mutex<usize>,
+    finished: Condvar,
+}
+
+/// A running call, counted against its [`ConcurrencyLimit`] until dropped.
+pub(crate) struct Permit<'a> {
+    limit: &'a ConcurrencyLimit,
+}
+
+impl ConcurrencyLimit {
+    pub(crate) fn new(limit: usize) -> Self {
+        Self {
+            limit: limit.max(1),
+            running: Mutex::new(0),
+            finished: Condvar::new(),
+        }
+    }
+
+    /// Blocks until fewer than `limit` calls are running.
+    pub(crate) fn acquire(&self) -> Permit<'_> {
+        let mut running = self.running.lock().unwrap_or_else(|e| e.into_inner());
+        while *running >= self.limit {
+            running = self
+                .finished
+                .wait(running)
+                .unwrap_or_else(|e| e.into_inner());
+        }
+        *running += 1;
+        Permit { limit: self }
+    }
+}
+
+impl Drop for Permit<'_> {
+    fn drop(&mut self) {
+        *self.limit.running.lock().unwrap_or_else(|e| e.into_inner()) -= 1;
+        self.limit.finished.notify_one();
+    }
+}
@@ -0,0 +1,68 @@
+//! Errors of service calls and method handlers.
+//!
+//! The call APIs of [`ServiceClient`](crate::ServiceClient) and
+//! [`ClientInstance`](crate::ClientInstance) return `Err(ServiceError)` when
+//! a call did not produce a response, telling apart timeouts, missing
+//! servers, invalid requests, transport failures and calls failed by the
+//! server.
+//!
+//! A handler registered with
+//! [`ServiceServer::add_fallible_method`](crate::ServiceServer::add_fallible_method)
+//! returns `Err(ServiceError)` to fail a call. The client receives the call
+//! with `CallState::Failed` and the message as [`ServiceError::Failed`].
+//!
+//! eCAL transports no error text for failed calls, so the message travels as
+//! the response payload of the failed call.
+
+use thiserror::Error;
+
+/// A service call that did not produce a response.
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum ServiceError {
+    /// The server failed the call, e.g. because its handler returned an error.
+    #[error("{0}")]
+    Failed(String),
+    /// The server did not answer within the timeout.
+    #[error("service call timed out")]
+    Timeout,
+    /// No server of the service is connected.
+    #[error("no service server available")]
+    NoInstance,
+    /// The method name is not a valid C string.
+    #[error("invalid method name '{0}'")]
+    InvalidMethod(String),
+    /// A request or response could not be encoded or decoded.
+    #[error("serialization failed: {0}")]
+    Serialization(String),
+    /// eCAL could not perform the call.
+    #[error("transport error: {0}")]
+    Transport(String),
+}
+
+impl ServiceError {
+    /// Creates an error failing a call with `message`, see [`ServiceError::Failed`].
+    pub fn new(message: impl Into<String>) -> Self {
+        Self::Failed(message.into())
+    }
+
+    /// Returns `true` for errors that may not occur when the call is
+    /// repeated: timeouts, a missing server and transport failures.
+    pub fn is_transient(&self) -> bool {
+        matches!(
+            self,
+            ServiceError::Timeout | ServiceError::NoInstance | ServiceError::Transport(_)
+        )
+    }
+}
+
+impl From<String> for ServiceError {
+    fn from(message: String) -> Self {
+        Self::Failed(message)
+    }
+}
+
+impl From<&str> for ServiceError {
+    fn from(message: &str) -> Self {
+        Self::new(message)
+    }
+}
@@ -0,0 +1,155 @@
+//! Connection events of service servers and clients.
+//!
+//! eCAL reports to a server when a client connects or disconnects, and to a
+//! client when a server appears, disappears or does not answer in time. A
+//! callback set with [`ServiceServer::set_event_callback`] or
+//! [`ServiceClient::set_event_callback`] receives these as [`ServiceEvent`]s,
+//! e.g. to log new clients or to notice a lost server before a call fails.
+//!
+//! As for publishers and subscribers, the eCAL C API passes no user data to
+//! the event callback, so events are dispatched by service name: servers (or
+//! clients) of the same service within one process see each other's events.
+//!
+//! [`ServiceServer::set_event_callback`]: crate::server::ServiceServer::set_event_callback
+//! [`ServiceClient::set_event_callback`]: crate::client::ServiceClient::set_event_callback
+
+use rustecal_core::error_hook::guard;
+use rustecal_core::types::EntityId;
+use rustecal_sys::{eCAL_SClientEventCallbackData, eCAL_SServerEventCallbackData, eCAL_SServiceId};
+use std::collections::BTreeMap;
+use std::ffi::CStr;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, RwLock};
+
+/// What happened between a service server and a client.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ServiceEventType {
+    /// The partner connected.
+    Connected,
+    /// The partner disconnected.
+    Disconnected,
+    /// A server did not answer a call in time (client events only).
+    Timeout,
+}
+
+/// A connection event of a service server or client.
+#[derive(Debug, Clone)]
+pub struct ServiceEvent {
+    pub event_type: ServiceEventType,
+    pub service_name: String,
+    /// The service entity reported by eCAL.
+    pub service_id: EntityId,
+    /// eCAL time of the event (microseconds).
+    pub time: i64,
+}
+
+type Callback = Arc<dyn Fn(&ServiceEvent) + Send + Sync + 'static>;
+
+/// Event callbacks of servers or clients, keyed by a registration token.
+pub(crate) struct EventRegistry {
+    next_token: AtomicU64,
+    callbacks: RwLock<BTreeMap<u64, (String, Callback)>>,
+}
+
+impl EventRegistry {
+    const fn new() -> Self {
+        Self {
+            next_token: AtomicU64::new(0),
+            callbacks: RwLock::new(BTreeMap::new()),
+        }
+    }
+
+    /// Registers `callback` for events of `service_name` and returns its token.
+    pub(crate) fn register(&self, service_name: &str, callback: Callback) -> u64 {
+        let token = self.next_token.fetch_add(1, Ordering::Relaxed);
+        self.callbacks
+            .write()
+            .unwrap_or_else(|e| e.into_inner())
+            .insert(token, (service_name.to_string(), callback));
+        token
+    }
+
+    pub(crate) fn unregister(&self, token: u64) {
+        self.callbacks
+            .write()
+            .unwrap_or_else(|e| e.into_inner())
+            .remove(&token);
+    }
+
+    /// Invokes all callbacks of the event's service.
+    fn dispatch(&self, event: &ServiceEvent) {
+        // callbacks may create or drop servers and clients, so they run without the lock
+        let callbacks: Vec<Callback> = self
+            .callbacks
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .values()
+            .filter(|(service, _)| *service == event.service_name)
+            .map(|(_, callback)| callback.clone())
+            .collect();
+        for callback in callbacks {
+            guard(
+                || format!("event callback of service '{}'", event.service_name),
+                || callback(event),
+            );
+        }
+    }
+}
+
+pub(crate) static SERVER_EVENTS: EventRegistry = EventRegistry::new();
+pub(crate) static CLIENT_EVENTS: EventRegistry = EventRegistry::new();
+
+fn event_of(service_id: &eCAL_SServiceId, event_type: ServiceEventType, time: i64) -> ServiceEvent {
+    let service_name = if service_id.service_name.is_null() {
+        String::new()
+    } else {
+        unsafe { CStr::from_ptr(service_id.service_name) }
+            .to_string_lossy()
+            .into_owned()
+    };
+    ServiceEvent {
+        event_type,
+        service_name,
+        service_id: service_id.service_id.into(),
+        time,
+    }
+}
+
+/// Event callback installed on every server.
+pub(crate) unsafe extern "C" fn server_event_trampoline(
+    service_id: *const eCAL_SServiceId,
+    data: *const eCAL_SServerEventCallbackData,
+) {
+    if service_id.is_null() || data.is_null() {
+        return;
+    }
+    let (service_id, data) = unsafe { (&*service_id, &*data) };
+    let event_type = match data.type_ {
+        rustecal_sys::eCAL_eServerEvent_eCAL_eServerEvent_connected => ServiceEventType::Connected,
+        rustecal_sys::eCAL_eServerEvent_eCAL_eServerEvent_disconnected => {
+            ServiceEventType::Disconnected
+        }
+        _ => return,
+    };
+    SERVER_EVENTS.dispatch(&event_of(service_id, event_type, data.time));
+}
+
+/// Event callback installed on every client.
+pub(crate) unsafe extern "C" fn client_event_trampoline(
+    service_id: *const eCAL_SServiceId,
+    data: *const eCAL_SClientEventCallbackData,
+) {
+    if service_id.is_null() || data.is_null() {
+        return;
+    }
+    let (service_id, data) = unsafe { (&*service_id, &*data) };
+    let event_type = match data.type_ {
+        rustecal_sys::eCAL_eClientEvent_eCAL_eClientEvent_connected => ServiceEventType::Connected,
+        rustecal_sys::eCAL_eClientEvent_eCAL_eClientEvent_disconnected => {
+            ServiceEventType::Disconnected
+        }
+        rustecal_sys::eCAL_eClientEvent_eCAL_eClientEvent_timeout => ServiceEventType::Timeout,
+        _ => return,
+    };
+    CLIENT_EVENTS.dispatch(&event_of(service_id, event_type, data.time));
+}
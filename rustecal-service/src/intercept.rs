@@ -0,0 +1,105 @@
+//! Interceptors wrapping every method call of a server or client.
+//!
+//! An interceptor receives a call together with the rest of the chain and
+//! decides whether and how to pass it on, so logging, metrics or access
+//! checks are written once instead of in every handler:
+//!
+//! ```ignore
+//! server.add_interceptor(|info, request, next| {
+//!     let start = Instant::now();
+//!     let response = next.run(info.clone(), request);
+//!     println!("{} took {:?}", info.method_name, start.elapsed());
+//!     response
+//! });
+//! ```
+//!
+//! Interceptors run in the order they were added, the first one outermost.
+//! Server interceptors wrap the handlers, not the introspection methods
+//! answered by every server (see [`crate::versioning`]); client
+//! interceptors wrap each attempt of [`ServiceClient::call`] and the calls
+//! built on it.
+//!
+//! Data that is not part of the payload, such as an auth token, travels in
+//! the header blob of a request: client interceptors attach it with
+//! [`encode_header_request`](crate::types::encode_header_request) and server
+//! interceptors read it from [`MethodInfo::header`].
+//!
+//! [`ServiceClient::call`]: crate::client::ServiceClient::call
+
+use crate::error::ServiceError;
+use crate::response::ServiceResponse;
+use crate::types::{MethodInfo, ServiceRequest};
+use std::sync::Arc;
+
+/// An interceptor of a [`ServiceServer`](crate::ServiceServer).
+pub type ServerInterceptor = Arc<
+    dyn Fn(MethodInfo, &[u8], ServerNext<'_>) -> Result<Vec<u8>, ServiceError>
+        + Send
+        + Sync
+        + 'static,
+>;
+
+/// An interceptor of a [`ServiceClient`](crate::ServiceClient).
+pub type ClientInterceptor = Arc<
+    dyn Fn(&str, ServiceRequest, ClientNext<'_>) -> Result<ServiceResponse, ServiceError>
+        + Send
+        + Sync
+        + 'static,
+>;
+
+/// The handler at the end of a server's interceptor chain.
+type ChainHandler<'a> = &'a (dyn Fn(MethodInfo, &[u8]) -> Result<Vec<u8>, ServiceError> + Sync);
+
+/// The rest of a server's interceptor chain, ending at the handler.
+pub struct ServerNext<'a> {
+    interceptors: &'a [ServerInterceptor],
+    handler: ChainHandler<'a>,
+}
+
+impl<'a> ServerNext<'a> {
+    pub(crate) fn new(interceptors: &'a [ServerInterceptor], handler: ChainHandler<'a>) -> Self {
+        Self {
+            interceptors,
+            handler,
+        }
+    }
+
+    /// Passes the call to the next interceptor, or to the handler.
+    pub fn run(self, info: MethodInfo, request: &[u8]) -> Result<Vec<u8>, ServiceError> {
+        match self.interceptors.split_first() {
+            Some((interceptor, rest)) => {
+                interceptor(info, request, ServerNext::new(rest, self.handler))
+            }
+            None => (self.handler)(info, request),
+        }
+    }
+}
+
+/// The rest of a client's interceptor chain, ending at the eCAL call.
+pub struct ClientNext<'a> {
+    interceptors: &'a [ClientInterceptor],
+    call: &'a dyn Fn(&str, ServiceRequest) -> Result<ServiceResponse, ServiceError>,
+}
+
+impl<'a> ClientNext<'a> {
+    pub(crate) fn new(
+        interceptors: &'a [ClientInterceptor],
+        call: &'a dyn Fn(&str, ServiceRequest) -> Result<ServiceResponse, ServiceError>,
+    ) -> Self {
+        Self { interceptors, call }
+    }
+
+    /// Passes the call to the next interceptor, or issues it.
+    pub fn run(
+        self,
+        method: &str,
+        request: ServiceRequest,
+    ) -> Result<ServiceResponse, ServiceError> {
+        match self.interceptors.split_first() {
+            Some((interceptor, rest)) => {
+                interceptor(method, request, ClientNext::new(rest, self.call))
+            }
+            None => (self.call)(method, request),
+        }
+    }
+}
@@ -4,24 +4,59 @@
 //!
 //! ## Functionality
 //! - `ServiceClient`: send requests to one or many services.
+//! - Per-instance calls with one result per server (`ServiceClient::call_each`, `call_each_parallel`).
+//! - Non-blocking calls with a response callback or future, cancellable (`ClientInstance::call_with_callback`, `call_async`).
+//! - Deadlines passed to the server (`ServiceClient::set_deadline_propagation`, `MethodInfo::deadline`).
 //! - `ServiceServer`: host services, handle requests with callbacks.
+//! - Handlers on a worker pool with per-method concurrency limits (`ServiceServer::set_worker_pool`).
+//! - Handlers failing calls with a message (`ServiceServer::add_fallible_method`, `ServiceError`).
+//! - `TypedServiceServer`: methods taking and returning typed messages (feature `typed`).
+//! - Connection events of servers and clients (`set_event_callback`, `ServiceEvent`).
+//! - Large responses transferred in chunks (`ServiceServer::add_chunked_method`, `ServiceClient::call_chunked`).
+//! - Interceptors wrapping every call of a server or client (`add_interceptor`).
+//! - Retrying transiently failed calls (`ServiceClient::set_retry_policy`, `RetryPolicy`).
+//! - Interface version negotiation (`ServiceClient::negotiate`).
+//! - Async method handlers on a tokio runtime (`ServiceServer::add_async_method`, feature `tokio`).
 //!
 //! ## Example
 //! '''rust
 //! use rustecal_service::ServiceClient;
 //! let client = ServiceClient::new("mirror_service").unwrap();
-//! let response = client.call("Hello!".as_bytes(), std::time::Duration::from_millis(500));
+//! let request = rustecal_service::ServiceRequest { payload: b"Hello!".to_vec() };
+//! let response = client.call("echo", request, Some(500));
 //! '''
 
+pub mod cancel;
+pub mod chunked;
 pub mod client;
 pub mod client_instance;
+pub mod dispatch;
+pub mod error;
+pub mod events;
+pub mod intercept;
 pub mod response;
+pub mod retry;
 pub mod server;
+#[cfg(feature = "typed")]
+pub mod typed_server;
 pub mod types;
+pub mod versioning;
+
+// Re-export the data type of typed methods
+pub use rustecal_core::types::DataTypeInfo;
 
 // Public API
+pub use cancel::{CancelToken, ResponseFuture};
 pub use client::ServiceClient;
 pub use client_instance::ClientInstance;
+pub use dispatch::WorkerPool;
+pub use error::ServiceError;
+pub use events::{ServiceEvent, ServiceEventType};
+pub use intercept::{ClientNext, ServerNext};
+pub use retry::{Backoff, RetryPolicy};
 pub use server::ServiceServer;
+#[cfg(feature = "typed")]
+pub use typed_server::TypedServiceServer;
 pub use types::ServiceRequest;
 pub use types::ServiceResponse;
+pub use versioning::NegotiationError;
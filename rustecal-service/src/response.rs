@@ -1,23 +1,43 @@
+use crate::error::ServiceError;
 use crate::types::{CallState, ServiceId};
 use rustecal_sys::*;
 use std::ffi::CStr;
+use std::time::{Duration, Instant};
 
 /// Represents a structured response to a service request,
 /// primarily used by clients to parse returned data.
 #[derive(Debug, Clone)]
 pub struct ServiceResponse {
     pub success: bool,
+    pub call_state: CallState,
     pub server_id: ServiceId,
     pub error_msg: Option<String>,
     pub payload: Vec<u8>,
+    /// Host name of the answering server.
+    pub server_host: String,
+    /// Process id of the answering server.
+    pub server_process_id: i32,
+    /// Time from issuing the call to receiving the response, measured by
+    /// the client; `None` for responses not received by a call.
+    pub round_trip: Option<Duration>,
 }
 
 impl ServiceResponse {
     /// Parses a raw FFI struct into a safe Rust response object.
     pub fn from_struct(response: &eCAL_SServiceResponse) -> Self {
-        let success = CallState::from(response.call_state).is_success();
+        let call_state = CallState::from(response.call_state);
+        let success = call_state.is_success();
 
         let server_id = unsafe { ServiceId::from_ffi(&response.server_id) };
+        // the host name is owned by the FFI struct, so it is copied
+        let entity = &response.server_id.service_id;
+        let server_host = if entity.host_name.is_null() {
+            String::new()
+        } else {
+            unsafe { CStr::from_ptr(entity.host_name) }
+                .to_string_lossy()
+                .into_owned()
+        };
 
         let error_msg = if response.error_msg.is_null() {
             None
@@ -29,7 +49,7 @@ impl ServiceResponse {
             })
         };
 
-        let payload = if response.response.is_null() || response.response_length == 0 {
+        let mut payload = if response.response.is_null() || response.response_length == 0 {
             vec![]
         } else {
             unsafe {
@@ -38,11 +58,43 @@ impl ServiceResponse {
             }
         };
 
+        // a failing handler sends its error message as the payload
+        let error_msg = if !success && !payload.is_empty() {
+            Some(String::from_utf8_lossy(&std::mem::take(&mut payload)).into_owned())
+        } else {
+            error_msg
+        };
+
         Self {
             success,
+            call_state,
             server_id,
             error_msg,
             payload,
+            server_host,
+            server_process_id: entity.process_id,
+            round_trip: None,
+        }
+    }
+
+    /// Sets the round-trip time of a call issued at `start`.
+    pub(crate) fn timed(mut self, start: Instant) -> Self {
+        self.round_trip = Some(start.elapsed());
+        self
+    }
+
+    /// Converts a response that is not successful into the matching [`ServiceError`].
+    pub fn into_result(self) -> Result<Self, ServiceError> {
+        let message = || {
+            self.error_msg
+                .clone()
+                .unwrap_or_else(|| "call failed".into())
+        };
+        match self.call_state {
+            CallState::Executed => Ok(self),
+            CallState::Timeout => Err(ServiceError::Timeout),
+            CallState::Failed => Err(ServiceError::Failed(message())),
+            CallState::None | CallState::Unknown(_) => Err(ServiceError::Transport(message())),
         }
     }
 }
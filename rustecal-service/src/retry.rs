@@ -0,0 +1,103 @@
+//! Retrying service calls that failed transiently.
+//!
+//! Right after startup a client often has no server yet, so its first calls
+//! fail with [`ServiceError::NoInstance`]. A [`RetryPolicy`] set with
+//! [`ServiceClient::set_retry_policy`] repeats such calls with a backoff:
+//!
+//! ```ignore
+//! client.set_retry_policy(Some(RetryPolicy::default()));
+//! let response = client.call("echo", request, Some(1000))?; // up to 3 attempts
+//! ```
+//!
+//! By default, calls are retried on errors that may go away by themselves
+//! ([`ServiceError::is_transient`]); `retry_on` selects other errors.
+//!
+//! [`ServiceClient::set_retry_policy`]: crate::client::ServiceClient::set_retry_policy
+
+use crate::error::ServiceError;
+use std::thread;
+use std::time::Duration;
+
+/// Wait between two attempts of a call.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Backoff {
+    /// The same delay after every attempt.
+    Fixed(Duration),
+    /// A delay doubling after every attempt, starting at `initial` and capped at `max`.
+    Exponential { initial: Duration, max: Duration },
+}
+
+impl Backoff {
+    /// Returns the delay after the failed attempt `attempt` (starting at 1).
+    pub fn delay(&self, attempt: u32) -> Duration {
+        match *self {
+            Backoff::Fixed(delay) => delay,
+            Backoff::Exponential { initial, max } => {
+                let factor = 1u32
+                    .checked_shl(attempt.saturating_sub(1))
+                    .unwrap_or(u32::MAX);
+                initial.saturating_mul(factor).min(max)
+            }
+        }
+    }
+}
+
+/// How often and on which errors a service call is repeated.
+#[derive(Debug, Clone, Copy)]
+pub struct RetryPolicy {
+    /// Total number of attempts, including the first one.
+    pub attempts: u32,
+    pub backoff: Backoff,
+    /// Returns `true` for errors that are retried.
+    pub retry_on: fn(&ServiceError) -> bool,
+}
+
+impl Default for RetryPolicy {
+    /// Three attempts with an exponential backoff from 100 ms, retrying transient errors.
+    fn default() -> Self {
+        Self {
+            attempts: 3,
+            backoff: Backoff::Exponential {
+                initial: Duration::from_millis(100),
+                max: Duration::from_secs(2),
+            },
+            retry_on: ServiceError::is_transient,
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// Creates a policy making up to `attempts` attempts, waiting `backoff`
+    /// in between and retrying transient errors.
+    pub fn new(attempts: u32, backoff: Backoff) -> Self {
+        Self {
+            attempts,
+            backoff,
+            retry_on: ServiceError::is_transient,
+        }
+    }
+
+    /// Retries the errors selected by `retry_on` instead of the transient ones.
+    pub fn retry_on(mut self, retry_on: fn(&ServiceError) -> bool) -> Self {
+        self.retry_on = retry_on;
+        self
+    }
+
+    /// Runs `call` until it succeeds, fails with an error that is not
+    /// retried, or the attempts are used up; returns the last result.
+    pub fn run<T>(
+        &self,
+        mut call: impl FnMut() -> Result<T, ServiceError>,
+    ) -> Result<T, ServiceError> {
+        let mut attempt = 1;
+        loop {
+            match call() {
+                Err(error) if attempt < self.attempts && (self.retry_on)(&error) => {
+                    thread::sleep(self.backoff.delay(attempt));
+                    attempt += 1;
+                }
+                result => return result,
+            }
+        }
+    }
+}
@@ -1,52 +1,345 @@
-use crate::types::{MethodInfo, ServiceCallback};
+use crate::chunked::ChunkStore;
+use crate::dispatch::{ConcurrencyLimit, WorkerPool};
+use crate::error::ServiceError;
+use crate::events::{SERVER_EVENTS, ServiceEvent, server_event_trampoline};
+use crate::intercept::{ServerInterceptor, ServerNext};
+use crate::types::{
+    FallibleServiceCallback, MethodInfo, PrefixCallback, RegisteredMethod, ServiceCallback,
+    decode_deadline_request, decode_header_request, decode_path_request,
+};
+use crate::versioning::{
+    METHODS_METHOD, VERSION_METHOD, encode_methods, encode_version, is_introspection_method,
+};
+use rustecal_core::callback_budget::CallbackBudget;
+use rustecal_core::error_hook::guard;
+use rustecal_core::handle_registry::{HandleKind, HandleRegistry, TrackedHandle};
+use rustecal_core::types::DataTypeInfo;
 use rustecal_sys::*;
 use std::collections::HashMap;
 use std::ffi::{CStr, CString};
 use std::os::raw::{c_int, c_void};
 use std::ptr;
-use std::sync::{Arc, Mutex};
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::{Arc, Mutex, RwLock};
+use std::time::Instant;
 
-type SharedCallback = Arc<Mutex<HashMap<String, ServiceCallback>>>;
+/// Method handler; errors fail the call.
+pub(crate) type Handler = FallibleServiceCallback;
+
+/// A registered method.
+struct Method {
+    handler: Handler,
+    /// Request and response types of typed methods.
+    types: Option<(DataTypeInfo, DataTypeInfo)>,
+}
+
+/// State shared between the server and the C dispatch callback.
+#[derive(Default)]
+struct ServerState {
+    callbacks: Mutex<HashMap<String, Arc<Method>>>,
+    budget: RwLock<Option<CallbackBudget>>,
+    version: AtomicU32,
+    pool: RwLock<Option<Arc<WorkerPool>>>,
+    limits: Mutex<HashMap<String, Arc<ConcurrencyLimit>>>,
+    interceptors: RwLock<Vec<ServerInterceptor>>,
+}
 
 /// Represents a service server that can handle RPC-style requests.
 pub struct ServiceServer {
     handle: *mut eCAL_ServiceServer,
-    callbacks: SharedCallback,
+    service_name: String,
+    state: Arc<ServerState>,
+    event_token: Option<u64>,
+    _tracked: Option<TrackedHandle>,
 }
 
 impl ServiceServer {
     pub fn new(service_name: &str) -> Result<Self, String> {
         let c_service_name = CString::new(service_name).map_err(|_| "Invalid service name")?;
 
-        let callbacks: SharedCallback = Arc::new(Mutex::new(HashMap::new()));
-        let handle = unsafe { eCAL_ServiceServer_New(c_service_name.as_ptr(), None) };
+        let state = Arc::new(ServerState::default());
+        let handle = unsafe {
+            eCAL_ServiceServer_New(c_service_name.as_ptr(), Some(server_event_trampoline))
+        };
         if handle.is_null() {
             return Err("Failed to create eCAL_ServiceServer".into());
         }
 
-        Ok(Self { handle, callbacks })
+        let server = Self {
+            handle,
+            service_name: service_name.to_string(),
+            state,
+            event_token: None,
+            _tracked: HandleRegistry::track(HandleKind::ServiceServer, service_name),
+        };
+        server.register_method(VERSION_METHOD, None)?;
+        server.register_method(METHODS_METHOD, None)?;
+        Ok(server)
+    }
+
+    /// Registers a callback for [`ServiceEvent`]s, replacing a previous one.
+    ///
+    /// The callback runs on an eCAL thread whenever a client connects or
+    /// disconnects. See [`crate::events`] for how events of servers sharing
+    /// a service name within one process are dispatched.
+    pub fn set_event_callback<F>(&mut self, callback: F)
+    where
+        F: Fn(&ServiceEvent) + Send + Sync + 'static,
+    {
+        if let Some(token) = self.event_token.take() {
+            SERVER_EVENTS.unregister(token);
+        }
+        self.event_token = Some(SERVER_EVENTS.register(&self.service_name, Arc::new(callback)));
+    }
+
+    /// Sets the interface version reported to [`ServiceClient::negotiate`](crate::ServiceClient::negotiate).
+    ///
+    /// Increase it whenever the request or response format of a method
+    /// changes incompatibly. Defaults to `0`.
+    pub fn set_version(&mut self, version: u32) {
+        self.state.version.store(version, Ordering::Relaxed);
+    }
+
+    /// Sets an execution time budget applied to every method handler.
+    ///
+    /// Handler invocations taking longer than the budget are reported
+    /// (see [`CallbackBudget`]). Passing `None` disables the measurement.
+    pub fn set_callback_budget(&mut self, budget: Option<CallbackBudget>) {
+        *self.state.budget.write().unwrap() = budget;
+    }
+
+    /// Runs method handlers on `pool` instead of the eCAL thread delivering
+    /// the call; `None` runs them on the eCAL thread (the default).
+    ///
+    /// The delivering eCAL thread waits for the handler to return the
+    /// response, so the pool adds no concurrency beyond eCAL's own delivery
+    /// threads; it only bounds how many handlers run at the same time. See
+    /// [`crate::dispatch`].
+    pub fn set_worker_pool(&mut self, pool: Option<Arc<WorkerPool>>) {
+        *self.state.pool.write().unwrap() = pool;
+    }
+
+    /// Limits how many calls of `method` run at the same time; `None`
+    /// removes the limit. A limit of `1` serializes the calls of a handler
+    /// that is not reentrant.
+    ///
+    /// The limit may be set before or after the method is added.
+    pub fn set_method_concurrency(&mut self, method: &str, limit: Option<usize>) {
+        let mut limits = self.state.limits.lock().unwrap();
+        match limit {
+            Some(limit) => {
+                limits.insert(method.to_string(), Arc::new(ConcurrencyLimit::new(limit)));
+            }
+            None => {
+                limits.remove(method);
+            }
+        }
+    }
+
+    /// Adds an interceptor wrapping the handlers of all methods.
+    ///
+    /// `interceptor` receives each call with the rest of the chain and
+    /// passes it on with [`ServerNext::run`], or answers it itself, e.g.
+    /// rejecting calls without a valid token in [`MethodInfo::header`].
+    /// Interceptors run in the order they were added, on the thread running
+    /// the handler. See [`crate::intercept`].
+    pub fn add_interceptor<F>(&mut self, interceptor: F)
+    where
+        F: Fn(MethodInfo, &[u8], ServerNext<'_>) -> Result<Vec<u8>, ServiceError>
+            + Send
+            + Sync
+            + 'static,
+    {
+        self.state
+            .interceptors
+            .write()
+            .unwrap()
+            .push(Arc::new(interceptor));
     }
 
     pub fn add_method(&mut self, method: &str, callback: ServiceCallback) -> Result<(), String> {
+        self.add_handler(
+            method,
+            Box::new(move |info, request| Ok(callback(info, request))),
+            None,
+        )
+    }
+
+    /// Registers a handler for `method` that can fail the call, replacing a
+    /// previous handler.
+    ///
+    /// An `Err` is reported to the client as a failed call (`CallState::Failed`)
+    /// carrying the error message, see [`crate::error`].
+    pub fn add_fallible_method(
+        &mut self,
+        method: &str,
+        callback: FallibleServiceCallback,
+    ) -> Result<(), String> {
+        self.add_handler(method, callback, None)
+    }
+
+    /// Like [`add_fallible_method`](Self::add_fallible_method), but advertises
+    /// the request and response types of the method to eCAL, so monitoring
+    /// tools show them.
+    pub fn add_typed_method(
+        &mut self,
+        method: &str,
+        request_type: DataTypeInfo,
+        response_type: DataTypeInfo,
+        callback: FallibleServiceCallback,
+    ) -> Result<(), String> {
+        self.add_handler(method, callback, Some((request_type, response_type)))
+    }
+
+    /// Registers an async handler for `method`, replacing a previous handler.
+    ///
+    /// Must be called within a tokio runtime; calls are handled by futures
+    /// spawned on that runtime, so a handler can await I/O such as database
+    /// lookups. eCAL's C API expects the response when its callback returns,
+    /// so the eCAL service thread waits for the future, but no runtime
+    /// thread is blocked. A handler that panics fails the call.
+    ///
+    /// The handler receives a copy of the request, as the future outlives
+    /// eCAL's request buffer.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `Err(String)` if there is no current tokio runtime, the
+    /// method name is reserved or eCAL rejects the registration.
+    #[cfg(feature = "tokio")]
+    pub fn add_async_method<F, Fut>(&mut self, method: &str, handler: F) -> Result<(), String>
+    where
+        F: Fn(MethodInfo, Vec<u8>) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Vec<u8>> + Send + 'static,
+    {
+        let runtime = tokio::runtime::Handle::try_current()
+            .map_err(|_| format!("Async method '{method}' registered outside a tokio runtime"))?;
+        self.add_handler(
+            method,
+            Box::new(move |info, request| {
+                let (sender, receiver) = std::sync::mpsc::sync_channel(1);
+                let response = handler(info, request.to_vec());
+                runtime.spawn(async move {
+                    let _ = sender.send(response.await);
+                });
+                // a panicking handler drops the sender
+                receiver
+                    .recv()
+                    .map_err(|_| ServiceError::new("async handler did not complete"))
+            }),
+            None,
+        )
+    }
+
+    /// Removes the handler of `method`, so eCAL no longer delivers its calls.
+    ///
+    /// Returns `false` if no handler was registered for `method`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `Err(String)` if the method name is reserved or eCAL fails
+    /// to remove the method.
+    pub fn remove_method(&mut self, method: &str) -> Result<bool, String> {
+        if is_introspection_method(method) {
+            return Err(format!("Method name '{method}' is reserved"));
+        }
+        if self
+            .state
+            .callbacks
+            .lock()
+            .unwrap()
+            .remove(method)
+            .is_none()
+        {
+            return Ok(false);
+        }
+
         let c_method = CString::new(method).map_err(|_| "Invalid method name")?;
+        let result =
+            unsafe { eCAL_ServiceServer_RemoveMethodCallback(self.handle, c_method.as_ptr()) };
+        if result != 0 {
+            Err(format!("Failed to remove method '{method}'"))
+        } else {
+            Ok(true)
+        }
+    }
 
-        let mut method_info: eCAL_SServiceMethodInformation = unsafe { std::mem::zeroed() };
-        method_info.method_name = c_method.as_ptr();
+    /// Returns the registered methods, sorted by name, with the types of typed methods.
+    ///
+    /// The introspection methods answered by every server are not included.
+    pub fn methods(&self) -> Vec<RegisteredMethod> {
+        let mut methods: Vec<RegisteredMethod> = self
+            .state
+            .callbacks
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|(name, method)| {
+                let (request_type, response_type) = match &method.types {
+                    Some((request, response)) => (Some(request.clone()), Some(response.clone())),
+                    None => (None, None),
+                };
+                RegisteredMethod {
+                    method_name: name.clone(),
+                    request_type,
+                    response_type,
+                }
+            })
+            .collect();
+        methods.sort_unstable_by(|a, b| a.method_name.cmp(&b.method_name));
+        methods
+    }
+
+    /// Registers `handler` for `method`, advertising `types` (request,
+    /// response) to eCAL if given.
+    pub(crate) fn add_handler(
+        &mut self,
+        method: &str,
+        handler: Handler,
+        types: Option<(DataTypeInfo, DataTypeInfo)>,
+    ) -> Result<(), String> {
+        if is_introspection_method(method) {
+            return Err(format!("Method name '{method}' is reserved"));
+        }
 
-        self.callbacks
+        self.register_method(method, types.as_ref())?;
+        self.state
+            .callbacks
             .lock()
             .unwrap()
-            .insert(method.to_string(), callback);
+            .insert(method.to_string(), Arc::new(Method { handler, types }));
+        Ok(())
+    }
+
+    /// Registers `method` with eCAL, routing its calls to [`Self::dispatch`].
+    fn register_method(
+        &self,
+        method: &str,
+        types: Option<&(DataTypeInfo, DataTypeInfo)>,
+    ) -> Result<(), String> {
+        let c_method = CString::new(method).map_err(|_| "Invalid method name")?;
+
+        let mut method_info: eCAL_SServiceMethodInformation = unsafe { std::mem::zeroed() };
+        method_info.method_name = c_method.as_ptr();
+
+        // strings referenced by `method_info`, kept alive until eCAL copied them
+        let mut strings = Vec::new();
+        if let Some((request_type, response_type)) = types {
+            method_info.request_type = Self::c_data_type(request_type, &mut strings)?;
+            method_info.response_type = Self::c_data_type(response_type, &mut strings)?;
+        }
 
         let result = unsafe {
             eCAL_ServiceServer_SetMethodCallback(
                 self.handle,
                 &method_info,
                 Some(Self::dispatch),
-                Arc::as_ptr(&self.callbacks) as *mut c_void,
+                Arc::as_ptr(&self.state) as *mut c_void,
             )
         };
 
+        drop(strings);
+
         if result != 0 {
             Err("Failed to register method callback".into())
         } else {
@@ -54,6 +347,72 @@ impl ServiceServer {
         }
     }
 
+    /// Converts `data_type` for eCAL; the returned struct borrows from `strings`
+    /// and `data_type`.
+    fn c_data_type(
+        data_type: &DataTypeInfo,
+        strings: &mut Vec<CString>,
+    ) -> Result<eCAL_SDataTypeInformation, String> {
+        let encoding = CString::new(data_type.encoding.as_str()).map_err(|_| "Invalid encoding")?;
+        let name = CString::new(data_type.type_name.as_str()).map_err(|_| "Invalid type name")?;
+        let info = eCAL_SDataTypeInformation {
+            encoding: encoding.as_ptr(),
+            name: name.as_ptr(),
+            descriptor: if data_type.descriptor.is_empty() {
+                ptr::null()
+            } else {
+                data_type.descriptor.as_ptr() as *const c_void
+            },
+            descriptor_length: data_type.descriptor.len(),
+        };
+        strings.push(encoding);
+        strings.push(name);
+        Ok(info)
+    }
+
+    /// Routes all calls below `prefix` (e.g. `"fs/"`) to one handler.
+    ///
+    /// eCAL only delivers calls to registered method names, so the prefix is
+    /// registered as a single method. Clients address a path below it with
+    /// [`ServiceClient::call_path`](crate::ServiceClient::call_path), which
+    /// encodes the path into the request (see
+    /// [`encode_path_request`](crate::types::encode_path_request)). The handler
+    /// receives the path, e.g. `"home/readme.txt"` for `fs/home/readme.txt`.
+    ///
+    /// Requests that do not carry a valid path fail with a [`ServiceError`].
+    pub fn add_prefix_handler(
+        &mut self,
+        prefix: &str,
+        handler: PrefixCallback,
+    ) -> Result<(), String> {
+        self.add_fallible_method(
+            prefix,
+            Box::new(move |info, request| match decode_path_request(request) {
+                Some((path, payload)) => Ok(handler(info, path, payload)),
+                None => Err(ServiceError::new("request carries no valid path")),
+            }),
+        )
+    }
+
+    /// Registers a handler for `method` whose response is transferred in
+    /// chunks of at most `chunk_size` bytes, for responses too large for a
+    /// single call. Clients fetch it with
+    /// [`ServiceClient::call_chunked`](crate::ServiceClient::call_chunked).
+    ///
+    /// The handler runs once per transfer; see [`crate::chunked`].
+    pub fn add_chunked_method(
+        &mut self,
+        method: &str,
+        chunk_size: usize,
+        callback: FallibleServiceCallback,
+    ) -> Result<(), String> {
+        let store = ChunkStore::new(chunk_size);
+        self.add_fallible_method(
+            method,
+            Box::new(move |info, request| store.handle(request, |payload| callback(info, payload))),
+        )
+    }
+
     unsafe extern "C" fn dispatch(
         method_info: *const eCAL_SServiceMethodInformation,
         request_ptr: *const c_void,
@@ -62,10 +421,7 @@ impl ServiceServer {
         response_len: *mut usize,
         user_data: *mut c_void,
     ) -> c_int {
-        let callbacks = {
-            let raw = user_data as *const Mutex<HashMap<String, ServiceCallback>>;
-            unsafe { &*raw }.lock().unwrap()
-        };
+        let state = unsafe { &*(user_data as *const ServerState) };
 
         let method_name = {
             if method_info.is_null() || unsafe { (*method_info).method_name }.is_null() {
@@ -84,20 +440,111 @@ impl ServiceServer {
         } else {
             unsafe { std::slice::from_raw_parts(request_ptr as *const u8, request_len) }
         };
+        let (remaining, request) = decode_deadline_request(request);
+        let deadline = remaining.map(|remaining| Instant::now() + remaining);
+        let (header, request) = decode_header_request(request);
+
+        let method = {
+            let callbacks = state.callbacks.lock().unwrap();
+
+            // introspection methods are answered from the server state
+            let response = match method_name.as_str() {
+                VERSION_METHOD => Some(encode_version(state.version.load(Ordering::Relaxed))),
+                METHODS_METHOD => {
+                    let mut methods: Vec<&str> = callbacks.keys().map(String::as_str).collect();
+                    methods.sort_unstable();
+                    Some(encode_methods(methods))
+                }
+                _ => None,
+            };
+            if let Some(response) = response {
+                return unsafe { Self::write_response(&response, response_ptr, response_len) };
+            }
 
+            // handlers run without the lock, so calls of other methods are not held up
+            match callbacks.get(&method_name) {
+                Some(method) => method.clone(),
+                None => return 1,
+            }
+        };
+        let (request_type, response_type) = match &method.types {
+            Some((request, response)) => (Some(request), Some(response)),
+            None => (None, None),
+        };
         let info = MethodInfo {
             method_name: method_name.clone(),
-            request_type: None,
-            response_type: None,
+            request_type: request_type.map(|t| t.type_name.clone()),
+            response_type: response_type.map(|t| t.type_name.clone()),
+            request_encoding: request_type.map(|t| t.encoding.clone()),
+            response_encoding: response_type.map(|t| t.encoding.clone()),
+            deadline,
+            header: header.map(<[u8]>::to_vec).unwrap_or_default(),
         };
 
-        let cb = match callbacks.get(&method_name) {
-            Some(cb) => cb,
-            None => return 1,
+        let limit = state.limits.lock().unwrap().get(&method_name).cloned();
+        let _permit = limit.as_ref().map(|limit| limit.acquire());
+
+        let budget = state.budget.read().unwrap().clone();
+        let interceptors = state.interceptors.read().unwrap().clone();
+        let pool = state.pool.read().unwrap().clone();
+        let response = match pool {
+            // the request buffer is only valid during this callback
+            Some(pool) => {
+                let request = request.to_vec();
+                pool.run(move || {
+                    Self::invoke(&method, &interceptors, info, &request, budget.as_ref())
+                })
+                .flatten()
+            }
+            None => Self::invoke(&method, &interceptors, info, request, budget.as_ref()),
         };
+        match response {
+            Some(Ok(response)) => unsafe {
+                Self::write_response(&response, response_ptr, response_len)
+            },
+            // the message travels as the payload of the failed call
+            Some(Err(error)) => {
+                let message = error.to_string();
+                unsafe { Self::write_response(message.as_bytes(), response_ptr, response_len) };
+                1
+            }
+            None => 1,
+        }
+    }
 
-        let response = cb(info, request);
+    /// Runs the handler of `method` behind `interceptors`, measuring both
+    /// against `budget`.
+    ///
+    /// Calls whose deadline passed while waiting for a permit or a worker
+    /// fail with [`ServiceError::Timeout`] without running the handler.
+    /// Returns `None` if the handler panicked.
+    fn invoke(
+        method: &Method,
+        interceptors: &[ServerInterceptor],
+        info: MethodInfo,
+        request: &[u8],
+        budget: Option<&CallbackBudget>,
+    ) -> Option<Result<Vec<u8>, ServiceError>> {
+        if info.is_expired() {
+            return Some(Err(ServiceError::Timeout));
+        }
+        let method_name = info.method_name.clone();
+        let next = ServerNext::new(interceptors, &*method.handler);
+        let source = || format!("service method '{method_name}'");
+        match budget {
+            Some(budget) => guard(source, || {
+                budget.measure(&method_name, || next.run(info, request))
+            }),
+            None => guard(source, || next.run(info, request)),
+        }
+    }
 
+    /// Copies `response` into an eCAL-allocated buffer handed back to the C API.
+    unsafe fn write_response(
+        response: &[u8],
+        response_ptr: *mut *mut c_void,
+        response_len: *mut usize,
+    ) -> c_int {
         let buffer = unsafe { eCAL_Malloc(response.len()) };
         if buffer.is_null() {
             return 1;
@@ -118,5 +565,8 @@ impl Drop for ServiceServer {
         unsafe {
             eCAL_ServiceServer_Delete(self.handle);
         }
+        if let Some(token) = self.event_token.take() {
+            SERVER_EVENTS.unregister(token);
+        }
     }
 }
@@ -0,0 +1,94 @@
+//! Service methods with typed requests and responses.
+//!
+//! A [`TypedServiceServer`] decodes requests and encodes responses with the
+//! message types of typed pub/sub ([`SubscriberMessage`] and
+//! [`PublisherMessage`]), so handlers work with messages instead of bytes:
+//!
+//! ```ignore
+//! let mut server = TypedServiceServer::new("math")?;
+//! server.add_method("Add", |_info, request: ProtobufMessage<AddRequest>| {
+//!     let sum = request.data.a + request.data.b;
+//!     ProtobufMessage { data: Arc::new(AddResponse { sum }) }
+//! })?;
+//! ```
+//!
+//! Calls whose request cannot be decoded fail with a [`ServiceError`]
+//! without invoking the handler.
+//! The request and response types are advertised with the method, so
+//! monitoring tools show them.
+
+use crate::error::ServiceError;
+use crate::server::ServiceServer;
+use crate::types::MethodInfo;
+use rustecal_core::callback_budget::CallbackBudget;
+use rustecal_pubsub::typed_publisher::PublisherMessage;
+use rustecal_pubsub::typed_subscriber::SubscriberMessage;
+
+/// A service server whose methods take and return typed messages.
+pub struct TypedServiceServer {
+    server: ServiceServer,
+}
+
+impl TypedServiceServer {
+    /// Creates a server for `service_name`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `Err(String)` if the underlying eCAL server could not be created.
+    pub fn new(service_name: &str) -> Result<Self, String> {
+        Ok(Self {
+            server: ServiceServer::new(service_name)?,
+        })
+    }
+
+    /// Registers `handler` for `method`, replacing a previous handler.
+    ///
+    /// Requests are decoded as `Req`, the returned `Resp` is encoded as the
+    /// response. `Req` must own its data; message types borrowing the request
+    /// buffer are not supported.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `Err(String)` if the method name is reserved or eCAL
+    /// rejects the registration.
+    pub fn add_method<Req, Resp, F>(&mut self, method: &str, handler: F) -> Result<(), String>
+    where
+        Req: for<'a> SubscriberMessage<'a>,
+        Resp: PublisherMessage,
+        F: Fn(MethodInfo, Req) -> Resp + Send + Sync + 'static,
+    {
+        let request_type = <Req as SubscriberMessage>::datatype();
+        let response_type = <Resp as PublisherMessage>::datatype();
+        let decode_type = request_type.clone();
+        self.server.add_handler(
+            method,
+            Box::new(move |info, request| {
+                let request = Req::from_bytes(request, &decode_type).ok_or_else(|| {
+                    ServiceError::new(format!(
+                        "request is not a valid '{}'",
+                        decode_type.type_name
+                    ))
+                })?;
+                let mut response = Vec::new();
+                handler(info, request).write_bytes(&mut response);
+                Ok(response)
+            }),
+            Some((request_type, response_type)),
+        )
+    }
+
+    /// Sets the interface version, see [`ServiceServer::set_version`].
+    pub fn set_version(&mut self, version: u32) {
+        self.server.set_version(version);
+    }
+
+    /// Sets an execution time budget, see [`ServiceServer::set_callback_budget`].
+    pub fn set_callback_budget(&mut self, budget: Option<CallbackBudget>) {
+        self.server.set_callback_budget(budget);
+    }
+
+    /// Returns the underlying server, e.g. to add untyped methods.
+    pub fn server_mut(&mut self) -> &mut ServiceServer {
+        &mut self.server
+    }
+}
@@ -1,6 +1,9 @@
+use crate::error::ServiceError;
+use rustecal_core::types::DataTypeInfo;
 use rustecal_sys::*;
+use std::time::{Duration, Instant};
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum CallState {
     None,
     Executed,
@@ -75,8 +78,46 @@ pub struct ServiceResponse {
 #[derive(Debug, Clone)]
 pub struct MethodInfo {
     pub method_name: String,
+    /// Request type name declared by a typed method.
     pub request_type: Option<String>,
+    /// Response type name declared by a typed method.
     pub response_type: Option<String>,
+    /// Encoding of the request type, e.g. `"proto"`.
+    pub request_encoding: Option<String>,
+    /// Encoding of the response type.
+    pub response_encoding: Option<String>,
+    /// Point in time after which the client no longer waits for the
+    /// response, if it passed its timeout along (see
+    /// [`encode_deadline_request`]).
+    pub deadline: Option<Instant>,
+    /// Header blob attached by the client, empty if none (see
+    /// [`encode_header_request`]).
+    pub header: Vec<u8>,
+}
+
+impl MethodInfo {
+    /// Returns the time left until the deadline, zero once it has passed.
+    pub fn remaining(&self) -> Option<Duration> {
+        self.deadline
+            .map(|deadline| deadline.saturating_duration_since(Instant::now()))
+    }
+
+    /// Returns `true` if the client no longer waits for the response, so a
+    /// handler can abort its work.
+    pub fn is_expired(&self) -> bool {
+        self.deadline
+            .is_some_and(|deadline| Instant::now() >= deadline)
+    }
+}
+
+/// A method registered on a server, see `ServiceServer::methods`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RegisteredMethod {
+    pub method_name: String,
+    /// Request type advertised by a typed method.
+    pub request_type: Option<DataTypeInfo>,
+    /// Response type advertised by a typed method.
+    pub response_type: Option<DataTypeInfo>,
 }
 
 /// The service callback signature used by ServiceServer.
@@ -85,3 +126,118 @@ pub struct MethodInfo {
 /// - Accepts `MethodInfo` and a reference to request bytes
 /// - Returns response bytes (`Vec<u8>`)
 pub type ServiceCallback = Box<dyn Fn(MethodInfo, &[u8]) -> Vec<u8> + Send + Sync + 'static>;
+
+/// The signature of a handler that can fail a call, see
+/// `ServiceServer::add_fallible_method`.
+pub type FallibleServiceCallback =
+    Box<dyn Fn(MethodInfo, &[u8]) -> Result<Vec<u8>, ServiceError> + Send + Sync + 'static>;
+
+/// The callback signature of a prefix handler (see `ServiceServer::add_prefix_handler`).
+///
+/// Receives the `MethodInfo`, the path below the prefix and the request bytes.
+pub type PrefixCallback = Box<dyn Fn(MethodInfo, &str, &[u8]) -> Vec<u8> + Send + Sync + 'static>;
+
+/// Encodes a request for a prefix method: the path length as little-endian
+/// `u32`, the UTF-8 path, then the payload.
+pub fn encode_path_request(path: &str, payload: &[u8]) -> Vec<u8> {
+    let mut request = Vec::with_capacity(4 + path.len() + payload.len());
+    request.extend_from_slice(&(path.len() as u32).to_le_bytes());
+    request.extend_from_slice(path.as_bytes());
+    request.extend_from_slice(payload);
+    request
+}
+
+/// Splits a request encoded by [`encode_path_request`] into path and payload.
+pub fn decode_path_request(request: &[u8]) -> Option<(&str, &[u8])> {
+    let len = u32::from_le_bytes(request.get(..4)?.try_into().ok()?) as usize;
+    let path = request.get(4..4 + len)?;
+    Some((std::str::from_utf8(path).ok()?, &request[4 + len..]))
+}
+
+/// Marks a request carrying the caller's deadline.
+pub const DEADLINE_MAGIC: &[u8; 8] = b"\0ecal-dl";
+
+/// Encodes a request carrying the time the caller waits for the response:
+/// [`DEADLINE_MAGIC`], the remaining milliseconds as little-endian `u32`,
+/// then the payload.
+///
+/// Servers built with rustecal strip the deadline before the handler runs
+/// and pass it in [`MethodInfo::deadline`]; other servers receive the
+/// encoded request, so clients only send deadlines when enabled with
+/// `ServiceClient::set_deadline_propagation`.
+pub fn encode_deadline_request(remaining: Duration, payload: &[u8]) -> Vec<u8> {
+    let millis = u32::try_from(remaining.as_millis()).unwrap_or(u32::MAX);
+    let mut request = Vec::with_capacity(DEADLINE_MAGIC.len() + 4 + payload.len());
+    request.extend_from_slice(DEADLINE_MAGIC);
+    request.extend_from_slice(&millis.to_le_bytes());
+    request.extend_from_slice(payload);
+    request
+}
+
+/// Splits a request encoded by [`encode_deadline_request`] into the
+/// remaining time and the payload; other requests are returned unchanged.
+pub fn decode_deadline_request(request: &[u8]) -> (Option<Duration>, &[u8]) {
+    let Some(rest) = request.strip_prefix(DEADLINE_MAGIC.as_slice()) else {
+        return (None, request);
+    };
+    match rest.get(..4) {
+        Some(millis) => {
+            let millis = u32::from_le_bytes(millis.try_into().unwrap_or_default());
+            (Some(Duration::from_millis(millis.into())), &rest[4..])
+        }
+        None => (None, request),
+    }
+}
+
+/// Marks a request carrying a header blob.
+pub const HEADER_MAGIC: &[u8; 8] = b"\0ecal-hd";
+
+/// Encodes a request carrying `header` next to the payload:
+/// [`HEADER_MAGIC`], the header length as little-endian `u32`, the header,
+/// then the payload.
+///
+/// Headers carry data for interceptors, such as auth tokens (see
+/// [`crate::intercept`]). Servers built with rustecal strip the header
+/// before the handler runs and pass it in [`MethodInfo::header`].
+pub fn encode_header_request(header: &[u8], payload: &[u8]) -> Vec<u8> {
+    let mut request = Vec::with_capacity(HEADER_MAGIC.len() + 4 + header.len() + payload.len());
+    request.extend_from_slice(HEADER_MAGIC);
+    request.extend_from_slice(&(header.len() as u32).to_le_bytes());
+    request.extend_from_slice(header);
+    request.extend_from_slice(payload);
+    request
+}
+
+/// Splits a request encoded by [`encode_header_request`] into header and
+/// payload; other requests are returned unchanged with no header.
+pub fn decode_header_request(request: &[u8]) -> (Option<&[u8]>, &[u8]) {
+    let decoded = request
+        .strip_prefix(HEADER_MAGIC.as_slice())
+        .and_then(|rest| {
+            let len = u32::from_le_bytes(rest.get(..4)?.try_into().ok()?) as usize;
+            let header = rest.get(4..4 + len)?;
+            Some((header, &rest[4 + len..]))
+        });
+    match decoded {
+        Some((header, payload)) => (Some(header), payload),
+        None => (None, request),
+    }
+}
+
+/// Prepends the deadline of a call with `timeout_ms` to `request` if
+/// `propagate` is set and the call has a finite timeout.
+pub(crate) fn with_deadline(
+    request: ServiceRequest,
+    timeout_ms: Option<i32>,
+    propagate: bool,
+) -> ServiceRequest {
+    match timeout_ms {
+        Some(timeout) if propagate && timeout > 0 => ServiceRequest {
+            payload: encode_deadline_request(
+                Duration::from_millis(timeout as u64),
+                &request.payload,
+            ),
+        },
+        _ => request,
+    }
+}
@@ -0,0 +1,103 @@
+//! Interface version negotiation between service clients and servers.
+//!
+//! Every [`ServiceServer`](crate::ServiceServer) answers two introspection
+//! methods next to its own:
+//!
+//! - [`VERSION_METHOD`] returns the interface version set with
+//!   [`ServiceServer::set_version`](crate::ServiceServer::set_version) as
+//!   decimal text (`"0"` unless set).
+//! - [`METHODS_METHOD`] returns the names of the registered methods,
+//!   one per line.
+//!
+//! Clients call [`ServiceClient::negotiate`](crate::ServiceClient::negotiate)
+//! before issuing real calls, so talking to an outdated server build fails
+//! early with a [`NegotiationError`] instead of with undecodable responses.
+
+use std::fmt;
+
+/// Name of the introspection method returning the interface version.
+pub const VERSION_METHOD: &str = "__version";
+
+/// Name of the introspection method listing the registered methods.
+pub const METHODS_METHOD: &str = "__methods";
+
+/// Returns `true` for the names of the introspection methods.
+pub fn is_introspection_method(method: &str) -> bool {
+    method == VERSION_METHOD || method == METHODS_METHOD
+}
+
+/// Encodes an interface version as response of [`VERSION_METHOD`].
+pub fn encode_version(version: u32) -> Vec<u8> {
+    version.to_string().into_bytes()
+}
+
+/// Decodes a response of [`VERSION_METHOD`].
+pub fn decode_version(response: &[u8]) -> Option<u32> {
+    std::str::from_utf8(response).ok()?.trim().parse().ok()
+}
+
+/// Encodes method names as response of [`METHODS_METHOD`].
+pub fn encode_methods<'a>(methods: impl IntoIterator<Item = &'a str>) -> Vec<u8> {
+    methods
+        .into_iter()
+        .collect::<Vec<_>>()
+        .join("\n")
+        .into_bytes()
+}
+
+/// Decodes a response of [`METHODS_METHOD`].
+pub fn decode_methods(response: &[u8]) -> Vec<String> {
+    String::from_utf8_lossy(response)
+        .lines()
+        .filter(|line| !line.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+/// Reasons why [`ServiceClient::negotiate`](crate::ServiceClient::negotiate) failed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum NegotiationError {
+    /// No server answered within the timeout.
+    NoServer,
+    /// A server answered without a valid version, e.g. a non-rustecal server.
+    Unsupported,
+    /// A server implements an older interface version than required.
+    Incompatible { required: u32, found: u32 },
+}
+
+impl fmt::Display for NegotiationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            NegotiationError::NoServer => write!(f, "no service server answered"),
+            NegotiationError::Unsupported => {
+                write!(f, "service server does not support version negotiation")
+            }
+            NegotiationError::Incompatible { required, found } => write!(
+                f,
+                "service server implements interface version {found}, but at least {required} is required"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for NegotiationError {}
+
+/// Checks the versions reported by all responding servers against `min_version`.
+///
+/// `None` entries stand for servers that did not report a valid version.
+/// Returns the lowest version on success.
+pub fn check_versions(versions: &[Option<u32>], min_version: u32) -> Result<u32, NegotiationError> {
+    let mut lowest: Option<u32> = None;
+    for version in versions {
+        let version = version.ok_or(NegotiationError::Unsupported)?;
+        lowest = Some(lowest.map_or(version, |lowest| lowest.min(version)));
+    }
+    match lowest {
+        None => Err(NegotiationError::NoServer),
+        Some(found) if found < min_version => Err(NegotiationError::Incompatible {
+            required: min_version,
+            found,
+        }),
+        Some(found) => Ok(found),
+    }
+}
@@ -0,0 +1,76 @@
+//! These tests call a server within the process and need an eCAL runtime:
+//! `cargo test -p rustecal-service --test async_call -- --ignored`.
+
+use rustecal_core::{Ecal, EcalComponents};
+use rustecal_service::{ServiceClient, ServiceError, ServiceRequest, ServiceServer};
+use std::future::Future;
+use std::sync::{Arc, mpsc};
+use std::task::{Context, Poll, Wake, Waker};
+use std::thread::{self, Thread};
+use std::time::{Duration, Instant};
+
+const TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Wakes the thread blocked in [`block_on`].
+struct ThreadWaker(Thread);
+
+impl Wake for ThreadWaker {
+    fn wake(self: Arc<Self>) {
+        self.0.unpark();
+    }
+}
+
+fn block_on<F: Future>(future: F) -> F::Output {
+    let mut future = std::pin::pin!(future);
+    let waker = Waker::from(Arc::new(ThreadWaker(thread::current())));
+    let mut cx = Context::from_waker(&waker);
+    loop {
+        if let Poll::Ready(output) = future.as_mut().poll(&mut cx) {
+            return output;
+        }
+        thread::park();
+    }
+}
+
+fn request() -> ServiceRequest {
+    ServiceRequest {
+        payload: b"ping".to_vec(),
+    }
+}
+
+#[test]
+#[ignore = "needs an eCAL runtime"]
+fn calls_time_out_without_waiting_for_the_response() {
+    Ecal::initialize(Some("async_call"), EcalComponents::SERVICE, None).unwrap();
+    let mut server = ServiceServer::new("async_call/slow").unwrap();
+    server
+        .add_method(
+            "sleep",
+            Box::new(|_info, request| {
+                thread::sleep(Duration::from_secs(1));
+                request.to_vec()
+            }),
+        )
+        .unwrap();
+    let client = ServiceClient::new("async_call/slow").unwrap();
+    assert!(client.wait_for_server(TIMEOUT));
+    let instance = client.get_client_instances().pop().unwrap();
+
+    let start = Instant::now();
+    let result = block_on(instance.call_async("sleep", request(), Some(100)));
+    assert!(matches!(result, Err(ServiceError::Timeout)));
+    assert!(start.elapsed() < Duration::from_millis(900));
+
+    let (sender, receiver) = mpsc::channel();
+    instance
+        .call_with_callback("sleep", request(), Some(100), move |result| {
+            sender
+                .send(result.map(|response| response.payload))
+                .unwrap();
+        })
+        .unwrap();
+    let result = receiver.recv_timeout(Duration::from_millis(900)).unwrap();
+    assert!(matches!(result, Err(ServiceError::Timeout)));
+    // the late response does not run the callback again
+    assert!(receiver.recv_timeout(Duration::from_secs(2)).is_err());
+}
@@ -0,0 +1,95 @@
+use rustecal_service::ServiceError;
+use rustecal_service::chunked::{
+    ChunkAssembler, ChunkHeader, ChunkStore, decode_chunk, encode_chunk, encode_chunk_request,
+};
+
+/// Fetches a response from `store` as a client would.
+fn fetch(store: &ChunkStore, response: &[u8]) -> (Vec<u8>, usize) {
+    let mut assembler = ChunkAssembler::new();
+    let mut calls = 0;
+    loop {
+        let request = assembler.next_request(b"request");
+        let chunk = store
+            .handle(&request, |payload| {
+                assert_eq!(payload, b"request");
+                Ok(response.to_vec())
+            })
+            .unwrap();
+        calls += 1;
+        if assembler.push(&chunk).unwrap() {
+            return (assembler.into_inner(), calls);
+        }
+    }
+}
+
+#[test]
+fn chunk_roundtrip() {
+    let header = ChunkHeader {
+        transfer_id: 7,
+        index: 2,
+        count: 3,
+    };
+    let chunk = encode_chunk(header, b"data");
+    assert_eq!(decode_chunk(&chunk), Some((header, &b"data"[..])));
+    assert_eq!(decode_chunk(&chunk[..10]), None);
+}
+
+#[test]
+fn reassembles_chunked_response() {
+    let store = ChunkStore::new(4);
+    let response: Vec<u8> = (0..10).collect();
+    assert_eq!(fetch(&store, &response), (response, 3));
+    // the transfer is dropped after the last chunk
+    assert_eq!(store.pending(), 0);
+}
+
+#[test]
+fn small_responses_need_one_call() {
+    let store = ChunkStore::new(4);
+    assert_eq!(fetch(&store, b"abcd"), (b"abcd".to_vec(), 1));
+    assert_eq!(fetch(&store, b""), (Vec::new(), 1));
+    assert_eq!(store.pending(), 0);
+}
+
+#[test]
+fn rejects_unknown_transfers_and_chunks() {
+    let store = ChunkStore::new(2);
+    let result = store.handle(&encode_chunk_request(42, 0, &[]), |_| unreachable!());
+    assert!(matches!(result, Err(ServiceError::Failed(_))));
+
+    let first = store
+        .handle(&encode_chunk_request(0, 0, &[]), |_| Ok(b"abcdef".to_vec()))
+        .unwrap();
+    let (header, _) = decode_chunk(&first).unwrap();
+    assert_eq!(header.count, 3);
+    let result = store.handle(
+        &encode_chunk_request(header.transfer_id, 3, &[]),
+        |_| unreachable!(),
+    );
+    assert!(result.is_err());
+    assert_eq!(store.pending(), 1);
+}
+
+#[test]
+fn assembler_detects_out_of_order_chunks() {
+    let first = ChunkHeader {
+        transfer_id: 1,
+        index: 0,
+        count: 2,
+    };
+    let mut assembler = ChunkAssembler::new();
+    assert_eq!(
+        assembler.push(&encode_chunk(ChunkHeader { index: 1, ..first }, b"b")),
+        Err(ServiceError::Transport(
+            "expected chunk 0, received 1 of 2".into()
+        ))
+    );
+    assert_eq!(assembler.push(&encode_chunk(first, b"a")), Ok(false));
+    let other = ChunkHeader {
+        transfer_id: 2,
+        index: 1,
+        count: 2,
+    };
+    assert!(assembler.push(&encode_chunk(other, b"x")).is_err());
+    assert!(!assembler.is_complete());
+}
@@ -0,0 +1,56 @@
+use rustecal_service::CancelToken;
+use rustecal_service::types::{MethodInfo, decode_deadline_request, encode_deadline_request};
+use std::time::{Duration, Instant};
+
+fn info(deadline: Option<Instant>) -> MethodInfo {
+    MethodInfo {
+        method_name: "render".into(),
+        request_type: None,
+        response_type: None,
+        request_encoding: None,
+        response_encoding: None,
+        deadline,
+        header: Vec::new(),
+    }
+}
+
+#[test]
+fn deadline_request_roundtrip() {
+    let request = encode_deadline_request(Duration::from_millis(1500), b"payload");
+    assert_eq!(
+        decode_deadline_request(&request),
+        (Some(Duration::from_millis(1500)), &b"payload"[..])
+    );
+}
+
+#[test]
+fn plain_requests_carry_no_deadline() {
+    assert_eq!(decode_deadline_request(b"payload"), (None, &b"payload"[..]));
+    assert_eq!(decode_deadline_request(b""), (None, &b""[..]));
+    // truncated header
+    let request = &encode_deadline_request(Duration::from_millis(10), b"")[..10];
+    assert_eq!(decode_deadline_request(request), (None, request));
+}
+
+#[test]
+fn method_info_reports_expiry() {
+    assert!(!info(None).is_expired());
+    assert_eq!(info(None).remaining(), None);
+
+    let pending = info(Some(Instant::now() + Duration::from_secs(60)));
+    assert!(!pending.is_expired());
+    assert!(pending.remaining().unwrap() > Duration::from_secs(59));
+
+    let expired = info(Some(Instant::now() - Duration::from_millis(1)));
+    assert!(expired.is_expired());
+    assert_eq!(expired.remaining(), Some(Duration::ZERO));
+}
+
+#[test]
+fn cancel_token_is_shared_by_clones() {
+    let token = CancelToken::new();
+    let clone = token.clone();
+    assert!(!clone.is_cancelled());
+    token.cancel();
+    assert!(clone.is_cancelled());
+}
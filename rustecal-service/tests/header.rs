@@ -0,0 +1,39 @@
+use rustecal_service::types::{
+    decode_deadline_request, decode_header_request, encode_deadline_request, encode_header_request,
+};
+use std::time::Duration;
+
+#[test]
+fn header_request_roundtrip() {
+    let request = encode_header_request(b"token=secret", b"payload");
+    assert_eq!(
+        decode_header_request(&request),
+        (Some(&b"token=secret"[..]), &b"payload"[..])
+    );
+    assert_eq!(
+        decode_header_request(&encode_header_request(b"", b"")),
+        (Some(&b""[..]), &b""[..])
+    );
+}
+
+#[test]
+fn plain_requests_carry_no_header() {
+    assert_eq!(decode_header_request(b"payload"), (None, &b"payload"[..]));
+    // header longer than the request
+    let request = &encode_header_request(b"token", b"")[..14];
+    assert_eq!(decode_header_request(request), (None, request));
+}
+
+#[test]
+fn deadline_wraps_header() {
+    let request = encode_deadline_request(
+        Duration::from_millis(250),
+        &encode_header_request(b"token", b"payload"),
+    );
+    let (remaining, request) = decode_deadline_request(&request);
+    assert_eq!(remaining, Some(Duration::from_millis(250)));
+    assert_eq!(
+        decode_header_request(request),
+        (Some(&b"token"[..]), &b"payload"[..])
+    );
+}
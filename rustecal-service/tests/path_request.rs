@@ -0,0 +1,20 @@
+use rustecal_service::types::{decode_path_request, encode_path_request};
+
+#[test]
+fn path_request_roundtrip() {
+    let request = encode_path_request("home/readme.txt", b"payload");
+    assert_eq!(
+        decode_path_request(&request),
+        Some(("home/readme.txt", &b"payload"[..]))
+    );
+    assert_eq!(
+        decode_path_request(&encode_path_request("", b"")),
+        Some(("", &b""[..]))
+    );
+}
+
+#[test]
+fn path_request_rejects_truncated_input() {
+    assert_eq!(decode_path_request(b"\x01\x00"), None);
+    assert_eq!(decode_path_request(b"\x05\x00\x00\x00abc"), None);
+}
@@ -0,0 +1,67 @@
+use rustecal_service::ServiceError;
+use rustecal_service::response::ServiceResponse;
+use rustecal_sys::{
+    eCAL_SServiceResponse, eCAL_eCallState, eCAL_eCallState_eCAL_eCallState_executed,
+    eCAL_eCallState_eCAL_eCallState_failed, eCAL_eCallState_eCAL_eCallState_timeouted,
+};
+use std::ffi::c_void;
+
+fn response(call_state: eCAL_eCallState, payload: &[u8]) -> ServiceResponse {
+    let mut raw: eCAL_SServiceResponse = unsafe { std::mem::zeroed() };
+    raw.call_state = call_state;
+    raw.response = payload.as_ptr() as *const c_void;
+    raw.response_length = payload.len();
+    ServiceResponse::from_struct(&raw)
+}
+
+#[test]
+fn failed_call_carries_handler_message() {
+    let error = ServiceError::from("unknown key 'x'");
+    let failed = response(
+        eCAL_eCallState_eCAL_eCallState_failed,
+        error.to_string().as_bytes(),
+    );
+    assert!(!failed.success);
+    assert_eq!(failed.error_msg.as_deref(), Some("unknown key 'x'"));
+    assert!(failed.payload.is_empty());
+}
+
+#[test]
+fn executed_call_keeps_payload() {
+    let executed = response(eCAL_eCallState_eCAL_eCallState_executed, b"ok");
+    assert!(executed.success);
+    assert_eq!(executed.error_msg, None);
+    assert_eq!(executed.payload, b"ok");
+}
+
+#[test]
+fn into_result_maps_failure_to_error() {
+    let failed = response(eCAL_eCallState_eCAL_eCallState_failed, b"timeout");
+    assert_eq!(
+        failed.into_result().unwrap_err(),
+        ServiceError::Failed("timeout".into())
+    );
+
+    let executed = response(eCAL_eCallState_eCAL_eCallState_executed, b"ok");
+    assert_eq!(executed.into_result().unwrap().payload, b"ok");
+}
+
+#[test]
+fn into_result_tells_timeouts_apart() {
+    let timed_out = response(eCAL_eCallState_eCAL_eCallState_timeouted, b"");
+    assert_eq!(timed_out.into_result().unwrap_err(), ServiceError::Timeout);
+}
+
+#[test]
+fn response_carries_server_host_and_process() {
+    let host = std::ffi::CString::new("robot-1").unwrap();
+    let mut raw: eCAL_SServiceResponse = unsafe { std::mem::zeroed() };
+    raw.call_state = eCAL_eCallState_eCAL_eCallState_executed;
+    raw.server_id.service_id.host_name = host.as_ptr();
+    raw.server_id.service_id.process_id = 4711;
+    let response = ServiceResponse::from_struct(&raw);
+    assert_eq!(response.server_host, "robot-1");
+    assert_eq!(response.server_process_id, 4711);
+    // only calls measure the round trip
+    assert_eq!(response.round_trip, None);
+}
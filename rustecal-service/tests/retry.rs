@@ -0,0 +1,66 @@
+use rustecal_service::{Backoff, RetryPolicy, ServiceError};
+use std::time::Duration;
+
+fn no_wait(attempts: u32) -> RetryPolicy {
+    RetryPolicy::new(attempts, Backoff::Fixed(Duration::ZERO))
+}
+
+#[test]
+fn exponential_backoff_doubles_up_to_max() {
+    let backoff = Backoff::Exponential {
+        initial: Duration::from_millis(100),
+        max: Duration::from_millis(500),
+    };
+    assert_eq!(backoff.delay(1), Duration::from_millis(100));
+    assert_eq!(backoff.delay(2), Duration::from_millis(200));
+    assert_eq!(backoff.delay(3), Duration::from_millis(400));
+    assert_eq!(backoff.delay(4), Duration::from_millis(500));
+    assert_eq!(backoff.delay(100), Duration::from_millis(500));
+}
+
+#[test]
+fn retries_transient_errors_until_success() {
+    let mut calls = 0;
+    let result = no_wait(3).run(|| {
+        calls += 1;
+        if calls < 3 {
+            Err(ServiceError::NoInstance)
+        } else {
+            Ok(calls)
+        }
+    });
+    assert_eq!(result, Ok(3));
+}
+
+#[test]
+fn stops_after_the_last_attempt() {
+    let mut calls = 0;
+    let result: Result<(), _> = no_wait(2).run(|| {
+        calls += 1;
+        Err(ServiceError::Timeout)
+    });
+    assert_eq!(result, Err(ServiceError::Timeout));
+    assert_eq!(calls, 2);
+}
+
+#[test]
+fn does_not_retry_other_errors() {
+    let mut calls = 0;
+    let result: Result<(), _> = no_wait(5).run(|| {
+        calls += 1;
+        Err(ServiceError::new("rejected"))
+    });
+    assert_eq!(result, Err(ServiceError::new("rejected")));
+    assert_eq!(calls, 1);
+}
+
+#[test]
+fn retry_on_selects_the_retried_errors() {
+    let policy = no_wait(3).retry_on(|e| matches!(e, ServiceError::Failed(_)));
+    let mut calls = 0;
+    let _: Result<(), _> = policy.run(|| {
+        calls += 1;
+        Err(ServiceError::new("busy"))
+    });
+    assert_eq!(calls, 3);
+}
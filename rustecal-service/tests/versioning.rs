@@ -0,0 +1,32 @@
+use rustecal_service::versioning::{
+    NegotiationError, check_versions, decode_methods, decode_version, encode_methods,
+    encode_version,
+};
+
+#[test]
+fn version_and_methods_roundtrip() {
+    assert_eq!(decode_version(&encode_version(42)), Some(42));
+    assert_eq!(decode_version(b"not a number"), None);
+    assert_eq!(
+        decode_methods(&encode_methods(["echo", "reverse"])),
+        vec!["echo".to_string(), "reverse".to_string()]
+    );
+    assert!(decode_methods(&encode_methods([])).is_empty());
+}
+
+#[test]
+fn negotiation_requires_all_servers_to_be_compatible() {
+    assert_eq!(check_versions(&[Some(3), Some(2)], 2), Ok(2));
+    assert_eq!(check_versions(&[], 1), Err(NegotiationError::NoServer));
+    assert_eq!(
+        check_versions(&[Some(3), None], 1),
+        Err(NegotiationError::Unsupported)
+    );
+    assert_eq!(
+        check_versions(&[Some(3), Some(1)], 2),
+        Err(NegotiationError::Incompatible {
+            required: 2,
+            found: 1
+        })
+    );
+}
@@ -0,0 +1,49 @@
+use rustecal_service::WorkerPool;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::thread;
+use std::time::Duration;
+
+#[test]
+fn rejects_empty_pool() {
+    assert!(WorkerPool::new(0).is_err());
+}
+
+#[test]
+fn runs_jobs_and_returns_results() {
+    let pool = WorkerPool::new(2).unwrap();
+    assert_eq!(pool.threads(), 2);
+    assert_eq!(pool.run(|| 6 * 7), Some(42));
+}
+
+#[test]
+fn reports_panicking_jobs() {
+    let pool = WorkerPool::new(1).unwrap();
+    assert_eq!(pool.run(|| -> u32 { panic!("handler failed") }), None);
+    // the worker survives the panic
+    assert_eq!(pool.run(|| 1), Some(1));
+}
+
+#[test]
+fn runs_jobs_concurrently() {
+    let pool = Arc::new(WorkerPool::new(2).unwrap());
+    let running = Arc::new(AtomicUsize::new(0));
+    let peak = Arc::new(AtomicUsize::new(0));
+    let callers: Vec<_> = (0..2)
+        .map(|_| {
+            let (pool, running, peak) = (pool.clone(), running.clone(), peak.clone());
+            thread::spawn(move || {
+                pool.run(move || {
+                    let now = running.fetch_add(1, Ordering::SeqCst) + 1;
+                    peak.fetch_max(now, Ordering::SeqCst);
+                    thread::sleep(Duration::from_millis(100));
+                    running.fetch_sub(1, Ordering::SeqCst);
+                })
+            })
+        })
+        .collect();
+    for caller in callers {
+        caller.join().unwrap();
+    }
+    assert_eq!(peak.load(Ordering::SeqCst), 2);
+}
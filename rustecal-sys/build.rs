@@ -1,10 +1,39 @@
 use std::{env, path::PathBuf};
 
+/// Returns the first set environment variable of `names`, registering all of them
+/// for rebuild tracking.
+fn env_any(names: &[&str]) -> Option<String> {
+    names.iter().for_each(|name| {
+        println!("cargo:rerun-if-env-changed={name}");
+    });
+    names.iter().find_map(|name| env::var(name).ok())
+}
+
+/// Maps a Rust target triple to the Debian multiarch directory name
+/// (e.g. `aarch64-unknown-linux-gnu` -> `aarch64-linux-gnu`).
+fn multiarch(target_arch: &str, target_env: &str) -> String {
+    match (target_arch, target_env) {
+        ("arm", _) => "arm-linux-gnueabihf".to_string(),
+        (arch, "musl") => format!("{arch}-linux-musl"),
+        (arch, _) => format!("{arch}-linux-gnu"),
+    }
+}
+
 fn main() {
     if std::env::var("DOCS_RS").is_ok() || std::env::var("CARGO_DOC").is_ok() {
         println!("cargo:warning=Skipping bindgen during documentation");
         return;
     }
+
+    // Build scripts run on the host, so the target must be taken from Cargo's
+    // environment rather than from `cfg!`.
+    let target = env::var("TARGET").unwrap();
+    let host = env::var("HOST").unwrap();
+    let target_os = env::var("CARGO_CFG_TARGET_OS").unwrap();
+    let target_arch = env::var("CARGO_CFG_TARGET_ARCH").unwrap();
+    let target_env = env::var("CARGO_CFG_TARGET_ENV").unwrap_or_default();
+    let cross = target != host;
+
     // Prepare bindgen builder
     let mut builder = bindgen::Builder::default()
         .header("wrapper.h")
@@ -16,10 +45,19 @@ fn main() {
         .derive_default(true)
         .wrap_unsafe_ops(true);
 
-    if cfg!(target_os = "windows") {
+    if cross {
+        // generate the bindings for the target ABI (pointer width, alignment)
+        builder = builder.clang_arg(format!("--target={target}"));
+        println!("cargo:warning=Cross-compiling from {host} to {target}");
+    }
+
+    // ECAL_HOME takes precedence, ECAL_DIR matches the CMake package variable
+    let ecal_home = env_any(&["ECAL_HOME", "ECAL_DIR"]);
+
+    if target_os == "windows" {
         // --- Windows: Use ECAL_HOME ---
         let ecal_home =
-            env::var("ECAL_HOME").expect("ECAL_HOME environment variable must be set on Windows");
+            ecal_home.expect("ECAL_HOME (or ECAL_DIR) environment variable must be set on Windows");
         let include_path = format!("{ecal_home}/include");
         let lib_path = format!("{ecal_home}/lib");
 
@@ -31,9 +69,11 @@ fn main() {
         // Debug info
         println!("cargo:warning=Building on Windows");
         println!("cargo:warning=Using ECAL_HOME = {ecal_home}");
-    } else if cfg!(target_os = "linux") {
-        match env::var("ECAL_HOME") {
-            Ok(ecal_home) => {
+    } else if target_os == "linux" {
+        let sysroot = env_any(&["PKG_CONFIG_SYSROOT_DIR", "PKG_CONFIG_SYSROOT"]);
+
+        match ecal_home {
+            Some(ecal_home) => {
                 println!("cargo:warning=Using ECAL_HOME = {ecal_home}");
                 let include_path = format!("{ecal_home}/include");
                 let lib_path = format!("{ecal_home}/lib");
@@ -42,17 +82,42 @@ fn main() {
 
                 builder = builder.clang_arg(format!("-I{include_path}"));
             }
-            _ => {
-                println!("cargo:warning=Using system-wide eCAL install");
-            }
+            None => match &sysroot {
+                Some(sysroot) => {
+                    println!("cargo:warning=Using eCAL install in sysroot {sysroot}");
+                    let multiarch = multiarch(&target_arch, &target_env);
+
+                    println!("cargo:rustc-link-search=native={sysroot}/usr/lib");
+                    println!("cargo:rustc-link-search=native={sysroot}/usr/lib/{multiarch}");
+                    println!("cargo:rustc-link-search=native={sysroot}/usr/local/lib");
+
+                    builder = builder
+                        .clang_arg(format!("-I{sysroot}/usr/local/include"))
+                        .clang_arg(format!("-I{sysroot}/usr/include"));
+                }
+                None => {
+                    if cross {
+                        println!(
+                            "cargo:warning=Cross-compiling without ECAL_HOME or \
+                             PKG_CONFIG_SYSROOT_DIR, falling back to host headers"
+                        );
+                    }
+                    println!("cargo:warning=Using system-wide eCAL install");
+                }
+            },
         };
 
+        // resolve <...> system headers of the target, not of the host
+        if let Some(sysroot) = &sysroot {
+            builder = builder.clang_arg(format!("--sysroot={sysroot}"));
+        }
+
         println!("cargo:rustc-link-lib=dylib=ecal_core_c");
 
         // Debug info
-        println!("cargo:warning=Building on Linux");
+        println!("cargo:warning=Building on Linux ({target_arch})");
     } else {
-        panic!("Unsupported platform for rustecal-sys build");
+        panic!("Unsupported platform for rustecal-sys build: {target}");
     }
 
     // Final bindgen output
@@ -4,24 +4,80 @@
 
 use rustecal_core::types::DataTypeInfo;
 use rustecal_pubsub::typed_publisher::PublisherMessage;
-use rustecal_pubsub::typed_subscriber::SubscriberMessage;
-use std::{borrow::Cow, sync::Arc};
+use rustecal_pubsub::typed_subscriber::{OwnedMessage, SubscriberMessage};
+use std::{fmt, sync::Arc};
+
+/// Payload of a [`BytesMessage`].
+#[derive(Debug, Clone)]
+enum BytesData<'a> {
+    /// A slice borrowed on receive, e.g. straight from shared memory.
+    Borrowed(&'a [u8]),
+    /// A shared buffer, handed to the publisher as is.
+    Shared(Arc<[u8]>),
+}
 
 /// A wrapper for raw‐binary messages used with typed eCAL pub/sub.
 ///
-/// Internally holds either a borrowed slice (on receive) or an owned
-/// `Arc<[u8]>` (on send).
+/// Internally holds either a borrowed slice (on receive) or a shared
+/// `Arc<[u8]>` (on send); read the payload with [`BytesMessage::data`],
+/// which replaces the former public `data` field (see the crate README for
+/// migrating).
 pub struct BytesMessage<'a> {
-    pub data: Cow<'a, [u8]>,
+    data: BytesData<'a>,
 }
 
 impl<'a> BytesMessage<'a> {
-    /// Construct for sending: takes ownership of an `Arc<[u8]>`.
+    /// Construct for sending: takes ownership of an `Arc<[u8]>` without copying.
     pub fn owned(data: Arc<[u8]>) -> BytesMessage<'static> {
         BytesMessage {
-            data: Cow::Owned(data.as_ref().to_vec()),
+            data: BytesData::Shared(data),
+        }
+    }
+
+    /// Construct for sending a borrowed slice; it is sent without copying.
+    pub fn borrowed(data: &'a [u8]) -> Self {
+        BytesMessage {
+            data: BytesData::Borrowed(data),
+        }
+    }
+
+    /// Returns the payload.
+    pub fn data(&self) -> &[u8] {
+        match &self.data {
+            BytesData::Borrowed(bytes) => bytes,
+            BytesData::Shared(bytes) => bytes,
+        }
+    }
+
+    /// Returns the shared buffer of the payload, or `None` if it is borrowed.
+    pub fn shared(&self) -> Option<&Arc<[u8]>> {
+        match &self.data {
+            BytesData::Borrowed(_) => None,
+            BytesData::Shared(bytes) => Some(bytes),
         }
     }
+
+    /// Copies a borrowed payload into a shared buffer, e.g. to keep or
+    /// forward a received message; shared payloads are kept as they are.
+    pub fn into_owned(self) -> BytesMessage<'static> {
+        BytesMessage::owned(match self.data {
+            BytesData::Borrowed(bytes) => Arc::from(bytes),
+            BytesData::Shared(bytes) => bytes,
+        })
+    }
+}
+
+impl AsRef<[u8]> for BytesMessage<'_> {
+    fn as_ref(&self) -> &[u8] {
+        self.data()
+    }
+}
+
+impl fmt::Display for BytesMessage<'_> {
+    /// Displays the payload size, e.g. `<1024 bytes>`.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "<{} bytes>", self.data().len())
+    }
 }
 
 //
@@ -40,14 +96,19 @@ impl<'a> SubscriberMessage<'a> for BytesMessage<'a> {
     /// On receive, we get a `&[u8]` slice straight from shared memory.
     fn from_bytes(bytes: &'a [u8], _info: &DataTypeInfo) -> Option<Self> {
         // zero‐copy: borrow the slice
-        Some(BytesMessage {
-            data: Cow::Borrowed(bytes),
-        })
+        Some(BytesMessage::borrowed(bytes))
+    }
+}
+
+impl OwnedMessage for BytesMessage<'static> {
+    /// Copies a payload borrowed from the receive buffer before it is queued.
+    fn into_owned(self) -> Self {
+        BytesMessage::into_owned(self)
     }
 }
 
 //
-// PublisherMessage: owns an Arc on send
+// PublisherMessage: shares the Arc on send
 //
 impl<'a> PublisherMessage for BytesMessage<'a> {
     /// same metadata as above
@@ -55,15 +116,22 @@ impl<'a> PublisherMessage for BytesMessage<'a> {
         <BytesMessage as SubscriberMessage>::datatype()
     }
 
-    /// For send, convert into an `Arc<[u8]>` so eCAL’s zero‐copy writer
-    /// can hand off the shared memory.  Note: this does copy *once*
-    /// into a fresh Arc; if you’re doing *true* zero‐copy send,
-    /// you’d use the PayloadWriter API instead of this path.
+    /// For send, hand out the payload as `Arc<[u8]>`: shared payloads are
+    /// returned without copying, borrowed ones are copied once.
     fn to_bytes(&self) -> Arc<[u8]> {
-        // if we’re already owned, reuse; otherwise clone the borrowed slice
         match &self.data {
-            Cow::Owned(vec) => Arc::from(&vec[..]),
-            Cow::Borrowed(s) => Arc::from(*s),
+            BytesData::Shared(bytes) => bytes.clone(),
+            BytesData::Borrowed(bytes) => Arc::from(*bytes),
         }
     }
+
+    /// Copies the payload into a send buffer.
+    fn write_bytes(&self, buffer: &mut Vec<u8>) {
+        buffer.extend_from_slice(self.data());
+    }
+
+    /// The payload is sent as it is, without a send buffer.
+    fn as_payload(&self) -> Option<&[u8]> {
+        Some(self.data())
+    }
 }
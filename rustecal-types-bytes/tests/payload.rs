@@ -0,0 +1,54 @@
+//! Payload handling of raw byte messages (`raw:bytes`).
+
+use rustecal_pubsub::typed_publisher::PublisherMessage;
+use rustecal_pubsub::typed_subscriber::SubscriberMessage;
+use rustecal_types_bytes::BytesMessage;
+use std::sync::Arc;
+
+const PAYLOAD: &[u8] = &[0x00, 0x01, 0xfe, 0xff];
+
+#[test]
+fn datatype_is_raw_bytes() {
+    let info = <BytesMessage as PublisherMessage>::datatype();
+    assert_eq!(info.encoding, "raw");
+    assert_eq!(info.type_name, "bytes");
+    assert!(info.descriptor.is_empty());
+}
+
+#[test]
+fn decodes_without_copying() {
+    let info = <BytesMessage as SubscriberMessage>::datatype();
+    let msg = BytesMessage::from_bytes(PAYLOAD, &info).unwrap();
+    assert!(std::ptr::eq(msg.data(), PAYLOAD));
+    assert!(msg.shared().is_none());
+}
+
+#[test]
+fn shared_payload_is_sent_without_copying() {
+    let shared: Arc<[u8]> = Arc::from(PAYLOAD);
+    let msg = BytesMessage::owned(shared.clone());
+    assert!(std::ptr::eq(msg.as_payload().unwrap(), &*shared));
+    assert!(Arc::ptr_eq(&msg.to_bytes(), &shared));
+
+    // forwarding keeps the buffer as well
+    let forwarded = msg.into_owned();
+    assert!(Arc::ptr_eq(forwarded.shared().unwrap(), &shared));
+}
+
+#[test]
+fn borrowed_payload_is_sent_without_copying() {
+    let msg = BytesMessage::borrowed(PAYLOAD);
+    assert!(std::ptr::eq(msg.as_payload().unwrap(), PAYLOAD));
+}
+
+#[test]
+fn into_owned_copies_borrowed_payload_once() {
+    let owned = BytesMessage::borrowed(PAYLOAD).into_owned();
+    let shared = owned.shared().unwrap();
+    assert!(!std::ptr::eq(&**shared, PAYLOAD));
+    assert_eq!(owned.data(), PAYLOAD);
+
+    let mut buffer = Vec::new();
+    owned.write_bytes(&mut buffer);
+    assert_eq!(buffer, PAYLOAD);
+}
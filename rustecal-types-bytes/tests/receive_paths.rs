@@ -0,0 +1,133 @@
+//! Received byte messages borrow eCAL's receive buffer; every path keeping
+//! them after the callback returned must hand out intact copies.
+//!
+//! These tests publish within the process and need an eCAL runtime:
+//! `cargo test -p rustecal-types-bytes -- --ignored`.
+
+use rustecal_pubsub::publisher::Timestamp;
+use rustecal_pubsub::typed_subscriber::Received;
+use rustecal_pubsub::{
+    DropPolicy, Ecal, EcalComponents, Executor, TypedPublisher, TypedSubscriber,
+};
+use rustecal_types_bytes::BytesMessage;
+use std::future::Future;
+use std::pin::pin;
+use std::sync::{Once, mpsc};
+use std::task::{Context, Poll, Waker};
+use std::thread;
+use std::time::{Duration, Instant};
+
+const TIMEOUT: Duration = Duration::from_secs(5);
+
+type BytesSubscriber = TypedSubscriber<'static, BytesMessage<'static>>;
+type BytesPublisher = TypedPublisher<BytesMessage<'static>>;
+
+/// Creates a subscriber on `topic` and a publisher connected to it.
+fn connect(topic: &str) -> (BytesSubscriber, BytesPublisher) {
+    static INIT: Once = Once::new();
+    INIT.call_once(|| {
+        let components = EcalComponents::PUBLISHER | EcalComponents::SUBSCRIBER;
+        Ecal::initialize(Some("receive_paths"), components, None).unwrap();
+    });
+    let subscriber = TypedSubscriber::new(topic).unwrap();
+    let publisher = TypedPublisher::new(topic).unwrap();
+    assert!(publisher.wait_for_subscribers(1, TIMEOUT));
+    assert!(subscriber.wait_for_publishers(1, TIMEOUT));
+    (subscriber, publisher)
+}
+
+/// Sends a sample of 256 `fill` bytes, reusing the receive buffer of the
+/// samples before.
+fn send(publisher: &BytesPublisher, fill: u8) {
+    let payload = [fill; 256];
+    publisher
+        .send(&BytesMessage::borrowed(&payload), Timestamp::Auto)
+        .unwrap();
+}
+
+fn assert_intact(msg: &Received<BytesMessage>, fill: u8) {
+    assert_eq!(msg.payload.as_ref(), [fill; 256]);
+}
+
+/// Polls `future` until it completes or [`TIMEOUT`] elapsed.
+fn block_on<F: Future>(future: F) -> F::Output {
+    let mut future = pin!(future);
+    let mut cx = Context::from_waker(Waker::noop());
+    let start = Instant::now();
+    loop {
+        match future.as_mut().poll(&mut cx) {
+            Poll::Ready(output) => return output,
+            Poll::Pending if start.elapsed() < TIMEOUT => thread::sleep(Duration::from_millis(10)),
+            Poll::Pending => panic!("timed out"),
+        }
+    }
+}
+
+#[test]
+#[ignore = "needs an eCAL runtime"]
+fn queued_messages_stay_intact() {
+    let (mut subscriber, publisher) = connect("receive_paths/queue");
+    subscriber.set_queue_capacity(4);
+    send(&publisher, 1);
+    send(&publisher, 2);
+
+    assert_intact(&subscriber.recv().unwrap(), 1);
+    assert_intact(&subscriber.recv_timeout(TIMEOUT).unwrap(), 2);
+}
+
+#[test]
+#[ignore = "needs an eCAL runtime"]
+fn latest_message_stays_intact() {
+    let (mut subscriber, publisher) = connect("receive_paths/latest");
+    subscriber.keep_latest();
+    send(&publisher, 1);
+
+    let start = Instant::now();
+    let latest = loop {
+        match subscriber.get_latest() {
+            Some(latest) => break latest,
+            None if start.elapsed() < TIMEOUT => thread::sleep(Duration::from_millis(10)),
+            None => panic!("no message received"),
+        }
+    };
+    send(&publisher, 2);
+    assert_intact(&latest, 1);
+}
+
+#[test]
+#[ignore = "needs an eCAL runtime"]
+fn channel_messages_stay_intact() {
+    let (subscriber, publisher) = connect("receive_paths/channel");
+    let channel = subscriber.into_channel(4, DropPolicy::DropNewest);
+    send(&publisher, 1);
+    send(&publisher, 2);
+
+    assert_intact(&channel.recv_timeout(TIMEOUT).unwrap(), 1);
+    assert_intact(&channel.recv_timeout(TIMEOUT).unwrap(), 2);
+}
+
+#[test]
+#[ignore = "needs an eCAL runtime"]
+fn executor_messages_stay_intact() {
+    let executor = Executor::new(1, 4, DropPolicy::Block);
+    let (sender, receiver) = mpsc::channel();
+    let (mut subscriber, publisher) = connect("receive_paths/executor");
+    subscriber.set_callback_on(&executor, move |msg| sender.send(msg).unwrap());
+    send(&publisher, 1);
+    send(&publisher, 2);
+
+    assert_intact(&receiver.recv_timeout(TIMEOUT).unwrap(), 1);
+    assert_intact(&receiver.recv_timeout(TIMEOUT).unwrap(), 2);
+}
+
+#[test]
+#[ignore = "needs an eCAL runtime"]
+fn streamed_messages_stay_intact() {
+    let (subscriber, publisher) = connect("receive_paths/stream");
+    let mut stream = subscriber.into_stream_with_capacity(4);
+    send(&publisher, 1);
+    send(&publisher, 2);
+
+    assert_intact(&block_on(stream.recv()).unwrap(), 1);
+    assert_intact(&block_on(stream.recv()).unwrap(), 2);
+}
@@ -0,0 +1,159 @@
+//! Decoding protobuf topics without their generated Rust types.
+//!
+//! eCAL transports the protobuf descriptor with the data type of a topic.
+//! [`DynamicDecoder`] parses it into a [`DescriptorPool`] and decodes
+//! payloads into [`DynamicMessage`]s, so tools like a topic echo can print
+//! any protobuf topic. [`DynamicProtobufSubscriber`] combines it with a
+//! [`DynamicSubscriber`]:
+//!
+//! ```ignore
+//! let mut subscriber = DynamicProtobufSubscriber::new("person")?;
+//! subscriber.set_callback(|msg: Received<DynamicMessage>| {
+//!     println!("{}: {:?}", msg.topic_name, msg.payload);
+//! });
+//! ```
+//!
+//! With the `json` feature, [`DynamicProtobufSubscriber::as_json`] delivers
+//! the messages as `serde_json` values in the protobuf JSON mapping, ready
+//! for web dashboards or debug output.
+
+use prost_reflect::{DescriptorPool, DynamicMessage, MessageDescriptor};
+use rustecal_core::types::DataTypeInfo;
+use rustecal_pubsub::dynamic_subscriber::{DynamicSubscriber, SampleMeta};
+use rustecal_pubsub::typed_subscriber::Received;
+use std::sync::Mutex;
+
+/// Decodes protobuf payloads with the descriptor of their data type.
+///
+/// The parsed descriptor is cached and only rebuilt when the type name or
+/// descriptor changes, e.g. after a publisher was restarted with a newer schema.
+#[derive(Debug, Default)]
+pub struct DynamicDecoder {
+    cached: Option<(DataTypeInfo, MessageDescriptor)>,
+}
+
+impl DynamicDecoder {
+    /// Creates a decoder with an empty cache.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Decodes `payload` as the message type described by `data_type`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `Err(String)` if `data_type` is not a protobuf type, its
+    /// descriptor cannot be parsed or does not contain the type, or the
+    /// payload is not a valid message of the type.
+    pub fn decode(
+        &mut self,
+        payload: &[u8],
+        data_type: &DataTypeInfo,
+    ) -> Result<DynamicMessage, String> {
+        let descriptor = self.descriptor(data_type)?;
+        DynamicMessage::decode(descriptor, payload).map_err(|e| e.to_string())
+    }
+
+    /// Returns the message descriptor of `data_type`, parsing it if not cached.
+    ///
+    /// # Errors
+    ///
+    /// See [`DynamicDecoder::decode`].
+    pub fn descriptor(&mut self, data_type: &DataTypeInfo) -> Result<MessageDescriptor, String> {
+        if let Some((cached_type, descriptor)) = &self.cached
+            && cached_type == data_type
+        {
+            return Ok(descriptor.clone());
+        }
+        if data_type.encoding != "proto" {
+            return Err(format!(
+                "'{}' is not a protobuf type (encoding '{}')",
+                data_type.type_name, data_type.encoding
+            ));
+        }
+        let pool = DescriptorPool::decode(data_type.descriptor.as_slice())
+            .map_err(|e| format!("invalid descriptor of '{}': {e}", data_type.type_name))?;
+        let descriptor = pool
+            .get_message_by_name(&data_type.type_name)
+            .ok_or_else(|| format!("descriptor does not contain '{}'", data_type.type_name))?;
+        self.cached = Some((data_type.clone(), descriptor.clone()));
+        Ok(descriptor)
+    }
+}
+
+/// Subscribes to a protobuf topic of any type, delivering [`DynamicMessage`]s.
+///
+/// Messages that cannot be decoded (e.g. non-protobuf topics) are discarded.
+pub struct DynamicProtobufSubscriber {
+    subscriber: DynamicSubscriber,
+}
+
+impl DynamicProtobufSubscriber {
+    /// Subscribes to `topic_name` without declaring a data type.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `Err(String)` if the underlying eCAL subscriber could not be created.
+    pub fn new(topic_name: &str) -> Result<Self, String> {
+        Ok(Self {
+            subscriber: DynamicSubscriber::new(topic_name)?,
+        })
+    }
+
+    /// Registers the callback receiving the decoded messages, replacing a previous one.
+    pub fn set_callback<F>(&mut self, callback: F)
+    where
+        F: Fn(Received<DynamicMessage>) + Send + Sync + 'static,
+    {
+        let decoder = Mutex::new(DynamicDecoder::new());
+        self.subscriber
+            .set_callback(move |payload, data_type, meta: SampleMeta| {
+                let decoded = decoder.lock().unwrap().decode(payload, data_type);
+                if let Ok(message) = decoded {
+                    callback(Received {
+                        payload: message,
+                        topic_name: meta.topic_name,
+                        encoding: data_type.encoding.clone(),
+                        type_name: data_type.type_name.clone(),
+                        timestamp: meta.timestamp,
+                        clock: meta.clock,
+                        size: meta.size,
+                        publisher_id: meta.publisher_id,
+                        host_name: meta.host_name,
+                        process_id: meta.process_id,
+                    });
+                }
+            });
+    }
+
+    /// Delivers the messages as JSON values, replacing the callback.
+    ///
+    /// Fields are named and formatted as in the protobuf JSON mapping
+    /// (e.g. `camelCase` names, 64-bit integers as strings).
+    #[cfg(feature = "json")]
+    pub fn as_json<F>(&mut self, callback: F)
+    where
+        F: Fn(Received<serde_json::Value>) + Send + Sync + 'static,
+    {
+        self.set_callback(move |msg| {
+            if let Ok(value) = to_json(&msg.payload) {
+                callback(msg.map(|_| value));
+            }
+        });
+    }
+
+    /// Returns the underlying [`DynamicSubscriber`], e.g. to wait for publishers.
+    pub fn subscriber(&self) -> &DynamicSubscriber {
+        &self.subscriber
+    }
+}
+
+/// Converts `message` to a JSON value in the protobuf JSON mapping.
+///
+/// # Errors
+///
+/// Returns an `Err(String)` if the message cannot be represented as JSON.
+#[cfg(feature = "json")]
+pub fn to_json(message: &DynamicMessage) -> Result<serde_json::Value, String> {
+    serde_json::to_value(message).map_err(|e| e.to_string())
+}
@@ -1,12 +1,19 @@
 //! # rustecal-types-protobuf
 //!
 //! Provides support for Protobuf message serialization with rustecal.
+//!
+//! Topics whose generated types are unknown at compile time can be decoded
+//! from their transported descriptor with [`DynamicProtobufSubscriber`].
+
+pub mod dynamic;
+
+pub use dynamic::{DynamicDecoder, DynamicProtobufSubscriber};
 
 use prost::Message;
 use prost_reflect::{FileDescriptor, ReflectMessage};
 use rustecal_core::types::DataTypeInfo;
 use rustecal_pubsub::typed_publisher::PublisherMessage;
-use rustecal_pubsub::typed_subscriber::SubscriberMessage;
+use rustecal_pubsub::typed_subscriber::{OwnedMessage, SubscriberMessage};
 use std::sync::Arc;
 
 /// Marker trait to opt-in a Protobuf type for use with eCAL.
@@ -92,6 +99,16 @@ where
     }
 }
 
+impl<T> OwnedMessage for ProtobufMessage<T>
+where
+    T: Send + Sync + 'static,
+{
+    /// Decoded messages own their data.
+    fn into_owned(self) -> Self {
+        self
+    }
+}
+
 impl<T> PublisherMessage for ProtobufMessage<T>
 where
     T: Message + Default + IsProtobufType + ReflectMessage,
@@ -114,4 +131,11 @@ where
             .expect("Failed to encode protobuf message");
         Arc::from(buf)
     }
+
+    /// Encodes the message directly into the publisher's send buffer.
+    fn write_bytes(&self, buffer: &mut Vec<u8>) {
+        self.data
+            .encode(buffer)
+            .expect("Failed to encode protobuf message");
+    }
 }
@@ -0,0 +1,153 @@
+//! Conformance vectors for protobuf messages of eCAL C++
+//! (`eCAL::protobuf::CPublisher<T>` / `CSubscriber<T>`).
+//!
+//! The message under test corresponds to
+//!
+//! ```proto
+//! syntax = "proto3";
+//! package pb.Conformance;
+//! message Sample {
+//!   int32 id = 1;
+//!   string name = 2;
+//!   repeated double values = 3;
+//! }
+//! ```
+
+use prost::Message;
+use prost_reflect::prost_types::{
+    DescriptorProto, FieldDescriptorProto, FileDescriptorProto, FileDescriptorSet,
+    field_descriptor_proto::{Label, Type},
+};
+use prost_reflect::{DescriptorPool, MessageDescriptor, ReflectMessage};
+use rustecal_pubsub::typed_publisher::PublisherMessage;
+use rustecal_pubsub::typed_subscriber::SubscriberMessage;
+use rustecal_types_protobuf::{DynamicDecoder, IsProtobufType, ProtobufMessage};
+use std::sync::{Arc, OnceLock};
+
+#[derive(Clone, PartialEq, Message)]
+struct Sample {
+    #[prost(int32, tag = "1")]
+    id: i32,
+    #[prost(string, tag = "2")]
+    name: String,
+    #[prost(double, repeated, tag = "3")]
+    values: Vec<f64>,
+}
+
+impl IsProtobufType for Sample {}
+
+impl ReflectMessage for Sample {
+    fn descriptor(&self) -> MessageDescriptor {
+        pool().get_message_by_name("pb.Conformance.Sample").unwrap()
+    }
+}
+
+fn field(name: &str, number: i32, label: Label, ty: Type) -> FieldDescriptorProto {
+    FieldDescriptorProto {
+        name: Some(name.into()),
+        number: Some(number),
+        label: Some(label as i32),
+        r#type: Some(ty as i32),
+        json_name: None,
+        ..Default::default()
+    }
+}
+
+fn pool() -> &'static DescriptorPool {
+    static POOL: OnceLock<DescriptorPool> = OnceLock::new();
+    POOL.get_or_init(|| {
+        let file = FileDescriptorProto {
+            name: Some("conformance.proto".into()),
+            package: Some("pb.Conformance".into()),
+            syntax: Some("proto3".into()),
+            message_type: vec![DescriptorProto {
+                name: Some("Sample".into()),
+                field: vec![
+                    field("id", 1, Label::Optional, Type::Int32),
+                    field("name", 2, Label::Optional, Type::String),
+                    field("values", 3, Label::Repeated, Type::Double),
+                ],
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+        DescriptorPool::from_file_descriptor_set(FileDescriptorSet { file: vec![file] }).unwrap()
+    })
+}
+
+/// `Sample { id: 42, name: "eCAL", values: [1.5, -2.0] }` serialized by protobuf C++.
+const SAMPLE: &[u8] = &[
+    0x08, 0x2a, // id = 42
+    0x12, 0x04, 0x65, 0x43, 0x41, 0x4c, // name = "eCAL"
+    0x1a, 0x10, // values, packed, 16 bytes
+    0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0xf8, 0x3f, // 1.5
+    0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0xc0, // -2.0
+];
+
+fn sample() -> Sample {
+    Sample {
+        id: 42,
+        name: "eCAL".into(),
+        values: vec![1.5, -2.0],
+    }
+}
+
+#[test]
+fn datatype_matches_cpp() {
+    let info = <ProtobufMessage<Sample> as PublisherMessage>::datatype();
+    assert_eq!(info.encoding, "proto");
+    assert_eq!(info.type_name, "pb.Conformance.Sample");
+
+    // eCAL C++ ships a serialized FileDescriptorSet containing the message's file
+    // and all of its dependencies, without source code info
+    let set = FileDescriptorSet::decode(info.descriptor.as_slice()).unwrap();
+    assert_eq!(set.file.len(), 1);
+    assert_eq!(set.file[0].name(), "conformance.proto");
+    assert!(set.file[0].source_code_info.is_none());
+    let pool = DescriptorPool::from_file_descriptor_set(set).unwrap();
+    assert!(pool.get_message_by_name("pb.Conformance.Sample").is_some());
+}
+
+#[test]
+fn encodes_like_cpp() {
+    let msg = ProtobufMessage {
+        data: Arc::new(sample()),
+    };
+    assert_eq!(&*msg.to_bytes(), SAMPLE);
+}
+
+#[test]
+fn decodes_cpp_payloads() {
+    let info = <ProtobufMessage<Sample> as SubscriberMessage>::datatype();
+    let msg = <ProtobufMessage<Sample> as SubscriberMessage>::from_bytes(SAMPLE, &info).unwrap();
+    assert_eq!(*msg.data, sample());
+}
+
+#[test]
+fn decodes_cpp_payloads_without_the_rust_type() {
+    let info = <ProtobufMessage<Sample> as SubscriberMessage>::datatype();
+    let mut decoder = DynamicDecoder::new();
+    let msg = decoder.decode(SAMPLE, &info).unwrap();
+    assert_eq!(msg.descriptor().full_name(), "pb.Conformance.Sample");
+    assert_eq!(msg.get_field_by_name("id").unwrap().as_i32(), Some(42));
+    assert_eq!(
+        msg.get_field_by_name("name").unwrap().as_str(),
+        Some("eCAL")
+    );
+
+    let mut raw = info.clone();
+    raw.encoding = "raw".into();
+    assert!(decoder.decode(SAMPLE, &raw).is_err());
+}
+
+#[cfg(feature = "json")]
+#[test]
+fn converts_dynamic_messages_to_json() {
+    let info = <ProtobufMessage<Sample> as SubscriberMessage>::datatype();
+    let msg = DynamicDecoder::new().decode(SAMPLE, &info).unwrap();
+    let json = rustecal_types_protobuf::dynamic::to_json(&msg).unwrap();
+    assert_eq!(
+        json,
+        serde_json::json!({ "id": 42, "name": "eCAL", "values": [1.5, -2.0] })
+    );
+}
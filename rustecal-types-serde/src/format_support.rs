@@ -4,6 +4,10 @@ pub trait FormatSupport {
     const ENCODING: &'static str;
     /// Serialize the payload to bytes.
     fn encode<T: serde::Serialize>(payload: &T) -> Vec<u8>;
+    /// Serialize the payload, appending to `buffer`.
+    fn encode_into<T: serde::Serialize>(payload: &T, buffer: &mut Vec<u8>) {
+        buffer.extend_from_slice(&Self::encode(payload));
+    }
     /// Deserialize the payload from bytes.
     fn decode<T: for<'de> serde::Deserialize<'de>>(bytes: &[u8]) -> Option<T>;
 }
@@ -37,5 +41,14 @@ macro_rules! make_format {
                 }
             }
         }
+        /// Decoded messages own their payload.
+        impl<T> rustecal_pubsub::typed_subscriber::OwnedMessage for $msg_type<T>
+        where
+            T: serde::Serialize + for<'de> serde::Deserialize<'de> + Clone + Send + Sync + 'static,
+        {
+            fn into_owned(self) -> Self {
+                self
+            }
+        }
     };
 }
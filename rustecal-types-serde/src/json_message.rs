@@ -14,6 +14,9 @@ impl FormatSupport for JsonSupport {
     fn encode<T: Serialize>(payload: &T) -> Vec<u8> {
         serde_json::to_vec(payload).expect("JSON serialization failed")
     }
+    fn encode_into<T: Serialize>(payload: &T, buffer: &mut Vec<u8>) {
+        serde_json::to_writer(buffer, payload).expect("JSON serialization failed")
+    }
     fn decode<T: for<'de> Deserialize<'de>>(bytes: &[u8]) -> Option<T> {
         serde_json::from_slice(bytes).ok()
     }
@@ -35,6 +38,9 @@ where
     fn to_bytes(&self) -> Arc<[u8]> {
         Arc::from(JsonSupport::encode(&*self.data))
     }
+    fn write_bytes(&self, buffer: &mut Vec<u8>) {
+        JsonSupport::encode_into(&*self.data, buffer)
+    }
 }
 impl<T> SubscriberMessage<'_> for JsonMessage<T>
 where
@@ -1,12 +1,23 @@
 //! # rustecal-types-serde
 //!
 //! eCAL Pub/Sub support for Serde-enabled messages.
+//!
+//! ## Features
+//! - `service`: service servers and clients with Serde requests and responses
+//!   (`JsonService`, `CborService`, `MsgpackService` and their clients).
 
 pub mod cbor_message;
 pub mod format_support;
 pub mod json_message;
 pub mod msgpack_message;
+#[cfg(feature = "service")]
+pub mod service;
 
 pub use cbor_message::CborMessage;
 pub use json_message::JsonMessage;
 pub use msgpack_message::MsgpackMessage;
+#[cfg(feature = "service")]
+pub use service::{
+    CborService, CborServiceClient, JsonService, JsonServiceClient, MsgpackService,
+    MsgpackServiceClient, SerdeService, SerdeServiceClient,
+};
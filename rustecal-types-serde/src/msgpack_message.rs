@@ -14,6 +14,9 @@ impl FormatSupport for MsgpackSupport {
     fn encode<T: Serialize>(payload: &T) -> Vec<u8> {
         rmp_serde::to_vec(payload).expect("MessagePack serialization failed")
     }
+    fn encode_into<T: Serialize>(payload: &T, buffer: &mut Vec<u8>) {
+        rmp_serde::encode::write(buffer, payload).expect("MessagePack serialization failed")
+    }
     fn decode<T: for<'de> Deserialize<'de>>(bytes: &[u8]) -> Option<T> {
         rmp_serde::from_slice(bytes).ok()
     }
@@ -35,6 +38,9 @@ where
     fn to_bytes(&self) -> Arc<[u8]> {
         Arc::from(MsgpackSupport::encode(&*self.data))
     }
+    fn write_bytes(&self, buffer: &mut Vec<u8>) {
+        MsgpackSupport::encode_into(&*self.data, buffer)
+    }
 }
 impl<T> SubscriberMessage<'_> for MsgpackMessage<T>
 where
@@ -0,0 +1,164 @@
+//! Service methods with Serde requests and responses.
+//!
+//! A [`SerdeService`] registers methods taking and returning Serde types,
+//! encoded with one of the formats of this crate; a [`SerdeServiceClient`]
+//! calls them. This gives a typed RPC path without Protobuf:
+//!
+//! ```ignore
+//! let mut service = JsonService::new("math")?;
+//! service.add_method("add", |request: AddRequest| {
+//!     Ok(AddResponse { sum: request.a + request.b })
+//! })?;
+//!
+//! let client = JsonServiceClient::new("math")?;
+//! let response: AddResponse = client.call("add", &AddRequest { a: 1, b: 2 }, Some(1000))?;
+//! ```
+//!
+//! Requests and responses that cannot be decoded fail the call with
+//! [`ServiceError::Serialization`]. The request and response types are
+//! advertised with the method.
+
+use crate::cbor_message::CborSupport;
+use crate::format_support::{FormatSupport, short_type_name};
+use crate::json_message::JsonSupport;
+use crate::msgpack_message::MsgpackSupport;
+use rustecal_core::types::DataTypeInfo;
+use rustecal_service::{ServiceClient, ServiceError, ServiceRequest, ServiceServer};
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+use std::marker::PhantomData;
+
+/// A service server with Serde requests and responses encoded with `F`.
+pub struct SerdeService<F: FormatSupport> {
+    server: ServiceServer,
+    _format: PhantomData<F>,
+}
+
+/// A service server with JSON requests and responses.
+pub type JsonService = SerdeService<JsonSupport>;
+/// A service server with CBOR requests and responses.
+pub type CborService = SerdeService<CborSupport>;
+/// A service server with MessagePack requests and responses.
+pub type MsgpackService = SerdeService<MsgpackSupport>;
+
+fn datatype<F: FormatSupport, T>() -> DataTypeInfo {
+    DataTypeInfo {
+        encoding: F::ENCODING.into(),
+        type_name: short_type_name::<T>(),
+        descriptor: vec![],
+    }
+}
+
+fn decode<F: FormatSupport, T: DeserializeOwned>(bytes: &[u8]) -> Result<T, ServiceError> {
+    F::decode(bytes).ok_or_else(|| {
+        ServiceError::Serialization(format!(
+            "not a valid {} '{}'",
+            F::ENCODING,
+            short_type_name::<T>()
+        ))
+    })
+}
+
+impl<F: FormatSupport + 'static> SerdeService<F> {
+    /// Creates a server for `service_name`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `Err(String)` if the underlying eCAL server could not be created.
+    pub fn new(service_name: &str) -> Result<Self, String> {
+        Ok(Self {
+            server: ServiceServer::new(service_name)?,
+            _format: PhantomData,
+        })
+    }
+
+    /// Registers `handler` for `method`, replacing a previous handler.
+    ///
+    /// An `Err` returned by the handler fails the call.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `Err(String)` if the method name is reserved or eCAL
+    /// rejects the registration.
+    pub fn add_method<Req, Resp, H>(&mut self, method: &str, handler: H) -> Result<(), String>
+    where
+        Req: DeserializeOwned,
+        Resp: Serialize,
+        H: Fn(Req) -> Result<Resp, ServiceError> + Send + Sync + 'static,
+    {
+        self.server.add_typed_method(
+            method,
+            datatype::<F, Req>(),
+            datatype::<F, Resp>(),
+            Box::new(move |_info, request| {
+                let response = handler(decode::<F, Req>(request)?)?;
+                Ok(F::encode(&response))
+            }),
+        )
+    }
+
+    /// Returns the underlying server, e.g. to set the interface version.
+    pub fn server_mut(&mut self) -> &mut ServiceServer {
+        &mut self.server
+    }
+}
+
+/// A service client calling methods with Serde requests and responses encoded with `F`.
+pub struct SerdeServiceClient<F: FormatSupport> {
+    client: ServiceClient,
+    _format: PhantomData<F>,
+}
+
+/// A service client with JSON requests and responses.
+pub type JsonServiceClient = SerdeServiceClient<JsonSupport>;
+/// A service client with CBOR requests and responses.
+pub type CborServiceClient = SerdeServiceClient<CborSupport>;
+/// A service client with MessagePack requests and responses.
+pub type MsgpackServiceClient = SerdeServiceClient<MsgpackSupport>;
+
+impl<F: FormatSupport> SerdeServiceClient<F> {
+    /// Creates a client for `service_name`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `Err(String)` if the underlying eCAL client could not be created.
+    pub fn new(service_name: &str) -> Result<Self, String> {
+        Ok(Self {
+            client: ServiceClient::new(service_name)?,
+            _format: PhantomData,
+        })
+    }
+
+    /// Calls `method` with `request` and decodes the response.
+    ///
+    /// # Errors
+    ///
+    /// See [`ServiceClient::call`]; a response that cannot be decoded as
+    /// `Resp` fails with [`ServiceError::Serialization`].
+    pub fn call<Req, Resp>(
+        &self,
+        method: &str,
+        request: &Req,
+        timeout_ms: Option<i32>,
+    ) -> Result<Resp, ServiceError>
+    where
+        Req: Serialize,
+        Resp: DeserializeOwned,
+    {
+        let request = ServiceRequest {
+            payload: F::encode(request),
+        };
+        let response = self.client.call(method, request, timeout_ms)?;
+        decode::<F, Resp>(&response.payload)
+    }
+
+    /// Returns the underlying client, e.g. to wait for a server.
+    pub fn client(&self) -> &ServiceClient {
+        &self.client
+    }
+
+    /// Returns the underlying client mutably, e.g. to set a retry policy.
+    pub fn client_mut(&mut self) -> &mut ServiceClient {
+        &mut self.client
+    }
+}
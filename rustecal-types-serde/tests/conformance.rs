@@ -0,0 +1,54 @@
+//! Conformance vectors for JSON messages.
+//!
+//! eCAL C++ has no built-in JSON message type; C++ nodes typically publish
+//! `nlohmann::json::dump()` output as `json:<TypeName>`. nlohmann orders
+//! object keys alphabetically and writes compact JSON, which matches
+//! `serde_json` for structs whose fields are declared in alphabetical order.
+
+use rustecal_pubsub::typed_publisher::PublisherMessage;
+use rustecal_pubsub::typed_subscriber::SubscriberMessage;
+use rustecal_types_serde::JsonMessage;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct Pose {
+    frame: String,
+    x: f64,
+    y: f64,
+}
+
+/// `{"frame":"map","x":1.5,"y":-2.0}` as written by `nlohmann::json::dump()`.
+const POSE: &[u8] = br#"{"frame":"map","x":1.5,"y":-2.0}"#;
+
+fn pose() -> Pose {
+    Pose {
+        frame: "map".into(),
+        x: 1.5,
+        y: -2.0,
+    }
+}
+
+#[test]
+fn datatype_matches_convention() {
+    let info = <JsonMessage<Pose> as PublisherMessage>::datatype();
+    assert_eq!(info.encoding, "json");
+    assert_eq!(info.type_name, "Pose");
+    assert!(info.descriptor.is_empty());
+}
+
+#[test]
+fn encodes_like_cpp() {
+    assert_eq!(&*JsonMessage::new(pose()).to_bytes(), POSE);
+}
+
+#[test]
+fn decodes_cpp_payloads() {
+    let info = <JsonMessage<Pose> as SubscriberMessage>::datatype();
+    let msg = <JsonMessage<Pose> as SubscriberMessage>::from_bytes(POSE, &info).unwrap();
+    assert_eq!(*msg.data, pose());
+
+    // whitespace as produced by `dump(2)` is accepted as well
+    let pretty = b"{\n  \"frame\": \"map\",\n  \"x\": 1.5,\n  \"y\": -2.0\n}";
+    let msg = <JsonMessage<Pose> as SubscriberMessage>::from_bytes(pretty, &info).unwrap();
+    assert_eq!(*msg.data, pose());
+}
@@ -1,5 +1,7 @@
-use rustecal_types_serde::format_support;
+use rustecal_types_serde::cbor_message::CborSupport;
+use rustecal_types_serde::format_support::{self, FormatSupport};
 use rustecal_types_serde::json_message::JsonSupport;
+use rustecal_types_serde::msgpack_message::MsgpackSupport;
 
 #[test]
 fn short_type_name_for_implementor() {
@@ -21,3 +23,18 @@ fn short_type_name_for_nested_type() {
         "TestType"
     );
 }
+
+fn assert_encode_into_appends<F: FormatSupport>() {
+    let payload = (7u32, "control".to_string());
+    let mut buffer = vec![0xAA];
+    F::encode_into(&payload, &mut buffer);
+    assert_eq!(buffer[0], 0xAA);
+    assert_eq!(&buffer[1..], F::encode(&payload).as_slice());
+}
+
+#[test]
+fn encode_into_matches_encode() {
+    assert_encode_into_appends::<JsonSupport>();
+    assert_encode_into_appends::<CborSupport>();
+    assert_encode_into_appends::<MsgpackSupport>();
+}
@@ -4,7 +4,8 @@
 
 use rustecal_core::types::DataTypeInfo;
 use rustecal_pubsub::typed_publisher::PublisherMessage;
-use rustecal_pubsub::typed_subscriber::SubscriberMessage;
+use rustecal_pubsub::typed_subscriber::{OwnedMessage, SubscriberMessage};
+use std::fmt;
 use std::str;
 use std::sync::Arc;
 
@@ -16,12 +17,27 @@ pub struct StringMessage {
     pub data: Arc<str>,
 }
 
+impl AsRef<[u8]> for StringMessage {
+    fn as_ref(&self) -> &[u8] {
+        self.data.as_bytes()
+    }
+}
+
+impl fmt::Display for StringMessage {
+    /// Displays the string content.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.data)
+    }
+}
+
 impl SubscriberMessage<'_> for StringMessage {
-    /// Returns metadata describing this message type (`utf-8` encoded string).
+    /// Returns metadata describing this message type.
+    ///
+    /// Matches the `base:std::string` type of eCAL C++ string messages.
     fn datatype() -> DataTypeInfo {
         DataTypeInfo {
-            encoding: "utf-8".to_string(),
-            type_name: "string".to_string(),
+            encoding: "base".to_string(),
+            type_name: "std::string".to_string(),
             descriptor: vec![],
         }
     }
@@ -34,6 +50,13 @@ impl SubscriberMessage<'_> for StringMessage {
     }
 }
 
+impl OwnedMessage for StringMessage {
+    /// Decoded strings own their data.
+    fn into_owned(self) -> Self {
+        self
+    }
+}
+
 impl PublisherMessage for StringMessage {
     /// Returns the same metadata as [`SubscriberMessage::datatype`].
     fn datatype() -> DataTypeInfo {
@@ -44,4 +67,9 @@ impl PublisherMessage for StringMessage {
     fn to_bytes(&self) -> Arc<[u8]> {
         Arc::from(self.data.as_bytes())
     }
+
+    /// Copies the string into the publisher's send buffer.
+    fn write_bytes(&self, buffer: &mut Vec<u8>) {
+        buffer.extend_from_slice(self.data.as_bytes());
+    }
 }
@@ -0,0 +1,44 @@
+//! Conformance vectors for `std::string` messages of eCAL C++
+//! (`eCAL::string::CPublisher` / `CSubscriber`).
+
+use rustecal_pubsub::typed_publisher::PublisherMessage;
+use rustecal_pubsub::typed_subscriber::SubscriberMessage;
+use rustecal_types_string::StringMessage;
+use std::sync::Arc;
+
+/// "Hello World from C++ 1" as sent by the eCAL C++ `hello_send` sample
+/// (no trailing NUL).
+const HELLO: &[u8] = &[
+    0x48, 0x65, 0x6c, 0x6c, 0x6f, 0x20, 0x57, 0x6f, 0x72, 0x6c, 0x64, 0x20, 0x66, 0x72, 0x6f, 0x6d,
+    0x20, 0x43, 0x2b, 0x2b, 0x20, 0x31,
+];
+
+/// UTF-8 multibyte content passes through unchanged.
+const UMLAUT: &[u8] = &[0x47, 0x72, 0xc3, 0xbc, 0xc3, 0x9f, 0x65];
+
+#[test]
+fn datatype_matches_cpp() {
+    let info = <StringMessage as PublisherMessage>::datatype();
+    assert_eq!(info.encoding, "base");
+    assert_eq!(info.type_name, "std::string");
+    assert!(info.descriptor.is_empty());
+}
+
+#[test]
+fn encodes_like_cpp() {
+    for (text, bytes) in [("Hello World from C++ 1", HELLO), ("Grüße", UMLAUT)] {
+        let msg = StringMessage {
+            data: Arc::from(text),
+        };
+        assert_eq!(&*msg.to_bytes(), bytes);
+    }
+}
+
+#[test]
+fn decodes_cpp_payloads() {
+    let info = <StringMessage as SubscriberMessage>::datatype();
+    for (text, bytes) in [("Hello World from C++ 1", HELLO), ("Grüße", UMLAUT)] {
+        let msg = StringMessage::from_bytes(bytes, &info).unwrap();
+        assert_eq!(&*msg.data, text);
+    }
+}
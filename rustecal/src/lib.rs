@@ -15,13 +15,18 @@
 //!
 //! Ecal::initialize(Some("example node"), EcalComponents::DEFAULT, None).unwrap();
 //! let pub_ = `TypedPublisher::<StringMessage>::new`("hello topic").unwrap();
-//! pub_.send(&StringMessage{data: "Hello!".into()}, Timestamp::Auto);
+//! let _ = pub_.send(&StringMessage{data: "Hello!".into()}, Timestamp::Auto);
 //! '''
 //!
 
 // —————————————————————————————————————————————————————————————————————————————
 // Core initialization & types (always available)
-pub use rustecal_core::{Configuration, Ecal, EcalComponents};
+pub use rustecal_core::{
+    DescriptorPolicy, Ecal, EcalComponents, EcalGuard, HandleRegistry, TopicPolicy, TopicRemap,
+};
+
+#[cfg(feature = "configuration")]
+pub use rustecal_core::{Configuration, LogSink};
 
 // —————————————————————————————————————————————————————————————————————————————
 // Pub/Sub API (requires the `pubsub` feature)
@@ -34,6 +39,7 @@ pub mod pubsub {
 #[cfg(feature = "pubsub")]
 pub use rustecal_pubsub::{
     // low‑level handles
+    OwnedMessage,
     Publisher,
     PublisherMessage,
     Subscriber,
@@ -53,19 +59,33 @@ pub mod service {
 
 #[cfg(feature = "service")]
 pub use rustecal_service::{
+    Backoff,
+    CancelToken,
     ClientInstance,
+    ClientNext,
+    ResponseFuture,
+    RetryPolicy,
+    ServerNext,
     ServiceClient,
+    ServiceError,
+    ServiceEvent,
+    ServiceEventType,
     // request/response types
     ServiceRequest,
     ServiceResponse,
     // server & client entrypoints
     ServiceServer,
+    TypedServiceServer,
+    WorkerPool,
 };
 
 #[cfg(feature = "service")]
 pub use rustecal_service::types::{
     CallState,
     // metadata & callback signature
+    FallibleServiceCallback,
     MethodInfo,
+    PrefixCallback,
+    RegisteredMethod,
     ServiceCallback,
 };